@@ -9,14 +9,18 @@ use crate::cli::commands::helpers::{
 };
 use crate::cli::commands::threads::parse_line_selection;
 use crate::output::{Formatter, OutputFormat};
+use seal_core::events::{Suggestion, ThreadKind};
+use seal_core::jj::reanchor::capture_anchor;
 use seal_core::scm::ScmRepo;
 
 /// Add a comment to a thread.
-#[tracing::instrument(skip(repo_root, message, format))]
+#[tracing::instrument(skip(repo_root, message, suggest, format))]
 pub fn run_comments_add(
     repo_root: &Path,
     thread_id: &str,
     message: &str,
+    suggest: Option<&str>,
+    draft: bool,
     author: Option<&str>,
     format: OutputFormat,
 ) -> Result<()> {
@@ -29,7 +33,26 @@ pub fn run_comments_add(
         return Err(thread_not_found_error(repo_root, thread_id));
     }
 
-    let result = services.comments().add_to_thread(thread_id, message, author)?;
+    let suggestion = suggest.map(parse_suggestion);
+    if draft {
+        services
+            .drafts()
+            .save_reply(thread_id, message, suggestion.clone(), author)?;
+        let output = serde_json::json!({
+            "thread_id": thread_id,
+            "draft": true,
+            "body": message,
+            "suggestion": suggestion,
+        });
+        let formatter = Formatter::new(format);
+        formatter.print(&output)?;
+        return Ok(());
+    }
+
+    let result =
+        services
+            .comments()
+            .add_to_thread(thread_id, message, suggestion.clone(), author)?;
 
     let author_str = seal_core::events::get_agent_identity(author)?;
     let output = serde_json::json!({
@@ -37,6 +60,7 @@ pub fn run_comments_add(
         "thread_id": thread_id,
         "author": author_str,
         "body": message,
+        "suggestion": suggestion,
     });
 
     let formatter = Formatter::new(format);
@@ -54,7 +78,8 @@ pub fn run_comments_add(
 /// # Arguments
 /// * `seal_root` - Path to main repo (where .seal/ lives)
 /// * `workspace_root` - Path to current workspace (for jj @ resolution)
-#[tracing::instrument(skip(seal_root, scm, message, format))]
+#[tracing::instrument(skip(seal_root, scm, message, suggest, format))]
+#[allow(clippy::too_many_arguments)]
 pub fn run_comment(
     seal_root: &Path,
     scm: &dyn ScmRepo,
@@ -62,6 +87,9 @@ pub fn run_comment(
     file: &str,
     line: &str,
     message: &str,
+    suggest: Option<&str>,
+    kind: Option<ThreadKind>,
+    draft: bool,
     author: Option<&str>,
     format: OutputFormat,
 ) -> Result<()> {
@@ -104,13 +132,54 @@ pub fn run_comment(
     // Resolve commit for thread creation
     let commit_hash = resolve_review_thread_commit(scm, &review);
 
+    // Snapshot the anchored lines so the thread can be re-anchored later
+    let anchor = if needs_new_thread {
+        capture_anchor(scm, file, &commit_hash, &selection).unwrap_or(None)
+    } else {
+        None
+    };
+
+    let suggestion = suggest.map(parse_suggestion);
+    if draft {
+        services.drafts().save_comment(
+            review_id,
+            file,
+            selection,
+            message,
+            suggestion.clone(),
+            commit_hash,
+            anchor,
+            kind,
+            author,
+        )?;
+        let output = serde_json::json!({
+            "review_id": review_id,
+            "file": file,
+            "line": start_line,
+            "draft": true,
+            "body": message,
+            "suggestion": suggestion,
+        });
+        let formatter = Formatter::new(format);
+        formatter.print(&output)?;
+        if format != OutputFormat::Json {
+            println!();
+            println!("Next:");
+            println!("  seal review submit {review_id} --lgtm|--block|--comment");
+        }
+        return Ok(());
+    }
+
     // Use core service to add comment (handles thread creation if needed)
     let result = services.comments().add_to_review(
         review_id,
         file,
         selection,
         message,
+        suggestion.clone(),
         commit_hash,
+        anchor,
+        kind,
         author,
     )?;
 
@@ -125,6 +194,7 @@ pub fn run_comment(
         "line": start_line,
         "author": author_str,
         "body": message,
+        "suggestion": suggestion,
     });
 
     let formatter = Formatter::new(format);
@@ -133,6 +203,13 @@ pub fn run_comment(
     Ok(())
 }
 
+/// Turn `--suggest` text into replacement lines. Empty text deletes the lines.
+pub(crate) fn parse_suggestion(text: &str) -> Suggestion {
+    Suggestion {
+        lines: text.lines().map(String::from).collect(),
+    }
+}
+
 /// List comments for a thread.
 pub fn run_comments_list(repo_root: &Path, thread_id: &str, format: OutputFormat) -> Result<()> {
     ensure_initialized(repo_root)?;
@@ -156,3 +233,89 @@ pub fn run_comments_list(repo_root: &Path, thread_id: &str, format: OutputFormat
 
     Ok(())
 }
+
+/// Edit a comment (original author only).
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the comment can't be edited.
+#[tracing::instrument(skip(repo_root, message, format))]
+pub fn run_comments_edit(
+    repo_root: &Path,
+    comment_id: &str,
+    message: &str,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(repo_root)?;
+
+    let services = open_services(repo_root)?;
+    services.comments().edit(comment_id, message, author)?;
+
+    let output = serde_json::json!({
+        "comment_id": comment_id,
+        "body": message,
+        "edited": true,
+    });
+
+    let formatter = Formatter::new(format);
+    formatter.print(&output)?;
+
+    Ok(())
+}
+
+/// Retract a comment (original author only).
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the comment can't be retracted.
+#[tracing::instrument(skip(repo_root, format))]
+pub fn run_comments_retract(
+    repo_root: &Path,
+    comment_id: &str,
+    reason: Option<String>,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(repo_root)?;
+
+    let services = open_services(repo_root)?;
+    let output = serde_json::json!({
+        "comment_id": comment_id,
+        "retracted": true,
+        "reason": reason,
+    });
+
+    services.comments().retract(comment_id, reason, author)?;
+
+    let formatter = Formatter::new(format);
+    formatter.print(&output)?;
+
+    Ok(())
+}
+
+/// Show the edit history of a comment.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the comment doesn't exist.
+pub fn run_comments_history(
+    repo_root: &Path,
+    comment_id: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(repo_root)?;
+
+    let services = open_services(repo_root)?;
+    let edits = services.comments().history(comment_id)?;
+
+    let formatter = Formatter::new(format);
+    formatter.print_list(
+        &edits,
+        "Comment has not been edited",
+        "edits",
+        &["seal comments edit <comment_id> \"...\""],
+    )?;
+
+    Ok(())
+}
@@ -2,12 +2,14 @@
 
 use anyhow::Result;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
 use crate::cli::commands::init::{events_path, index_path, is_initialized, SEAL_DIR};
 use seal_core::events::EventEnvelope;
-use seal_core::log::open_or_create;
+use seal_core::log::{list_review_ids, open_or_create, ReviewLog};
+use seal_core::signing::{PublicKeys, Verification};
 use crate::output::{Formatter, OutputFormat};
 use seal_core::projection::{sync_from_log_with_backup, ProjectionDb};
 use seal_core::scm::{resolve_backend, BackendDetection, ScmPreference};
@@ -83,6 +85,14 @@ pub fn run_doctor(
 
         // Check 5: index.db gitignored
         checks.push(check_index_gitignored(repo_root));
+
+        // Check 6: event signatures (v2 review logs only)
+        if detect_version(repo_root).ok().flatten() == Some(DataVersion::V2) {
+            checks.push(check_event_signatures(repo_root));
+        }
+
+        // Check 7: duplicate event IDs
+        checks.push(check_duplicate_events(repo_root));
     }
 
     let healthy = checks.iter().all(|c| c.status != "fail");
@@ -332,6 +342,185 @@ fn check_review_logs_parseable(repo_root: &Path) -> CheckResult {
     }
 }
 
+/// Check event signatures against the public keys in `.seal/keys/`.
+///
+/// Forged (badly signed) events fail the check. Unsigned events from agents
+/// that have a registered key, and signed events from agents without one,
+/// are warnings.
+fn check_event_signatures(repo_root: &Path) -> CheckResult {
+    let keys = match PublicKeys::load(repo_root) {
+        Ok(keys) => keys,
+        Err(e) => {
+            return CheckResult::fail(
+                "event_signatures",
+                &format!("Cannot load public keys: {e:#}"),
+                "Fix or remove malformed files in .seal/keys/",
+            );
+        }
+    };
+
+    let review_ids = list_review_ids(repo_root).unwrap_or_default();
+    let mut signed = 0usize;
+    let mut unsigned = 0usize;
+    let mut forged = Vec::new();
+    let mut unsigned_keyed = Vec::new();
+    let mut unknown_key = Vec::new();
+
+    for review_id in &review_ids {
+        let Ok(lines) = ReviewLog::new(repo_root, review_id).and_then(|log| log.read_lines_from(0))
+        else {
+            continue;
+        };
+        for (idx, line) in lines {
+            let location = format!("{review_id} line {}", idx + 1);
+            let author = EventEnvelope::from_json_line(&line)
+                .map(|e| e.author)
+                .unwrap_or_default();
+            match keys.verify_line(&line) {
+                Ok(Verification::Valid) => signed += 1,
+                Ok(Verification::Unsigned) => {
+                    unsigned += 1;
+                    if keys.contains(&author) {
+                        unsigned_keyed.push(format!("{location} ({author})"));
+                    }
+                }
+                Ok(Verification::UnknownKey) => unknown_key.push(format!("{location} ({author})")),
+                Ok(Verification::Invalid(reason)) => forged.push(format!("{location}: {reason}")),
+                // Unparseable lines are reported by the events_parseable check
+                Err(_) => {}
+            }
+        }
+    }
+
+    let summarize = |items: &[String]| {
+        let mut shown: Vec<&str> = items.iter().take(3).map(String::as_str).collect();
+        let more = items.len().saturating_sub(3);
+        let more_note = format!("... and {more} more");
+        if more > 0 {
+            shown.push(&more_note);
+        }
+        shown.join("; ")
+    };
+
+    if !forged.is_empty() {
+        return CheckResult::fail(
+            "event_signatures",
+            &format!(
+                "{} event(s) have invalid signatures: {}",
+                forged.len(),
+                summarize(&forged)
+            ),
+            "Investigate who wrote these events; restore the affected review logs from history",
+        );
+    }
+    if !unsigned_keyed.is_empty() {
+        return CheckResult::warn(
+            "event_signatures",
+            &format!(
+                "{} unsigned event(s) from agents with signing keys: {}",
+                unsigned_keyed.len(),
+                summarize(&unsigned_keyed)
+            ),
+            Some("Events written before the key was generated are expected; others may be forged"),
+        );
+    }
+    if !unknown_key.is_empty() {
+        return CheckResult::warn(
+            "event_signatures",
+            &format!(
+                "{} signed event(s) from agents without a public key: {}",
+                unknown_key.len(),
+                summarize(&unknown_key)
+            ),
+            Some("Commit the agent's .seal/keys/<agent>.pub"),
+        );
+    }
+
+    if keys.is_empty() && signed == 0 {
+        CheckResult::pass(
+            "event_signatures",
+            "signing not configured (no keys in .seal/keys)",
+        )
+    } else {
+        CheckResult::pass(
+            "event_signatures",
+            &format!("{signed} signed event(s) verified, {unsigned} unsigned"),
+        )
+    }
+}
+
+/// Check for events that appear more than once in the logs.
+///
+/// The projection applies each event ID once, so duplicates are harmless but
+/// usually mean a merge or copy went wrong. Legacy events without an ID are
+/// not checked.
+fn check_duplicate_events(repo_root: &Path) -> CheckResult {
+    let mut lines: Vec<(String, String)> = Vec::new();
+    if detect_version(repo_root).ok().flatten() == Some(DataVersion::V2) {
+        for review_id in list_review_ids(repo_root).unwrap_or_default() {
+            let Ok(review_lines) =
+                ReviewLog::new(repo_root, &review_id).and_then(|log| log.read_lines_from(0))
+            else {
+                continue;
+            };
+            lines.extend(
+                review_lines
+                    .into_iter()
+                    .map(|(idx, line)| (format!("{review_id} line {}", idx + 1), line)),
+            );
+        }
+    } else if let Ok(contents) = std::fs::read_to_string(events_path(repo_root)) {
+        lines.extend(
+            contents
+                .lines()
+                .enumerate()
+                .map(|(idx, line)| (format!("events.jsonl line {}", idx + 1), line.to_string())),
+        );
+    }
+
+    let mut first_seen: HashMap<String, String> = HashMap::new();
+    let mut duplicates = Vec::new();
+    let mut with_id = 0usize;
+    for (location, line) in lines {
+        // Unparseable lines are reported by the events_parseable check
+        let Some(event_id) = EventEnvelope::from_json_line(&line)
+            .ok()
+            .and_then(|e| e.event_id)
+        else {
+            continue;
+        };
+        with_id += 1;
+        match first_seen.get(&event_id) {
+            Some(first) => duplicates.push(format!("{event_id} at {location} (first at {first})")),
+            None => {
+                first_seen.insert(event_id, location);
+            }
+        }
+    }
+
+    if duplicates.is_empty() {
+        return CheckResult::pass(
+            "duplicate_events",
+            &format!("no duplicate event IDs ({with_id} events with IDs)"),
+        );
+    }
+
+    let more = duplicates.len().saturating_sub(3);
+    let mut shown = duplicates.iter().take(3).cloned().collect::<Vec<_>>();
+    if more > 0 {
+        shown.push(format!("... and {more} more"));
+    }
+    CheckResult::warn(
+        "duplicate_events",
+        &format!(
+            "{} duplicate event(s), applied only once: {}",
+            duplicates.len(),
+            shown.join("; ")
+        ),
+        Some("Remove the repeated lines from the affected logs"),
+    )
+}
+
 /// Check if index.db is in sync with events.jsonl.
 fn check_index_sync(repo_root: &Path) -> CheckResult {
     let db_result = ProjectionDb::open(&index_path(repo_root));
@@ -0,0 +1,217 @@
+//! Implementation of `seal events tail`.
+//!
+//! Prints events appended to review logs as NDJSON, for orchestrators that
+//! would otherwise poll `seal inbox`. Each line is the event as written in
+//! its log plus its `review_id`. Logs that can't be followed, e.g. because a
+//! checkout truncated them, are reported inline as `{"anomaly": {...}}` and
+//! followed again from their new end.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::cli::commands::helpers::ensure_initialized;
+use crate::cli::commands::init::index_path;
+use seal_core::core::tail::{EventFilter, TailItem};
+use seal_core::core::{CoreContext, SealServices};
+
+/// How often the review logs are checked with `--follow`.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Print events appended to review logs.
+///
+/// Starts where the projection last synced each log, or from the start of
+/// every log when the filter has a `since` time. With `follow`, keeps
+/// printing new events until interrupted or the output is closed.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized, or a log or the projection
+/// can't be read.
+pub fn run_events_tail(seal_root: &Path, filter: &EventFilter, follow: bool) -> Result<()> {
+    ensure_initialized(seal_root)?;
+    let services = CoreContext::new(seal_root, &index_path(seal_root))?.services()?;
+    write_tail(&services, filter, follow, &mut io::stdout().lock())
+}
+
+fn write_tail(
+    services: &SealServices,
+    filter: &EventFilter,
+    follow: bool,
+    out: &mut impl Write,
+) -> Result<()> {
+    let tail = services.tail();
+    let mut position = tail.start(filter.since.is_some())?;
+    loop {
+        for item in tail.poll(&mut position, filter)? {
+            if let TailItem::Anomaly(anomaly) = &item {
+                tracing::warn!(review_id = %anomaly.review_id, "{}", anomaly.detail);
+            }
+            let written = writeln!(out, "{}", record(&item)?).and_then(|()| out.flush());
+            match written {
+                // The reader went away, e.g. `| head`
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+                result => result?,
+            }
+        }
+        if !follow {
+            return Ok(());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The NDJSON record for a tailed item.
+fn record(item: &TailItem) -> Result<Value> {
+    match item {
+        TailItem::Event(event) => {
+            let mut record: Value = serde_json::from_str(&event.line)?;
+            if let Some(fields) = record.as_object_mut() {
+                fields.insert("review_id".to_string(), json!(event.review_id));
+            }
+            Ok(record)
+        }
+        TailItem::Anomaly(anomaly) => Ok(json!({ "anomaly": anomaly })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::commands::init::run_init;
+    use crate::cli::commands::serve::SealServer;
+    use chrono::DateTime;
+    use seal_core::scm::git::GitRepo;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(root)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn server(root: &Path, agent: &str) -> SealServer {
+        SealServer::open(
+            root,
+            Box::new(GitRepo::new(root.to_path_buf())),
+            Some(agent.to_string()),
+        )
+        .unwrap()
+    }
+
+    /// Create a review by `author` with one comment, returning its ID.
+    fn review(root: &Path, author: &str, reviewers: &[&str]) -> String {
+        let author = server(root, author);
+        let created = author
+            .handle(
+                "reviews.create",
+                json!({ "title": "Change", "reviewers": reviewers }),
+            )
+            .unwrap();
+        author
+            .handle(
+                "comments.add",
+                json!({ "review_id": created["review_id"], "file": "lib.rs", "line": "1", "body": "note" }),
+            )
+            .unwrap();
+        created["review_id"].as_str().unwrap().to_string()
+    }
+
+    /// Records printed by a one-shot tail of every event.
+    fn tail(root: &Path, filter: EventFilter) -> Vec<Value> {
+        let services = CoreContext::new(root, &index_path(root))
+            .unwrap()
+            .services()
+            .unwrap();
+        let filter = EventFilter {
+            since: Some(DateTime::UNIX_EPOCH),
+            ..filter
+        };
+        let mut out = Vec::new();
+        write_tail(&services, &filter, false, &mut out).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_tail_filters() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        std::fs::write(root.join("lib.rs"), "fn a() {}\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "add lib"]);
+        run_init(root, false).unwrap();
+
+        let for_alice = review(root, "bob", &["alice"]);
+        let unrelated = review(root, "carol", &[]);
+        server(root, "alice")
+            .handle(
+                "comments.add",
+                json!({ "review_id": for_alice, "file": "lib.rs", "line": "1", "body": "ok" }),
+            )
+            .unwrap();
+
+        let all = tail(root, EventFilter::default());
+        assert!(all
+            .iter()
+            .all(|r| r["review_id"].is_string() && r["event"].is_string()));
+        assert!(all.iter().any(|r| r["review_id"] == unrelated.as_str()));
+
+        let relevant = tail(
+            root,
+            EventFilter {
+                relevant_to: Some("alice".to_string()),
+                ..EventFilter::default()
+            },
+        );
+        assert!(!relevant.is_empty());
+        assert!(relevant
+            .iter()
+            .all(|r| r["review_id"] == for_alice.as_str() && r["author"] == "bob"));
+
+        let comments = tail(
+            root,
+            EventFilter {
+                review_id: Some(unrelated.clone()),
+                event_types: vec!["comment_added".to_string()],
+                ..EventFilter::default()
+            },
+        );
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0]["data"]["body"], "note");
+
+        // Without a start time, only events after the last sync are printed
+        let services = CoreContext::new(root, &index_path(root))
+            .unwrap()
+            .services()
+            .unwrap();
+        let mut out = Vec::new();
+        write_tail(&services, &EventFilter::default(), false, &mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_anomaly_record() {
+        let anomaly = TailItem::Anomaly(seal_core::projection::SyncAnomaly {
+            review_id: "cr-1".to_string(),
+            kind: seal_core::projection::AnomalyKind::Shrunk,
+            detail: "file shrunk".to_string(),
+        });
+        let record = record(&anomaly).unwrap();
+        assert_eq!(record["anomaly"]["kind"], "Shrunk");
+        assert_eq!(record["anomaly"]["review_id"], "cr-1");
+    }
+}
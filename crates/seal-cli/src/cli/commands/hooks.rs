@@ -0,0 +1,68 @@
+//! Implementation of `seal hooks` commands.
+//!
+//! Hooks live in `.seal/hooks.toml` and run as events are appended; see
+//! [`seal_core::hooks`].
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+use crate::cli::commands::helpers::ensure_initialized;
+use crate::output::{Formatter, OutputFormat};
+use seal_core::hooks::{self, Hooks, TrustStore};
+
+/// Trust the current contents of `.seal/hooks.toml` so its hooks run.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized, the hooks file is invalid, or
+/// the trust store can't be located or written.
+pub fn run_hooks_trust(seal_root: &Path, format: OutputFormat) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let store = TrustStore::for_user()
+        .context("Cannot determine where to record trusted hooks. Set HOME or XDG_CONFIG_HOME.")?;
+    let hooks = Hooks::trust(seal_root, &store)?;
+
+    let formatter = Formatter::new(format);
+    formatter.print(&serde_json::json!({
+        "hooks_file": Hooks::path(seal_root),
+        "hooks": hooks.len(),
+        "trusted_in": store.path(),
+    }))?;
+    Ok(())
+}
+
+/// Run hooks for events written since a time, e.g. after adding a hook or
+/// pulling events written elsewhere.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized, or the hooks file or review
+/// logs can't be read. Failed hook runs are reported, not returned.
+pub fn run_hooks_replay(
+    seal_root: &Path,
+    since: DateTime<Utc>,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let store = TrustStore::for_user();
+    let runs = hooks::replay(seal_root, store.as_ref(), since, dry_run)?;
+    let failed = runs.iter().filter(|run| run.ok == Some(false)).count();
+
+    let formatter = Formatter::new(format);
+    formatter.print_list(
+        &runs,
+        "No hooks matched events in that time range",
+        "runs",
+        &["seal events tail --since <time>"],
+    )?;
+
+    if failed > 0 && format != OutputFormat::Json {
+        eprintln!("{failed} hook run(s) failed");
+    }
+
+    Ok(())
+}
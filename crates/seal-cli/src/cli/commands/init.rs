@@ -1,9 +1,11 @@
 //! Implementation of `seal init` command.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use std::fs;
 use std::path::Path;
+use std::process::Command;
 
+use seal_core::scm::BackendDetection;
 use seal_core::version::{detect_version, write_version_file, DataVersion};
 
 /// The directory name for seal data
@@ -18,19 +20,49 @@ pub const REVIEWS_DIR: &str = "reviews";
 /// The gitignore filename
 pub const GITIGNORE_FILE: &str = ".gitignore";
 
-/// Files to ignore (local caches, not to be tracked)
+/// Files to ignore (local caches and unpublished drafts, not to be tracked)
 const GITIGNORE_CONTENT: &str = "# Local caches (do not track)
 index.db
 index.db-journal
+drafts/
 ";
 
+/// The gitattributes filename
+pub const GITATTRIBUTES_FILE: &str = ".gitattributes";
+
+/// Event logs merged by `seal merge-driver` (paths relative to `.seal/`)
+const GITATTRIBUTES_LINES: [&str; 2] = [
+    "events.jsonl merge=seal",
+    "reviews/*/events.jsonl merge=seal",
+];
+
+/// The jj merge tool's arguments (`$left` is ours, `$right` theirs)
+const JJ_MERGE_ARGS: &str =
+    r#"["merge-driver", "$base", "$left", "$right", "--output", "$output"]"#;
+
 /// Run the init command.
 ///
 /// Creates the .seal directory with v2 structure:
 /// - .seal/version (contains "2")
 /// - .seal/reviews/ (empty directory for per-review event logs)
 /// - .seal/.gitignore (ignores index.db)
-pub fn run_init(repo_root: &Path) -> Result<()> {
+///
+/// With `install_merge_driver`, also registers `seal merge-driver` with git
+/// and jj, even if seal was already initialized.
+///
+/// # Errors
+///
+/// Returns an error if the `.seal` directory or the merge driver config can't
+/// be written.
+pub fn run_init(repo_root: &Path, install_merge_driver: bool) -> Result<()> {
+    init_seal_dir(repo_root)?;
+    if install_merge_driver {
+        install_merge_driver_for(repo_root)?;
+    }
+    Ok(())
+}
+
+fn init_seal_dir(repo_root: &Path) -> Result<()> {
     // Auto-migrate from legacy .crit/ if present
     crate::cli::commands::helpers::auto_migrate_crit_to_seal(repo_root)?;
 
@@ -101,7 +133,6 @@ fn ensure_gitignore(seal_dir: &Path) -> Result<()> {
     let gitignore_path = seal_dir.join(GITIGNORE_FILE);
 
     if gitignore_path.exists() {
-        // Check if index.db is already ignored
         let content = fs::read_to_string(&gitignore_path)
             .with_context(|| format!("Failed to read {}", gitignore_path.display()))?;
 
@@ -110,6 +141,11 @@ fn ensure_gitignore(seal_dir: &Path) -> Result<()> {
             let updated = format!("{}\n{}", content.trim_end(), GITIGNORE_CONTENT);
             fs::write(&gitignore_path, updated)
                 .with_context(|| format!("Failed to update {}", gitignore_path.display()))?;
+        } else if !content.lines().any(|line| line.trim() == "drafts/") {
+            // Written before drafts existed
+            let updated = format!("{}\ndrafts/\n", content.trim_end());
+            fs::write(&gitignore_path, updated)
+                .with_context(|| format!("Failed to update {}", gitignore_path.display()))?;
         }
     } else {
         // Create new gitignore
@@ -120,6 +156,98 @@ fn ensure_gitignore(seal_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Register `seal merge-driver` so concurrent appends to a review log merge
+/// as a union instead of leaving conflict markers.
+///
+/// git gets a `merge=seal` attribute on the logs plus a merge driver and a
+/// mergetool in its local config; jj gets a merge tool in its repo config,
+/// since jj materializes conflicts instead of calling merge drivers.
+fn install_merge_driver_for(repo_root: &Path) -> Result<()> {
+    let detection = BackendDetection::detect(repo_root);
+    if detection.git_root.is_none() && detection.jj_root.is_none() {
+        bail!(
+            "No git or jj repository found at {}; cannot install merge driver",
+            repo_root.display()
+        );
+    }
+
+    if detection.git_root.is_some() {
+        ensure_gitattributes(&repo_root.join(SEAL_DIR))?;
+        for (key, value) in [
+            ("merge.seal.name", "seal event log union merge"),
+            ("merge.seal.driver", "seal merge-driver %O %A %B"),
+            (
+                "mergetool.seal.cmd",
+                r#"seal merge-driver "$BASE" "$LOCAL" "$REMOTE" --output "$MERGED""#,
+            ),
+            ("mergetool.seal.trustExitCode", "true"),
+        ] {
+            run_config(repo_root, "git", &["config", key, value])?;
+        }
+        println!("✓ Registered seal merge driver with git");
+        println!(
+            "  Commit .seal/.gitattributes; other clones run `seal init --install-merge-driver`"
+        );
+    }
+
+    if detection.jj_root.is_some() {
+        for (key, value) in [
+            ("merge-tools.seal.program", "seal"),
+            ("merge-tools.seal.merge-args", JJ_MERGE_ARGS),
+        ] {
+            run_config(repo_root, "jj", &["config", "set", "--repo", key, value])?;
+        }
+        println!("✓ Registered seal merge tool with jj");
+        println!("  Resolve conflicted review logs with: jj resolve --tool seal <path>");
+    }
+
+    Ok(())
+}
+
+/// Ensure .gitattributes routes the event logs to the seal merge driver.
+fn ensure_gitattributes(seal_dir: &Path) -> Result<()> {
+    let path = seal_dir.join(GITATTRIBUTES_FILE);
+    let mut content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    let missing: Vec<&str> = GITATTRIBUTES_LINES
+        .into_iter()
+        .filter(|line| !content.lines().any(|existing| existing.trim() == *line))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for line in missing {
+        content.push_str(line);
+        content.push('\n');
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Run a `git config` / `jj config set` command in the repository.
+fn run_config(repo_root: &Path, program: &str, args: &[&str]) -> Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .with_context(|| format!("Failed to run {program}"))?;
+    if !output.status.success() {
+        bail!(
+            "{program} {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
 /// Check if seal is initialized in the given directory.
 ///
 /// Returns true if either v1 (events.jsonl) or v2 (version file or reviews dir) exists.
@@ -161,7 +289,7 @@ mod tests {
         let temp = TempDir::new().unwrap();
         let repo_root = temp.path();
 
-        run_init(repo_root).unwrap();
+        run_init(repo_root, false).unwrap();
 
         // Check v2 structure
         assert!(repo_root.join(SEAL_DIR).exists());
@@ -182,6 +310,22 @@ mod tests {
         let gitignore =
             std::fs::read_to_string(repo_root.join(SEAL_DIR).join(GITIGNORE_FILE)).unwrap();
         assert!(gitignore.contains("index.db"));
+        assert!(gitignore.contains("drafts/"));
+    }
+
+    #[test]
+    fn test_init_adds_drafts_to_existing_gitignore() {
+        let temp = TempDir::new().unwrap();
+        let repo_root = temp.path();
+        run_init(repo_root, false).unwrap();
+
+        let gitignore_path = repo_root.join(SEAL_DIR).join(GITIGNORE_FILE);
+        std::fs::write(&gitignore_path, "index.db\nindex.db-journal\n").unwrap();
+        run_init(repo_root, false).unwrap();
+        run_init(repo_root, false).unwrap();
+
+        let gitignore = std::fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(gitignore.matches("drafts/").count(), 1);
     }
 
     #[test]
@@ -190,14 +334,51 @@ mod tests {
         let repo_root = temp.path();
 
         // First init
-        run_init(repo_root).unwrap();
+        run_init(repo_root, false).unwrap();
 
         // Second init should succeed
-        run_init(repo_root).unwrap();
+        run_init(repo_root, false).unwrap();
 
         assert!(is_initialized(repo_root));
     }
 
+    #[test]
+    fn test_init_installs_merge_driver_in_git_repo() {
+        let temp = TempDir::new().unwrap();
+        let repo_root = temp.path();
+        let status = Command::new("git")
+            .arg("init")
+            .current_dir(repo_root)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success());
+
+        run_init(repo_root, true).unwrap();
+        // Again, on an initialized repo: nothing is duplicated
+        run_init(repo_root, true).unwrap();
+
+        let attributes =
+            fs::read_to_string(repo_root.join(SEAL_DIR).join(GITATTRIBUTES_FILE)).unwrap();
+        assert_eq!(attributes.lines().collect::<Vec<_>>(), GITATTRIBUTES_LINES);
+
+        let driver = Command::new("git")
+            .args(["config", "merge.seal.driver"])
+            .current_dir(repo_root)
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&driver.stdout).trim(),
+            "seal merge-driver %O %A %B"
+        );
+    }
+
+    #[test]
+    fn test_install_merge_driver_requires_repo() {
+        let temp = TempDir::new().unwrap();
+        assert!(run_init(temp.path(), true).is_err());
+    }
+
     #[test]
     fn test_is_initialized_false_when_missing() {
         let temp = TempDir::new().unwrap();
@@ -0,0 +1,42 @@
+//! Implementation of `seal keys` commands.
+//!
+//! Signing keys let other agents verify who wrote an event. The public key is
+//! written to `.seal/keys/<agent>.pub` and should be committed.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cli::commands::helpers::ensure_initialized;
+use crate::output::{Formatter, OutputFormat};
+use seal_core::events::get_agent_identity;
+use seal_core::signing::generate_key;
+
+/// Generate a signing key for the current agent.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized, the agent already has a key
+/// and `force` isn't set, or the key can't be written.
+pub fn run_keys_generate(
+    seal_root: &Path,
+    author: Option<&str>,
+    force: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let agent = get_agent_identity(author)?;
+    let generated = generate_key(seal_root, &agent, force)?;
+
+    let formatter = Formatter::new(format);
+    formatter.print(&generated)?;
+
+    if format != OutputFormat::Json {
+        println!();
+        println!("Events written as {agent} are now signed. Commit the public key:");
+        println!("  git add {}", generated.public_key_path.display());
+        println!("  seal doctor    # verifies signatures");
+    }
+
+    Ok(())
+}
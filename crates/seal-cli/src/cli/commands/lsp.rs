@@ -0,0 +1,1041 @@
+//! Implementation of `seal lsp`.
+//!
+//! A Language Server Protocol server that shows open review threads in the
+//! editor. Each open thread on an open or approved review becomes a
+//! diagnostic on the lines it is anchored to, followed to the current commit
+//! with `calculate_drift` and, when the buffer has unsaved edits, to the
+//! buffer by re-anchoring the thread's snapshot.
+//!
+//! Code actions reply to, resolve, or apply a suggestion from the thread
+//! under the cursor. The review logs are polled every second, so threads
+//! written by other agents appear without reopening the file. Drift is
+//! cached per commit, so edits to the buffer only re-anchor.
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cli::commands::helpers::ensure_initialized;
+use crate::cli::commands::init::index_path;
+use crate::jsonrpc::{self, RpcError, RpcResult, PARSE_ERROR};
+use seal_core::core::threads::follow_renames;
+use seal_core::core::{CoreContext, SealServices};
+use seal_core::jj::drift::calculate_drift;
+use seal_core::jj::reanchor::reanchor;
+use seal_core::projection::{ThreadDetail, ThreadSummary};
+use seal_core::scm::ScmRepo;
+
+/// How often the review logs are checked for new events.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Review statuses whose threads are shown.
+const ACTIVE_STATUSES: [&str; 2] = ["open", "approved"];
+
+const RESOLVE_COMMAND: &str = "seal.resolve";
+const REPLY_COMMAND: &str = "seal.reply";
+
+/// Canned replies offered when `seal.reply` is run without a message.
+const QUICK_REPLIES: [&str; 3] = ["Done", "Won't fix", "Acknowledged"];
+
+/// LSP diagnostic severities.
+const WARNING: u8 = 2;
+const INFORMATION: u8 = 3;
+const HINT: u8 = 4;
+
+/// Serve the Language Server Protocol on stdin/stdout until the client exits.
+///
+/// # Errors
+///
+/// Returns an error if the projection can't be opened, or stdin or stdout fail.
+pub fn run_lsp(seal_root: &Path, scm: Box<dyn ScmRepo>, identity: Option<String>) -> Result<()> {
+    let mut server = LspServer::open(seal_root, scm, identity)?;
+
+    // Read on another thread so the logs can be polled while the client is idle
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let stdin = io::stdin();
+        loop {
+            let message = jsonrpc::read_framed(&mut stdin.lock());
+            let done = !matches!(message, Ok(Some(_)));
+            if sender.send(message).is_err() || done {
+                break;
+            }
+        }
+    });
+
+    let mut stdout = io::stdout().lock();
+    let mut last_poll = Instant::now();
+    while !server.exited {
+        let wait = POLL_INTERVAL.saturating_sub(last_poll.elapsed());
+        let mut outgoing = match receiver.recv_timeout(wait) {
+            Ok(Ok(Some(text))) => match serde_json::from_str(&text) {
+                Ok(message) => server.handle(&message),
+                Err(e) => vec![jsonrpc::response(
+                    Value::Null,
+                    Err(RpcError::new(PARSE_ERROR, format!("Parse error: {e}"))),
+                )],
+            },
+            Ok(Ok(None)) | Err(RecvTimeoutError::Disconnected) => break,
+            Ok(Err(e)) => return Err(e),
+            Err(RecvTimeoutError::Timeout) => Vec::new(),
+        };
+        // Poll on a timer rather than when idle, so a busy client can't starve it
+        if last_poll.elapsed() >= POLL_INTERVAL {
+            outgoing.extend(server.poll());
+            last_poll = Instant::now();
+        }
+        for message in &outgoing {
+            jsonrpc::write_framed(&mut stdout, message)?;
+        }
+    }
+    Ok(())
+}
+
+/// Editor state and the services used to answer requests.
+pub struct LspServer {
+    services: SealServices,
+    scm: Box<dyn ScmRepo>,
+    /// Author of events written through the server (`--agent`, else the environment)
+    identity: Option<String>,
+    /// Text of each open document, by URI
+    documents: HashMap<String, String>,
+    /// Threads last published for each open document, by URI
+    placements: HashMap<String, Vec<Placement>>,
+    /// Open threads on active reviews as of the last refresh
+    threads: Vec<ThreadSummary>,
+    /// Details of threads placed since the last refresh, by thread ID
+    details: HashMap<String, ThreadDetail>,
+    /// First line (1-based) of each thread in `commit`, by thread ID
+    drifted: HashMap<String, Option<u32>>,
+    /// Contents of each file in `commit`, by path
+    committed: HashMap<String, Option<String>>,
+    /// Thread each outstanding quick-reply prompt is for, by request ID
+    pending_replies: HashMap<String, String>,
+    next_request: u64,
+    /// Commit the placements were computed against
+    commit: Option<String>,
+    exited: bool,
+}
+
+/// Where a thread is shown in a document.
+#[derive(Debug, Clone)]
+struct Placement {
+    thread_id: String,
+    /// First line (0-based)
+    start: u32,
+    /// Last line (0-based, inclusive)
+    end: u32,
+    /// Latest comment with a suggestion, if any
+    suggestion: Option<(String, String)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentItem {
+    uri: String,
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidOpenParams {
+    text_document: TextDocumentItem,
+}
+
+#[derive(Deserialize)]
+struct DocumentId {
+    uri: String,
+}
+
+#[derive(Deserialize)]
+struct ContentChange {
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidChangeParams {
+    text_document: DocumentId,
+    content_changes: Vec<ContentChange>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidSaveParams {
+    text_document: DocumentId,
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidCloseParams {
+    text_document: DocumentId,
+}
+
+#[derive(Deserialize)]
+struct Position {
+    line: u32,
+}
+
+#[derive(Deserialize)]
+struct Range {
+    start: Position,
+    end: Position,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CodeActionParams {
+    text_document: DocumentId,
+    range: Range,
+}
+
+#[derive(Deserialize)]
+struct ExecuteCommandParams {
+    command: String,
+    #[serde(default)]
+    arguments: Vec<Value>,
+}
+
+impl LspServer {
+    /// Open the projection for `seal_root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if seal isn't initialized or the projection can't be opened.
+    pub fn open(seal_root: &Path, scm: Box<dyn ScmRepo>, identity: Option<String>) -> Result<Self> {
+        ensure_initialized(seal_root)?;
+        let ctx = CoreContext::new(seal_root, &index_path(seal_root))?;
+        Ok(Self {
+            services: ctx.services()?,
+            scm,
+            identity,
+            documents: HashMap::new(),
+            placements: HashMap::new(),
+            threads: Vec::new(),
+            details: HashMap::new(),
+            drifted: HashMap::new(),
+            committed: HashMap::new(),
+            pending_replies: HashMap::new(),
+            next_request: 0,
+            commit: None,
+            exited: false,
+        })
+    }
+
+    /// Handle one message from the client, returning the messages to send.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let id = message.get("id").cloned();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // A response to one of our requests
+            return id.map_or_else(Vec::new, |id| self.handle_response(&id, message));
+        };
+
+        tracing::debug!(method, "lsp message");
+        let mut outgoing = Vec::new();
+        match id {
+            Some(id) => {
+                let result = self.request(method, params, &mut outgoing);
+                outgoing.insert(0, jsonrpc::response(id, result));
+            }
+            None => {
+                if let Err(e) = self.notify(method, params, &mut outgoing) {
+                    tracing::warn!(method, error = %e.message, "lsp notification failed");
+                }
+            }
+        }
+        outgoing
+    }
+
+    /// Pick up events written by other processes, and commits made since the
+    /// last publish, returning diagnostics for documents they change.
+    pub fn poll(&mut self) -> Vec<Value> {
+        let applied = match self.services.sync().sync() {
+            Ok(report) => report.applied,
+            Err(e) => {
+                tracing::warn!(error = %e, "sync failed");
+                return Vec::new();
+            }
+        };
+        let commit = self.scm.current_commit().ok();
+        if applied == 0 && commit == self.commit {
+            return Vec::new();
+        }
+        self.publish_all().unwrap_or_else(|e| {
+            tracing::warn!(error = %e.message, "publishing diagnostics failed");
+            Vec::new()
+        })
+    }
+
+    fn request(&mut self, method: &str, params: Value, outgoing: &mut Vec<Value>) -> RpcResult {
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": {
+                        "openClose": true,
+                        "change": 1,
+                        "save": { "includeText": true },
+                    },
+                    "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+                    "executeCommandProvider": { "commands": [RESOLVE_COMMAND, REPLY_COMMAND] },
+                },
+                "serverInfo": { "name": "seal", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => Ok(Value::Null),
+            "textDocument/codeAction" => Ok(self.code_actions(&jsonrpc::params(params)?)),
+            "workspace/executeCommand" => self.execute(&jsonrpc::params(params)?, outgoing),
+            _ => Err(RpcError::method_not_found(method)),
+        }
+    }
+
+    fn notify(
+        &mut self,
+        method: &str,
+        params: Value,
+        outgoing: &mut Vec<Value>,
+    ) -> Result<(), RpcError> {
+        let uri = match method {
+            "textDocument/didOpen" => {
+                let p: DidOpenParams = jsonrpc::params(params)?;
+                self.documents
+                    .insert(p.text_document.uri.clone(), p.text_document.text);
+                p.text_document.uri
+            }
+            "textDocument/didChange" => {
+                let p: DidChangeParams = jsonrpc::params(params)?;
+                // Full sync: the last change holds the whole document
+                let Some(change) = p.content_changes.into_iter().last() else {
+                    return Ok(());
+                };
+                let uri = p.text_document.uri;
+                self.documents.insert(uri.clone(), change.text);
+                // Only the buffer changed; new events and commits come with polling
+                outgoing.push(self.publish(&uri));
+                return Ok(());
+            }
+            "textDocument/didSave" => {
+                let p: DidSaveParams = jsonrpc::params(params)?;
+                if let Some(text) = p.text {
+                    self.documents.insert(p.text_document.uri.clone(), text);
+                }
+                p.text_document.uri
+            }
+            "textDocument/didClose" => {
+                let p: DidCloseParams = jsonrpc::params(params)?;
+                self.documents.remove(&p.text_document.uri);
+                self.placements.remove(&p.text_document.uri);
+                outgoing.push(publish(&p.text_document.uri, &[]));
+                return Ok(());
+            }
+            "exit" => {
+                self.exited = true;
+                return Ok(());
+            }
+            // initialized, $/cancelRequest, $/setTrace, ...
+            _ => return Ok(()),
+        };
+
+        self.refresh()?;
+        outgoing.push(self.publish(&uri));
+        Ok(())
+    }
+
+    /// Handle the client's answer to a quick-reply prompt.
+    fn handle_response(&mut self, id: &Value, message: &Value) -> Vec<Value> {
+        let Some(thread_id) = self.pending_replies.remove(&id.to_string()) else {
+            return Vec::new();
+        };
+        // The prompt was dismissed
+        let Some(title) = message["result"]["title"].as_str() else {
+            return Vec::new();
+        };
+        let result = self
+            .reply(&thread_id, title)
+            .and_then(|()| self.publish_all());
+        result.unwrap_or_else(|e| vec![show_error(&e.message)])
+    }
+
+    fn code_actions(&self, p: &CodeActionParams) -> Value {
+        let uri = &p.text_document.uri;
+        let (Some(placements), Some(text)) = (self.placements.get(uri), self.documents.get(uri))
+        else {
+            return json!([]);
+        };
+
+        let mut actions = Vec::new();
+        for placement in placements
+            .iter()
+            .filter(|pl| pl.start <= p.range.end.line && p.range.start.line <= pl.end)
+        {
+            let thread_id = &placement.thread_id;
+            actions.push(command_action(
+                &format!("Reply to {thread_id}"),
+                REPLY_COMMAND,
+                thread_id,
+            ));
+            actions.push(command_action(
+                &format!("Resolve {thread_id}"),
+                RESOLVE_COMMAND,
+                thread_id,
+            ));
+
+            let Some((comment_id, author)) = &placement.suggestion else {
+                continue;
+            };
+            let title = format!("Apply suggestion from {author} ({comment_id})");
+            match self
+                .services
+                .suggestions()
+                .apply_to_contents(self.scm.as_ref(), comment_id, text)
+            {
+                Ok((_, patched)) => actions.push(json!({
+                    "title": title,
+                    "kind": "quickfix",
+                    "isPreferred": true,
+                    "edit": { "changes": { uri.as_str(): [{
+                        "range": { "start": { "line": 0, "character": 0 }, "end": end_of(text) },
+                        "newText": patched,
+                    }] } },
+                })),
+                Err(e) => actions.push(json!({
+                    "title": title,
+                    "kind": "quickfix",
+                    "disabled": { "reason": e.to_string() },
+                })),
+            }
+        }
+        Value::Array(actions)
+    }
+
+    fn execute(&mut self, p: &ExecuteCommandParams, outgoing: &mut Vec<Value>) -> RpcResult {
+        let thread_id = p
+            .arguments
+            .first()
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::invalid_params("expected a thread ID argument"))?
+            .to_string();
+        match p.command.as_str() {
+            RESOLVE_COMMAND => {
+                self.services
+                    .threads()
+                    .resolve(&thread_id, None, self.identity.as_deref())?;
+            }
+            REPLY_COMMAND => {
+                if let Some(message) = p.arguments.get(1).and_then(Value::as_str) {
+                    self.reply(&thread_id, message)?;
+                } else {
+                    // Ask which reply to post; the answer arrives as a response
+                    self.next_request += 1;
+                    let id = json!(format!("seal-{}", self.next_request));
+                    self.pending_replies
+                        .insert(id.to_string(), thread_id.clone());
+                    outgoing.push(json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "method": "window/showMessageRequest",
+                        "params": {
+                            "type": INFORMATION,
+                            "message": format!("Reply to {thread_id}"),
+                            "actions": QUICK_REPLIES.map(|title| json!({ "title": title })),
+                        },
+                    }));
+                    return Ok(Value::Null);
+                }
+            }
+            command => {
+                return Err(RpcError::invalid_params(format!(
+                    "unknown command: {command}"
+                )))
+            }
+        }
+        outgoing.extend(self.publish_all()?);
+        Ok(Value::Null)
+    }
+
+    fn reply(&self, thread_id: &str, body: &str) -> Result<(), RpcError> {
+        self.services
+            .comments()
+            .add_to_thread(thread_id, body, None, self.identity.as_deref())?;
+        Ok(())
+    }
+
+    /// Refresh, then recompute diagnostics for every open document.
+    fn publish_all(&mut self) -> Result<Vec<Value>, RpcError> {
+        self.refresh()?;
+        let uris: Vec<String> = self.documents.keys().cloned().collect();
+        Ok(uris.iter().map(|uri| self.publish(uri)).collect())
+    }
+
+    /// Sync, then reload open threads on active reviews, with paths followed
+    /// through renames. Drift is only recomputed if the commit changed.
+    fn refresh(&mut self) -> Result<(), RpcError> {
+        self.services.sync().sync()?;
+        let mut threads = Vec::new();
+        for status in ACTIVE_STATUSES {
+            for review in
+                self.services
+                    .reviews()
+                    .list_filtered(Some(status), None, None, false, None)?
+            {
+                threads.extend(self.services.threads().list(
+                    &review.review_id,
+                    Some("open"),
+                    None,
+                )?);
+            }
+        }
+        let commit = self.scm.current_commit().ok();
+        if commit != self.commit {
+            self.drifted.clear();
+            self.committed.clear();
+            self.commit = commit;
+        }
+        if let Some(commit) = &self.commit {
+            follow_renames(self.scm.as_ref(), commit, &mut threads);
+        }
+        self.threads = threads;
+        self.details.clear();
+        Ok(())
+    }
+
+    /// Load what placing threads in `path` needs and isn't cached yet: the
+    /// committed file, thread details, and each thread's drift.
+    fn load_file(&mut self, path: &str) {
+        if !self.committed.contains_key(path) {
+            let contents = self
+                .commit
+                .as_deref()
+                .and_then(|commit| self.scm.show_file(commit, path).ok());
+            self.committed.insert(path.to_string(), contents);
+        }
+        for summary in self.threads.iter().filter(|t| t.file_path == path) {
+            if !self.details.contains_key(&summary.thread_id) {
+                if let Ok(thread) = self.services.threads().get(&summary.thread_id) {
+                    self.details.insert(summary.thread_id.clone(), thread);
+                }
+            }
+            if !self.drifted.contains_key(&summary.thread_id) {
+                let line = drifted_line(self.scm.as_ref(), self.commit.as_deref(), summary);
+                self.drifted.insert(summary.thread_id.clone(), line);
+            }
+        }
+    }
+
+    /// Place the loaded threads in the document at `uri` and build its
+    /// diagnostics.
+    fn publish(&mut self, uri: &str) -> Value {
+        let Some(path) = self.relative_path(uri) else {
+            return publish(uri, &[]);
+        };
+        if !self.documents.contains_key(uri) {
+            return publish(uri, &[]);
+        }
+        self.load_file(&path);
+
+        let text = &self.documents[uri];
+        let committed = self.committed.get(&path).and_then(Option::as_deref);
+        let lines: Vec<&str> = text.lines().collect();
+
+        let mut placements = Vec::new();
+        let mut diagnostics = Vec::new();
+        for summary in self.threads.iter().filter(|t| t.file_path == path) {
+            let Some(thread) = self.details.get(&summary.thread_id) else {
+                continue;
+            };
+            let drifted = self.drifted.get(&summary.thread_id).copied().flatten();
+            let (start, end, moved) = locate(summary, thread, drifted, text, committed);
+            // Clamp to the document, e.g. when the anchored lines were deleted
+            let last = u32::try_from(lines.len().saturating_sub(1)).unwrap_or(u32::MAX);
+            let (start, end) = (start.min(last), end.min(last).max(start.min(last)));
+            let end_character = lines
+                .get(end as usize)
+                .map_or(0, |line| line.encode_utf16().count());
+
+            let mut message = thread_message(thread);
+            if moved {
+                message.push_str("\n(the commented code has changed since)");
+            }
+            diagnostics.push(json!({
+                "range": {
+                    "start": { "line": start, "character": 0 },
+                    "end": { "line": end, "character": end_character },
+                },
+                "severity": severity(thread.kind.as_deref()),
+                "source": "seal",
+                "code": thread.thread_id,
+                "message": message,
+                "data": { "review_id": thread.review_id, "thread_id": thread.thread_id },
+            }));
+            placements.push(Placement {
+                thread_id: thread.thread_id.clone(),
+                start,
+                end,
+                suggestion: thread
+                    .comments
+                    .iter()
+                    .rev()
+                    .find(|c| c.retracted_at.is_none() && c.suggestion.is_some())
+                    .map(|c| (c.comment_id.clone(), c.author.clone())),
+            });
+        }
+
+        self.placements.insert(uri.to_string(), placements);
+        publish(uri, &diagnostics)
+    }
+
+    /// Path of a `file://` URI relative to the repository root.
+    fn relative_path(&self, uri: &str) -> Option<String> {
+        let path = PathBuf::from(percent_decode(uri.strip_prefix("file://")?));
+        let root = self.scm.root();
+        let relative = match path.strip_prefix(root) {
+            Ok(relative) => relative.to_path_buf(),
+            // Either side may be reached through a symlink
+            Err(_) => path
+                .canonicalize()
+                .ok()?
+                .strip_prefix(root.canonicalize().ok()?)
+                .ok()?
+                .to_path_buf(),
+        };
+        Some(relative.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+/// First line (1-based) of a thread's selection in `commit`, followed by drift.
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+fn drifted_line(scm: &dyn ScmRepo, commit: Option<&str>, summary: &ThreadSummary) -> Option<u32> {
+    // Drift diffs a single path, so it can't follow a rename
+    let commit = commit.filter(|_| summary.moved_from.is_none())?;
+    calculate_drift(
+        scm,
+        &summary.file_path,
+        summary.selection_start as u32,
+        &summary.commit_hash,
+        commit,
+    )
+    .ok()?
+    .current_line()
+}
+
+/// The 0-based lines a thread covers in `text`, and whether its anchored
+/// lines could only be approximated.
+///
+/// `drifted` is where the selection starts in the current commit; if the
+/// buffer has unsaved edits, the thread's snapshot is re-anchored in it.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn locate(
+    summary: &ThreadSummary,
+    thread: &ThreadDetail,
+    drifted: Option<u32>,
+    text: &str,
+    committed: Option<&str>,
+) -> (u32, u32, bool) {
+    let original = u32::try_from(summary.selection_start).unwrap_or(0);
+    let span = summary.selection_end.map_or(0, |end| {
+        u32::try_from(end.saturating_sub(summary.selection_start)).unwrap_or(0)
+    });
+    let range = |line: u32| {
+        let start = line.saturating_sub(1);
+        (start, start.saturating_add(span))
+    };
+
+    if committed == Some(text) {
+        if let Some(line) = drifted {
+            let (start, end) = range(line);
+            return (start, end, false);
+        }
+    }
+    if let Some(found) = thread
+        .anchor
+        .as_ref()
+        .and_then(|snapshot| reanchor(snapshot, text, drifted.unwrap_or(original)))
+    {
+        return (
+            found.start_line.saturating_sub(1),
+            found.end_line.saturating_sub(1),
+            false,
+        );
+    }
+    let (start, end) = range(drifted.unwrap_or(original));
+    (start, end, drifted.is_none())
+}
+
+/// A `textDocument/publishDiagnostics` notification.
+fn publish(uri: &str, diagnostics: &[Value]) -> Value {
+    jsonrpc::notification(
+        "textDocument/publishDiagnostics",
+        json!({ "uri": uri, "diagnostics": diagnostics }),
+    )
+}
+
+fn show_error(message: &str) -> Value {
+    jsonrpc::notification(
+        "window/showMessage",
+        json!({ "type": 1, "message": message }),
+    )
+}
+
+fn command_action(title: &str, command: &str, thread_id: &str) -> Value {
+    json!({
+        "title": title,
+        "kind": "quickfix",
+        "command": { "title": title, "command": command, "arguments": [thread_id] },
+    })
+}
+
+/// Blocking threads stand out; the rest stay out of the way.
+fn severity(kind: Option<&str>) -> u8 {
+    match kind {
+        Some("blocking") => WARNING,
+        Some("nit" | "praise") => HINT,
+        _ => INFORMATION,
+    }
+}
+
+/// The thread's conversation, one comment per line.
+fn thread_message(thread: &ThreadDetail) -> String {
+    let mut message = thread.kind.as_ref().map_or_else(
+        || thread.thread_id.clone(),
+        |kind| format!("[{kind}] {}", thread.thread_id),
+    );
+    for comment in thread.comments.iter().filter(|c| c.retracted_at.is_none()) {
+        let _ = write!(message, "\n{}: {}", comment.author, comment.body);
+        if comment.suggestion.is_some() {
+            message.push_str(" (suggestion)");
+        }
+    }
+    message
+}
+
+/// Position just past the last character of `text`.
+fn end_of(text: &str) -> Value {
+    let line = text.matches('\n').count();
+    let character = text
+        .rsplit('\n')
+        .next()
+        .map_or(0, |last| last.encode_utf16().count());
+    json!({ "line": line, "character": character })
+}
+
+/// Decode `%XX` escapes in a URI path.
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(byte) = path
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::commands::init::run_init;
+    use crate::cli::commands::serve::SealServer;
+    use seal_core::scm::git::GitRepo;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(root)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// A git repo with `lib.rs` committed and a review by bob with a
+    /// blocking thread on line 2. Returns the repo, bob's server and the
+    /// thread ID.
+    fn setup_review(suggest: Option<&str>) -> (TempDir, SealServer, String) {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        std::fs::write(root.join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "add lib"]);
+        run_init(root, false).unwrap();
+
+        let bob = SealServer::open(
+            root,
+            Box::new(GitRepo::new(root.to_path_buf())),
+            Some("bob".to_string()),
+        )
+        .unwrap();
+        let review = bob
+            .handle("reviews.create", json!({ "title": "Add lib" }))
+            .unwrap();
+        let comment = bob
+            .handle(
+                "comments.add",
+                json!({
+                    "review_id": review["review_id"],
+                    "file": "lib.rs",
+                    "line": "2",
+                    "body": "why?",
+                    "suggest": suggest,
+                    "kind": "blocking",
+                }),
+            )
+            .unwrap();
+        let thread_id = comment["thread_id"].as_str().unwrap().to_string();
+        (temp, bob, thread_id)
+    }
+
+    /// An LSP client session against `root` as alice.
+    struct Client {
+        server: LspServer,
+        uri: String,
+        next_id: u64,
+    }
+
+    impl Client {
+        fn new(root: &Path) -> Self {
+            let mut server = LspServer::open(
+                root,
+                Box::new(GitRepo::new(root.to_path_buf())),
+                Some("alice".to_string()),
+            )
+            .unwrap();
+            let init = server.handle(
+                &json!({ "jsonrpc": "2.0", "id": 0, "method": "initialize", "params": {} }),
+            );
+            assert!(init[0]["result"]["capabilities"]["codeActionProvider"].is_object());
+            Self {
+                server,
+                uri: format!("file://{}", root.join("lib.rs").display()),
+                next_id: 0,
+            }
+        }
+
+        /// Send a request; returns its result and any messages sent with it.
+        fn request(&mut self, method: &str, params: Value) -> (Value, Vec<Value>) {
+            self.next_id += 1;
+            let mut outgoing = self.server.handle(&json!({
+                "jsonrpc": "2.0", "id": self.next_id, "method": method, "params": params,
+            }));
+            let response = outgoing.remove(0);
+            assert_eq!(response["id"], self.next_id);
+            assert!(response.get("error").is_none(), "{response}");
+            (response["result"].clone(), outgoing)
+        }
+
+        fn notify(&mut self, method: &str, params: Value) -> Vec<Value> {
+            self.server
+                .handle(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+        }
+
+        fn open(&mut self, text: &str) -> Vec<Value> {
+            let uri = self.uri.clone();
+            self.notify(
+                "textDocument/didOpen",
+                json!({ "textDocument": { "uri": uri, "languageId": "rust", "version": 1, "text": text } }),
+            )
+        }
+
+        fn change(&mut self, text: &str) -> Vec<Value> {
+            let uri = self.uri.clone();
+            self.notify(
+                "textDocument/didChange",
+                json!({ "textDocument": { "uri": uri, "version": 2 }, "contentChanges": [{ "text": text }] }),
+            )
+        }
+
+        fn code_actions(&mut self, line: u32) -> Vec<Value> {
+            let uri = self.uri.clone();
+            let position = json!({ "line": line, "character": 0 });
+            let (result, _) = self.request(
+                "textDocument/codeAction",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "range": { "start": position, "end": position },
+                    "context": { "diagnostics": [] },
+                }),
+            );
+            result.as_array().unwrap().clone()
+        }
+    }
+
+    fn diagnostics(messages: &[Value]) -> &Vec<Value> {
+        assert_eq!(messages.len(), 1, "{messages:?}");
+        assert_eq!(messages[0]["method"], "textDocument/publishDiagnostics");
+        messages[0]["params"]["diagnostics"].as_array().unwrap()
+    }
+
+    #[test]
+    fn test_threads_follow_drift_and_unsaved_edits() {
+        let (temp, _bob, thread_id) = setup_review(None);
+        let root = temp.path();
+        let moved = "// one\n// two\nfn a() {}\nfn b() {}\n";
+        std::fs::write(root.join("lib.rs"), moved).unwrap();
+        git(root, &["commit", "-q", "-am", "add header"]);
+
+        let mut client = Client::new(root);
+        let published = client.open(moved);
+        let diagnostic = &diagnostics(&published)[0];
+        assert_eq!(diagnostic["range"]["start"]["line"], 3);
+        assert_eq!(diagnostic["range"]["end"]["character"], 9);
+        assert_eq!(diagnostic["severity"], WARNING);
+        assert_eq!(diagnostic["code"], thread_id.as_str());
+        assert!(diagnostic["message"]
+            .as_str()
+            .unwrap()
+            .contains("bob: why?"));
+
+        // An unsaved line above the thread moves it in the buffer
+        let published = client.change(&format!("fn z() {{}}\n{moved}"));
+        assert_eq!(diagnostics(&published)[0]["range"]["start"]["line"], 4);
+    }
+
+    #[test]
+    fn test_code_actions_apply_and_resolve() {
+        let (temp, _bob, thread_id) = setup_review(Some("fn b() -> u8 { 1 }"));
+        let mut client = Client::new(temp.path());
+        client.open("fn a() {}\nfn b() {}\n");
+
+        assert!(client.code_actions(0).is_empty());
+        let actions = client.code_actions(1);
+        let titles: Vec<&str> = actions
+            .iter()
+            .map(|a| a["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles.len(), 3, "{titles:?}");
+        assert_eq!(actions[1]["command"]["command"], RESOLVE_COMMAND);
+        let edit = &actions[2]["edit"]["changes"][&client.uri][0];
+        assert_eq!(edit["newText"], "fn a() {}\nfn b() -> u8 { 1 }\n");
+        assert_eq!(edit["range"]["end"]["line"], 2);
+
+        // Edited in the buffer: the suggestion no longer applies
+        client.change("fn a() {}\nfn b() { 2 }\n");
+        let actions = client.code_actions(1);
+        assert!(actions[2]["disabled"]["reason"].is_string());
+
+        let (result, published) = client.request(
+            "workspace/executeCommand",
+            json!({ "command": RESOLVE_COMMAND, "arguments": [thread_id] }),
+        );
+        assert!(result.is_null());
+        assert!(diagnostics(&published).is_empty());
+    }
+
+    #[test]
+    fn test_quick_reply_and_new_threads_from_disk() {
+        let (temp, bob, thread_id) = setup_review(None);
+        let mut client = Client::new(temp.path());
+        client.open("fn a() {}\nfn b() {}\n");
+
+        // Without a message, the client is asked which reply to post
+        let (_, outgoing) = client.request(
+            "workspace/executeCommand",
+            json!({ "command": REPLY_COMMAND, "arguments": [thread_id] }),
+        );
+        assert_eq!(outgoing[0]["method"], "window/showMessageRequest");
+        let published = client.server.handle(&json!({
+            "jsonrpc": "2.0", "id": outgoing[0]["id"], "result": { "title": "Done" },
+        }));
+        assert!(diagnostics(&published)[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("alice: Done"));
+
+        // Nothing changed on disk
+        assert!(client.server.poll().is_empty());
+
+        // Bob starts another thread from his own process
+        let review_id = bob
+            .handle("threads.get", json!({ "thread_id": thread_id }))
+            .unwrap()["review_id"]
+            .clone();
+        bob.handle(
+            "comments.add",
+            json!({ "review_id": review_id, "file": "lib.rs", "line": "1", "body": "nit", "kind": "nit" }),
+        )
+        .unwrap();
+        // Typing only re-places the threads already loaded
+        let published = client.change("fn a() {}\nfn b() {}\n\n");
+        assert_eq!(diagnostics(&published).len(), 1);
+
+        let published = client.server.poll();
+        let diagnostics = diagnostics(&published);
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().any(|d| d["severity"] == HINT));
+    }
+
+    #[test]
+    fn test_percent_decode_and_end_of() {
+        assert_eq!(percent_decode("/a%20b/c%C3%A9.rs"), "/a b/cé.rs");
+        assert_eq!(percent_decode("/100%"), "/100%");
+        assert_eq!(end_of("a\nbé"), json!({ "line": 1, "character": 2 }));
+    }
+
+    #[test]
+    fn test_locate_malformed_selection() {
+        let summary = |start: i64, end: i64| ThreadSummary {
+            thread_id: "th-1".to_string(),
+            file_path: "lib.rs".to_string(),
+            selection_start: start,
+            selection_end: Some(end),
+            status: "open".to_string(),
+            comment_count: 1,
+            commit_hash: "abc".to_string(),
+            moved_from: None,
+            kind: None,
+        };
+        let thread = ThreadDetail {
+            thread_id: "th-1".to_string(),
+            review_id: "cr-1".to_string(),
+            file_path: "lib.rs".to_string(),
+            selection_type: "range".to_string(),
+            selection_start: 5,
+            selection_end: Some(2),
+            commit_hash: "abc".to_string(),
+            author: "bob".to_string(),
+            created_at: String::new(),
+            status: "open".to_string(),
+            status_changed_at: None,
+            status_changed_by: None,
+            resolve_reason: None,
+            reopen_reason: None,
+            anchor: None,
+            kind: None,
+            comments: Vec::new(),
+        };
+
+        // End before start: a single line rather than a wrapped span
+        assert_eq!(
+            locate(&summary(5, 2), &thread, None, "", None),
+            (4, 4, true)
+        );
+        assert_eq!(
+            locate(&summary(-3, 2), &thread, None, "", None),
+            (0, 5, true)
+        );
+        assert_eq!(
+            locate(&summary(1, i64::from(u32::MAX)), &thread, Some(3), "", None),
+            (2, u32::MAX, false)
+        );
+    }
+}
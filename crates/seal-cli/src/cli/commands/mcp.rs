@@ -0,0 +1,547 @@
+//! Implementation of `seal mcp`.
+//!
+//! A Model Context Protocol server on stdin/stdout, so agents can call seal
+//! as tools instead of shelling out and parsing `--format json`.
+//!
+//! Each tool is a seal subcommand. Its input schema is derived from the
+//! subcommand's clap arguments, and a call runs that subcommand with
+//! `--format json` under the identity the server was launched with, so tools
+//! behave exactly like the CLI (validation, auto-approval, hints in errors).
+//! Reviews are also offered as resources: `seal://review/<id>` for the full
+//! review and `seal://review/<id>/diff` for its diff.
+
+use anyhow::{bail, Context, Result};
+use clap::{Arg, ArgAction, CommandFactory, ValueEnum};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli::Cli;
+use crate::jsonrpc::{self, RpcError, RpcResult, NOT_FOUND};
+use seal_core::scm::ScmPreference;
+
+/// Protocol revisions this server speaks, newest first.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const REVIEW_URI_PREFIX: &str = "seal://review/";
+
+/// An MCP tool backed by a seal subcommand.
+struct Tool {
+    name: &'static str,
+    /// Subcommand path, e.g. `["threads", "resolve"]`
+    command: &'static [&'static str],
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "create_review",
+        command: &["reviews", "create"],
+    },
+    Tool {
+        name: "comment",
+        command: &["comment"],
+    },
+    Tool {
+        name: "reply",
+        command: &["reply"],
+    },
+    Tool {
+        name: "lgtm",
+        command: &["lgtm"],
+    },
+    Tool {
+        name: "block",
+        command: &["block"],
+    },
+    Tool {
+        name: "resolve",
+        command: &["threads", "resolve"],
+    },
+    Tool {
+        name: "inbox",
+        command: &["inbox"],
+    },
+    Tool {
+        name: "show_review",
+        command: &["review"],
+    },
+];
+
+/// Serve MCP requests on stdin until it is closed.
+///
+/// Every call is made as `agent`, the identity the server was launched with.
+///
+/// # Errors
+///
+/// Returns an error if the seal executable can't be located, or stdin or
+/// stdout fail.
+pub fn run_mcp(agent: &str, path: Option<&Path>, scm: Option<ScmPreference>) -> Result<()> {
+    let mut launch_args = vec!["--agent".to_string(), agent.to_string()];
+    if let Some(path) = path {
+        launch_args.extend(["--path".to_string(), path.display().to_string()]);
+    }
+    if let Some(scm) = scm.and_then(|scm| scm.to_possible_value()) {
+        launch_args.extend(["--scm".to_string(), scm.get_name().to_string()]);
+    }
+    let exe = std::env::current_exe().context("Failed to locate the seal executable")?;
+    let server = McpServer::new(exe, launch_args);
+    jsonrpc::serve(io::stdin().lock(), io::stdout().lock(), |method, params| {
+        server.handle(method, params)
+    })
+}
+
+/// MCP request handler.
+pub struct McpServer {
+    /// The seal binary that runs tool calls
+    exe: PathBuf,
+    /// Global options given to every call (`--agent`, `--path`, `--scm`)
+    launch_args: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct InitializeParams {
+    #[serde(rename = "protocolVersion")]
+    protocol_version: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CallToolParams {
+    name: String,
+    #[serde(default)]
+    arguments: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct ReadResourceParams {
+    uri: String,
+}
+
+impl McpServer {
+    #[must_use]
+    pub const fn new(exe: PathBuf, launch_args: Vec<String>) -> Self {
+        Self { exe, launch_args }
+    }
+
+    /// Handle one method call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the JSON-RPC error to send back if the method is unknown, its
+    /// params are invalid, or a tool or resource can't be run or read.
+    pub fn handle(&self, method: &str, params: Value) -> RpcResult {
+        match method {
+            "initialize" => {
+                let p: InitializeParams = jsonrpc::params(params)?;
+                // Answer in the client's revision if we speak it, else our newest
+                let version = p
+                    .protocol_version
+                    .as_deref()
+                    .filter(|v| PROTOCOL_VERSIONS.contains(v))
+                    .unwrap_or(PROTOCOL_VERSIONS[0]);
+                Ok(json!({
+                    "protocolVersion": version,
+                    "capabilities": { "tools": {}, "resources": {} },
+                    "serverInfo": { "name": "seal", "version": env!("CARGO_PKG_VERSION") },
+                }))
+            }
+            "notifications/initialized" | "ping" => Ok(json!({})),
+            "tools/list" => {
+                let tools = TOOLS
+                    .iter()
+                    .map(tool_definition)
+                    .collect::<Result<Vec<_>>>()
+                    .map_err(|e| RpcError::internal(&e))?;
+                Ok(json!({ "tools": tools }))
+            }
+            "tools/call" => {
+                let p: CallToolParams = jsonrpc::params(params)?;
+                let tool = TOOLS
+                    .iter()
+                    .find(|tool| tool.name == p.name)
+                    .ok_or_else(|| RpcError::invalid_params(format!("Unknown tool: {}", p.name)))?;
+                let args = tool_args(tool, &p.arguments).map_err(RpcError::invalid_params)?;
+                Ok(self.call_tool(&args))
+            }
+            "resources/templates/list" => Ok(json!({
+                "resourceTemplates": [
+                    {
+                        "uriTemplate": format!("{REVIEW_URI_PREFIX}{{review_id}}"),
+                        "name": "review",
+                        "description": "A review with its votes, threads and comments",
+                        "mimeType": "application/json",
+                    },
+                    {
+                        "uriTemplate": format!("{REVIEW_URI_PREFIX}{{review_id}}/diff"),
+                        "name": "review-diff",
+                        "description": "The diff under review",
+                        "mimeType": "application/json",
+                    },
+                ],
+            })),
+            "resources/list" => {
+                let output = self
+                    .run(&["reviews", "list", "--status", "open"])
+                    .map_err(|e| RpcError::internal(&e))?;
+                let list: Value =
+                    serde_json::from_str(&output).map_err(|e| RpcError::internal(&e.into()))?;
+                let resources: Vec<Value> = list["reviews"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|review| {
+                        let id = review["review_id"].as_str()?;
+                        Some(json!({
+                            "uri": format!("{REVIEW_URI_PREFIX}{id}"),
+                            "name": id,
+                            "title": review["title"],
+                            "mimeType": "application/json",
+                        }))
+                    })
+                    .collect();
+                Ok(json!({ "resources": resources }))
+            }
+            "resources/read" => {
+                let p: ReadResourceParams = jsonrpc::params(params)?;
+                let args = resource_args(&p.uri).ok_or_else(|| {
+                    RpcError::new(NOT_FOUND, format!("Resource not found: {}", p.uri))
+                })?;
+                let text = self.run(&args).map_err(|e| {
+                    RpcError::new(NOT_FOUND, format!("Cannot read {}: {e:#}", p.uri))
+                })?;
+                Ok(json!({
+                    "contents": [{ "uri": p.uri, "mimeType": "application/json", "text": text }],
+                }))
+            }
+            _ => Err(RpcError::method_not_found(method)),
+        }
+    }
+
+    /// Run a tool's command. Command failures are tool errors the model can
+    /// read and act on, not protocol errors.
+    fn call_tool(&self, args: &[String]) -> Value {
+        match self.run(args) {
+            Ok(output) => {
+                let mut result = json!({ "content": [{ "type": "text", "text": output }] });
+                if let Ok(structured @ Value::Object(_)) = serde_json::from_str::<Value>(&output) {
+                    result["structuredContent"] = structured;
+                }
+                result
+            }
+            Err(e) => json!({
+                "content": [{ "type": "text", "text": format!("{e:#}") }],
+                "isError": true,
+            }),
+        }
+    }
+
+    /// Run seal with the launch options and `--format json`, returning stdout.
+    fn run<S: AsRef<str>>(&self, args: &[S]) -> Result<String> {
+        let output = Command::new(&self.exe)
+            .args(&self.launch_args)
+            .args(["--format", "json"])
+            .args(args.iter().map(AsRef::as_ref))
+            // Errors are read by a model, not debugged
+            .env_remove("RUST_BACKTRACE")
+            .env_remove("RUST_LIB_BACKTRACE")
+            .output()
+            .with_context(|| format!("Failed to run {}", self.exe.display()))?;
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        String::from_utf8(output.stdout).context("seal output was not valid UTF-8")
+    }
+}
+
+/// The subcommand behind a tool.
+fn tool_command(tool: &Tool) -> Result<clap::Command> {
+    let mut command = Cli::command();
+    for name in tool.command {
+        command = command
+            .find_subcommand(name)
+            .with_context(|| format!("No subcommand {name} for tool {}", tool.name))?
+            .clone();
+    }
+    Ok(command)
+}
+
+/// The tool's MCP definition, described and typed from its subcommand.
+fn tool_definition(tool: &Tool) -> Result<Value> {
+    let command = tool_command(tool)?;
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for arg in tool_arguments(&command) {
+        let name = arg.get_id().as_str();
+        let mut schema = arg_schema(arg);
+        if let Some(help) = arg.get_help() {
+            schema["description"] = json!(help.to_string());
+        }
+        properties.insert(name.to_string(), schema);
+        if arg.is_required_set() {
+            required.push(name);
+        }
+    }
+    Ok(json!({
+        "name": tool.name,
+        "description": command.get_about().map(ToString::to_string),
+        "inputSchema": {
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        },
+    }))
+}
+
+/// Arguments a tool accepts. Global options are fixed by the server.
+fn tool_arguments(command: &clap::Command) -> impl Iterator<Item = &Arg> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set() && !arg.is_global_set())
+}
+
+/// JSON Schema for one argument's value.
+fn arg_schema(arg: &Arg) -> Value {
+    if !arg.get_action().takes_values() {
+        return json!({ "type": "boolean" });
+    }
+
+    let possible: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .map(|value| value.get_name().to_string())
+        .collect();
+    let integer = is_integer(arg);
+    let mut schema = if !possible.is_empty() {
+        json!({ "type": "string", "enum": possible })
+    } else if integer {
+        json!({ "type": "integer", "minimum": 0 })
+    } else {
+        json!({ "type": "string" })
+    };
+    if let [default] = arg.get_default_values() {
+        let default = default.to_string_lossy();
+        schema["default"] = match default.parse::<u64>() {
+            Ok(n) if integer => json!(n),
+            _ => json!(default),
+        };
+    }
+
+    if matches!(arg.get_action(), ArgAction::Append) {
+        json!({ "type": "array", "items": schema })
+    } else {
+        schema
+    }
+}
+
+fn is_integer(arg: &Arg) -> bool {
+    let type_id = arg.get_value_parser().type_id();
+    [
+        TypeId::of::<u32>(),
+        TypeId::of::<u64>(),
+        TypeId::of::<usize>(),
+        TypeId::of::<i64>(),
+    ]
+    .iter()
+    .any(|id| type_id == *id)
+}
+
+/// Command line for a tool call: options first, then `--` and positionals,
+/// so values starting with `-` aren't taken for flags.
+fn tool_args(tool: &Tool, arguments: &Map<String, Value>) -> Result<Vec<String>> {
+    let command = tool_command(tool)?;
+    let known: Vec<&Arg> = tool_arguments(&command).collect();
+    if let Some(unknown) = arguments
+        .keys()
+        .find(|key| !known.iter().any(|arg| arg.get_id() == key.as_str()))
+    {
+        bail!("{} has no argument '{unknown}'", tool.name);
+    }
+
+    let mut args: Vec<String> = tool.command.iter().map(ToString::to_string).collect();
+    let mut positionals = Vec::new();
+    for arg in known {
+        let Some(value) = arguments.get(arg.get_id().as_str()) else {
+            continue;
+        };
+        let name = arg.get_id().as_str();
+        let Some(long) = arg.get_long() else {
+            positionals.push((arg.get_index().unwrap_or(0), values(name, value)?));
+            continue;
+        };
+        if !arg.get_action().takes_values() {
+            if value
+                .as_bool()
+                .with_context(|| format!("{name} must be a boolean"))?
+            {
+                args.push(format!("--{long}"));
+            }
+            continue;
+        }
+        for value in values(name, value)? {
+            args.push(format!("--{long}={value}"));
+        }
+    }
+
+    positionals.sort_by_key(|(index, _)| *index);
+    if !positionals.is_empty() {
+        args.push("--".to_string());
+        args.extend(positionals.into_iter().flat_map(|(_, values)| values));
+    }
+    Ok(args)
+}
+
+/// Command-line values for an argument: one per array element, or one.
+fn values(name: &str, value: &Value) -> Result<Vec<String>> {
+    let scalar = |value: &Value| match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        _ => bail!("{name} must be a string, number or array of them"),
+    };
+    match value {
+        Value::Array(items) => items.iter().map(scalar).collect(),
+        Value::Null => Ok(Vec::new()),
+        value => Ok(vec![scalar(value)?]),
+    }
+}
+
+/// Command line that renders a resource, if the URI names one.
+fn resource_args(uri: &str) -> Option<Vec<String>> {
+    let path = uri.strip_prefix(REVIEW_URI_PREFIX)?;
+    let (review_id, diff) = path
+        .strip_suffix("/diff")
+        .map_or((path, false), |review_id| (review_id, true));
+    if review_id.is_empty() || review_id.contains('/') {
+        return None;
+    }
+    let command = if diff { "diff" } else { "review" };
+    Some(vec![
+        command.to_string(),
+        "--".to_string(),
+        review_id.to_string(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str) -> &'static Tool {
+        TOOLS.iter().find(|tool| tool.name == name).unwrap()
+    }
+
+    fn args(name: &str, arguments: Value) -> Result<Vec<String>> {
+        tool_args(tool(name), arguments.as_object().unwrap())
+    }
+
+    #[test]
+    fn test_tool_schemas_follow_cli_arguments() {
+        let comment = tool_definition(tool("comment")).unwrap();
+        let schema = &comment["inputSchema"];
+        assert_eq!(
+            schema["required"],
+            json!(["review_id", "file", "line", "message"])
+        );
+        assert_eq!(schema["properties"]["draft"]["type"], "boolean");
+        assert_eq!(
+            schema["properties"]["kind"]["enum"],
+            json!(["blocking", "nit", "question", "praise"])
+        );
+        assert!(schema["properties"].get("agent").is_none());
+        assert!(comment["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("Add a comment"));
+
+        let show = tool_definition(tool("show_review")).unwrap();
+        let context = &show["inputSchema"]["properties"]["context"];
+        assert_eq!(context["type"], "integer");
+        assert_eq!(context["default"], 3);
+
+        let resolve = tool_definition(tool("resolve")).unwrap();
+        assert_eq!(
+            resolve["inputSchema"]["properties"]["thread_ids"]["type"],
+            "array"
+        );
+    }
+
+    #[test]
+    fn test_every_tool_has_a_definition() {
+        for tool in TOOLS {
+            tool_definition(tool).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_tool_args_put_positionals_after_options() {
+        let argv = args(
+            "comment",
+            json!({
+                "review_id": "cr-1",
+                "file": "src/a.rs",
+                "line": "3",
+                "message": "-1 is wrong here",
+                "draft": true,
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            argv,
+            [
+                "comment",
+                "--file=src/a.rs",
+                "--line=3",
+                "--draft",
+                "--",
+                "cr-1",
+                "-1 is wrong here"
+            ]
+        );
+
+        let argv = args("resolve", json!({ "thread_ids": ["th-1", "th-2"] })).unwrap();
+        assert_eq!(argv, ["threads", "resolve", "--", "th-1", "th-2"]);
+
+        assert!(args("lgtm", json!({ "review_id": "cr-1", "agent": "eve" })).is_err());
+    }
+
+    #[test]
+    fn test_handle_protocol_methods() {
+        let server = McpServer::new(PathBuf::from("seal"), Vec::new());
+
+        let init = server
+            .handle("initialize", json!({ "protocolVersion": "2025-03-26" }))
+            .unwrap();
+        assert_eq!(init["protocolVersion"], "2025-03-26");
+        assert_eq!(init["serverInfo"]["name"], "seal");
+
+        let tools = server.handle("tools/list", Value::Null).unwrap();
+        assert_eq!(tools["tools"].as_array().unwrap().len(), TOOLS.len());
+
+        let unknown = server
+            .handle("tools/call", json!({ "name": "merge", "arguments": {} }))
+            .unwrap_err();
+        assert_eq!(unknown.code, jsonrpc::INVALID_PARAMS);
+
+        let missing = server
+            .handle("resources/read", json!({ "uri": "file:///etc/passwd" }))
+            .unwrap_err();
+        assert_eq!(missing.code, NOT_FOUND);
+    }
+
+    #[test]
+    fn test_resource_args() {
+        assert_eq!(
+            resource_args("seal://review/cr-1").unwrap(),
+            ["review", "--", "cr-1"]
+        );
+        assert_eq!(
+            resource_args("seal://review/cr-1/diff").unwrap(),
+            ["diff", "--", "cr-1"]
+        );
+        assert!(resource_args("seal://review/").is_none());
+        assert!(resource_args("seal://review/a/b/diff").is_none());
+    }
+}
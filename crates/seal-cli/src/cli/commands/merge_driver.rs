@@ -0,0 +1,31 @@
+//! Implementation of `seal merge-driver`.
+//!
+//! Called by git (as a merge driver or mergetool) and jj (as a merge tool)
+//! to merge two versions of a `.seal` event log. Registered by
+//! `seal init --install-merge-driver`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use seal_core::log::merge::union_merge;
+
+/// Merge `ours` and `theirs` into `output`, or into `ours` if no output is given.
+///
+/// On failure nothing is written and the exit status tells git/jj the
+/// conflict remains.
+///
+/// # Errors
+///
+/// Returns an error if either side can't be read or merged, or the result
+/// can't be written.
+pub fn run_merge_driver(ours: &Path, theirs: &Path, output: Option<&Path>) -> Result<()> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+    };
+    let merged = union_merge(&read(ours)?, &read(theirs)?)
+        .context("Cannot merge event logs; resolve the conflict by hand")?;
+
+    let output = output.unwrap_or(ours);
+    fs::write(output, merged).with_context(|| format!("Failed to write {}", output.display()))
+}
@@ -21,19 +21,38 @@ fn is_legacy_review_id(s: &str) -> bool {
 }
 
 /// Extract a stable, unique key for an event for dedup purposes.
-/// Includes timestamp, event type discriminant, and event-specific IDs.
+/// Uses the event ID when present; legacy events without one are keyed by
+/// [`legacy_dedup_key`].
 fn event_dedup_key(e: &EventEnvelope) -> String {
+    if let Some(event_id) = &e.event_id {
+        return event_id.clone();
+    }
+    legacy_dedup_key(e)
+}
+
+/// Key an event by timestamp, event type discriminant, and event-specific
+/// IDs. Matches a legacy event to its migrated copy, which was given an
+/// event ID when it was appended.
+fn legacy_dedup_key(e: &EventEnvelope) -> String {
     let specific = match &e.event {
         Event::ReviewCreated(ev) => ev.review_id.clone(),
         Event::ReviewersRequested(ev) => ev.review_id.clone(),
+        Event::ReviewersUnrequested(ev) => ev.review_id.clone(),
         Event::ReviewerVoted(ev) => format!("{}:{}", ev.review_id, e.author),
+        Event::ReviewerVoteRetracted(ev) => format!("{}:{}", ev.review_id, e.author),
         Event::ReviewApproved(ev) => ev.review_id.clone(),
         Event::ReviewMerged(ev) => ev.review_id.clone(),
         Event::ReviewAbandoned(ev) => ev.review_id.clone(),
+        Event::ReviewReopened(ev) => ev.review_id.clone(),
+        Event::ReviewUpdated(ev) => format!("{}:{}", ev.review_id, ev.commit),
+        Event::ReviewEdited(ev) => format!("{}:{}", ev.review_id, e.author),
         Event::ThreadCreated(ev) => ev.thread_id.clone(),
         Event::CommentAdded(ev) => ev.comment_id.clone(),
+        Event::CommentEdited(ev) => ev.comment_id.clone(),
+        Event::CommentRetracted(ev) => ev.comment_id.clone(),
         Event::ThreadResolved(ev) => ev.thread_id.clone(),
         Event::ThreadReopened(ev) => ev.thread_id.clone(),
+        Event::ThreadKindChanged(ev) => ev.thread_id.clone(),
     };
     format!(
         "{}:{:?}:{}",
@@ -48,14 +67,22 @@ fn event_type_label(event: &Event) -> &'static str {
     match event {
         Event::ReviewCreated(_) => "ReviewCreated",
         Event::ReviewersRequested(_) => "ReviewersRequested",
+        Event::ReviewersUnrequested(_) => "ReviewersUnrequested",
         Event::ReviewerVoted(_) => "ReviewerVoted",
+        Event::ReviewerVoteRetracted(_) => "ReviewerVoteRetracted",
         Event::ReviewApproved(_) => "ReviewApproved",
         Event::ReviewMerged(_) => "ReviewMerged",
         Event::ReviewAbandoned(_) => "ReviewAbandoned",
+        Event::ReviewReopened(_) => "ReviewReopened",
+        Event::ReviewUpdated(_) => "ReviewUpdated",
+        Event::ReviewEdited(_) => "ReviewEdited",
         Event::ThreadCreated(_) => "ThreadCreated",
         Event::CommentAdded(_) => "CommentAdded",
+        Event::CommentEdited(_) => "CommentEdited",
+        Event::CommentRetracted(_) => "CommentRetracted",
         Event::ThreadResolved(_) => "ThreadResolved",
         Event::ThreadReopened(_) => "ThreadReopened",
+        Event::ThreadKindChanged(_) => "ThreadKindChanged",
     }
 }
 
@@ -240,9 +267,7 @@ pub fn run_migrate(
         let mut sorted_events = review_events.clone();
         sorted_events.sort_by(|a, b| a.ts.cmp(&b.ts));
 
-        for event in &sorted_events {
-            log.append(event)?;
-        }
+        log.append_batch(&sorted_events)?;
     }
 
     // Backup or remove legacy file
@@ -363,22 +388,10 @@ fn run_remigrate_from_backup(seal_root: &Path, dry_run: bool, format: OutputForm
         let log = open_or_create_review(seal_root, review_id)?;
         let existing_events = log.read_all().unwrap_or_default();
 
-        // Build set of existing event keys for dedup (includes event-specific IDs)
-        let existing_keys: std::collections::HashSet<String> =
-            existing_events.iter().map(event_dedup_key).collect();
-
-        let mut sorted = backup_review_events.clone();
-        sorted.sort_by(|a, b| a.ts.cmp(&b.ts));
-
-        let mut recovered_for_review = 0;
-        for event in &sorted {
-            let key = event_dedup_key(event);
-            if !existing_keys.contains(&key) {
-                if !dry_run {
-                    log.append(event)?;
-                }
-                recovered_for_review += 1;
-            }
+        let recovered = missing_from_log(&existing_events, backup_review_events);
+        let recovered_for_review = recovered.len();
+        if !dry_run {
+            log.insert_unclocked(&recovered)?;
         }
 
         if recovered_for_review > 0 && !dry_run {
@@ -423,6 +436,39 @@ fn run_remigrate_from_backup(seal_root: &Path, dry_run: bool, format: OutputForm
     Ok(())
 }
 
+/// Backup events missing from a review log, each with the index of the
+/// existing event to insert it before.
+///
+/// A recovered event goes right after the existing event that preceded it in
+/// the backup. Appending it would stamp a clock above events written since
+/// the migration, and replay would apply it after them.
+fn missing_from_log(
+    existing_events: &[EventEnvelope],
+    backup_events: &[EventEnvelope],
+) -> Vec<(usize, EventEnvelope)> {
+    // Position of each existing event, by ID and by legacy key
+    let mut existing_positions: HashMap<String, usize> = HashMap::new();
+    for (idx, event) in existing_events.iter().enumerate() {
+        existing_positions.insert(legacy_dedup_key(event), idx);
+        if let Some(event_id) = &event.event_id {
+            existing_positions.insert(event_id.clone(), idx);
+        }
+    }
+
+    let mut sorted = backup_events.to_vec();
+    sorted.sort_by(|a, b| a.ts.cmp(&b.ts));
+
+    let mut insert_at = 0;
+    let mut missing = Vec::new();
+    for event in sorted {
+        match existing_positions.get(&event_dedup_key(&event)) {
+            Some(&idx) => insert_at = insert_at.max(idx + 1),
+            None => missing.push((insert_at, event)),
+        }
+    }
+    missing
+}
+
 /// Build a map from thread_id to review_id using ThreadCreated events.
 fn build_thread_review_map(events: &[EventEnvelope]) -> HashMap<String, String> {
     let mut map = HashMap::new();
@@ -443,14 +489,22 @@ fn resolve_review_id<'a>(
     match event {
         Event::ReviewCreated(e) => Some(&e.review_id),
         Event::ReviewersRequested(e) => Some(&e.review_id),
+        Event::ReviewersUnrequested(e) => Some(&e.review_id),
         Event::ReviewerVoted(e) => Some(&e.review_id),
+        Event::ReviewerVoteRetracted(e) => Some(&e.review_id),
         Event::ReviewApproved(e) => Some(&e.review_id),
         Event::ReviewMerged(e) => Some(&e.review_id),
         Event::ReviewAbandoned(e) => Some(&e.review_id),
+        Event::ReviewReopened(e) => Some(&e.review_id),
+        Event::ReviewUpdated(e) => Some(&e.review_id),
+        Event::ReviewEdited(e) => Some(&e.review_id),
         Event::ThreadCreated(e) => Some(&e.review_id),
-        Event::CommentAdded(e) => thread_to_review.get(&e.thread_id).map(|s| s.as_str()),
-        Event::ThreadResolved(e) => thread_to_review.get(&e.thread_id).map(|s| s.as_str()),
-        Event::ThreadReopened(e) => thread_to_review.get(&e.thread_id).map(|s| s.as_str()),
+        Event::CommentAdded(e) => thread_to_review.get(&e.thread_id).map(String::as_str),
+        Event::CommentEdited(e) => thread_to_review.get(&e.thread_id).map(String::as_str),
+        Event::CommentRetracted(e) => thread_to_review.get(&e.thread_id).map(String::as_str),
+        Event::ThreadResolved(e) => thread_to_review.get(&e.thread_id).map(String::as_str),
+        Event::ThreadReopened(e) => thread_to_review.get(&e.thread_id).map(String::as_str),
+        Event::ThreadKindChanged(e) => thread_to_review.get(&e.thread_id).map(String::as_str),
     }
 }
 
@@ -462,6 +516,7 @@ mod tests {
         ThreadResolved, VoteType,
     };
     use seal_core::log::{list_review_ids, open_or_create};
+    use seal_core::projection::{rebuild_from_review_logs, ProjectionDb};
     use tempfile::tempdir;
 
     fn make_review_created(review_id: &str) -> EventEnvelope {
@@ -475,6 +530,7 @@ mod tests {
                 initial_commit: "commit456".to_string(),
                 title: format!("Test Review {}", review_id),
                 description: None,
+                depends_on: None,
             }),
         )
     }
@@ -488,6 +544,8 @@ mod tests {
                 file_path: "src/main.rs".to_string(),
                 selection: CodeSelection::line(42),
                 commit_hash: "abc123".to_string(),
+                anchor: None,
+                kind: None,
             }),
         )
     }
@@ -499,6 +557,7 @@ mod tests {
                 review_id: review_id.to_string(),
                 vote: VoteType::Lgtm,
                 reason: Some("Looks good".to_string()),
+                commit: None,
             }),
         )
     }
@@ -510,6 +569,7 @@ mod tests {
                 comment_id: comment_id.to_string(),
                 thread_id: thread_id.to_string(),
                 body: body.to_string(),
+                suggestion: None,
             }),
         )
     }
@@ -765,6 +825,62 @@ mod tests {
         );
     }
 
+    /// Recovered events are older than events written since the migration,
+    /// so replay must apply them first.
+    #[test]
+    fn test_remigrate_from_backup_replays_recovered_events_first() {
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+
+        let seal_dir = seal_root.join(".seal");
+        fs::create_dir(&seal_dir).unwrap();
+
+        let ev_review = make_review_created("cr-001");
+        let ev_thread = make_thread_created("cr-001", "th-001");
+        let ev_resolved = make_thread_resolved("th-001");
+        let ev_block = EventEnvelope::new(
+            "reviewer",
+            Event::ReviewerVoted(ReviewerVoted {
+                review_id: "cr-001".to_string(),
+                vote: VoteType::Block,
+                reason: None,
+                commit: None,
+            }),
+        );
+
+        let backup_log = open_or_create(&seal_dir.join("events.jsonl.v1.backup")).unwrap();
+        for event in [&ev_review, &ev_thread, &ev_resolved, &ev_block] {
+            backup_log.append(event).unwrap();
+        }
+
+        // The migration lost the resolve and the block; since then the thread
+        // was reopened and the reviewer changed their vote
+        fs::write(seal_dir.join("version"), "2\n").unwrap();
+        let review_log = open_or_create_review(seal_root, "cr-001").unwrap();
+        review_log.append(&ev_review).unwrap();
+        review_log.append(&ev_thread).unwrap();
+        review_log.append(&make_thread_reopened("th-001")).unwrap();
+        review_log.append(&make_vote("cr-001")).unwrap();
+
+        run_migrate(seal_root, false, true, true, OutputFormat::Text).unwrap();
+
+        let events = review_log.read_all().unwrap();
+        assert_eq!(events.len(), 6);
+        assert_eq!(events[2].event_id, ev_resolved.event_id);
+        assert_eq!(events[3].event_id, ev_block.event_id);
+        assert!(events[2].clock.is_none() && events[3].clock.is_none());
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+        rebuild_from_review_logs(&db, seal_root).unwrap();
+
+        let thread = db.get_thread("th-001").unwrap().unwrap();
+        assert_eq!(thread.status, "open");
+        let votes = db.get_votes("cr-001").unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].vote, "lgtm");
+    }
+
     #[test]
     fn test_migrate_no_backup() {
         let dir = tempdir().unwrap();
@@ -809,6 +925,7 @@ mod tests {
                 initial_commit: "commit456".to_string(),
                 title: "Malicious review".to_string(),
                 description: None,
+                depends_on: None,
             }),
         ))
         .unwrap();
@@ -3,26 +3,52 @@
 pub mod agents;
 pub mod comments;
 pub mod doctor;
+pub mod events;
 pub mod helpers;
+pub mod hooks;
 pub mod init;
+pub mod keys;
+pub mod lsp;
+pub mod mcp;
+pub mod merge_driver;
 pub mod migrate;
+pub mod owners;
 pub mod reviews;
+pub mod search;
+pub mod serve;
 pub mod status;
+pub mod suggestions;
 pub mod sync;
 pub mod threads;
 
 pub use agents::{get_crit_instructions, run_agents_init, run_agents_show};
-pub use comments::{run_comment, run_comments_add, run_comments_list};
+pub use comments::{
+    run_comment, run_comments_add, run_comments_edit, run_comments_history, run_comments_list,
+    run_comments_retract,
+};
 pub use doctor::run_doctor;
+pub use events::run_events_tail;
+pub use hooks::{run_hooks_replay, run_hooks_trust};
 pub use init::run_init;
+pub use keys::run_keys_generate;
+pub use lsp::run_lsp;
+pub use mcp::run_mcp;
+pub use merge_driver::run_merge_driver;
 pub use migrate::run_migrate;
+pub use owners::run_owners_explain;
 pub use reviews::{
-    parse_since, run_block, run_inbox, run_lgtm, run_review, run_reviews_abandon,
-    run_reviews_approve, run_reviews_create, run_reviews_list, run_reviews_merge,
-    run_reviews_request, run_reviews_show,
+    parse_since, run_block, run_inbox, run_lgtm, run_review, run_review_discard, run_review_drafts,
+    run_review_submit, run_reviews_abandon, run_reviews_approve, run_reviews_create,
+    run_reviews_create_stack, run_reviews_edit, run_reviews_list, run_reviews_merge,
+    run_reviews_reopen, run_reviews_request, run_reviews_retract_vote, run_reviews_show,
+    run_reviews_unrequest, run_reviews_update,
 };
+pub use search::run_search;
+pub use serve::run_serve;
 pub use status::{run_diff, run_status};
+pub use suggestions::run_suggestions_apply;
 pub use sync::run_sync;
 pub use threads::{
-    run_threads_create, run_threads_list, run_threads_reopen, run_threads_resolve, run_threads_show,
+    run_threads_create, run_threads_list, run_threads_reopen, run_threads_resolve,
+    run_threads_set_kind, run_threads_show,
 };
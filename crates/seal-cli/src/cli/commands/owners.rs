@@ -0,0 +1,47 @@
+//! Implementation of `seal owners` commands.
+//!
+//! Code owners live in `.seal/owners` and are requested as reviewers
+//! automatically when a review is created.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cli::commands::helpers::ensure_initialized;
+use crate::output::{Formatter, OutputFormat};
+use seal_core::owners::Owners;
+
+/// Show which owners rule applies to a path.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or `.seal/owners` is invalid.
+pub fn run_owners_explain(seal_root: &Path, path: &str, format: OutputFormat) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let owners = Owners::load(seal_root)?;
+    let mut matching = owners.matching_rules(path);
+    // The last matching rule is in effect; earlier ones are overridden
+    let rule = matching.pop();
+
+    let result = serde_json::json!({
+        "path": path,
+        "owners": rule.map(|r| r.owners.clone()).unwrap_or_default(),
+        "rule": rule,
+        "overridden": matching,
+    });
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    if format != OutputFormat::Json && rule.is_none() {
+        println!();
+        if owners.is_empty() {
+            println!("No owners file. Add rules to .seal/owners, e.g.:");
+            println!("  src/log/  alice bob");
+        } else {
+            println!("No rule in .seal/owners matches {path}");
+        }
+    }
+
+    Ok(())
+}
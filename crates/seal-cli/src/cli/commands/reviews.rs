@@ -6,9 +6,12 @@ use std::path::Path;
 
 use crate::cli::commands::helpers::{ensure_initialized, open_services};
 use seal_core::sealignore::{AllFilesIgnoredError, SealIgnore};
+use seal_core::core::CoreError;
+use seal_core::core::reviews::{current_review_commit, mark_stale_votes, AutoApproval};
+use seal_core::core::threads::follow_renames;
 use seal_core::events::VoteType;
 use crate::output::{Formatter, OutputFormat};
-use seal_core::projection::{ReviewDetail, ThreadSummary};
+use seal_core::projection::{ReviewDetail, ReviewSummary, StaleVote, ThreadSummary};
 use seal_core::scm::ScmRepo;
 
 /// Parse a --since value into a DateTime.
@@ -124,6 +127,19 @@ pub fn run_reviews_create(
         result["reviewers"] = serde_json::json!(reviewers);
     }
 
+    // Code owners were requested by the service; read them back
+    let requested = open_services(seal_root)?
+        .reviews()
+        .get(&review_id)?
+        .reviewers;
+    let owners: Vec<&String> = requested
+        .iter()
+        .filter(|r| !reviewer_list.as_ref().is_some_and(|list| list.contains(r)))
+        .collect();
+    if !owners.is_empty() {
+        result["owners_requested"] = serde_json::json!(owners);
+    }
+
     let formatter = Formatter::new(format);
     formatter.print(&result)?;
 
@@ -131,7 +147,7 @@ pub fn run_reviews_create(
     if format != OutputFormat::Json {
         println!();
         println!("Next:");
-        if reviewer_list.is_none() {
+        if requested.is_empty() {
             println!("  seal reviews request {review_id} --reviewers <name>");
         }
         println!("  seal comment {review_id} --file <path> --line <n> \"feedback\"");
@@ -141,49 +157,171 @@ pub fn run_reviews_create(
     Ok(())
 }
 
+/// Create linked reviews for each change in a stack.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the stack can't be reviewed.
+pub fn run_reviews_create_stack(
+    seal_root: &Path,
+    scm: &dyn ScmRepo,
+    revset: &str,
+    reviewers: Option<String>,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let reviewer_list: Option<Vec<String>> = reviewers.map(|r| {
+        r.split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    });
+
+    let services = open_services(seal_root)?;
+    let stack = services
+        .reviews()
+        .create_stack(scm, revset, reviewer_list, author)?;
+
+    let formatter = Formatter::new(format);
+    if format == OutputFormat::Json {
+        formatter.print(&stack)?;
+        return Ok(());
+    }
+
+    for (depth, review) in stack.reviews.iter().enumerate() {
+        println!("{}{}", tree_prefix(depth), formatter.format(review)?);
+    }
+    if !stack.skipped.is_empty() {
+        println!(
+            "Skipped (no reviewable files): {}",
+            stack.skipped.join(", ")
+        );
+    }
+
+    println!();
+    println!("Next:");
+    println!("  seal reviews list");
+    if let Some(bottom) = stack.reviews.first() {
+        println!("  seal review {}", bottom.review_id);
+    }
+
+    Ok(())
+}
+
 /// List reviews with optional filters.
+#[allow(clippy::too_many_arguments)]
 pub fn run_reviews_list(
     seal_root: &Path,
+    scm: &dyn ScmRepo,
     status: Option<&str>,
     author: Option<&str>,
     needs_reviewer: Option<&str>,
     has_unresolved: bool,
+    label: Option<&str>,
     format: OutputFormat,
 ) -> Result<()> {
     ensure_initialized(seal_root)?;
 
     let services = open_services(seal_root)?;
-    let reviews = services.reviews().list_filtered(status, author, needs_reviewer, has_unresolved)?;
+    let mut reviews =
+        services
+            .reviews()
+            .list_filtered(status, author, needs_reviewer, has_unresolved, label)?;
+    services.reviews().flag_stale_votes(scm, &mut reviews)?;
 
     // Build context-aware empty message
     let empty_msg = if needs_reviewer.is_some() {
         "No reviews need your attention"
     } else if has_unresolved {
         "No reviews have unresolved threads"
-    } else if status.is_some() || author.is_some() {
+    } else if status.is_some() || author.is_some() || label.is_some() {
         "No reviews match the filters"
     } else {
         "No reviews yet"
     };
 
     let formatter = Formatter::new(format);
-    formatter.print_list(
-        &reviews,
-        empty_msg,
-        "reviews",
-        &["seal reviews show <id>", "seal lgtm <id> -m \"...\""],
-    )?;
+    if format == OutputFormat::Json || reviews.is_empty() {
+        formatter.print_list(
+            &reviews,
+            empty_msg,
+            "reviews",
+            &["seal reviews show <id>", "seal lgtm <id> -m \"...\""],
+        )?;
+        return Ok(());
+    }
+
+    // Render stacked reviews as a tree under the review they depend on
+    for (depth, review) in stack_tree(&reviews) {
+        println!("{}{}", tree_prefix(depth), formatter.format(review)?);
+    }
 
     Ok(())
 }
 
+/// Indentation for a review `depth` levels up a stack.
+fn tree_prefix(depth: usize) -> String {
+    if depth == 0 {
+        String::new()
+    } else {
+        format!("{}└─ ", "   ".repeat(depth - 1))
+    }
+}
+
+/// Order reviews so each one is followed by the reviews stacked on it.
+///
+/// Returns each review with its depth in the stack. Reviews whose parent
+/// isn't in the list are shown at the top level.
+fn stack_tree(reviews: &[ReviewSummary]) -> Vec<(usize, &ReviewSummary)> {
+    fn visit<'a>(
+        reviews: &'a [ReviewSummary],
+        review: &'a ReviewSummary,
+        depth: usize,
+        out: &mut Vec<(usize, &'a ReviewSummary)>,
+    ) {
+        out.push((depth, review));
+        for child in reviews
+            .iter()
+            .filter(|r| r.depends_on.as_deref() == Some(review.review_id.as_str()))
+        {
+            visit(reviews, child, depth + 1, out);
+        }
+    }
+
+    let mut out = Vec::with_capacity(reviews.len());
+    for root in reviews.iter().filter(|r| {
+        r.depends_on
+            .as_deref()
+            .is_none_or(|parent| !reviews.iter().any(|p| p.review_id == parent))
+    }) {
+        visit(reviews, root, 0, &mut out);
+    }
+    // A dependency cycle has no root; don't drop its reviews from the listing
+    for review in reviews {
+        if !out.iter().any(|(_, r)| r.review_id == review.review_id) {
+            out.push((0, review));
+        }
+    }
+    out
+}
+
 /// Show details for a specific review.
-pub fn run_reviews_show(repo_root: &Path, review_id: &str, format: OutputFormat) -> Result<()> {
+pub fn run_reviews_show(
+    repo_root: &Path,
+    scm: &dyn ScmRepo,
+    review_id: &str,
+    format: OutputFormat,
+) -> Result<()> {
     use crate::cli::commands::helpers::get_review;
 
     ensure_initialized(repo_root)?;
 
-    let review = get_review(repo_root, review_id)?;
+    let mut review = get_review(repo_root, review_id)?;
+    if let Some(current) = current_review_commit(scm, &review) {
+        mark_stale_votes(&mut review.votes, &current);
+    }
 
     let formatter = Formatter::new(format);
     formatter.print(&review)?;
@@ -226,9 +364,51 @@ pub fn run_reviews_request(
     Ok(())
 }
 
+/// Withdraw review requests from reviewers.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or a reviewer isn't requested.
+#[tracing::instrument(skip(repo_root, format))]
+pub fn run_reviews_unrequest(
+    repo_root: &Path,
+    review_id: &str,
+    reviewers: &str,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(repo_root)?;
+
+    let reviewer_list: Vec<String> = reviewers
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if reviewer_list.is_empty() {
+        bail!("No reviewers specified");
+    }
+
+    let services = open_services(repo_root)?;
+    services
+        .reviews()
+        .unrequest_reviewers(review_id, reviewer_list.clone(), author)?;
+
+    let result = serde_json::json!({
+        "review_id": review_id,
+        "unrequested": reviewer_list,
+    });
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    Ok(())
+}
+
 /// Approve a review.
 pub fn run_reviews_approve(
     repo_root: &Path,
+    scm: &dyn ScmRepo,
     review_id: &str,
     author: Option<&str>,
     format: OutputFormat,
@@ -247,7 +427,7 @@ pub fn run_reviews_approve(
         );
     }
 
-    services.reviews().approve(review_id, author)?;
+    services.reviews().approve(scm, review_id, author)?;
 
     let result = serde_json::json!({
         "review_id": review_id,
@@ -295,6 +475,164 @@ pub fn run_reviews_abandon(
     Ok(())
 }
 
+/// Reopen an abandoned review.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the review isn't abandoned.
+pub fn run_reviews_reopen(
+    repo_root: &Path,
+    review_id: &str,
+    reason: Option<String>,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(repo_root)?;
+
+    let services = open_services(repo_root)?;
+    services
+        .reviews()
+        .reopen(review_id, reason.clone(), author)?;
+
+    let result = serde_json::json!({
+        "review_id": review_id,
+        "status": "open",
+        "reason": reason,
+    });
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    Ok(())
+}
+
+/// Withdraw the current agent's vote on a review.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the agent hasn't voted.
+pub fn run_reviews_retract_vote(
+    repo_root: &Path,
+    review_id: &str,
+    reason: Option<String>,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(repo_root)?;
+
+    let services = open_services(repo_root)?;
+    let author_str = seal_core::events::get_agent_identity(author)?;
+    services
+        .reviews()
+        .retract_vote(review_id, reason.clone(), Some(&author_str))?;
+
+    let result = serde_json::json!({
+        "review_id": review_id,
+        "voter": author_str,
+        "retracted": true,
+        "reason": reason,
+    });
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    Ok(())
+}
+
+/// Record a new revision of a review.
+///
+/// # Arguments
+/// * `seal_root` - Path to main repo (where .seal/ lives)
+/// * `commit` - Commit for the revision; resolved from the review's anchor if not provided
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the revision can't be recorded.
+pub fn run_reviews_update(
+    seal_root: &Path,
+    scm: &dyn ScmRepo,
+    review_id: &str,
+    commit: Option<String>,
+    note: Option<String>,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let services = open_services(seal_root)?;
+    let recorded = services
+        .reviews()
+        .update(scm, review_id, commit, note, author)?;
+    let revision = recorded.revision;
+
+    let result = serde_json::json!({
+        "review_id": review_id,
+        "revision": revision,
+        "commit": recorded.commit_hash,
+        "note": recorded.note,
+    });
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    if format != OutputFormat::Json {
+        println!();
+        println!("Next:");
+        println!("  seal diff {review_id} --since-rev {}", revision - 1);
+    }
+
+    Ok(())
+}
+
+/// Change a review's title, description or labels.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the review can't be edited.
+#[allow(clippy::too_many_arguments)]
+pub fn run_reviews_edit(
+    seal_root: &Path,
+    review_id: &str,
+    title: Option<String>,
+    description: Option<String>,
+    add_labels: Vec<String>,
+    remove_labels: Vec<String>,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    if title.is_none() && description.is_none() && add_labels.is_empty() && remove_labels.is_empty()
+    {
+        bail!("Nothing to edit. Pass --title, --description, --add-label or --remove-label.");
+    }
+
+    let services = open_services(seal_root)?;
+    services.reviews().edit(
+        review_id,
+        title,
+        description,
+        add_labels,
+        remove_labels,
+        author,
+    )?;
+
+    // Re-open to read back the edited review
+    let review = open_services(seal_root)?.reviews().get(review_id)?;
+
+    let result = serde_json::json!({
+        "review_id": review_id,
+        "title": review.title,
+        "description": review.description,
+        "labels": review.labels,
+    });
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    Ok(())
+}
+
 /// Mark a review as merged.
 ///
 /// # Arguments
@@ -322,6 +660,18 @@ pub fn run_reviews_merge(
     if review.status == "abandoned" {
         bail!("Cannot merge abandoned review: {}", review_id);
     }
+    // Stacked reviews merge bottom-up; check before --self-approve writes anything
+    if let Some(parent_id) = &review.depends_on {
+        let parent = services.reviews().get(parent_id)?;
+        if parent.status == "open" || parent.status == "approved" {
+            return Err(CoreError::DependencyNotMerged {
+                review_id: review_id.to_string(),
+                depends_on: parent_id.clone(),
+                status: parent.status,
+            }
+            .into());
+        }
+    }
     if review.status == "open" && !self_approve {
         bail!(
             "Cannot merge unapproved review: {}. Approve it first, or use --self-approve.",
@@ -330,7 +680,7 @@ pub fn run_reviews_merge(
     }
     if review.status == "open" && self_approve {
         // Auto-approve the review first
-        services.reviews().approve(review_id, author)?;
+        services.reviews().approve(scm, review_id, author)?;
     }
 
     // Re-open the database to get fresh state after any approval
@@ -358,6 +708,19 @@ pub fn run_reviews_merge(
         );
     }
 
+    // Unresolved blocking threads hold up a merge just like a block vote
+    let blocking_threads = services.db().get_open_blocking_threads(review_id)?;
+    if !blocking_threads.is_empty() {
+        bail!(
+            "Cannot merge review with unresolved blocking threads:\n{}\n\nResolve them with 'seal threads resolve <id>' or downgrade them with 'seal threads set-kind <id> <kind>' before merging.",
+            blocking_threads
+                .iter()
+                .map(|id| format!("  - {id}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
     // Get final commit hash - either provided or auto-detected from active backend.
     let final_commit = match commit {
         Some(c) => c,
@@ -366,7 +729,9 @@ pub fn run_reviews_merge(
             .context("Failed to get current commit for merge")?,
     };
 
-    services.reviews().mark_merged(review_id, final_commit.clone(), author)?;
+    services
+        .reviews()
+        .mark_merged(scm, review_id, final_commit.clone(), author)?;
 
     let result = serde_json::json!({
         "review_id": review_id,
@@ -381,9 +746,10 @@ pub fn run_reviews_merge(
 }
 
 /// Vote LGTM on a review.
-#[tracing::instrument(skip(repo_root, format, message))]
+#[tracing::instrument(skip(repo_root, scm, format, message))]
 pub fn run_lgtm(
     repo_root: &Path,
+    scm: &dyn ScmRepo,
     review_id: &str,
     message: Option<String>,
     author: Option<&str>,
@@ -391,6 +757,7 @@ pub fn run_lgtm(
 ) -> Result<()> {
     run_vote(
         repo_root,
+        scm,
         review_id,
         VoteType::Lgtm,
         message,
@@ -400,9 +767,10 @@ pub fn run_lgtm(
 }
 
 /// Block a review (request changes).
-#[tracing::instrument(skip(repo_root, format, reason))]
+#[tracing::instrument(skip(repo_root, scm, format, reason))]
 pub fn run_block(
     repo_root: &Path,
+    scm: &dyn ScmRepo,
     review_id: &str,
     reason: String,
     author: Option<&str>,
@@ -410,6 +778,7 @@ pub fn run_block(
 ) -> Result<()> {
     run_vote(
         repo_root,
+        scm,
         review_id,
         VoteType::Block,
         Some(reason),
@@ -421,6 +790,7 @@ pub fn run_block(
 /// Internal vote handler.
 fn run_vote(
     repo_root: &Path,
+    scm: &dyn ScmRepo,
     review_id: &str,
     vote: VoteType,
     reason: Option<String>,
@@ -440,28 +810,17 @@ fn run_vote(
     if review.status == "abandoned" {
         bail!("Cannot vote on abandoned review: {}", review_id);
     }
-    let review_status = review.status.clone();
 
-    // Resolve author identity for output and auto-approve check
+    // Resolve author identity for output
     let author_str = seal_core::events::get_agent_identity(author)?;
 
-    services.reviews().vote(review_id, vote.clone(), reason.clone(), author)?;
-
-    // Auto-approve on LGTM if review is open and no blocking votes from others
-    let auto_approved = if vote == VoteType::Lgtm && review_status == "open" {
-        // Re-sync to see our newly recorded vote
-        let services = open_services(repo_root)?;
-        let has_blocks = services.db().has_blocking_votes_from_others(review_id, &author_str)?;
-        if !has_blocks {
-            // Auto-approve the review
-            services.reviews().approve(review_id, author)?;
-            true
-        } else {
-            false
-        }
-    } else {
-        false
-    };
+    let approval = services.reviews().vote_and_approve(
+        scm,
+        review_id,
+        vote,
+        reason.clone(),
+        Some(&author_str),
+    )?;
 
     let mut result = serde_json::json!({
         "review_id": review_id,
@@ -469,9 +828,125 @@ fn run_vote(
         "reason": reason,
         "voter": author_str,
     });
-    if auto_approved {
+    add_approval(&mut result, &approval);
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    Ok(())
+}
+
+/// Report an automatic approval, or the unmet policy that prevented one.
+fn add_approval(result: &mut serde_json::Value, approval: &AutoApproval) {
+    if approval.approved {
         result["auto_approved"] = serde_json::json!(true);
     }
+    if !approval.unmet_policy.is_empty() {
+        result["unmet_policy"] = serde_json::json!(approval.unmet_policy);
+    }
+}
+
+/// Publish the current agent's drafts on a review together with a vote.
+///
+/// `vote` is `None` for `--comment`, which publishes the drafts alone.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the drafts can't be submitted.
+#[tracing::instrument(skip(seal_root, scm, format, message))]
+pub fn run_review_submit(
+    seal_root: &Path,
+    scm: &dyn ScmRepo,
+    review_id: &str,
+    vote: Option<VoteType>,
+    message: Option<String>,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let services = open_services(seal_root)?;
+    let review_status = services.reviews().get(review_id)?.status;
+    let author_str = seal_core::events::get_agent_identity(author)?;
+
+    let submitted = services
+        .drafts()
+        .submit(scm, review_id, vote, message, Some(&author_str))?;
+
+    let approval = if submitted.vote == Some(VoteType::Lgtm) && review_status == "open" {
+        services
+            .reviews()
+            .auto_approve(scm, review_id, Some(&author_str))?
+    } else {
+        AutoApproval::default()
+    };
+
+    let mut result = serde_json::json!({
+        "review_id": review_id,
+        "voter": author_str,
+        "vote": submitted.vote.map(|v| v.to_string()),
+        "comments": submitted.comment_ids,
+        "threads_created": submitted.threads_created,
+    });
+    add_approval(&mut result, &approval);
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    Ok(())
+}
+
+/// List the current agent's draft comments on a review.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the drafts can't be read.
+pub fn run_review_drafts(
+    seal_root: &Path,
+    review_id: &str,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let services = open_services(seal_root)?;
+    services.reviews().get(review_id)?;
+    let drafts = services.drafts().list(review_id, author)?;
+
+    let formatter = Formatter::new(format);
+    formatter.print_list(
+        &drafts,
+        "No drafts",
+        "drafts",
+        &[&format!(
+            "seal review submit {review_id} --lgtm|--block|--comment"
+        )],
+    )?;
+
+    Ok(())
+}
+
+/// Throw away the current agent's draft comments on a review.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the drafts can't be removed.
+pub fn run_review_discard(
+    seal_root: &Path,
+    review_id: &str,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let services = open_services(seal_root)?;
+    services.reviews().get(review_id)?;
+    let discarded = services.drafts().discard(review_id, author)?;
+
+    let result = serde_json::json!({
+        "review_id": review_id,
+        "discarded": discarded,
+    });
 
     let formatter = Formatter::new(format);
     formatter.print(&result)?;
@@ -500,11 +975,19 @@ pub fn run_review(
     ensure_initialized(seal_root)?;
 
     let services = open_services(seal_root)?;
-    let review = services.reviews().get(review_id)?;
+    let review = services.reviews().get_checked(scm, review_id)?;
+
+    // Policy only gates reviews that can still be approved or merged
+    let policy = if review.status == "open" || review.status == "approved" {
+        Some(services.reviews().evaluate_policy(scm, review_id, None)?)
+            .filter(|eval| !eval.rules.is_empty())
+    } else {
+        None
+    };
 
     // For JSON output, build a complete structure
     if matches!(format, OutputFormat::Json) {
-        let threads = services.threads().list(review_id, None, None)?;
+        let mut threads = services.threads().list(review_id, None, None)?;
         let mut threads_with_comments = Vec::new();
 
         // Determine commit for context (same logic as text/pretty output)
@@ -514,6 +997,7 @@ pub fn run_review(
             .or_else(|| scm.commit_for_anchor(&review.scm_anchor).ok())
             .or_else(|| scm.commit_for_anchor(&review.jj_change_id).ok())
             .unwrap_or_else(|| review.initial_commit.clone());
+        follow_renames(scm, &commit_ref, &mut threads);
 
         // Pre-fetch file contents for all thread files (one show_file per unique file)
         let mut file_cache: std::collections::HashMap<String, String> =
@@ -567,9 +1051,11 @@ pub fn run_review(
             threads_with_comments.push(serde_json::json!({
                 "thread_id": thread.thread_id,
                 "file_path": thread.file_path,
+                "moved_from": thread.moved_from,
                 "selection_start": thread.selection_start,
                 "selection_end": thread.selection_end,
                 "status": thread.status,
+                "kind": thread.kind,
                 "context": context_value,
                 "comments": filtered_comments,
             }));
@@ -579,6 +1065,9 @@ pub fn run_review(
             "review": review,
             "threads": threads_with_comments,
         });
+        if let Some(eval) = &policy {
+            result["policy"] = serde_json::json!(eval);
+        }
 
         // Include per-file diffs when requested
         if include_diffs {
@@ -613,10 +1102,23 @@ pub fn run_review(
         println!("\n  {}", desc);
     }
 
-    // Show votes if any
-    if !review.votes.is_empty() {
+    if !review.labels.is_empty() {
+        println!("  Labels: {}", review.labels.join(", "));
+    }
+
+    // Neighbours in a review stack
+    if let Some(parent) = &review.depends_on {
+        println!("  Depends on: {parent}");
+    }
+    if !review.dependents.is_empty() {
+        println!("  Stacked below: {}", review.dependents.join(", "));
+    }
+
+    // Show votes if any; votes cast on an older commit are listed separately
+    let (stale_votes, votes): (Vec<_>, Vec<_>) = review.votes.iter().partition(|v| v.stale);
+    if !votes.is_empty() {
         println!("\n  Votes:");
-        for vote in &review.votes {
+        for vote in &votes {
             let icon = if vote.vote == "lgtm" { "✓" } else { "✗" };
             let reason = vote.reason.as_deref().unwrap_or("");
             if reason.is_empty() {
@@ -626,9 +1128,41 @@ pub fn run_review(
             }
         }
     }
+    if !stale_votes.is_empty() {
+        println!("\n  Stale votes (review changed since):");
+        for vote in &stale_votes {
+            let commit = vote.commit_hash.as_deref().map_or("?", short_commit);
+            println!("    ~ {} ({} on {})", vote.reviewer, vote.vote, commit);
+        }
+    }
+
+    // Show revisions once the review has been updated
+    if review.revisions.len() > 1 {
+        println!("\n  Revisions:");
+        for rev in &review.revisions {
+            let short = short_commit(&rev.commit_hash);
+            let date = &rev.created_at[..rev.created_at.len().min(10)];
+            match &rev.note {
+                Some(note) => println!(
+                    "    r{} {} {} {}: {}",
+                    rev.revision, short, rev.author, date, note
+                ),
+                None => println!("    r{} {} {} {}", rev.revision, short, rev.author, date),
+            }
+        }
+    }
+
+    if let Some(eval) = &policy {
+        let verdict = if eval.satisfied { "met" } else { "not met" };
+        println!("\n  Policy ({verdict}):");
+        for rule in &eval.rules {
+            let icon = if rule.satisfied { "✓" } else { "✗" };
+            println!("    {} {}: {}", icon, rule.rule, rule.detail);
+        }
+    }
 
     // Get threads grouped by file
-    let threads = services.threads().list(review_id, None, None)?;
+    let mut threads = services.threads().list(review_id, None, None)?;
 
     // Determine commit for context/diff rendering
     let commit_ref = review
@@ -637,6 +1171,7 @@ pub fn run_review(
         .or_else(|| scm.commit_for_anchor(&review.scm_anchor).ok())
         .or_else(|| scm.commit_for_anchor(&review.jj_change_id).ok())
         .unwrap_or_else(|| review.initial_commit.clone());
+    follow_renames(scm, &commit_ref, &mut threads);
 
     // Pre-fetch file contents for thread files when include_diffs is requested.
     let mut file_cache: std::collections::HashMap<String, String> =
@@ -724,6 +1259,10 @@ pub fn run_review(
                 }
                 _ => format!("line {}", thread.selection_start),
             };
+            let line_info = match &thread.moved_from {
+                Some(old) => format!("{line_info}, moved from {old}"),
+                None => line_info,
+            };
 
             let new_indicator = if since.is_some() {
                 format!(" [+{}]", comments.len())
@@ -732,8 +1271,12 @@ pub fn run_review(
             };
 
             println!(
-                "\n  {} {} ({}){}",
-                status_icon, thread.thread_id, line_info, new_indicator
+                "\n  {} {}{} ({}){}",
+                status_icon,
+                thread.thread_id,
+                kind_marker(thread.kind.as_deref()),
+                line_info,
+                new_indicator
             );
 
             // Show code context if requested
@@ -758,14 +1301,37 @@ pub fn run_review(
 
             // Show comments (already filtered)
             for comment in comments {
+                if comment.retracted_at.is_some() {
+                    println!(
+                        "\n    ▸ {} ({}): [retracted]",
+                        comment.author,
+                        &comment.created_at[..10]
+                    );
+                    continue;
+                }
+                let edited = if comment.edited_at.is_some() {
+                    " (edited)"
+                } else {
+                    ""
+                };
                 println!(
-                    "\n    ▸ {} ({}):",
+                    "\n    ▸ {} ({}){}:",
                     comment.author,
-                    &comment.created_at[..10]
+                    &comment.created_at[..10],
+                    edited
                 );
                 for line in comment.body.lines() {
                     println!("       {}", line);
                 }
+                if let Some(suggestion) = &comment.suggestion {
+                    println!(
+                        "       Suggested change (seal suggestions apply {}):",
+                        comment.comment_id
+                    );
+                    for line in &suggestion.lines {
+                        println!("       + {line}");
+                    }
+                }
             }
         }
     }
@@ -828,13 +1394,48 @@ fn print_file_diffs_text(
     }
 }
 
+/// Abbreviate a commit hash for display.
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+/// Print the inbox section for votes cast before the review changed.
+fn print_stale_votes(votes: &[StaleVote]) {
+    if votes.is_empty() {
+        return;
+    }
+    println!("Reviews changed since your vote ({}):", votes.len());
+    for v in votes {
+        println!(
+            "  {} · {} by {} [your {} is stale]",
+            v.review_id, v.title, v.author, v.vote
+        );
+        println!(
+            "    voted on {}, now at {}",
+            short_commit(&v.voted_commit),
+            short_commit(&v.current_commit)
+        );
+    }
+    println!();
+}
+
 /// Show inbox - reviews and threads needing the agent's attention.
-#[tracing::instrument(skip(repo_root, format))]
-pub fn run_inbox(repo_root: &Path, agent: &str, format: OutputFormat) -> Result<()> {
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the inbox can't be loaded.
+#[tracing::instrument(skip(repo_root, scm, format))]
+pub fn run_inbox(
+    repo_root: &Path,
+    scm: &dyn ScmRepo,
+    agent: &str,
+    format: OutputFormat,
+) -> Result<()> {
     ensure_initialized(repo_root)?;
 
     let services = open_services(repo_root)?;
-    let inbox = services.inbox().get(agent)?;
+    let mut inbox = services.inbox().get(agent)?;
+    inbox.stale_votes = services.inbox().stale_votes(scm, agent)?;
 
     if matches!(format, OutputFormat::Json) {
         let formatter = Formatter::new(format);
@@ -844,6 +1445,7 @@ pub fn run_inbox(repo_root: &Path, agent: &str, format: OutputFormat) -> Result<
 
     // Text/pretty output
     let total_items = inbox.reviews_awaiting_vote.len()
+        + inbox.stale_votes.len()
         + inbox.threads_with_new_responses.len()
         + inbox.open_threads_on_my_reviews.len();
 
@@ -880,7 +1482,10 @@ pub fn run_inbox(repo_root: &Path, agent: &str, format: OutputFormat) -> Result<
         println!();
     }
 
-    // Section 2: Threads with new responses
+    // Section 2: Votes cast before the review changed
+    print_stale_votes(&inbox.stale_votes);
+
+    // Section 3: Threads with new responses
     if !inbox.threads_with_new_responses.is_empty() {
         println!(
             "Threads with new responses ({}):",
@@ -888,15 +1493,19 @@ pub fn run_inbox(repo_root: &Path, agent: &str, format: OutputFormat) -> Result<
         );
         for t in &inbox.threads_with_new_responses {
             println!(
-                "  {} · {}:{} (+{} new)",
-                t.thread_id, t.file_path, t.selection_start, t.new_response_count
+                "  {}{} · {}:{} (+{} new)",
+                t.thread_id,
+                kind_marker(t.kind.as_deref()),
+                t.file_path,
+                t.selection_start,
+                t.new_response_count
             );
             println!("    in {} ({})", t.review_id, t.review_title);
         }
         println!();
     }
 
-    // Section 3: Open threads on my reviews
+    // Section 4: Open threads on my reviews
     if !inbox.open_threads_on_my_reviews.is_empty() {
         println!(
             "Open feedback on your reviews ({}):",
@@ -909,8 +1518,13 @@ pub fn run_inbox(repo_root: &Path, agent: &str, format: OutputFormat) -> Result<
                 String::new()
             };
             println!(
-                "  {} · {}:{} by {}{}",
-                t.thread_id, t.file_path, t.selection_start, t.thread_author, comments_info
+                "  {}{} · {}:{} by {}{}",
+                t.thread_id,
+                kind_marker(t.kind.as_deref()),
+                t.file_path,
+                t.selection_start,
+                t.thread_author,
+                comments_info
             );
             println!("    in {} ({})", t.review_id, t.review_title);
         }
@@ -920,6 +1534,11 @@ pub fn run_inbox(repo_root: &Path, agent: &str, format: OutputFormat) -> Result<
     Ok(())
 }
 
+/// " [blocking]"-style suffix for a thread's kind, empty if it has none.
+fn kind_marker(kind: Option<&str>) -> String {
+    kind.map(|kind| format!(" [{kind}]")).unwrap_or_default()
+}
+
 // ============================================================================
 // File diff helpers for --include-diffs
 // ============================================================================
@@ -1277,4 +1896,202 @@ diff --git a/src/lib.rs b/src/lib.rs
         let result = split_diff_by_file("");
         assert!(result.is_empty());
     }
+
+    /// An initialized repo with one open review by alice.
+    fn setup_review(review_id: &str) -> (tempfile::TempDir, seal_core::log::ReviewLog) {
+        use seal_core::events::{Event, EventEnvelope, ReviewCreated};
+        use seal_core::log::{AppendLog, ReviewLog};
+
+        let dir = tempfile::tempdir().unwrap();
+        crate::cli::commands::init::run_init(dir.path(), false).unwrap();
+        let log = ReviewLog::new(dir.path(), review_id).unwrap();
+        log.append(&EventEnvelope::new(
+            "alice",
+            Event::ReviewCreated(ReviewCreated {
+                review_id: review_id.to_string(),
+                jj_change_id: "main".to_string(),
+                scm_kind: Some("git".to_string()),
+                scm_anchor: Some("main".to_string()),
+                initial_commit: "abc123".to_string(),
+                title: "Fix".to_string(),
+                description: None,
+                depends_on: None,
+            }),
+        ))
+        .unwrap();
+        (dir, log)
+    }
+
+    #[test]
+    fn test_retracting_only_lgtm_blocks_merge() {
+        use seal_core::events::{Event, EventEnvelope, ReviewApproved, ReviewerVoted};
+        use seal_core::log::AppendLog;
+        use seal_core::scm::git::GitRepo;
+
+        let (dir, log) = setup_review("cr-lgtm");
+        let root = dir.path();
+        log.append(&EventEnvelope::new(
+            "bob",
+            Event::ReviewerVoted(ReviewerVoted {
+                review_id: "cr-lgtm".to_string(),
+                vote: VoteType::Lgtm,
+                reason: None,
+                commit: None,
+            }),
+        ))
+        .unwrap();
+        log.append(&EventEnvelope::new(
+            "bob",
+            Event::ReviewApproved(ReviewApproved {
+                review_id: "cr-lgtm".to_string(),
+            }),
+        ))
+        .unwrap();
+
+        run_reviews_retract_vote(root, "cr-lgtm", None, Some("bob"), OutputFormat::Json).unwrap();
+        let review = open_services(root)
+            .unwrap()
+            .reviews()
+            .get("cr-lgtm")
+            .unwrap();
+        assert_eq!(review.status, "open");
+
+        let scm = GitRepo::new(root.to_path_buf());
+        let err = run_reviews_merge(
+            root,
+            &scm,
+            "cr-lgtm",
+            Some("def456".to_string()),
+            false,
+            Some("alice"),
+            OutputFormat::Json,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unapproved"), "{err}");
+    }
+
+    #[test]
+    fn test_review_submit_publishes_all_or_nothing() {
+        use seal_core::drafts::{Draft, DraftStore, DraftTarget};
+        use seal_core::events::CodeSelection;
+        use seal_core::log::AppendLog;
+        use seal_core::scm::git::GitRepo;
+
+        let (dir, log) = setup_review("cr-drafts");
+        let root = dir.path();
+        let scm = GitRepo::new(root.to_path_buf());
+        let store = DraftStore::new(root, "cr-drafts", "bob").unwrap();
+        let draft = |target| Draft {
+            created_at: chrono::Utc::now(),
+            target,
+            body: "Nit".to_string(),
+            suggestion: None,
+        };
+        store
+            .push(draft(DraftTarget::Location {
+                file_path: "src/lib.rs".to_string(),
+                selection: CodeSelection::line(3),
+                commit_hash: "abc123".to_string(),
+                anchor: None,
+                kind: None,
+            }))
+            .unwrap();
+        store
+            .push(draft(DraftTarget::Thread {
+                thread_id: "th-gone".to_string(),
+            }))
+            .unwrap();
+
+        // The reply can't be posted, so neither the new thread nor the vote is
+        let submit = || {
+            run_review_submit(
+                root,
+                &scm,
+                "cr-drafts",
+                Some(VoteType::Lgtm),
+                None,
+                Some("bob"),
+                OutputFormat::Json,
+            )
+        };
+        assert!(submit().is_err());
+        assert_eq!(log.len().unwrap(), 1);
+        assert_eq!(store.load().unwrap().len(), 2);
+
+        let mut drafts = store.load().unwrap();
+        drafts.pop();
+        store.clear().unwrap();
+        store.push(drafts.remove(0)).unwrap();
+        submit().unwrap();
+
+        let events: Vec<_> = log
+            .read_all()
+            .unwrap()
+            .iter()
+            .map(|e| e.event.name())
+            .collect();
+        // The LGTM then auto-approves the review
+        assert_eq!(
+            events,
+            vec![
+                "ReviewCreated",
+                "ThreadCreated",
+                "CommentAdded",
+                "ReviewerVoted",
+                "ReviewApproved"
+            ]
+        );
+        assert!(store.load().unwrap().is_empty());
+    }
+
+    fn summary(review_id: &str, depends_on: Option<&str>) -> ReviewSummary {
+        ReviewSummary {
+            review_id: review_id.to_string(),
+            jj_change_id: String::new(),
+            scm_kind: "git".to_string(),
+            scm_anchor: String::new(),
+            title: review_id.to_string(),
+            author: "alice".to_string(),
+            status: "open".to_string(),
+            thread_count: 0,
+            open_thread_count: 0,
+            reviewers: Vec::new(),
+            stale_votes: Vec::new(),
+            depends_on: depends_on.map(ToString::to_string),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_stack_tree_nests_dependents() {
+        // Listed newest first, as `reviews list` does
+        let reviews = vec![
+            summary("cr-top", Some("cr-mid")),
+            summary("cr-other", None),
+            summary("cr-mid", Some("cr-base")),
+            summary("cr-base", None),
+        ];
+        let tree: Vec<(usize, &str)> = stack_tree(&reviews)
+            .into_iter()
+            .map(|(depth, r)| (depth, r.review_id.as_str()))
+            .collect();
+        assert_eq!(
+            tree,
+            vec![
+                (0, "cr-other"),
+                (0, "cr-base"),
+                (1, "cr-mid"),
+                (2, "cr-top")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stack_tree_shows_orphans_at_top_level() {
+        // The parent was filtered out (e.g. already merged)
+        let reviews = vec![summary("cr-mid", Some("cr-base"))];
+        let tree = stack_tree(&reviews);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].0, 0);
+    }
 }
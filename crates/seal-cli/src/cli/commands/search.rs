@@ -0,0 +1,36 @@
+//! Implementation of `seal search`.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cli::commands::helpers::{ensure_initialized, open_services};
+use crate::output::{Formatter, OutputFormat};
+use seal_core::projection::SearchFilter;
+
+/// Full-text search over review titles, descriptions, and comment bodies.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the search fails.
+pub fn run_search(
+    seal_root: &Path,
+    query: &str,
+    filter: &SearchFilter,
+    limit: usize,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let services = open_services(seal_root)?;
+    let hits = services.search().query(query, filter, limit)?;
+
+    let formatter = Formatter::new(format);
+    formatter.print_list(
+        &hits,
+        &format!("No matches for \"{query}\""),
+        "results",
+        &["seal review <review_id>", "seal threads show <thread_id>"],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,548 @@
+//! Implementation of `seal serve`.
+//!
+//! A JSON-RPC 2.0 server on stdin/stdout for orchestrators and editors that
+//! would otherwise spawn `seal` for every call. The SCM backend and the
+//! projection database are opened once; each request starts with an
+//! incremental sync, so events appended by other processes are picked up
+//! without rereading unchanged logs.
+//!
+//! Methods follow the service layer: `reviews.*`, `threads.*`, `comments.*`,
+//! `inbox.get` and `sync.*`. Service errors carry their `CoreError` kind in
+//! `error.data.kind`.
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io;
+use std::path::Path;
+
+use crate::cli::commands::comments::parse_suggestion;
+use crate::cli::commands::helpers::{ensure_initialized, resolve_review_thread_commit};
+use crate::cli::commands::init::index_path;
+use crate::cli::commands::threads::parse_line_selection;
+use crate::jsonrpc::{self, to_result, RpcError, RpcResult};
+use seal_core::core::{CoreContext, CoreError, SealServices};
+use seal_core::events::{get_agent_identity, ThreadKind, VoteType};
+use seal_core::jj::reanchor::capture_anchor;
+use seal_core::scm::ScmRepo;
+
+/// Serve JSON-RPC requests on stdin until it is closed.
+///
+/// # Errors
+///
+/// Returns an error if `--stdio` isn't given, the projection can't be opened,
+/// or stdin or stdout fail.
+pub fn run_serve(
+    seal_root: &Path,
+    scm: Box<dyn ScmRepo>,
+    identity: Option<String>,
+    stdio: bool,
+) -> Result<()> {
+    if !stdio {
+        bail!("No transport given. Use 'seal serve --stdio'.");
+    }
+    let server = SealServer::open(seal_root, scm, identity)?;
+    jsonrpc::serve(io::stdin().lock(), io::stdout().lock(), |method, params| {
+        server.handle(method, params)
+    })
+}
+
+/// State kept open across requests.
+pub struct SealServer {
+    services: SealServices,
+    scm: Box<dyn ScmRepo>,
+    /// Author of events written through the server (`--agent`, else the environment)
+    identity: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListReviewsParams {
+    status: Option<String>,
+    author: Option<String>,
+    needs_reviewer: Option<String>,
+    #[serde(default)]
+    has_unresolved: bool,
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReviewParams {
+    review_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateReviewParams {
+    title: String,
+    description: Option<String>,
+    reviewers: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VoteParams {
+    review_id: String,
+    vote: VoteType,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReviewersParams {
+    review_id: String,
+    reviewers: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReviewReasonParams {
+    review_id: String,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListThreadsParams {
+    review_id: String,
+    status: Option<String>,
+    file: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThreadParams {
+    thread_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThreadReasonParams {
+    thread_id: String,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReplyParams {
+    thread_id: String,
+    body: String,
+    suggest: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommentParams {
+    review_id: String,
+    file: String,
+    /// Line number or range, as for `seal comment --line`
+    line: String,
+    body: String,
+    suggest: Option<String>,
+    kind: Option<ThreadKind>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EditCommentParams {
+    comment_id: String,
+    body: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommentReasonParams {
+    comment_id: String,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InboxParams {
+    agent: Option<String>,
+}
+
+impl SealServer {
+    /// Open the projection for `seal_root` and sync it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if seal isn't initialized or the projection can't be opened.
+    pub fn open(seal_root: &Path, scm: Box<dyn ScmRepo>, identity: Option<String>) -> Result<Self> {
+        ensure_initialized(seal_root)?;
+        let ctx = CoreContext::new(seal_root, &index_path(seal_root))?;
+        Ok(Self {
+            services: ctx.services()?,
+            scm,
+            identity,
+        })
+    }
+
+    /// Handle one method call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the JSON-RPC error to send back if the method is unknown, its
+    /// params are invalid, or the service call fails.
+    pub fn handle(&self, method: &str, params: Value) -> RpcResult {
+        // `sync.*` report on the sync themselves
+        if !method.starts_with("sync.") {
+            self.services.sync().sync()?;
+        }
+
+        match method {
+            _ if method.starts_with("reviews.") => self.handle_review(method, params),
+            _ if method.starts_with("threads.") || method.starts_with("comments.") => {
+                self.handle_thread(method, params)
+            }
+            "inbox.get" => {
+                let p: InboxParams = jsonrpc::params(params)?;
+                let agent = match p.agent {
+                    Some(agent) => agent,
+                    None => self.author()?,
+                };
+                to_result(self.services.inbox().get(&agent)?)
+            }
+            "sync.sync" => to_result(self.services.sync().sync()?),
+            "sync.rebuild" => {
+                let result = self.services.sync().rebuild()?;
+                Ok(json!({
+                    "events_rebuilt": result.events_rebuilt,
+                    "sync_report": result.sync_report,
+                }))
+            }
+            _ => Err(RpcError::method_not_found(method)),
+        }
+    }
+
+    /// Handle a `reviews.*` method call.
+    fn handle_review(&self, method: &str, params: Value) -> RpcResult {
+        let author = self.identity.as_deref();
+        let reviews = self.services.reviews();
+        match method {
+            "reviews.list" => {
+                let p: ListReviewsParams = jsonrpc::params(params)?;
+                to_result(reviews.list_filtered(
+                    p.status.as_deref(),
+                    p.author.as_deref(),
+                    p.needs_reviewer.as_deref(),
+                    p.has_unresolved,
+                    p.label.as_deref(),
+                )?)
+            }
+            "reviews.get" => {
+                let p: ReviewParams = jsonrpc::params(params)?;
+                to_result(reviews.get_checked(self.scm.as_ref(), &p.review_id)?)
+            }
+            "reviews.create" => {
+                let p: CreateReviewParams = jsonrpc::params(params)?;
+                let review_id = reviews.create(
+                    self.scm.as_ref(),
+                    p.title,
+                    p.description,
+                    p.reviewers,
+                    author,
+                )?;
+                Ok(json!({ "review_id": review_id }))
+            }
+            "reviews.vote" => {
+                let p: VoteParams = jsonrpc::params(params)?;
+                let approval = reviews.vote_and_approve(
+                    self.scm.as_ref(),
+                    &p.review_id,
+                    p.vote,
+                    p.reason,
+                    author,
+                )?;
+                Ok(json!({
+                    "review_id": p.review_id,
+                    "vote": p.vote.to_string(),
+                    "voter": self.author()?,
+                    "auto_approved": approval.approved,
+                    "unmet_policy": approval.unmet_policy,
+                }))
+            }
+            "reviews.request" => {
+                let p: ReviewersParams = jsonrpc::params(params)?;
+                reviews.request_reviewers(&p.review_id, p.reviewers, author)?;
+                Ok(json!({ "review_id": p.review_id }))
+            }
+            "reviews.approve" => {
+                let p: ReviewParams = jsonrpc::params(params)?;
+                reviews.approve(self.scm.as_ref(), &p.review_id, author)?;
+                Ok(json!({ "review_id": p.review_id, "status": "approved" }))
+            }
+            "reviews.abandon" => {
+                let p: ReviewReasonParams = jsonrpc::params(params)?;
+                reviews.abandon(&p.review_id, p.reason, author)?;
+                Ok(json!({ "review_id": p.review_id, "status": "abandoned" }))
+            }
+            _ => Err(RpcError::method_not_found(method)),
+        }
+    }
+
+    /// Handle a `threads.*` or `comments.*` method call.
+    fn handle_thread(&self, method: &str, params: Value) -> RpcResult {
+        let author = self.identity.as_deref();
+        let reviews = self.services.reviews();
+        let threads = self.services.threads();
+        let comments = self.services.comments();
+        match method {
+            "threads.list" => {
+                let p: ListThreadsParams = jsonrpc::params(params)?;
+                reviews.get(&p.review_id)?;
+                to_result(threads.list(&p.review_id, p.status.as_deref(), p.file.as_deref())?)
+            }
+            "threads.get" => {
+                let p: ThreadParams = jsonrpc::params(params)?;
+                to_result(threads.get(&p.thread_id)?)
+            }
+            "threads.resolve" => {
+                let p: ThreadReasonParams = jsonrpc::params(params)?;
+                threads.resolve(&p.thread_id, p.reason, author)?;
+                Ok(json!({ "thread_id": p.thread_id, "status": "resolved" }))
+            }
+            "threads.reopen" => {
+                let p: ThreadReasonParams = jsonrpc::params(params)?;
+                threads.reopen(&p.thread_id, p.reason, author)?;
+                Ok(json!({ "thread_id": p.thread_id, "status": "open" }))
+            }
+            "comments.list" => {
+                let p: ThreadParams = jsonrpc::params(params)?;
+                threads.get(&p.thread_id)?;
+                to_result(comments.list(&p.thread_id)?)
+            }
+            "comments.reply" => {
+                let p: ReplyParams = jsonrpc::params(params)?;
+                let suggestion = p.suggest.as_deref().map(parse_suggestion);
+                let result = comments.add_to_thread(&p.thread_id, &p.body, suggestion, author)?;
+                Ok(json!({ "comment_id": result.comment_id, "thread_id": result.thread_id }))
+            }
+            "comments.add" => self.comment(jsonrpc::params(params)?),
+            "comments.edit" => {
+                let p: EditCommentParams = jsonrpc::params(params)?;
+                comments.edit(&p.comment_id, &p.body, author)?;
+                Ok(json!({ "comment_id": p.comment_id }))
+            }
+            "comments.retract" => {
+                let p: CommentReasonParams = jsonrpc::params(params)?;
+                comments.retract(&p.comment_id, p.reason, author)?;
+                Ok(json!({ "comment_id": p.comment_id, "retracted": true }))
+            }
+            _ => Err(RpcError::method_not_found(method)),
+        }
+    }
+
+    /// Comment on a file and line, starting a thread there if there isn't one.
+    fn comment(&self, p: CommentParams) -> RpcResult {
+        let selection = parse_line_selection(&p.line).map_err(RpcError::invalid_params)?;
+        let review = self.services.reviews().get(&p.review_id)?;
+        let commit_hash = resolve_review_thread_commit(self.scm.as_ref(), &review);
+
+        let start_line = i64::from(selection.start_line());
+        let needs_new_thread = self
+            .services
+            .threads()
+            .find_at_location(&p.review_id, &p.file, start_line)?
+            .is_none();
+        let anchor = if needs_new_thread {
+            if !self
+                .scm
+                .file_exists(&commit_hash, &p.file)
+                .map_err(CoreError::Internal)?
+            {
+                return Err(CoreError::FileNotFound {
+                    review_id: p.review_id,
+                    commit: commit_hash,
+                    file_path: p.file,
+                }
+                .into());
+            }
+            capture_anchor(self.scm.as_ref(), &p.file, &commit_hash, &selection).unwrap_or(None)
+        } else {
+            None
+        };
+
+        let result = self.services.comments().add_to_review(
+            &p.review_id,
+            &p.file,
+            selection,
+            &p.body,
+            p.suggest.as_deref().map(parse_suggestion),
+            commit_hash,
+            anchor,
+            p.kind,
+            self.identity.as_deref(),
+        )?;
+        Ok(json!({
+            "comment_id": result.comment_id,
+            "thread_id": result.thread_id,
+            "thread_created": result.thread_created,
+        }))
+    }
+
+    fn author(&self) -> Result<String, RpcError> {
+        get_agent_identity(self.identity.as_deref()).map_err(|e| CoreError::Internal(e).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::commands::init::run_init;
+    use crate::jsonrpc::{FORBIDDEN, INVALID_PARAMS, NOT_FOUND};
+    use seal_core::scm::git::GitRepo;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(root)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// A git repo with two commits and seal initialized.
+    fn setup_repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        std::fs::write(root.join("README.md"), "hello\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "initial"]);
+        std::fs::write(root.join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        git(root, &["add", "lib.rs"]);
+        git(root, &["commit", "-q", "-m", "add lib"]);
+        run_init(root, false).unwrap();
+        temp
+    }
+
+    fn server(root: &Path, agent: &str) -> SealServer {
+        SealServer::open(
+            root,
+            Box::new(GitRepo::new(root.to_path_buf())),
+            Some(agent.to_string()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_review_workflow_over_rpc() {
+        let temp = setup_repo();
+        let alice = server(temp.path(), "alice");
+
+        let created = alice
+            .handle("reviews.create", json!({ "title": "Add lib" }))
+            .unwrap();
+        let review_id = created["review_id"].as_str().unwrap().to_string();
+
+        let comment = alice
+            .handle(
+                "comments.add",
+                json!({ "review_id": review_id, "file": "lib.rs", "line": "2", "body": "why?" }),
+            )
+            .unwrap();
+        assert_eq!(comment["thread_created"], true);
+        let thread_id = comment["thread_id"].as_str().unwrap().to_string();
+
+        alice
+            .handle(
+                "comments.reply",
+                json!({ "thread_id": thread_id, "body": "because" }),
+            )
+            .unwrap();
+        alice
+            .handle("threads.resolve", json!({ "thread_id": thread_id }))
+            .unwrap();
+
+        let thread = alice
+            .handle("threads.get", json!({ "thread_id": thread_id }))
+            .unwrap();
+        assert_eq!(thread["status"], "resolved");
+        assert_eq!(thread["comments"].as_array().unwrap().len(), 2);
+        assert_eq!(thread["anchor"]["lines"][0], "fn b() {}");
+    }
+
+    #[test]
+    fn test_sees_writes_from_other_processes() {
+        let temp = setup_repo();
+        let alice = server(temp.path(), "alice");
+        let bob = server(temp.path(), "bob");
+
+        let created = bob
+            .handle(
+                "reviews.create",
+                json!({ "title": "From bob", "reviewers": ["alice"] }),
+            )
+            .unwrap();
+
+        let reviews = alice.handle("reviews.list", Value::Null).unwrap();
+        assert_eq!(reviews.as_array().unwrap().len(), 1);
+        let inbox = alice.handle("inbox.get", json!({})).unwrap();
+        assert_eq!(
+            inbox["reviews_awaiting_vote"][0]["review_id"],
+            created["review_id"]
+        );
+
+        // An LGTM approves the review, as `seal lgtm` does
+        let voted = alice
+            .handle(
+                "reviews.vote",
+                json!({ "review_id": created["review_id"], "vote": "lgtm" }),
+            )
+            .unwrap();
+        assert_eq!(voted["auto_approved"], true);
+        let review = bob
+            .handle("reviews.get", json!({ "review_id": created["review_id"] }))
+            .unwrap();
+        assert_eq!(review["status"], "approved");
+    }
+
+    #[test]
+    fn test_errors_are_typed() {
+        let temp = setup_repo();
+        let alice = server(temp.path(), "alice");
+
+        let missing = alice
+            .handle("reviews.get", json!({ "review_id": "cr-none" }))
+            .unwrap_err();
+        assert_eq!(missing.code, NOT_FOUND);
+        assert_eq!(missing.data.unwrap()["kind"], "review_not_found");
+
+        let bad = alice
+            .handle("reviews.get", json!({ "review": "cr-none" }))
+            .unwrap_err();
+        assert_eq!(bad.code, INVALID_PARAMS);
+
+        let created = alice
+            .handle("reviews.create", json!({ "title": "Mine" }))
+            .unwrap();
+        let comment = alice
+            .handle(
+                "comments.add",
+                json!({ "review_id": created["review_id"], "file": "lib.rs", "line": "1", "body": "x" }),
+            )
+            .unwrap();
+        let edit = server(temp.path(), "bob")
+            .handle(
+                "comments.edit",
+                json!({ "comment_id": comment["comment_id"], "body": "y" }),
+            )
+            .unwrap_err();
+        assert_eq!(edit.code, FORBIDDEN);
+        assert_eq!(edit.data.unwrap()["kind"], "not_comment_author");
+
+        let no_file = alice
+            .handle(
+                "comments.add",
+                json!({ "review_id": created["review_id"], "file": "nope.rs", "line": "1", "body": "x" }),
+            )
+            .unwrap_err();
+        assert_eq!(no_file.data.unwrap()["kind"], "file_not_found");
+    }
+}
@@ -2,13 +2,16 @@
 
 use anyhow::Result;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::Path;
 
 use crate::cli::commands::helpers::{ensure_initialized, open_services, review_not_found_error};
 use seal_core::sealignore::{AllFilesIgnoredError, SealIgnore};
-use seal_core::jj::drift::{calculate_drift, DriftResult};
+use seal_core::jj::drift::{FileDrift, LineRange, RangeDriftKind};
+use seal_core::jj::reanchor::reanchor_thread;
 use crate::output::{Formatter, OutputFormat};
-use seal_core::projection::ThreadSummary;
+use seal_core::events::{CodeSelection, ThreadKind};
+use seal_core::projection::{ThreadDetail, ThreadSummary};
 use seal_core::scm::ScmRepo;
 
 /// Thread status with drift information.
@@ -16,10 +19,29 @@ use seal_core::scm::ScmRepo;
 pub struct ThreadStatusEntry {
     pub thread_id: String,
     pub file_path: String,
+    /// Path the thread was created on, if the file has since been renamed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moved_from: Option<String>,
     pub original_line: i64,
+    pub original_end_line: Option<i64>,
     pub current_line: Option<i64>,
+    /// Last line of the selection after drift (includes replacement lines)
+    pub current_end_line: Option<i64>,
+    /// "unknown" if the drift check failed; `drift_detail` says why
     pub drift_status: String,
+    /// Which lines changed, e.g. "lines 12-14 of 10-20 changed"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drift_detail: Option<String>,
+    /// Original lines that were edited or removed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_lines: Vec<LineRange>,
+    /// How confident we are that `current_line` points at the original code
+    /// (1.0 for exact drift tracking, lower for fuzzy re-anchoring).
+    pub anchor_confidence: Option<f64>,
     pub status: String,
+    /// Severity: "blocking", "nit", "question" or "praise"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
     pub comment_count: i64,
 }
 
@@ -31,6 +53,8 @@ pub struct ReviewStatus {
     pub status: String,
     pub total_threads: usize,
     pub open_threads: usize,
+    /// Open blocking threads; these hold up a merge like a block vote
+    pub blocking_threads: usize,
     pub threads_with_drift: usize,
     pub threads: Vec<ThreadStatusEntry>,
 }
@@ -45,6 +69,7 @@ pub fn run_status(
     scm: &dyn ScmRepo,
     review_id: Option<&str>,
     unresolved_only: bool,
+    kind: Option<ThreadKind>,
     format: OutputFormat,
 ) -> Result<()> {
     ensure_initialized(seal_root)?;
@@ -67,70 +92,40 @@ pub fn run_status(
     };
 
     let mut statuses = Vec::new();
+    // Each file is diffed once per thread commit, however many threads it has
+    let mut file_drifts: HashMap<(String, String), Result<FileDrift, String>> = HashMap::new();
 
     for review in reviews {
         // Get threads for this review
         let status_filter = if unresolved_only { Some("open") } else { None };
-        let threads = services.threads().list(&review.review_id, status_filter, None)?;
+        let mut threads = services
+            .threads()
+            .list(&review.review_id, status_filter, None)?;
+        if let Some(kind) = kind {
+            threads.retain(|t| t.kind.as_deref() == Some(kind.as_str()));
+        }
 
         let mut thread_entries = Vec::new();
         let mut drift_count = 0;
 
         for thread in &threads {
-            // Calculate drift for this thread
-            let thread_detail = services.threads().get_optional(&thread.thread_id)?;
-            let drift_result = if let Some(td) = &thread_detail {
-                calculate_drift(
-                    scm,
-                    &td.file_path,
-                    td.selection_start as u32,
-                    &td.commit_hash,
-                    &current_commit,
-                )
-                .unwrap_or(DriftResult::Unchanged {
-                    current_line: td.selection_start as u32,
-                })
-            } else {
-                DriftResult::Unchanged {
-                    current_line: thread.selection_start as u32,
-                }
-            };
-
-            let (current_line, drift_status) = match &drift_result {
-                DriftResult::Unchanged { current_line } => {
-                    (Some(*current_line as i64), "unchanged".to_string())
-                }
-                DriftResult::Shifted {
-                    current_line,
-                    original_line,
-                } => {
-                    drift_count += 1;
-                    let delta = *current_line as i64 - *original_line as i64;
-                    let direction = if delta > 0 { "+" } else { "" };
-                    (
-                        Some(*current_line as i64),
-                        format!("shifted({}{delta})", direction),
-                    )
-                }
-                DriftResult::Modified => {
-                    drift_count += 1;
-                    (None, "modified".to_string())
-                }
-                DriftResult::Deleted => {
-                    drift_count += 1;
-                    (None, "deleted".to_string())
-                }
-            };
-
-            thread_entries.push(ThreadStatusEntry {
-                thread_id: thread.thread_id.clone(),
-                file_path: thread.file_path.clone(),
-                original_line: thread.selection_start,
-                current_line,
-                drift_status,
-                status: thread.status.clone(),
-                comment_count: thread.comment_count,
+            let detail = services.threads().get_optional(&thread.thread_id)?;
+            let file_drift = detail.as_ref().map_or(Err("the thread has no details"), |td| {
+                file_drifts
+                    .entry((td.file_path.clone(), td.commit_hash.clone()))
+                    .or_insert_with(|| {
+                        FileDrift::between(scm, &td.file_path, &td.commit_hash, &current_commit)
+                            .map_err(|e| format!("{e:#}"))
+                    })
+                    .as_ref()
+                    .map_err(String::as_str)
             });
+            let (entry, drifted) =
+                thread_status_entry(scm, thread, detail.as_ref(), file_drift, &current_commit);
+            if drifted {
+                drift_count += 1;
+            }
+            thread_entries.push(entry);
         }
 
         let open_count = threads.iter().filter(|t| t.status == "open").count();
@@ -141,6 +136,7 @@ pub fn run_status(
             status: review.status.clone(),
             total_threads: threads.len(),
             open_threads: open_count,
+            blocking_threads: review.blocking_threads.len(),
             threads_with_drift: drift_count,
             threads: thread_entries,
         });
@@ -166,15 +162,116 @@ pub fn run_status(
     Ok(())
 }
 
+/// Work out where a thread's selection is now, following drift and renames.
+///
+/// `file_drift` is how the thread's file changed since the thread's commit,
+/// or why that couldn't be worked out, which is reported as "unknown" drift.
+/// Also returns whether the thread drifted from where it was created.
+fn thread_status_entry(
+    scm: &dyn ScmRepo,
+    thread: &ThreadSummary,
+    thread_detail: Option<&ThreadDetail>,
+    file_drift: Result<&FileDrift, &str>,
+    current_commit: &str,
+) -> (ThreadStatusEntry, bool) {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let original = LineRange::new(
+        thread.selection_start as u32,
+        thread.selection_end.unwrap_or(thread.selection_start) as u32,
+    );
+    let mut entry = ThreadStatusEntry {
+        thread_id: thread.thread_id.clone(),
+        file_path: thread.file_path.clone(),
+        moved_from: None,
+        original_line: thread.selection_start,
+        original_end_line: thread.selection_end,
+        current_line: None,
+        current_end_line: None,
+        drift_status: "unknown".to_string(),
+        drift_detail: None,
+        changed_lines: Vec::new(),
+        anchor_confidence: None,
+        status: thread.status.clone(),
+        kind: thread.kind.clone(),
+        comment_count: thread.comment_count,
+    };
+    let drift = match file_drift {
+        Ok(file) => file.range(original),
+        Err(error) => {
+            entry.drift_detail = Some(format!("drift check failed: {error}"));
+            return (entry, false);
+        }
+    };
+
+    let mut current = drift.current;
+    let mut anchor_confidence = Some(1.0);
+    let drift_status = match drift.kind {
+        RangeDriftKind::Unchanged => "unchanged".to_string(),
+        RangeDriftKind::Shifted => {
+            let delta = current.map_or(0, |c| i64::from(c.start) - i64::from(original.start));
+            let direction = if delta > 0 { "+" } else { "" };
+            format!("shifted({direction}{delta})")
+        }
+        RangeDriftKind::PartiallyModified => "partially_modified".to_string(),
+        RangeDriftKind::Modified | RangeDriftKind::Deleted => {
+            let label = if drift.kind == RangeDriftKind::Modified {
+                "modified"
+            } else {
+                "deleted"
+            };
+
+            // The anchored lines changed: try to find them again by content
+            let relocated = thread_detail.and_then(|td| {
+                let selection = td.selection_end.map_or_else(
+                    || CodeSelection::line(original.start),
+                    |_| CodeSelection::range(original.start, original.end),
+                );
+                reanchor_thread(
+                    scm,
+                    &td.file_path,
+                    drift.renamed_to.as_deref().unwrap_or(&td.file_path),
+                    &selection,
+                    td.anchor.as_ref(),
+                    &td.commit_hash,
+                    current_commit,
+                )
+                .ok()
+                .flatten()
+            });
+
+            current = relocated
+                .as_ref()
+                .map(|found| LineRange::new(found.start_line, found.end_line));
+            anchor_confidence = relocated.as_ref().map(|found| found.confidence);
+            relocated.map_or_else(|| label.to_string(), |_| format!("relocated({label})"))
+        }
+    };
+    let drifted = drift.kind != RangeDriftKind::Unchanged || drift.renamed_to.is_some();
+
+    if let Some(renamed_to) = &drift.renamed_to {
+        entry.moved_from = Some(std::mem::replace(&mut entry.file_path, renamed_to.clone()));
+    }
+    entry.current_line = current.map(|c| i64::from(c.start));
+    entry.current_end_line = current.map(|c| i64::from(c.end));
+    entry.drift_status = drift_status;
+    entry.drift_detail =
+        (drift.kind == RangeDriftKind::PartiallyModified).then(|| drift.summary());
+    entry.changed_lines = drift.changed;
+    entry.anchor_confidence = anchor_confidence;
+    (entry, drifted)
+}
+
 /// Show diff for a review.
 ///
 /// # Arguments
 /// * `seal_root` - Path to main repo (where .seal/ lives)
 /// * `workspace_root` - Path to current workspace (for jj @ resolution)
+/// * `since_rev` - If set, show only what changed after this revision (interdiff)
 pub fn run_diff(
     seal_root: &Path,
     scm: &dyn ScmRepo,
     review_id: &str,
+    since_rev: Option<i64>,
     format: OutputFormat,
 ) -> Result<()> {
     ensure_initialized(seal_root)?;
@@ -198,11 +295,16 @@ pub fn run_diff(
         .or_else(|| scm.commit_for_anchor(&review.jj_change_id).ok())
         .unwrap_or_else(|| review.initial_commit.clone());
 
-    // Get the base commit: parent of target_commit (not initial_commit)
-    // This shows ALL files changed in the review, even after rewrites
-    let base_commit = scm
-        .parent_commit(&target_commit)
-        .unwrap_or_else(|_| review.initial_commit.clone());
+    // Get the base commit:
+    // - For interdiffs: the commit recorded for the requested revision
+    // - Otherwise: parent of target_commit (not initial_commit), which shows
+    //   ALL files changed in the review, even after rewrites
+    let base_commit = match since_rev {
+        Some(rev) => services.reviews().revision(review_id, rev)?.commit_hash,
+        None => scm
+            .parent_commit(&target_commit)
+            .unwrap_or_else(|_| review.initial_commit.clone()),
+    };
 
     // Get the diff between base and target
     let diff = scm.diff_git(&base_commit, &target_commit)?;
@@ -212,8 +314,8 @@ pub fn run_diff(
     let sealignore = SealIgnore::load(seal_root);
     let (changed_files, ignored_count) = sealignore.filter_files(all_files);
 
-    // Check if all files were ignored
-    if changed_files.is_empty() && ignored_count > 0 {
+    // Check if all files were ignored (an empty interdiff just means nothing changed)
+    if changed_files.is_empty() && ignored_count > 0 && since_rev.is_none() {
         return Err(AllFilesIgnoredError {
             ignored_count,
             has_sealignore: SealIgnore::has_sealignore_file(seal_root),
@@ -225,7 +327,7 @@ pub fn run_diff(
     let threads = services.threads().list(review_id, None, None)?;
 
     // Build structured output
-    let result = serde_json::json!({
+    let mut result = serde_json::json!({
         "review_id": review_id,
         "base_commit": base_commit,
         "initial_commit": review.initial_commit,
@@ -235,6 +337,9 @@ pub fn run_diff(
         "threads_by_file": group_threads_by_file(&threads),
         "diff": diff,
     });
+    if let Some(rev) = since_rev {
+        result["since_revision"] = serde_json::json!(rev);
+    }
 
     let formatter = Formatter::new(format);
     formatter.print(&result)?;
@@ -280,3 +385,35 @@ fn group_threads_by_file(threads: &[ThreadSummary]) -> serde_json::Value {
         })
         .collect::<Vec<_>>())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seal_core::scm::git::GitRepo;
+
+    #[test]
+    fn test_failed_drift_check_is_unknown_not_unchanged() {
+        let thread = ThreadSummary {
+            thread_id: "th-001".to_string(),
+            file_path: "src/main.rs".to_string(),
+            selection_start: 3,
+            selection_end: Some(5),
+            status: "open".to_string(),
+            comment_count: 1,
+            commit_hash: "abc123".to_string(),
+            moved_from: None,
+            kind: None,
+        };
+        let scm = GitRepo::new(std::env::temp_dir());
+
+        let (entry, drifted) =
+            thread_status_entry(&scm, &thread, None, Err("diff failed"), "def456");
+        assert_eq!(entry.drift_status, "unknown");
+        assert_eq!(
+            entry.drift_detail.as_deref(),
+            Some("drift check failed: diff failed")
+        );
+        assert_eq!(entry.current_line, None);
+        assert!(!drifted);
+    }
+}
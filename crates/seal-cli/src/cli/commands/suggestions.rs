@@ -0,0 +1,44 @@
+//! Implementation of `seal suggestions` commands.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cli::commands::helpers::{ensure_initialized, open_services};
+use crate::output::{Formatter, OutputFormat};
+use seal_core::scm::ScmRepo;
+
+/// Apply a comment's suggested change to the working copy.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the suggestion can't be applied.
+pub fn run_suggestions_apply(
+    seal_root: &Path,
+    scm: &dyn ScmRepo,
+    comment_id: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(seal_root)?;
+
+    let services = open_services(seal_root)?;
+    let applied = services.suggestions().apply(scm, comment_id)?;
+
+    if format == OutputFormat::Json {
+        let formatter = Formatter::new(format);
+        formatter.print(&applied)?;
+        return Ok(());
+    }
+
+    println!(
+        "Applied {} to {}:{}-{}",
+        applied.comment_id, applied.file_path, applied.start_line, applied.end_line
+    );
+    for line in &applied.removed {
+        println!("- {line}");
+    }
+    for line in &applied.added {
+        println!("+ {line}");
+    }
+
+    Ok(())
+}
@@ -139,6 +139,7 @@ mod tests {
                 initial_commit: "commit456".to_string(),
                 title: format!("Review {review_id}"),
                 description: Some("Test description".to_string()),
+                depends_on: None,
             }),
         ))
         .unwrap();
@@ -150,6 +151,8 @@ mod tests {
                 file_path: "src/main.rs".to_string(),
                 selection: CodeSelection::range(10, 20),
                 commit_hash: "abc123".to_string(),
+                anchor: None,
+                kind: None,
             }),
         ))
         .unwrap();
@@ -7,8 +7,10 @@ use crate::cli::commands::helpers::{
     ensure_initialized, open_services, resolve_review_thread_commit, review_not_found_error,
     thread_not_found_error,
 };
-use seal_core::events::CodeSelection;
+use seal_core::core::threads::follow_renames;
+use seal_core::events::{CodeSelection, ThreadKind};
 use seal_core::jj::context::{extract_context, format_context};
+use seal_core::jj::reanchor::capture_anchor;
 use crate::output::{Formatter, OutputFormat};
 use seal_core::scm::ScmRepo;
 
@@ -17,12 +19,14 @@ use seal_core::scm::ScmRepo;
 /// # Arguments
 /// * `seal_root` - Path to main repo (where .seal/ lives)
 /// * `workspace_root` - Path to current workspace (for jj @ resolution)
+#[allow(clippy::too_many_arguments)]
 pub fn run_threads_create(
     seal_root: &Path,
     scm: &dyn ScmRepo,
     review_id: &str,
     file: &str,
     lines: &str,
+    kind: Option<ThreadKind>,
     author: Option<&str>,
     format: OutputFormat,
 ) -> Result<()> {
@@ -60,12 +64,17 @@ pub fn run_threads_create(
         );
     }
 
+    // Snapshot the anchored lines so the thread can be re-anchored later
+    let anchor = capture_anchor(scm, file, &commit_hash, &selection).unwrap_or(None);
+
     // Use core service to create the thread
     let thread_id = services.threads().create(
         review_id,
         file,
         selection.clone(),
         commit_hash.clone(),
+        anchor,
+        kind,
         author,
     )?;
 
@@ -79,6 +88,7 @@ pub fn run_threads_create(
         "selection_start": selection.start_line(),
         "selection_end": selection.end_line(),
         "commit_hash": commit_hash,
+        "kind": kind.map(|k| k.as_str()),
         "author": author_str,
     });
 
@@ -89,11 +99,14 @@ pub fn run_threads_create(
 }
 
 /// List threads for a review with optional filters.
+#[allow(clippy::too_many_arguments)]
 pub fn run_threads_list(
     repo_root: &Path,
+    scm: &dyn ScmRepo,
     review_id: &str,
     status: Option<&str>,
     file: Option<&str>,
+    kind: Option<ThreadKind>,
     verbose: bool,
     since: Option<chrono::DateTime<chrono::Utc>>,
     format: OutputFormat,
@@ -102,12 +115,23 @@ pub fn run_threads_list(
 
     let services = open_services(repo_root)?;
 
-    // Verify review exists
-    if services.reviews().get_optional(review_id)?.is_none() {
+    let Some(review) = services.reviews().get_optional(review_id)? else {
         return Err(review_not_found_error(repo_root, review_id));
-    }
+    };
 
-    let threads = services.threads().list(review_id, status, file)?;
+    // Follow renamed files first so --file matches either the old or new path
+    let mut threads = services.threads().list(review_id, status, None)?;
+    follow_renames(
+        scm,
+        &resolve_review_thread_commit(scm, &review),
+        &mut threads,
+    );
+    if let Some(file) = file {
+        threads.retain(|t| t.file_path == file || t.moved_from.as_deref() == Some(file));
+    }
+    if let Some(kind) = kind {
+        threads.retain(|t| t.kind.as_deref() == Some(kind.as_str()));
+    }
 
     // Filter threads by --since (only those with recent comments)
     let threads: Vec<_> = if let Some(since_dt) = since {
@@ -129,7 +153,7 @@ pub fn run_threads_list(
     // Build context-aware empty message
     let empty_msg = if since.is_some() {
         "No threads with activity since the specified time"
-    } else if status.is_some() || file.is_some() {
+    } else if status.is_some() || file.is_some() || kind.is_some() {
         "No threads match the filters"
     } else {
         "No threads yet"
@@ -138,28 +162,7 @@ pub fn run_threads_list(
     if verbose && !threads.is_empty() {
         // Verbose mode: show first comment for each thread
         for thread in &threads {
-            let line_range = match thread.selection_end {
-                Some(end) if end != thread.selection_start => {
-                    format!("{}:{}-{}", thread.file_path, thread.selection_start, end)
-                }
-                _ => format!("{}:{}", thread.file_path, thread.selection_start),
-            };
-
-            let status_icon = if thread.status == "open" {
-                "○"
-            } else {
-                "✓"
-            };
-
-            println!(
-                "{} {} {} ({}, {} comment{})",
-                status_icon,
-                thread.thread_id,
-                line_range,
-                thread.status,
-                thread.comment_count,
-                if thread.comment_count == 1 { "" } else { "s" }
-            );
+            println!("{}", thread_heading(thread));
 
             // Get first comment if any
             let comments = services.comments().list(&thread.thread_id)?;
@@ -194,6 +197,44 @@ pub fn run_threads_list(
     Ok(())
 }
 
+/// One-line summary of a thread for `seal threads list --verbose`.
+fn thread_heading(thread: &seal_core::projection::ThreadSummary) -> String {
+    let line_range = match thread.selection_end {
+        Some(end) if end != thread.selection_start => {
+            format!("{}:{}-{}", thread.file_path, thread.selection_start, end)
+        }
+        _ => format!("{}:{}", thread.file_path, thread.selection_start),
+    };
+
+    let status_icon = if thread.status == "open" {
+        "○"
+    } else {
+        "✓"
+    };
+    let moved = thread
+        .moved_from
+        .as_ref()
+        .map(|old| format!(" [moved from {old}]"))
+        .unwrap_or_default();
+    let kind = thread
+        .kind
+        .as_ref()
+        .map(|kind| format!(" [{kind}]"))
+        .unwrap_or_default();
+
+    format!(
+        "{} {}{} {}{} ({}, {} comment{})",
+        status_icon,
+        thread.thread_id,
+        kind,
+        line_range,
+        moved,
+        thread.status,
+        thread.comment_count,
+        if thread.comment_count == 1 { "" } else { "s" }
+    )
+}
+
 /// Show details for a specific thread with optional context.
 ///
 /// # Arguments
@@ -284,6 +325,7 @@ mod colors {
     pub const RESET: &str = "\x1b[0m";
     pub const BOLD: &str = "\x1b[1m";
     pub const DIM: &str = "\x1b[2m";
+    pub const RED: &str = "\x1b[31m";
     pub const GREEN: &str = "\x1b[32m";
     pub const YELLOW: &str = "\x1b[33m";
     pub const BLUE: &str = "\x1b[34m";
@@ -291,6 +333,67 @@ mod colors {
     pub const CYAN: &str = "\x1b[36m";
 }
 
+/// Print a comment's suggested change as removed and added lines.
+fn print_suggestion(
+    comment_id: &str,
+    removed: &[String],
+    added: &[String],
+    c: &impl Fn(&str, &str) -> String,
+) {
+    println!(
+        "  {}",
+        c(
+            colors::DIM,
+            &format!("Suggested change (seal suggestions apply {comment_id}):")
+        )
+    );
+    for line in removed {
+        println!("  {}", c(colors::RED, &format!("- {line}")));
+    }
+    for line in added {
+        println!("  {}", c(colors::GREEN, &format!("+ {line}")));
+    }
+}
+
+/// Print one comment of a thread conversation.
+fn print_comment(
+    thread: &seal_core::projection::ThreadDetail,
+    comment: &seal_core::projection::Comment,
+    c: &impl Fn(&str, &str) -> String,
+) {
+    println!();
+    let edited = if comment.edited_at.is_some() && comment.retracted_at.is_none() {
+        format!(" {}", c(colors::DIM, "(edited)"))
+    } else {
+        String::new()
+    };
+    println!(
+        "{} ({}){}",
+        c(colors::MAGENTA, &comment.author),
+        c(colors::DIM, &format_timestamp(&comment.created_at)),
+        edited
+    );
+    if comment.retracted_at.is_some() {
+        let marker = comment.retract_reason.as_deref().map_or_else(
+            || "[retracted]".to_string(),
+            |reason| format!("[retracted: {reason}]"),
+        );
+        println!("  {}", c(colors::DIM, &marker));
+        return;
+    }
+    // Indent the body for readability
+    for line in comment.body.lines() {
+        println!("  {line}");
+    }
+    if let Some(suggestion) = &comment.suggestion {
+        let removed = thread
+            .anchor
+            .as_ref()
+            .map_or(&[][..], |a| a.lines.as_slice());
+        print_suggestion(&comment.comment_id, removed, &suggestion.lines, c);
+    }
+}
+
 /// Format and print a thread as a human-readable conversation.
 fn print_conversation(
     thread: &seal_core::projection::ThreadDetail,
@@ -328,10 +431,17 @@ fn print_conversation(
         _ => format!("line {}", thread.selection_start),
     };
 
+    let kind_indicator = match thread.kind.as_deref() {
+        Some("blocking") => format!(" {}", c(colors::RED, "[BLOCKING]")),
+        Some(kind) => format!(" {}", c(colors::DIM, &format!("[{}]", kind.to_uppercase()))),
+        None => String::new(),
+    };
+
     println!(
-        "{} {} on {} ({})",
+        "{} {}{} on {} ({})",
         bold(&format!("Thread {}", thread.thread_id)),
         status_indicator,
+        kind_indicator,
         c(colors::CYAN, &thread.file_path),
         c(colors::DIM, &line_range)
     );
@@ -353,16 +463,7 @@ fn print_conversation(
 
     // Comments as conversation
     for comment in &thread.comments {
-        println!();
-        println!(
-            "{} ({})",
-            c(colors::MAGENTA, &comment.author),
-            c(colors::DIM, &format_timestamp(&comment.created_at))
-        );
-        // Indent the body for readability
-        for line in comment.body.lines() {
-            println!("  {}", line);
-        }
+        print_comment(thread, comment, &c);
     }
 
     // Status changes
@@ -536,6 +637,39 @@ pub fn run_threads_reopen(
     Ok(())
 }
 
+/// Change a thread's kind, or clear it.
+///
+/// # Errors
+///
+/// Returns an error if seal isn't initialized or the kind can't be changed.
+pub fn run_threads_set_kind(
+    repo_root: &Path,
+    thread_id: &str,
+    kind: Option<ThreadKind>,
+    author: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    ensure_initialized(repo_root)?;
+
+    let services = open_services(repo_root)?;
+
+    if services.threads().get_optional(thread_id)?.is_none() {
+        return Err(thread_not_found_error(repo_root, thread_id));
+    }
+
+    services.threads().set_kind(thread_id, kind, author)?;
+
+    let result = serde_json::json!({
+        "thread_id": thread_id,
+        "kind": kind.map(|k| k.as_str()),
+    });
+
+    let formatter = Formatter::new(format);
+    formatter.print(&result)?;
+
+    Ok(())
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
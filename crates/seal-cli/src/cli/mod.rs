@@ -6,6 +6,7 @@ use std::io::IsTerminal;
 pub mod commands;
 
 use crate::output::OutputFormat;
+use seal_core::events::Event;
 use seal_core::scm::ScmPreference;
 
 /// Agent-centric distributed code review tool for Git and jj
@@ -83,7 +84,29 @@ impl Cli {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Initialize a new .seal directory in the current repository
-    Init,
+    Init {
+        /// Register `seal merge-driver` with git (.gitattributes and git config)
+        /// and jj (repo config) so concurrent appends to a review log merge cleanly
+        #[arg(long)]
+        install_merge_driver: bool,
+    },
+
+    /// Merge two versions of a .seal event log (called by git and jj)
+    #[command(name = "merge-driver")]
+    MergeDriver {
+        /// Common ancestor version (unused: logs are append-only, so a union suffices)
+        base: std::path::PathBuf,
+
+        /// Our version; receives the result unless --output is given
+        ours: std::path::PathBuf,
+
+        /// Their version
+        theirs: std::path::PathBuf,
+
+        /// Write the result here instead of over OURS
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
 
     /// Health check - verify SCM detection, .seal/, and sync status
     Doctor,
@@ -120,6 +143,26 @@ pub enum Commands {
     #[command(subcommand)]
     Comments(CommentsCommands),
 
+    /// Apply suggested changes from comments
+    #[command(subcommand)]
+    Suggestions(SuggestionsCommands),
+
+    /// Stream review events
+    #[command(subcommand)]
+    Events(EventsCommands),
+
+    /// Run event hooks (.seal/hooks.toml)
+    #[command(subcommand)]
+    Hooks(HooksCommands),
+
+    /// Inspect code owners (.seal/owners)
+    #[command(subcommand)]
+    Owners(OwnersCommands),
+
+    /// Manage event signing keys
+    #[command(subcommand)]
+    Keys(KeysCommands),
+
     /// Show status of reviews
     Status {
         /// Review ID (optional - shows all if omitted)
@@ -128,12 +171,20 @@ pub enum Commands {
         /// Show only unresolved threads
         #[arg(long)]
         unresolved_only: bool,
+
+        /// Show only threads of this kind
+        #[arg(long)]
+        kind: Option<ThreadKind>,
     },
 
     /// Show diff for a review
     Diff {
         /// Review ID
         review_id: String,
+
+        /// Only show changes made after this revision (interdiff)
+        #[arg(long = "since-rev", value_name = "N")]
+        since_rev: Option<i64>,
     },
 
     /// Interactive UI for browsing reviews
@@ -155,6 +206,18 @@ pub enum Commands {
         /// Comment message
         #[arg(value_name = "MESSAGE")]
         message: String,
+
+        /// Suggested replacement for the thread's lines (apply with `seal suggestions apply`)
+        #[arg(long, value_name = "TEXT")]
+        suggest: Option<String>,
+
+        /// Kind of thread, if this comment starts a new one
+        #[arg(long)]
+        kind: Option<ThreadKind>,
+
+        /// Save as a draft, published later by `seal review submit`
+        #[arg(long)]
+        draft: bool,
     },
 
     /// Approve a review (LGTM - Looks Good To Me)
@@ -177,10 +240,12 @@ pub enum Commands {
         reason: String,
     },
 
-    /// Show full review with all threads and comments
+    /// Show full review with all threads and comments, or submit drafts
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Review {
         /// Review ID
-        review_id: String,
+        #[arg(required = true)]
+        review_id: Option<String>,
 
         /// Number of context lines around each thread (default: 3)
         #[arg(long, default_value = "3")]
@@ -197,6 +262,9 @@ pub enum Commands {
         /// Include per-file diffs and orphaned thread content in JSON output
         #[arg(long)]
         include_diffs: bool,
+
+        #[command(subcommand)]
+        command: Option<ReviewCommands>,
     },
 
     /// Reply to an existing thread (shortcut for `comments add`)
@@ -207,11 +275,59 @@ pub enum Commands {
         /// Reply message
         #[arg(value_name = "MESSAGE")]
         message: String,
+
+        /// Suggested replacement for the thread's lines (apply with `seal suggestions apply`)
+        #[arg(long, value_name = "TEXT")]
+        suggest: Option<String>,
+
+        /// Save as a draft, published later by `seal review submit`
+        #[arg(long)]
+        draft: bool,
     },
 
     /// Show reviews and threads needing your attention
     Inbox,
 
+    /// Full-text search over review titles, descriptions, and comments
+    Search {
+        /// Search terms (all must match; end a term with * for a prefix match)
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only show matches written by this agent
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Only show matches on reviews with this status
+        #[arg(long)]
+        status: Option<ReviewStatus>,
+
+        /// Only show comments on this file or under this directory
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Only show matches since this timestamp (ISO 8601 or relative like "1h", "2d")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Maximum number of results
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Run a language server on stdio, showing open threads as editor diagnostics
+    Lsp,
+
+    /// Run an MCP (Model Context Protocol) server on stdio, exposing seal as agent tools
+    Mcp,
+
+    /// Serve the seal API as JSON-RPC 2.0 for long-running agents and editors
+    Serve {
+        /// Read newline-delimited requests from stdin and write responses to stdout
+        #[arg(long)]
+        stdio: bool,
+    },
+
     /// Sync projection database from event logs
     Sync {
         /// Full rebuild from scratch (destructive)
@@ -236,6 +352,153 @@ pub enum AgentsCommands {
     Show,
 }
 
+// ============================================================================
+// Review subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum ReviewCommands {
+    /// Publish your draft comments together with a vote
+    Submit {
+        /// Review ID
+        review_id: String,
+
+        /// Approve (LGTM)
+        #[arg(long, conflicts_with_all = ["block", "comment"], required_unless_present_any = ["block", "comment"])]
+        lgtm: bool,
+
+        /// Request changes (requires --message)
+        #[arg(long, conflicts_with = "comment", requires = "message")]
+        block: bool,
+
+        /// Publish the drafts without voting
+        #[arg(long)]
+        comment: bool,
+
+        /// Vote message (the reason, when blocking)
+        #[arg(long = "message", short = 'm')]
+        message: Option<String>,
+    },
+
+    /// List your unpublished draft comments
+    Drafts {
+        /// Review ID
+        review_id: String,
+    },
+
+    /// Throw away your draft comments
+    Discard {
+        /// Review ID
+        review_id: String,
+    },
+}
+
+// ============================================================================
+// Suggestions subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum SuggestionsCommands {
+    /// Patch the working copy with a comment's suggested change
+    Apply {
+        /// Comment ID (e.g., th-abc.1)
+        comment_id: String,
+    },
+}
+
+// ============================================================================
+// Owners subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum OwnersCommands {
+    /// Show which owners rule matches a path
+    Explain {
+        /// Repository-relative file path
+        file: String,
+    },
+}
+
+// ============================================================================
+// Events subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum EventsCommands {
+    /// Print events appended to review logs as NDJSON
+    Tail {
+        /// Keep running and print events as they are appended
+        #[arg(long, short = 'f')]
+        follow: bool,
+
+        /// Start with events since this timestamp (ISO 8601 or relative like "1h", "2d")
+        #[arg(long, required_unless_present = "follow")]
+        since: Option<String>,
+
+        /// Only events on this review
+        #[arg(long)]
+        review: Option<String>,
+
+        /// Only events written by this agent
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Only events of this type, e.g. `CommentAdded` (repeatable)
+        #[arg(long = "type", value_name = "EVENT", value_parser = parse_event_type)]
+        event_types: Vec<String>,
+
+        /// Only events by others on reviews this agent authors or reviews, or threads they joined
+        #[arg(long = "for", value_name = "AGENT")]
+        for_agent: Option<String>,
+    },
+}
+
+/// An event type name for `--type`; unknown names are rejected rather than
+/// silently matching nothing.
+fn parse_event_type(name: &str) -> Result<String, String> {
+    Event::parse_name(name).map(str::to_string).ok_or_else(|| {
+        format!(
+            "unknown event type '{name}' (expected one of: {})",
+            Event::NAMES.join(", ")
+        )
+    })
+}
+
+// ============================================================================
+// Hooks subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum HooksCommands {
+    /// Trust the current .seal/hooks.toml so its hooks run (review it first)
+    Trust,
+
+    /// Run hooks for events already written, e.g. after adding a hook
+    Replay {
+        /// Events since this timestamp (ISO 8601 or relative like "1h", "2d")
+        #[arg(long)]
+        since: String,
+
+        /// Show the hooks that would run without running them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+// ============================================================================
+// Keys subcommands
+// ============================================================================
+
+#[derive(Subcommand, Debug)]
+pub enum KeysCommands {
+    /// Generate a signing key for the current agent
+    Generate {
+        /// Replace an existing key
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 // ============================================================================
 // Reviews subcommands
 // ============================================================================
@@ -245,16 +508,21 @@ pub enum ReviewsCommands {
     /// Create a new review for the current change
     Create {
         /// Review title
-        #[arg(long)]
-        title: String,
+        #[arg(long, required_unless_present = "stack")]
+        title: Option<String>,
 
         /// Optional description
-        #[arg(long = "description", visible_alias = "desc")]
+        #[arg(long = "description", visible_alias = "desc", conflicts_with = "stack")]
         description: Option<String>,
 
         /// Comma-separated list of reviewers to request
         #[arg(long = "reviewers", visible_alias = "reviewer")]
         reviewers: Option<String>,
+
+        /// Create one review per change in a linear stack (jj revset or git
+        /// range, e.g. `trunk()..@` or `main..HEAD`), each depending on the one below
+        #[arg(long, conflicts_with = "title")]
+        stack: Option<String>,
     },
 
     /// List reviews
@@ -274,6 +542,10 @@ pub enum ReviewsCommands {
         /// Show only reviews with unresolved threads
         #[arg(long)]
         has_unresolved: bool,
+
+        /// Show only reviews with this label
+        #[arg(long)]
+        label: Option<String>,
     },
 
     /// Show review details
@@ -292,6 +564,52 @@ pub enum ReviewsCommands {
         reviewers: String,
     },
 
+    /// Withdraw review requests from reviewers
+    Unrequest {
+        /// Review ID
+        review_id: String,
+
+        /// Comma-separated list of reviewers
+        #[arg(long = "reviewers", visible_alias = "reviewer")]
+        reviewers: String,
+    },
+
+    /// Record a new revision of a review after pushing changes
+    Update {
+        /// Review ID
+        review_id: String,
+
+        /// Commit for the new revision (defaults to the review's change/branch)
+        #[arg(long)]
+        commit: Option<String>,
+
+        /// Note describing what changed in this revision
+        #[arg(long, short = 'm')]
+        note: Option<String>,
+    },
+
+    /// Change a review's title, description or labels
+    Edit {
+        /// Review ID
+        review_id: String,
+
+        /// New title
+        #[arg(long)]
+        title: Option<String>,
+
+        /// New description (an empty string clears it)
+        #[arg(long = "description", visible_alias = "desc")]
+        description: Option<String>,
+
+        /// Label to add (repeatable or comma-separated)
+        #[arg(long = "add-label", value_name = "LABEL", value_delimiter = ',')]
+        add_labels: Vec<String>,
+
+        /// Label to remove (repeatable or comma-separated)
+        #[arg(long = "remove-label", value_name = "LABEL", value_delimiter = ',')]
+        remove_labels: Vec<String>,
+    },
+
     /// Approve a review
     Approve {
         /// Review ID
@@ -308,6 +626,27 @@ pub enum ReviewsCommands {
         reason: Option<String>,
     },
 
+    /// Reopen an abandoned review
+    Reopen {
+        /// Review ID
+        review_id: String,
+
+        /// Reason for reopening
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Withdraw your vote (LGTM or block) on a review
+    #[command(name = "retract-vote")]
+    RetractVote {
+        /// Review ID
+        review_id: String,
+
+        /// Reason for retracting
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
     /// Mark a review as merged (records that the code has landed)
     #[command(name = "mark-merged")]
     MarkMerged {
@@ -332,6 +671,19 @@ pub enum ReviewStatus {
     Abandoned,
 }
 
+impl ReviewStatus {
+    /// Status name as stored in the projection.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Approved => "approved",
+            Self::Merged => "merged",
+            Self::Abandoned => "abandoned",
+        }
+    }
+}
+
 // ============================================================================
 // Threads subcommands
 // ============================================================================
@@ -350,6 +702,10 @@ pub enum ThreadsCommands {
         /// Line or range (e.g., "42" or "10-20")
         #[arg(long)]
         lines: String,
+
+        /// Kind of thread (blocking threads hold up a merge)
+        #[arg(long)]
+        kind: Option<ThreadKind>,
     },
 
     /// List threads for a review
@@ -365,6 +721,10 @@ pub enum ThreadsCommands {
         #[arg(long)]
         file: Option<String>,
 
+        /// Filter by kind
+        #[arg(long)]
+        kind: Option<ThreadKind>,
+
         /// Show first comment body for each thread
         #[arg(long, short = 'v')]
         verbose: bool,
@@ -427,6 +787,20 @@ pub enum ThreadsCommands {
         #[arg(long)]
         reason: Option<String>,
     },
+
+    /// Change a thread's kind
+    SetKind {
+        /// Thread ID
+        thread_id: String,
+
+        /// New kind
+        #[arg(required_unless_present = "clear")]
+        kind: Option<ThreadKind>,
+
+        /// Remove the thread's kind instead
+        #[arg(long, conflicts_with = "kind")]
+        clear: bool,
+    },
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -435,6 +809,30 @@ pub enum ThreadStatus {
     Resolved,
 }
 
+/// Severity of a thread.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ThreadKind {
+    /// Must be fixed before merging
+    Blocking,
+    /// Minor or stylistic
+    Nit,
+    /// Asks for clarification
+    Question,
+    /// Positive feedback
+    Praise,
+}
+
+impl From<ThreadKind> for seal_core::events::ThreadKind {
+    fn from(kind: ThreadKind) -> Self {
+        match kind {
+            ThreadKind::Blocking => Self::Blocking,
+            ThreadKind::Nit => Self::Nit,
+            ThreadKind::Question => Self::Question,
+            ThreadKind::Praise => Self::Praise,
+        }
+    }
+}
+
 // ============================================================================
 // Comments subcommands
 // ============================================================================
@@ -453,6 +851,14 @@ pub enum CommentsCommands {
         /// Comment message (positional argument)
         #[arg(value_name = "MESSAGE")]
         message_positional: Option<String>,
+
+        /// Suggested replacement for the thread's lines (apply with `seal suggestions apply`)
+        #[arg(long, value_name = "TEXT")]
+        suggest: Option<String>,
+
+        /// Save as a draft, published later by `seal review submit`
+        #[arg(long)]
+        draft: bool,
     },
 
     /// List comments in a thread
@@ -460,4 +866,34 @@ pub enum CommentsCommands {
         /// Thread ID
         thread_id: String,
     },
+
+    /// Edit one of your own comments
+    Edit {
+        /// Comment ID (e.g., th-abc.1)
+        comment_id: String,
+
+        /// New comment body (positional or use --message)
+        #[arg(long = "message", visible_alias = "msg")]
+        message: Option<String>,
+
+        /// New comment body (positional argument)
+        #[arg(value_name = "MESSAGE")]
+        message_positional: Option<String>,
+    },
+
+    /// Retract one of your own comments
+    Retract {
+        /// Comment ID (e.g., th-abc.1)
+        comment_id: String,
+
+        /// Reason for retracting
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Show the edit history of a comment
+    History {
+        /// Comment ID (e.g., th-abc.1)
+        comment_id: String,
+    },
 }
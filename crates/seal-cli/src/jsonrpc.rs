@@ -0,0 +1,410 @@
+//! JSON-RPC 2.0 over newline-delimited JSON, for `seal serve` and `seal mcp`.
+//!
+//! Each line of input is one request (or a batch array of requests) and
+//! each response is written as one line. Notifications (requests without an
+//! `id`) are handled but get no response.
+//!
+//! `seal lsp` speaks the same messages with LSP's `Content-Length` framing
+//! instead; see [`read_framed`] and [`write_framed`].
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+use seal_core::core::CoreError;
+
+/// Invalid JSON was received.
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON sent is not a valid request object.
+pub const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Invalid method parameters.
+pub const INVALID_PARAMS: i64 = -32602;
+/// Internal error.
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// The repository isn't initialized or needs migrating.
+pub const NOT_INITIALIZED: i64 = -32001;
+/// A review, thread, comment, revision or file doesn't exist.
+pub const NOT_FOUND: i64 = -32002;
+/// The caller isn't allowed to make the change.
+pub const FORBIDDEN: i64 = -32003;
+/// The change conflicts with the current state of the review.
+pub const CONFLICT: i64 = -32004;
+
+/// Result of a method call.
+pub type RpcResult = std::result::Result<Value, RpcError>;
+
+/// A JSON-RPC error object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    #[must_use]
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    #[must_use]
+    pub fn method_not_found(method: &str) -> Self {
+        Self::new(METHOD_NOT_FOUND, format!("Method not found: {method}"))
+    }
+
+    #[must_use]
+    pub fn invalid_params(message: impl std::fmt::Display) -> Self {
+        Self::new(INVALID_PARAMS, format!("Invalid params: {message}"))
+    }
+
+    #[must_use]
+    pub fn internal(error: &anyhow::Error) -> Self {
+        Self::new(INTERNAL_ERROR, format!("{error:#}"))
+    }
+}
+
+/// Service errors keep their variant in `data.kind`, so clients can match on
+/// it instead of the message.
+impl From<CoreError> for RpcError {
+    fn from(error: CoreError) -> Self {
+        let code = match &error {
+            CoreError::NotInitialized { .. } | CoreError::V1NeedsMigration => NOT_INITIALIZED,
+            CoreError::ReviewNotFound { .. }
+            | CoreError::ThreadNotFound { .. }
+            | CoreError::CommentNotFound { .. }
+            | CoreError::RevisionNotFound { .. }
+            | CoreError::FileNotFound { .. } => NOT_FOUND,
+            CoreError::NotCommentAuthor { .. }
+            | CoreError::NotReviewAuthor { .. }
+            | CoreError::NotThreadAuthor { .. }
+            | CoreError::PolicyNotSatisfied { .. } => FORBIDDEN,
+            CoreError::InvalidLabel { .. } | CoreError::InvalidStack { .. } => INVALID_PARAMS,
+            CoreError::Internal(_) => INTERNAL_ERROR,
+            _ => CONFLICT,
+        };
+        let mut data = json!({ "kind": error.kind() });
+        if let CoreError::PolicyNotSatisfied { unmet, .. } = &error {
+            data["unmet"] = json!(unmet);
+        }
+        let message = match &error {
+            CoreError::Internal(e) => format!("{e:#}"),
+            e => e.to_string(),
+        };
+        Self {
+            code,
+            message,
+            data: Some(data),
+        }
+    }
+}
+
+/// Deserialize method params; absent params read as an empty object.
+///
+/// # Errors
+///
+/// Returns an invalid-params error if they don't match `T`.
+pub fn params<T: DeserializeOwned>(params: Value) -> std::result::Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(RpcError::invalid_params)
+}
+
+/// Serialize a method's return value.
+///
+/// # Errors
+///
+/// Returns an internal error if the value can't be serialized.
+pub fn to_result(value: impl Serialize) -> RpcResult {
+    serde_json::to_value(value).map_err(|e| RpcError::internal(&e.into()))
+}
+
+/// Read requests from `input` until EOF, writing a response line for each.
+///
+/// # Errors
+///
+/// Returns an error if reading `input` or writing `output` fails.
+pub fn serve(
+    mut input: impl BufRead,
+    mut output: impl Write,
+    mut handler: impl FnMut(&str, Value) -> RpcResult,
+) -> Result<()> {
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        let reply = match std::str::from_utf8(&line) {
+            Ok(text) if text.trim().is_empty() => continue,
+            Ok(text) => handle_message(text, &mut handler),
+            // Not UTF-8, so not JSON either; answer it and keep serving
+            Err(e) => Some(response(
+                Value::Null,
+                Err(RpcError::new(PARSE_ERROR, format!("Parse error: {e}"))),
+            )),
+        };
+        if let Some(reply) = reply {
+            writeln!(output, "{reply}")?;
+            output.flush()?;
+        }
+    }
+}
+
+/// Handle one line of input, returning the response, if any.
+pub fn handle_message(
+    line: &str,
+    handler: &mut impl FnMut(&str, Value) -> RpcResult,
+) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => {
+            return Some(response(
+                Value::Null,
+                Err(RpcError::new(PARSE_ERROR, format!("Parse error: {e}"))),
+            ))
+        }
+    };
+
+    match message {
+        Value::Array(batch) if batch.is_empty() => Some(response(
+            Value::Null,
+            Err(RpcError::new(INVALID_REQUEST, "Empty batch")),
+        )),
+        Value::Array(batch) => {
+            let responses: Vec<Value> = batch
+                .iter()
+                .filter_map(|request| handle_request(request, handler))
+                .collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        request => handle_request(&request, handler),
+    }
+}
+
+fn handle_request(
+    request: &Value,
+    handler: &mut impl FnMut(&str, Value) -> RpcResult,
+) -> Option<Value> {
+    let id = request.get("id").cloned();
+    let method = request.get("method").and_then(Value::as_str);
+    let (Some(method), Some("2.0")) = (method, request.get("jsonrpc").and_then(Value::as_str))
+    else {
+        return Some(response(
+            id.unwrap_or(Value::Null),
+            Err(RpcError::new(INVALID_REQUEST, "Invalid request")),
+        ));
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    if !(params.is_null() || params.is_object() || params.is_array()) {
+        return id.map(|id| {
+            response(
+                id,
+                Err(RpcError::invalid_params(
+                    "params must be an object or array",
+                )),
+            )
+        });
+    }
+
+    tracing::debug!(method, "rpc request");
+    let result = handler(method, params);
+    // Notifications get no response, even on error
+    id.map(|id| response(id, result))
+}
+
+/// A response to the request with `id`.
+#[must_use]
+pub fn response(id: Value, result: RpcResult) -> Value {
+    let (key, value) = match result {
+        Ok(result) => ("result", result),
+        Err(error) => ("error", json!(error)),
+    };
+    let mut message = json!({ "jsonrpc": "2.0" });
+    message["id"] = id;
+    message[key] = value;
+    message
+}
+
+/// A notification (a request that expects no response).
+#[must_use]
+pub fn notification(method: &str, params: Value) -> Value {
+    let mut message = json!({ "jsonrpc": "2.0", "method": method });
+    message["params"] = params;
+    message
+}
+
+/// Read one `Content-Length`-framed message. Returns `None` at end of input.
+///
+/// # Errors
+///
+/// Fails on I/O errors, a missing or invalid `Content-Length` header, or
+/// input ending mid-message.
+pub fn read_framed(input: &mut impl BufRead) -> Result<Option<String>> {
+    let mut length = None;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if input.read_line(&mut header)? == 0 {
+            if length.is_none() {
+                return Ok(None);
+            }
+            bail!("Input ended inside a message header");
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            // Tolerate blank lines between messages
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .with_context(|| format!("Invalid Content-Length: {value}"))?,
+                );
+            }
+        }
+    }
+
+    let mut body = vec![0; length.unwrap_or(0)];
+    input
+        .read_exact(&mut body)
+        .context("Input ended inside a message")?;
+    Ok(Some(
+        String::from_utf8(body).context("Message is not valid UTF-8")?,
+    ))
+}
+
+/// Write one message with a `Content-Length` header.
+///
+/// # Errors
+///
+/// Fails on I/O errors.
+pub fn write_framed(output: &mut impl Write, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo(method: &str, params: Value) -> RpcResult {
+        match method {
+            "echo" => Ok(params),
+            "missing" => Err(CoreError::ReviewNotFound {
+                review_id: "cr-x".to_string(),
+            }
+            .into()),
+            _ => Err(RpcError::method_not_found(method)),
+        }
+    }
+
+    fn handle(line: &str) -> Option<Value> {
+        handle_message(line, &mut echo)
+    }
+
+    #[test]
+    fn test_request_and_notification() {
+        let response =
+            handle(r#"{"jsonrpc":"2.0","id":7,"method":"echo","params":{"a":1}}"#).unwrap();
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["result"]["a"], 1);
+
+        assert!(handle(r#"{"jsonrpc":"2.0","method":"echo"}"#).is_none());
+    }
+
+    #[test]
+    fn test_protocol_errors() {
+        assert_eq!(handle("{not json").unwrap()["error"]["code"], PARSE_ERROR);
+        assert_eq!(
+            handle(r#"{"id":1,"method":"echo"}"#).unwrap()["error"]["code"],
+            INVALID_REQUEST
+        );
+        assert_eq!(
+            handle(r#"{"jsonrpc":"2.0","id":1,"method":"nope"}"#).unwrap()["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(handle("[]").unwrap()["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_core_errors_are_typed() {
+        let response = handle(r#"{"jsonrpc":"2.0","id":"a","method":"missing"}"#).unwrap();
+        assert_eq!(response["id"], "a");
+        assert_eq!(response["error"]["code"], NOT_FOUND);
+        assert_eq!(response["error"]["data"]["kind"], "review_not_found");
+        assert_eq!(response["error"]["message"], "Review not found: cr-x");
+    }
+
+    #[test]
+    fn test_batch_skips_notifications() {
+        let response = handle(
+            r#"[{"jsonrpc":"2.0","id":1,"method":"echo"},{"jsonrpc":"2.0","method":"echo"}]"#,
+        )
+        .unwrap();
+        assert_eq!(response.as_array().unwrap().len(), 1);
+        assert!(handle(r#"[{"jsonrpc":"2.0","method":"echo"}]"#).is_none());
+    }
+
+    #[test]
+    fn test_framed_round_trip() {
+        let mut buffer = Vec::new();
+        let first = notification("initialized", json!({}));
+        let second = response(json!(1), Ok(json!("é")));
+        write_framed(&mut buffer, &first).unwrap();
+        write_framed(&mut buffer, &second).unwrap();
+
+        let mut input = buffer.as_slice();
+        let read = |input: &mut &[u8]| {
+            serde_json::from_str::<Value>(&read_framed(input).unwrap().unwrap()).unwrap()
+        };
+        assert_eq!(read(&mut input), first);
+        assert_eq!(read(&mut input), second);
+        assert!(read_framed(&mut input).unwrap().is_none());
+
+        let mut truncated = "Content-Length: 10\r\n\r\n{}".as_bytes();
+        assert!(read_framed(&mut truncated).is_err());
+    }
+
+    #[test]
+    fn test_serve_writes_one_line_per_response() {
+        let input = "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"echo\"}\n\n{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"echo\"}\n";
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output, echo).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_serve_answers_invalid_utf8_and_continues() {
+        let mut input = b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"\xff\"}\n".to_vec();
+        input.extend_from_slice(b"{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"echo\"}\n");
+        let mut output = Vec::new();
+        serve(input.as_slice(), &mut output, echo).unwrap();
+
+        let responses: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["error"]["code"], PARSE_ERROR);
+        assert_eq!(responses[1]["id"], 2);
+    }
+}
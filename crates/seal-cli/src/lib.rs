@@ -1,5 +1,6 @@
 //! seal-cli — CLI transport and formatting for the seal code review tool.
 
 pub mod cli;
+pub mod jsonrpc;
 pub mod output;
 pub mod telemetry;
@@ -1,22 +1,30 @@
 //! seal - Agent-centric distributed code review tool for Git and jj
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Parser;
 use std::env;
 
 use seal_cli::cli::commands::{
-    run_agents_init, run_agents_show, run_block, run_comment, run_comments_add, run_comments_list,
-    run_diff, run_doctor, run_inbox, run_init, run_lgtm, run_migrate, run_review,
-    run_reviews_abandon, run_reviews_approve, run_reviews_create, run_reviews_list,
-    run_reviews_merge, run_reviews_request, run_reviews_show, run_status, run_sync,
-    run_threads_create, run_threads_list, run_threads_reopen, run_threads_resolve,
+    run_agents_init, run_agents_show, run_block, run_comment, run_comments_add, run_comments_edit,
+    run_comments_history, run_comments_list, run_comments_retract, run_diff, run_doctor,
+    run_events_tail, run_hooks_replay, run_hooks_trust, run_inbox, run_init, run_keys_generate,
+    run_lgtm, run_lsp, run_mcp, run_merge_driver, run_migrate, run_owners_explain, run_review,
+    run_review_discard, run_review_drafts, run_review_submit, run_reviews_abandon,
+    run_reviews_approve, run_reviews_create, run_reviews_create_stack, run_reviews_edit,
+    run_reviews_list, run_reviews_merge, run_reviews_reopen, run_reviews_request,
+    run_reviews_retract_vote, run_reviews_show, run_reviews_unrequest, run_reviews_update,
+    run_search, run_serve, run_status, run_suggestions_apply, run_sync, run_threads_create,
+    run_threads_list, run_threads_reopen, run_threads_resolve, run_threads_set_kind,
     run_threads_show,
 };
 use seal_cli::cli::{
-    AgentsCommands, Cli, Commands, CommentsCommands, ReviewsCommands, ThreadsCommands,
+    AgentsCommands, Cli, Commands, CommentsCommands, EventsCommands, HooksCommands, KeysCommands,
+    OwnersCommands, ReviewCommands, ReviewsCommands, SuggestionsCommands, ThreadsCommands,
 };
-use seal_core::events::get_agent_identity;
+use seal_core::core::tail::EventFilter;
+use seal_core::events::{get_agent_identity, VoteType};
 use seal_core::jj::{resolve_seal_root_from_path, resolve_workspace_root};
+use seal_core::projection::SearchFilter;
 use seal_core::scm::{resolve_backend, resolve_preference};
 
 /// Resolve identity based on CLI flags.
@@ -71,8 +79,19 @@ fn main() -> Result<()> {
     let identity = resolve_identity(&cli)?;
 
     match cli.command {
-        Commands::Init => {
-            run_init(&seal_root)?;
+        Commands::Init {
+            install_merge_driver,
+        } => {
+            run_init(&seal_root, install_merge_driver)?;
+        }
+
+        Commands::MergeDriver {
+            base: _,
+            ours,
+            theirs,
+            output,
+        } => {
+            run_merge_driver(&ours, &theirs, output.as_deref())?;
         }
 
         Commands::Doctor => {
@@ -101,30 +120,38 @@ fn main() -> Result<()> {
                 title,
                 description,
                 reviewers,
+                stack,
             } => {
                 let scm = resolve_backend(&workspace_root, scm_preference)?;
-                run_reviews_create(
-                    &seal_root,
-                    scm.as_ref(),
-                    title,
-                    description,
-                    reviewers,
-                    identity.as_deref(),
-                    format,
-                )?;
+                match (stack, title) {
+                    (Some(revset), _) => run_reviews_create_stack(
+                        &seal_root,
+                        scm.as_ref(),
+                        &revset,
+                        reviewers,
+                        identity.as_deref(),
+                        format,
+                    )?,
+                    (None, Some(title)) => run_reviews_create(
+                        &seal_root,
+                        scm.as_ref(),
+                        title,
+                        description,
+                        reviewers,
+                        identity.as_deref(),
+                        format,
+                    )?,
+                    (None, None) => bail!("--title is required unless --stack is given"),
+                }
             }
             ReviewsCommands::List {
                 status,
                 author,
                 needs_review,
                 has_unresolved,
+                label,
             } => {
-                let status_str = status.map(|s| match s {
-                    seal_cli::cli::ReviewStatus::Open => "open",
-                    seal_cli::cli::ReviewStatus::Approved => "approved",
-                    seal_cli::cli::ReviewStatus::Merged => "merged",
-                    seal_cli::cli::ReviewStatus::Abandoned => "abandoned",
-                });
+                let status_str = status.as_ref().map(seal_cli::cli::ReviewStatus::as_str);
                 // For --needs-review, use the subcommand --author as identity (if provided),
                 // falling back to resolved identity.
                 // When --needs-review is used, --author should NOT also filter by review author.
@@ -136,17 +163,21 @@ fn main() -> Result<()> {
                     // Normal case: --author filters by review author
                     (author.as_deref().map(String::from), None)
                 };
+                let scm = resolve_backend(&workspace_root, scm_preference)?;
                 run_reviews_list(
                     &seal_root,
+                    scm.as_ref(),
                     status_str,
                     author_filter.as_deref(),
                     needs_reviewer.as_deref(),
                     has_unresolved,
+                    label.as_deref(),
                     format,
                 )?;
             }
             ReviewsCommands::Show { review_id } => {
-                run_reviews_show(&seal_root, &review_id, format)?;
+                let scm = resolve_backend(&workspace_root, scm_preference)?;
+                run_reviews_show(&seal_root, scm.as_ref(), &review_id, format)?;
             }
             ReviewsCommands::Request {
                 review_id,
@@ -160,12 +191,77 @@ fn main() -> Result<()> {
                     format,
                 )?;
             }
+            ReviewsCommands::Unrequest {
+                review_id,
+                reviewers,
+            } => {
+                run_reviews_unrequest(
+                    &seal_root,
+                    &review_id,
+                    &reviewers,
+                    identity.as_deref(),
+                    format,
+                )?;
+            }
             ReviewsCommands::Approve { review_id } => {
-                run_reviews_approve(&seal_root, &review_id, identity.as_deref(), format)?;
+                let scm = resolve_backend(&workspace_root, scm_preference)?;
+                run_reviews_approve(
+                    &seal_root,
+                    scm.as_ref(),
+                    &review_id,
+                    identity.as_deref(),
+                    format,
+                )?;
+            }
+            ReviewsCommands::Update {
+                review_id,
+                commit,
+                note,
+            } => {
+                let scm = resolve_backend(&workspace_root, scm_preference)?;
+                run_reviews_update(
+                    &seal_root,
+                    scm.as_ref(),
+                    &review_id,
+                    commit,
+                    note,
+                    identity.as_deref(),
+                    format,
+                )?;
+            }
+            ReviewsCommands::Edit {
+                review_id,
+                title,
+                description,
+                add_labels,
+                remove_labels,
+            } => {
+                run_reviews_edit(
+                    &seal_root,
+                    &review_id,
+                    title,
+                    description,
+                    add_labels,
+                    remove_labels,
+                    identity.as_deref(),
+                    format,
+                )?;
             }
             ReviewsCommands::Abandon { review_id, reason } => {
                 run_reviews_abandon(&seal_root, &review_id, reason, identity.as_deref(), format)?;
             }
+            ReviewsCommands::Reopen { review_id, reason } => {
+                run_reviews_reopen(&seal_root, &review_id, reason, identity.as_deref(), format)?;
+            }
+            ReviewsCommands::RetractVote { review_id, reason } => {
+                run_reviews_retract_vote(
+                    &seal_root,
+                    &review_id,
+                    reason,
+                    identity.as_deref(),
+                    format,
+                )?;
+            }
             ReviewsCommands::MarkMerged {
                 review_id,
                 commit,
@@ -189,6 +285,7 @@ fn main() -> Result<()> {
                 review_id,
                 file,
                 lines,
+                kind,
             } => {
                 let scm = resolve_backend(&workspace_root, scm_preference)?;
                 run_threads_create(
@@ -197,6 +294,7 @@ fn main() -> Result<()> {
                     &review_id,
                     &file,
                     &lines,
+                    kind.map(Into::into),
                     identity.as_deref(),
                     format,
                 )?;
@@ -205,6 +303,7 @@ fn main() -> Result<()> {
                 review_id,
                 status,
                 file,
+                kind,
                 verbose,
                 since,
             } => {
@@ -215,11 +314,14 @@ fn main() -> Result<()> {
                 let since_dt = since
                     .map(|s| seal_cli::cli::commands::reviews::parse_since(&s))
                     .transpose()?;
+                let scm = resolve_backend(&workspace_root, scm_preference)?;
                 run_threads_list(
                     &seal_root,
+                    scm.as_ref(),
                     &review_id,
                     status_str,
                     file.as_deref(),
+                    kind.map(Into::into),
                     verbose,
                     since_dt,
                     format,
@@ -266,6 +368,20 @@ fn main() -> Result<()> {
             ThreadsCommands::Reopen { thread_id, reason } => {
                 run_threads_reopen(&seal_root, &thread_id, reason, identity.as_deref(), format)?;
             }
+            ThreadsCommands::SetKind {
+                thread_id,
+                kind,
+                clear: _,
+            } => {
+                // `kind` is required unless --clear, so `None` means clear
+                run_threads_set_kind(
+                    &seal_root,
+                    &thread_id,
+                    kind.map(Into::into),
+                    identity.as_deref(),
+                    format,
+                )?;
+            }
         },
 
         Commands::Comments(cmd) => match cmd {
@@ -273,21 +389,98 @@ fn main() -> Result<()> {
                 thread_id,
                 message,
                 message_positional,
+                suggest,
+                draft,
             } => {
                 // Support both --message and positional argument
                 let msg = message.or(message_positional).ok_or_else(|| {
                     anyhow::anyhow!("Message is required (use --message or provide as argument)")
                 })?;
-                run_comments_add(&seal_root, &thread_id, &msg, identity.as_deref(), format)?;
+                run_comments_add(
+                    &seal_root,
+                    &thread_id,
+                    &msg,
+                    suggest.as_deref(),
+                    draft,
+                    identity.as_deref(),
+                    format,
+                )?;
             }
             CommentsCommands::List { thread_id } => {
                 run_comments_list(&seal_root, &thread_id, format)?;
             }
+            CommentsCommands::Edit {
+                comment_id,
+                message,
+                message_positional,
+            } => {
+                let msg = message.or(message_positional).ok_or_else(|| {
+                    anyhow::anyhow!("Message is required (use --message or provide as argument)")
+                })?;
+                run_comments_edit(&seal_root, &comment_id, &msg, identity.as_deref(), format)?;
+            }
+            CommentsCommands::Retract { comment_id, reason } => {
+                run_comments_retract(&seal_root, &comment_id, reason, identity.as_deref(), format)?;
+            }
+            CommentsCommands::History { comment_id } => {
+                run_comments_history(&seal_root, &comment_id, format)?;
+            }
+        },
+
+        Commands::Keys(cmd) => match cmd {
+            KeysCommands::Generate { force } => {
+                run_keys_generate(&seal_root, identity.as_deref(), force, format)?;
+            }
+        },
+
+        Commands::Suggestions(cmd) => match cmd {
+            SuggestionsCommands::Apply { comment_id } => {
+                let scm = resolve_backend(&workspace_root, scm_preference)?;
+                run_suggestions_apply(&seal_root, scm.as_ref(), &comment_id, format)?;
+            }
+        },
+
+        Commands::Events(cmd) => match cmd {
+            EventsCommands::Tail {
+                follow,
+                since,
+                review,
+                author,
+                event_types,
+                for_agent,
+            } => {
+                let filter = EventFilter {
+                    review_id: review,
+                    author,
+                    event_types,
+                    since: since
+                        .as_deref()
+                        .map(seal_cli::cli::commands::reviews::parse_since)
+                        .transpose()?,
+                    relevant_to: for_agent,
+                };
+                run_events_tail(&seal_root, &filter, follow)?;
+            }
+        },
+
+        Commands::Hooks(cmd) => match cmd {
+            HooksCommands::Trust => run_hooks_trust(&seal_root, format)?,
+            HooksCommands::Replay { since, dry_run } => {
+                let since = seal_cli::cli::commands::reviews::parse_since(&since)?;
+                run_hooks_replay(&seal_root, since, dry_run, format)?;
+            }
+        },
+
+        Commands::Owners(cmd) => match cmd {
+            OwnersCommands::Explain { file } => {
+                run_owners_explain(&seal_root, &file, format)?;
+            }
         },
 
         Commands::Status {
             review_id,
             unresolved_only,
+            kind,
         } => {
             let scm = resolve_backend(&workspace_root, scm_preference)?;
             run_status(
@@ -295,13 +488,17 @@ fn main() -> Result<()> {
                 scm.as_ref(),
                 review_id.as_deref(),
                 unresolved_only,
+                kind.map(Into::into),
                 format,
             )?;
         }
 
-        Commands::Diff { review_id } => {
+        Commands::Diff {
+            review_id,
+            since_rev,
+        } => {
             let scm = resolve_backend(&workspace_root, scm_preference)?;
-            run_diff(&seal_root, scm.as_ref(), &review_id, format)?;
+            run_diff(&seal_root, scm.as_ref(), &review_id, since_rev, format)?;
         }
 
         Commands::Ui => {
@@ -316,6 +513,9 @@ fn main() -> Result<()> {
             file,
             line,
             message,
+            suggest,
+            kind,
+            draft,
         } => {
             let scm = resolve_backend(&workspace_root, scm_preference)?;
             run_comment(
@@ -325,26 +525,86 @@ fn main() -> Result<()> {
                 &file,
                 &line,
                 &message,
+                suggest.as_deref(),
+                kind.map(Into::into),
+                draft,
                 identity.as_deref(),
                 format,
             )?;
         }
 
         Commands::Lgtm { review_id, message } => {
-            run_lgtm(&seal_root, &review_id, message, identity.as_deref(), format)?;
+            let scm = resolve_backend(&workspace_root, scm_preference)?;
+            run_lgtm(
+                &seal_root,
+                scm.as_ref(),
+                &review_id,
+                message,
+                identity.as_deref(),
+                format,
+            )?;
         }
 
         Commands::Block { review_id, reason } => {
-            run_block(&seal_root, &review_id, reason, identity.as_deref(), format)?;
+            let scm = resolve_backend(&workspace_root, scm_preference)?;
+            run_block(
+                &seal_root,
+                scm.as_ref(),
+                &review_id,
+                reason,
+                identity.as_deref(),
+                format,
+            )?;
         }
 
+        Commands::Review {
+            command: Some(cmd), ..
+        } => match cmd {
+            ReviewCommands::Submit {
+                review_id,
+                lgtm,
+                block,
+                comment: _,
+                message,
+            } => {
+                let vote = if lgtm {
+                    Some(VoteType::Lgtm)
+                } else if block {
+                    Some(VoteType::Block)
+                } else {
+                    None
+                };
+                let scm = resolve_backend(&workspace_root, scm_preference)?;
+                run_review_submit(
+                    &seal_root,
+                    scm.as_ref(),
+                    &review_id,
+                    vote,
+                    message,
+                    identity.as_deref(),
+                    format,
+                )?;
+            }
+            ReviewCommands::Drafts { review_id } => {
+                run_review_drafts(&seal_root, &review_id, identity.as_deref(), format)?;
+            }
+            ReviewCommands::Discard { review_id } => {
+                run_review_discard(&seal_root, &review_id, identity.as_deref(), format)?;
+            }
+        },
+
         Commands::Review {
             review_id,
             context,
             no_context,
             since,
             include_diffs,
+            command: None,
         } => {
+            // clap requires the review ID when there is no subcommand
+            let Some(review_id) = review_id else {
+                bail!("Missing review ID");
+            };
             let context_lines = if no_context { 0 } else { context };
             let since_dt = since
                 .map(|s| seal_cli::cli::commands::reviews::parse_since(&s))
@@ -361,11 +621,18 @@ fn main() -> Result<()> {
             )?;
         }
 
-        Commands::Reply { thread_id, message } => {
+        Commands::Reply {
+            thread_id,
+            message,
+            suggest,
+            draft,
+        } => {
             run_comments_add(
                 &seal_root,
                 &thread_id,
                 &message,
+                suggest.as_deref(),
+                draft,
                 identity.as_deref(),
                 format,
             )?;
@@ -373,7 +640,43 @@ fn main() -> Result<()> {
 
         Commands::Inbox => {
             let agent = get_agent_identity(identity.as_deref())?;
-            run_inbox(&seal_root, &agent, format)?;
+            let scm = resolve_backend(&workspace_root, scm_preference)?;
+            run_inbox(&seal_root, scm.as_ref(), &agent, format)?;
+        }
+
+        Commands::Search {
+            query,
+            author,
+            status,
+            file,
+            since,
+            limit,
+        } => {
+            let filter = SearchFilter {
+                author,
+                status: status.map(|s| s.as_str().to_string()),
+                file,
+                since: since
+                    .map(|s| seal_cli::cli::commands::reviews::parse_since(&s))
+                    .transpose()?
+                    .map(|dt| dt.to_rfc3339()),
+            };
+            run_search(&seal_root, &query.join(" "), &filter, limit, format)?;
+        }
+
+        Commands::Lsp => {
+            let scm = resolve_backend(&workspace_root, scm_preference)?;
+            run_lsp(&seal_root, scm, identity)?;
+        }
+
+        Commands::Mcp => {
+            let agent = get_agent_identity(identity.as_deref())?;
+            run_mcp(&agent, cli.path.as_deref(), cli.scm)?;
+        }
+
+        Commands::Serve { stdio } => {
+            let scm = resolve_backend(&workspace_root, scm_preference)?;
+            run_serve(&seal_root, scm, identity, stdio)?;
         }
 
         Commands::Sync {
@@ -1,11 +1,13 @@
-//! Comment service — add comment, add comment with auto-thread-create.
+//! Comment service — add comment, add comment with auto-thread-create,
+//! edit and retract.
 
 use crate::events::{
-    get_agent_identity, make_comment_id, new_thread_id, CodeSelection, CommentAdded, Event,
-    EventEnvelope, ThreadCreated,
+    get_agent_identity, make_comment_id, new_thread_id, AnchorSnapshot, CodeSelection,
+    CommentAdded, CommentEdited, CommentRetracted, Event, EventEnvelope, Suggestion, ThreadCreated,
+    ThreadKind,
 };
 use crate::log::{open_or_create_review, AppendLog};
-use crate::projection::{Comment, ProjectionDb};
+use crate::projection::{Comment, CommentEdit, ProjectionDb};
 
 use super::{CoreContext, CoreError, CoreResult};
 
@@ -38,6 +40,7 @@ impl<'a> CommentService<'a> {
         &self,
         thread_id: &str,
         body: &str,
+        suggestion: Option<Suggestion>,
         author: Option<&str>,
     ) -> CoreResult<AddCommentResult> {
         let thread = self
@@ -76,6 +79,7 @@ impl<'a> CommentService<'a> {
                 comment_id: comment_id.clone(),
                 thread_id: thread_id.to_string(),
                 body: body.to_string(),
+                suggestion,
             }),
         );
 
@@ -96,16 +100,19 @@ impl<'a> CommentService<'a> {
     /// - If an open thread exists at the file+line, adds a comment to it
     /// - If no thread exists, creates one and adds the comment
     ///
-    /// The `commit_hash` is used when creating a new thread. If `None`, the
-    /// caller must provide an SCM repo to resolve the commit from the review's
-    /// anchor.
+    /// The `commit_hash`, `anchor` snapshot and thread `kind` are only used
+    /// when a new thread is created.
+    #[allow(clippy::too_many_arguments)]
     pub fn add_to_review(
         &self,
         review_id: &str,
         file_path: &str,
         selection: CodeSelection,
         body: &str,
+        suggestion: Option<Suggestion>,
         commit_hash: String,
+        anchor: Option<AnchorSnapshot>,
+        kind: Option<ThreadKind>,
         author: Option<&str>,
     ) -> CoreResult<AddCommentResult> {
         // Verify review exists and is open or approved
@@ -129,6 +136,7 @@ impl<'a> CommentService<'a> {
         let start_line = selection.start_line() as i64;
 
         // Check for existing thread at this location
+        let mut events = Vec::new();
         let (thread_id, comment_number, thread_created) = match self
             .db
             .find_thread_at_location(review_id, file_path, start_line)
@@ -148,7 +156,7 @@ impl<'a> CommentService<'a> {
                 // Create new thread
                 let new_thread_id = new_thread_id();
 
-                let thread_event = EventEnvelope::new(
+                events.push(EventEnvelope::new(
                     &author_str,
                     Event::ThreadCreated(ThreadCreated {
                         thread_id: new_thread_id.clone(),
@@ -156,12 +164,10 @@ impl<'a> CommentService<'a> {
                         file_path: file_path.to_string(),
                         selection: selection.clone(),
                         commit_hash,
+                        anchor,
+                        kind,
                     }),
-                );
-
-                let log = open_or_create_review(self.ctx.seal_root(), review_id)
-                    .map_err(CoreError::Internal)?;
-                log.append(&thread_event).map_err(CoreError::Internal)?;
+                ));
 
                 (new_thread_id, 1, true)
             }
@@ -170,18 +176,20 @@ impl<'a> CommentService<'a> {
         // Add the comment
         let comment_id = make_comment_id(&thread_id, comment_number);
 
-        let comment_event = EventEnvelope::new(
+        events.push(EventEnvelope::new(
             &author_str,
             Event::CommentAdded(CommentAdded {
                 comment_id: comment_id.clone(),
                 thread_id: thread_id.clone(),
                 body: body.to_string(),
+                suggestion,
             }),
-        );
+        ));
 
+        // Thread and first comment land together, never a thread without a comment
         let log = open_or_create_review(self.ctx.seal_root(), review_id)
             .map_err(CoreError::Internal)?;
-        log.append(&comment_event).map_err(CoreError::Internal)?;
+        log.append_batch(&events).map_err(CoreError::Internal)?;
 
         Ok(AddCommentResult {
             comment_id,
@@ -190,10 +198,147 @@ impl<'a> CommentService<'a> {
         })
     }
 
+    /// Replace the body of an existing comment.
+    ///
+    /// Only the comment's original author may edit it, and retracted comments
+    /// cannot be edited. The previous body stays in the edit history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the comment doesn't exist, is retracted, or belongs
+    /// to another author, or if the event can't be appended.
+    pub fn edit(&self, comment_id: &str, body: &str, author: Option<&str>) -> CoreResult<()> {
+        let (thread_id, review_id, author_str) = self.check_comment_owner(comment_id, author)?;
+
+        let event = EventEnvelope::new(
+            &author_str,
+            Event::CommentEdited(CommentEdited {
+                comment_id: comment_id.to_string(),
+                thread_id,
+                body: body.to_string(),
+            }),
+        );
+
+        let log =
+            open_or_create_review(self.ctx.seal_root(), &review_id).map_err(CoreError::Internal)?;
+        log.append(&event).map_err(CoreError::Internal)?;
+
+        Ok(())
+    }
+
+    /// Retract a comment.
+    ///
+    /// Only the comment's original author may retract it. The comment stays in
+    /// the thread, but its body and edit history are no longer shown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the comment doesn't exist, is already retracted, or
+    /// belongs to another author, or if the event can't be appended.
+    pub fn retract(
+        &self,
+        comment_id: &str,
+        reason: Option<String>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        let (thread_id, review_id, author_str) = self.check_comment_owner(comment_id, author)?;
+
+        let event = EventEnvelope::new(
+            &author_str,
+            Event::CommentRetracted(CommentRetracted {
+                comment_id: comment_id.to_string(),
+                thread_id,
+                reason,
+            }),
+        );
+
+        let log =
+            open_or_create_review(self.ctx.seal_root(), &review_id).map_err(CoreError::Internal)?;
+        log.append(&event).map_err(CoreError::Internal)?;
+
+        Ok(())
+    }
+
     /// List comments for a thread.
     pub fn list(&self, thread_id: &str) -> CoreResult<Vec<Comment>> {
         self.db
             .list_comments(thread_id)
             .map_err(CoreError::Internal)
     }
+
+    /// List the edit history of a comment (oldest first).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the comment doesn't exist.
+    pub fn history(&self, comment_id: &str) -> CoreResult<Vec<CommentEdit>> {
+        if self
+            .db
+            .get_comment(comment_id)
+            .map_err(CoreError::Internal)?
+            .is_none()
+        {
+            return Err(CoreError::CommentNotFound {
+                comment_id: comment_id.to_string(),
+            });
+        }
+        self.db
+            .list_comment_edits(comment_id)
+            .map_err(CoreError::Internal)
+    }
+
+    /// Verify that `author` may change `comment_id`.
+    ///
+    /// Returns the comment's thread ID, review ID and the resolved author.
+    fn check_comment_owner(
+        &self,
+        comment_id: &str,
+        author: Option<&str>,
+    ) -> CoreResult<(String, String, String)> {
+        let (thread_id, comment) = self
+            .db
+            .get_comment(comment_id)
+            .map_err(CoreError::Internal)?
+            .ok_or_else(|| CoreError::CommentNotFound {
+                comment_id: comment_id.to_string(),
+            })?;
+
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+        if comment.author != author_str {
+            return Err(CoreError::NotCommentAuthor {
+                comment_id: comment_id.to_string(),
+                author: comment.author,
+            });
+        }
+        if comment.retracted_at.is_some() {
+            return Err(CoreError::CommentRetracted {
+                comment_id: comment_id.to_string(),
+            });
+        }
+
+        let thread = self
+            .db
+            .get_thread(&thread_id)
+            .map_err(CoreError::Internal)?
+            .ok_or_else(|| CoreError::ThreadNotFound {
+                thread_id: thread_id.clone(),
+            })?;
+
+        // Same rule as adding comments: the review must still be active
+        if let Some(review) = self
+            .db
+            .get_review(&thread.review_id)
+            .map_err(CoreError::Internal)?
+        {
+            if review.status != "open" && review.status != "approved" {
+                return Err(CoreError::InvalidReviewStatus {
+                    review_id: thread.review_id,
+                    actual: review.status,
+                    expected: "open or approved".to_string(),
+                });
+            }
+        }
+
+        Ok((thread_id, thread.review_id, author_str))
+    }
 }
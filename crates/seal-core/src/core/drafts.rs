@@ -0,0 +1,359 @@
+//! Draft service — save, list and discard draft comments, and submit them
+//! together with a vote.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::drafts::{Draft, DraftStore, DraftTarget};
+use crate::events::{
+    get_agent_identity, make_comment_id, new_thread_id, AnchorSnapshot, CodeSelection,
+    CommentAdded, Event, EventEnvelope, ReviewerVoted, Suggestion, ThreadCreated, ThreadKind,
+    VoteType,
+};
+use crate::log::{open_or_create_review, AppendLog};
+use crate::projection::{ProjectionDb, ReviewDetail};
+use crate::scm::ScmRepo;
+
+use super::{CoreContext, CoreError, CoreResult};
+
+/// What a submit published.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubmitResult {
+    pub review_id: String,
+    /// Comment IDs created from drafts, in draft order.
+    pub comment_ids: Vec<String>,
+    /// Threads started by drafts.
+    pub threads_created: Vec<String>,
+    /// The vote cast, if any.
+    pub vote: Option<VoteType>,
+}
+
+/// A new thread started earlier in the same submit.
+struct BatchThread {
+    thread_id: String,
+    file_path: String,
+    start: i64,
+    end: i64,
+}
+
+/// Service for draft operations.
+pub struct DraftService<'a> {
+    ctx: &'a CoreContext,
+    db: &'a ProjectionDb,
+}
+
+impl<'a> DraftService<'a> {
+    pub(crate) const fn new(ctx: &'a CoreContext, db: &'a ProjectionDb) -> Self {
+        Self { ctx, db }
+    }
+
+    /// Save a draft reply to an existing thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the thread doesn't exist, its review isn't open,
+    /// or the draft can't be saved.
+    pub fn save_reply(
+        &self,
+        thread_id: &str,
+        body: &str,
+        suggestion: Option<Suggestion>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        let thread = self
+            .db
+            .get_thread(thread_id)
+            .map_err(CoreError::Internal)?
+            .ok_or_else(|| CoreError::ThreadNotFound {
+                thread_id: thread_id.to_string(),
+            })?;
+        self.require_open(&thread.review_id)?;
+
+        self.push(
+            &thread.review_id,
+            DraftTarget::Thread {
+                thread_id: thread_id.to_string(),
+            },
+            body,
+            suggestion,
+            author,
+        )
+    }
+
+    /// Save a draft comment on lines of a file.
+    ///
+    /// The `commit_hash`, `anchor` snapshot and thread `kind` are only used if
+    /// no open thread exists at the location when the draft is submitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review isn't open or the draft can't be saved.
+    #[allow(clippy::too_many_arguments)]
+    pub fn save_comment(
+        &self,
+        review_id: &str,
+        file_path: &str,
+        selection: CodeSelection,
+        body: &str,
+        suggestion: Option<Suggestion>,
+        commit_hash: String,
+        anchor: Option<AnchorSnapshot>,
+        kind: Option<ThreadKind>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        self.require_open(review_id)?;
+
+        self.push(
+            review_id,
+            DraftTarget::Location {
+                file_path: file_path.to_string(),
+                selection,
+                commit_hash,
+                anchor,
+                kind,
+            },
+            body,
+            suggestion,
+            author,
+        )
+    }
+
+    /// List the author's drafts on a review, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review ID or agent name is invalid, or the
+    /// drafts file can't be read.
+    pub fn list(&self, review_id: &str, author: Option<&str>) -> CoreResult<Vec<Draft>> {
+        self.store(review_id, author)?
+            .load()
+            .map_err(CoreError::Internal)
+    }
+
+    /// Throw away the author's drafts on a review. Returns how many there were.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review ID or agent name is invalid, or the
+    /// drafts file can't be read or removed.
+    pub fn discard(&self, review_id: &str, author: Option<&str>) -> CoreResult<usize> {
+        let store = self.store(review_id, author)?;
+        let count = store.load().map_err(CoreError::Internal)?.len();
+        store.clear().map_err(CoreError::Internal)?;
+        Ok(count)
+    }
+
+    /// Publish the author's drafts, followed by an optional vote.
+    ///
+    /// All events are written in one batch, so reviewers never see part of
+    /// the submission. A draft that can no longer be posted (e.g. its thread
+    /// is gone) leaves the log and the drafts untouched.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`CoreError::NothingToSubmit`] if there are no drafts and no
+    /// vote, or if the review isn't open or a draft can't be posted.
+    pub fn submit(
+        &self,
+        scm: &dyn ScmRepo,
+        review_id: &str,
+        vote: Option<VoteType>,
+        reason: Option<String>,
+        author: Option<&str>,
+    ) -> CoreResult<SubmitResult> {
+        let review = self.require_open(review_id)?;
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+        let store = DraftStore::new(self.ctx.seal_root(), review_id, &author_str)
+            .map_err(CoreError::Internal)?;
+        let drafts = store.load().map_err(CoreError::Internal)?;
+
+        if drafts.is_empty() && vote.is_none() {
+            return Err(CoreError::NothingToSubmit {
+                review_id: review_id.to_string(),
+            });
+        }
+
+        let mut events = Vec::new();
+        let mut comment_ids = Vec::new();
+        let mut new_threads: Vec<BatchThread> = Vec::new();
+        let mut next_numbers: HashMap<String, u32> = HashMap::new();
+
+        for draft in drafts {
+            let thread_id = self.draft_thread(
+                review_id,
+                &author_str,
+                draft.target,
+                &mut new_threads,
+                &mut next_numbers,
+                &mut events,
+            )?;
+
+            let number = match next_numbers.get(&thread_id) {
+                Some(n) => *n,
+                None => self
+                    .db
+                    .get_next_comment_number(&thread_id)
+                    .map_err(CoreError::Internal)?
+                    .ok_or_else(|| CoreError::ThreadNotFound {
+                        thread_id: thread_id.clone(),
+                    })?,
+            };
+            next_numbers.insert(thread_id.clone(), number + 1);
+
+            let comment_id = make_comment_id(&thread_id, number);
+            events.push(EventEnvelope::new(
+                &author_str,
+                Event::CommentAdded(CommentAdded {
+                    comment_id: comment_id.clone(),
+                    thread_id,
+                    body: draft.body,
+                    suggestion: draft.suggestion,
+                }),
+            ));
+            comment_ids.push(comment_id);
+        }
+
+        if let Some(vote) = vote {
+            // Same as a plain vote: record the commit the reviewer looked at
+            let commit = scm.commit_for_anchor(&review.scm_anchor).ok();
+            events.push(EventEnvelope::new(
+                &author_str,
+                Event::ReviewerVoted(ReviewerVoted {
+                    review_id: review_id.to_string(),
+                    vote,
+                    reason,
+                    commit,
+                }),
+            ));
+        }
+
+        let log =
+            open_or_create_review(self.ctx.seal_root(), review_id).map_err(CoreError::Internal)?;
+        log.append_batch(&events).map_err(CoreError::Internal)?;
+        store.clear().map_err(CoreError::Internal)?;
+
+        Ok(SubmitResult {
+            review_id: review_id.to_string(),
+            comment_ids,
+            threads_created: new_threads.into_iter().map(|t| t.thread_id).collect(),
+            vote,
+        })
+    }
+
+    /// The thread a draft comment goes to, queueing a `ThreadCreated` event
+    /// if it starts a new one.
+    fn draft_thread(
+        &self,
+        review_id: &str,
+        author: &str,
+        target: DraftTarget,
+        new_threads: &mut Vec<BatchThread>,
+        next_numbers: &mut HashMap<String, u32>,
+        events: &mut Vec<EventEnvelope>,
+    ) -> CoreResult<String> {
+        match target {
+            DraftTarget::Thread { thread_id } => {
+                let thread = self
+                    .db
+                    .get_thread(&thread_id)
+                    .map_err(CoreError::Internal)?
+                    .ok_or_else(|| CoreError::ThreadNotFound {
+                        thread_id: thread_id.clone(),
+                    })?;
+                if thread.review_id != review_id {
+                    return Err(CoreError::ThreadNotFound { thread_id });
+                }
+                Ok(thread_id)
+            }
+            DraftTarget::Location {
+                file_path,
+                selection,
+                commit_hash,
+                anchor,
+                kind,
+            } => {
+                // Drafts on the same lines share the thread the first one starts
+                let line = i64::from(selection.start_line());
+                if let Some(existing) = new_threads
+                    .iter()
+                    .find(|t| t.file_path == file_path && t.start <= line && t.end >= line)
+                {
+                    return Ok(existing.thread_id.clone());
+                }
+                if let Some(existing) = self
+                    .db
+                    .find_thread_at_location(review_id, &file_path, line)
+                    .map_err(CoreError::Internal)?
+                {
+                    return Ok(existing);
+                }
+
+                let thread_id = new_thread_id();
+                new_threads.push(BatchThread {
+                    thread_id: thread_id.clone(),
+                    file_path: file_path.clone(),
+                    start: line,
+                    end: i64::from(selection.end_line()),
+                });
+                next_numbers.insert(thread_id.clone(), 1);
+                events.push(EventEnvelope::new(
+                    author,
+                    Event::ThreadCreated(ThreadCreated {
+                        thread_id: thread_id.clone(),
+                        review_id: review_id.to_string(),
+                        file_path,
+                        selection,
+                        commit_hash,
+                        anchor,
+                        kind,
+                    }),
+                ));
+                Ok(thread_id)
+            }
+        }
+    }
+
+    fn push(
+        &self,
+        review_id: &str,
+        target: DraftTarget,
+        body: &str,
+        suggestion: Option<Suggestion>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        self.store(review_id, author)?
+            .push(Draft {
+                created_at: Utc::now(),
+                target,
+                body: body.to_string(),
+                suggestion,
+            })
+            .map_err(CoreError::Internal)
+    }
+
+    fn store(&self, review_id: &str, author: Option<&str>) -> CoreResult<DraftStore> {
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+        DraftStore::new(self.ctx.seal_root(), review_id, &author_str).map_err(CoreError::Internal)
+    }
+
+    /// Drafts can only be written or submitted on open or approved reviews.
+    fn require_open(&self, review_id: &str) -> CoreResult<ReviewDetail> {
+        let review = self
+            .db
+            .get_review(review_id)
+            .map_err(CoreError::Internal)?
+            .ok_or_else(|| CoreError::ReviewNotFound {
+                review_id: review_id.to_string(),
+            })?;
+        if review.status != "open" && review.status != "approved" {
+            return Err(CoreError::InvalidReviewStatus {
+                review_id: review_id.to_string(),
+                actual: review.status,
+                expected: "open or approved".to_string(),
+            });
+        }
+        Ok(review)
+    }
+}
@@ -24,6 +24,56 @@ pub enum CoreError {
     #[error("Thread not found: {thread_id}")]
     ThreadNotFound { thread_id: String },
 
+    /// A comment was not found.
+    #[error("Comment not found: {comment_id}")]
+    CommentNotFound { comment_id: String },
+
+    /// Only the original author may edit or retract a comment.
+    #[error(
+        "Comment {comment_id} was written by {author}; only the original author can change it"
+    )]
+    NotCommentAuthor { comment_id: String, author: String },
+
+    /// Only the review's author may change its title or description.
+    #[error("Review {review_id} was created by {author}; only its author can change the title or description")]
+    NotReviewAuthor { review_id: String, author: String },
+
+    /// Only the thread's author may downgrade it from blocking.
+    #[error("Thread {thread_id} was started by {author}; only its author can downgrade it from blocking")]
+    NotThreadAuthor { thread_id: String, author: String },
+
+    /// Labels are single words without commas.
+    #[error("Invalid label '{label}': labels cannot be empty or contain whitespace or commas")]
+    InvalidLabel { label: String },
+
+    /// The reviewer has no vote on the review to retract.
+    #[error("{reviewer} has no vote on review {review_id} to retract")]
+    NoVoteToRetract { review_id: String, reviewer: String },
+
+    /// The reviewer isn't currently requested on the review.
+    #[error("{reviewer} is not a requested reviewer of review {review_id}")]
+    ReviewerNotRequested { review_id: String, reviewer: String },
+
+    /// `submit` was given no vote and there are no drafts to publish.
+    #[error("Nothing to submit on review {review_id}: no drafts and no vote")]
+    NothingToSubmit { review_id: String },
+
+    /// The comment has already been retracted.
+    #[error("Comment {comment_id} has been retracted")]
+    CommentRetracted { comment_id: String },
+
+    /// The comment doesn't carry a suggested change.
+    #[error("Comment {comment_id} has no suggestion")]
+    NoSuggestion { comment_id: String },
+
+    /// The suggestion can't be applied because the anchored code moved or changed.
+    #[error("Cannot apply suggestion {comment_id} to {file_path}: {reason}")]
+    SuggestionConflict {
+        comment_id: String,
+        file_path: String,
+        reason: String,
+    },
+
     /// Operation not allowed because the review is not in the expected status.
     #[error("Review {review_id} has status '{actual}', expected '{expected}'")]
     InvalidReviewStatus {
@@ -32,6 +82,41 @@ pub enum CoreError {
         expected: String,
     },
 
+    /// The review already points at this commit, so there is no new revision.
+    #[error("Review {review_id} is already at {commit} (revision {revision}); nothing to update")]
+    ReviewUnchanged {
+        review_id: String,
+        commit: String,
+        revision: i64,
+    },
+
+    /// The requested revision does not exist.
+    #[error("Review {review_id} has no revision {revision} (latest is {latest})")]
+    RevisionNotFound {
+        review_id: String,
+        revision: i64,
+        latest: i64,
+    },
+
+    /// The review doesn't meet the repository's review policy (`.seal/policy.toml`).
+    #[error("Review {review_id} does not satisfy the review policy:\n  - {}", .unmet.join("\n  - "))]
+    PolicyNotSatisfied {
+        review_id: String,
+        unmet: Vec<String>,
+    },
+
+    /// The review is stacked on a review that hasn't been merged yet.
+    #[error("Review {review_id} depends on {depends_on}, which is still {status}; merge it first")]
+    DependencyNotMerged {
+        review_id: String,
+        depends_on: String,
+        status: String,
+    },
+
+    /// The revset given for a stacked review isn't a usable stack.
+    #[error("Cannot create a review stack from '{revset}': {reason}")]
+    InvalidStack { revset: String, reason: String },
+
     /// The file does not exist at the given commit.
     #[error("File does not exist in review {review_id} at {commit}: {file_path}")]
     FileNotFound {
@@ -44,3 +129,36 @@ pub enum CoreError {
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
 }
+
+impl CoreError {
+    /// Stable, machine-readable name of the error variant, for transports
+    /// (such as `seal serve`) that report errors as data rather than text.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::NotInitialized { .. } => "not_initialized",
+            Self::V1NeedsMigration => "v1_needs_migration",
+            Self::ReviewNotFound { .. } => "review_not_found",
+            Self::ThreadNotFound { .. } => "thread_not_found",
+            Self::CommentNotFound { .. } => "comment_not_found",
+            Self::NotCommentAuthor { .. } => "not_comment_author",
+            Self::NotReviewAuthor { .. } => "not_review_author",
+            Self::NotThreadAuthor { .. } => "not_thread_author",
+            Self::InvalidLabel { .. } => "invalid_label",
+            Self::NoVoteToRetract { .. } => "no_vote_to_retract",
+            Self::ReviewerNotRequested { .. } => "reviewer_not_requested",
+            Self::NothingToSubmit { .. } => "nothing_to_submit",
+            Self::CommentRetracted { .. } => "comment_retracted",
+            Self::NoSuggestion { .. } => "no_suggestion",
+            Self::SuggestionConflict { .. } => "suggestion_conflict",
+            Self::InvalidReviewStatus { .. } => "invalid_review_status",
+            Self::ReviewUnchanged { .. } => "review_unchanged",
+            Self::RevisionNotFound { .. } => "revision_not_found",
+            Self::PolicyNotSatisfied { .. } => "policy_not_satisfied",
+            Self::DependencyNotMerged { .. } => "dependency_not_merged",
+            Self::InvalidStack { .. } => "invalid_stack",
+            Self::FileNotFound { .. } => "file_not_found",
+            Self::Internal(_) => "internal",
+        }
+    }
+}
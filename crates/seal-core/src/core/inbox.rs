@@ -1,8 +1,10 @@
 //! Inbox service — get inbox summary for an agent.
 
 use crate::projection::{
-    InboxSummary, OpenThreadOnMyReview, ProjectionDb, ReviewAwaitingVote, ThreadWithNewResponses,
+    InboxSummary, OpenThreadOnMyReview, ProjectionDb, ReviewAwaitingVote, StaleVote,
+    ThreadWithNewResponses,
 };
+use crate::scm::ScmRepo;
 
 use super::{CoreError, CoreResult};
 
@@ -21,6 +23,31 @@ impl<'a> InboxService<'a> {
         self.db.get_inbox(agent).map_err(CoreError::Internal)
     }
 
+    /// Get the agent's votes on reviews whose commit changed since they voted.
+    ///
+    /// Reviews whose anchor can no longer be resolved are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent's votes can't be read from the projection.
+    pub fn stale_votes(&self, scm: &dyn ScmRepo, agent: &str) -> CoreResult<Vec<StaleVote>> {
+        let candidates = self
+            .db
+            .get_stale_vote_candidates(agent)
+            .map_err(CoreError::Internal)?;
+
+        Ok(candidates
+            .into_iter()
+            .filter_map(|mut vote| {
+                let current = scm.commit_for_anchor(&vote.scm_anchor).ok()?;
+                (current != vote.voted_commit).then(|| {
+                    vote.current_commit = current;
+                    vote
+                })
+            })
+            .collect())
+    }
+
     /// Get reviews awaiting the agent's vote.
     pub fn reviews_awaiting_vote(&self, agent: &str) -> CoreResult<Vec<ReviewAwaitingVote>> {
         self.db
@@ -1,7 +1,7 @@
 //! Service layer for seal-core.
 //!
-//! Provides typed, high-level APIs for review, thread, comment, inbox, and sync
-//! operations. The service layer encapsulates projection database management and
+//! Provides typed, high-level APIs for review, thread, comment, draft, inbox, search, sync, and
+//! tail operations. The service layer encapsulates projection database management and
 //! event log appends behind a clean interface.
 //!
 //! # Usage
@@ -20,10 +20,15 @@
 //! ```
 
 pub mod comments;
+pub mod drafts;
 pub mod errors;
 pub mod inbox;
+pub mod policy;
 pub mod reviews;
+pub mod search;
+pub mod suggestions;
 pub mod sync;
+pub mod tail;
 pub mod threads;
 
 pub use errors::{CoreError, CoreResult};
@@ -131,18 +136,42 @@ impl SealServices {
         comments::CommentService::new(&self.ctx, &self.db)
     }
 
+    /// Access draft operations.
+    #[must_use]
+    pub const fn drafts(&self) -> drafts::DraftService<'_> {
+        drafts::DraftService::new(&self.ctx, &self.db)
+    }
+
     /// Access inbox operations.
     #[must_use]
     pub fn inbox(&self) -> inbox::InboxService<'_> {
         inbox::InboxService::new(&self.db)
     }
 
+    /// Access search operations.
+    #[must_use]
+    pub const fn search(&self) -> search::SearchService<'_> {
+        search::SearchService::new(&self.db)
+    }
+
+    /// Access suggestion operations.
+    #[must_use]
+    pub const fn suggestions(&self) -> suggestions::SuggestionService<'_> {
+        suggestions::SuggestionService::new(&self.db)
+    }
+
     /// Access sync operations.
     #[must_use]
     pub fn sync(&self) -> sync::SyncService<'_> {
         sync::SyncService::new(&self.ctx, &self.db)
     }
 
+    /// Access tail operations.
+    #[must_use]
+    pub const fn tail(&self) -> tail::TailService<'_> {
+        tail::TailService::new(&self.ctx, &self.db)
+    }
+
     /// Get a reference to the underlying projection database.
     ///
     /// Useful for advanced queries not covered by the service layer.
@@ -0,0 +1,604 @@
+//! Per-repository review policy, read from `.seal/policy.toml`.
+//!
+//! The file is optional; a missing file means the default (permissive) policy.
+//! `.seal/policy.json` with the same fields is read if there is no TOML file.
+//! Rules are checked by [`Policy::evaluate`] before a review is approved or
+//! marked merged.
+//!
+//! ```toml
+//! min_lgtms = 2
+//! no_outstanding_blocks = true
+//! require_resolved_threads = true
+//! forbid_self_approval = true
+//!
+//! [[required_reviewers]]
+//! paths = ["crates/seal-core/src/log/"]
+//! reviewers = ["bob", "carol"]
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::projection::{ReviewDetail, ReviewerVote};
+
+/// Policy file name inside `.seal/`.
+pub const POLICY_FILE: &str = "policy.toml";
+
+/// JSON policy file, read when there is no [`POLICY_FILE`].
+pub const JSON_POLICY_FILE: &str = "policy.json";
+
+/// Review rules configured for a repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)] // each flag is an independent opt-in rule
+pub struct Policy {
+    /// LGTMs cast against an older commit than the review's current one
+    /// don't count towards approval.
+    pub ignore_stale_lgtms: bool,
+    /// Minimum number of LGTMs before a review can be approved or merged.
+    pub min_lgtms: u32,
+    /// Refuse while any reviewer has a blocking vote or a blocking thread is open.
+    pub no_outstanding_blocks: bool,
+    /// Refuse while any thread is still open.
+    pub require_resolved_threads: bool,
+    /// The review author can't approve their own review, and their own LGTM
+    /// doesn't count.
+    pub forbid_self_approval: bool,
+    /// Reviewers who must sign off when a review touches matching paths.
+    pub required_reviewers: Vec<RequiredReviewers>,
+}
+
+/// A CODEOWNERS-style rule: changes to `paths` need an LGTM from one of `reviewers`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequiredReviewers {
+    /// Gitignore-style path patterns, relative to the repository root.
+    pub paths: Vec<String>,
+    /// Agents who can sign off; an LGTM from any one of them is enough.
+    pub reviewers: Vec<String>,
+}
+
+/// Outcome of checking one policy rule against a review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleResult {
+    pub rule: String,
+    pub satisfied: bool,
+    pub detail: String,
+}
+
+/// Outcome of checking every configured rule against a review.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PolicyEvaluation {
+    pub satisfied: bool,
+    pub rules: Vec<RuleResult>,
+}
+
+impl PolicyEvaluation {
+    fn new(rules: Vec<RuleResult>) -> Self {
+        Self {
+            satisfied: rules.iter().all(|r| r.satisfied),
+            rules,
+        }
+    }
+
+    /// Unmet rules, formatted as `rule: detail`.
+    #[must_use]
+    pub fn unmet(&self) -> Vec<String> {
+        self.rules
+            .iter()
+            .filter(|r| !r.satisfied)
+            .map(|r| format!("{}: {}", r.rule, r.detail))
+            .collect()
+    }
+}
+
+impl Policy {
+    /// Path of the policy file for a repository.
+    #[must_use]
+    pub fn path(seal_root: &Path) -> PathBuf {
+        seal_root.join(".seal").join(POLICY_FILE)
+    }
+
+    /// Load the policy for a repository from `.seal/policy.toml`, or
+    /// `.seal/policy.json` if there is no TOML file, falling back to the
+    /// default if neither exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(seal_root: &Path) -> Result<Self> {
+        let path = Self::path(seal_root);
+        if path.exists() {
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            return toml::from_str(&contents)
+                .with_context(|| format!("Invalid review policy in {}", path.display()));
+        }
+
+        let json_path = seal_root.join(".seal").join(JSON_POLICY_FILE);
+        if !json_path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&json_path)
+            .with_context(|| format!("Failed to read {}", json_path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid review policy in {}", json_path.display()))
+    }
+
+    /// Whether any rule is configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether a vote counts towards approval under this policy.
+    #[must_use]
+    pub fn counts_vote(&self, vote: &ReviewerVote) -> bool {
+        !(self.ignore_stale_lgtms && vote.stale && vote.vote == "lgtm")
+    }
+
+    /// Check the configured rules against a review.
+    ///
+    /// `review.votes` should already have staleness marked (see
+    /// `ReviewService::get_checked`). `changed_files` are the files the review
+    /// touches, used for `required_reviewers`. `approver` is the agent about
+    /// to approve, if any; without one, an existing approval by the author is
+    /// checked instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a `required_reviewers` path pattern is invalid.
+    pub fn evaluate(
+        &self,
+        review: &ReviewDetail,
+        changed_files: &[String],
+        approver: Option<&str>,
+    ) -> Result<PolicyEvaluation> {
+        let mut rules = Vec::new();
+
+        let lgtms: Vec<&ReviewerVote> = review.votes.iter().filter(|v| v.vote == "lgtm").collect();
+        let counted: Vec<&str> = lgtms
+            .iter()
+            .filter(|v| self.counts_vote(v))
+            .filter(|v| !(self.forbid_self_approval && v.reviewer == review.author))
+            .map(|v| v.reviewer.as_str())
+            .collect();
+
+        if self.min_lgtms > 0 {
+            let count = counted.len();
+            rules.push(RuleResult {
+                rule: "min_lgtms".to_string(),
+                satisfied: count >= self.min_lgtms as usize,
+                detail: format!("{count} of {} required LGTMs", self.min_lgtms),
+            });
+        }
+
+        if self.ignore_stale_lgtms && !lgtms.is_empty() {
+            rules.push(stale_lgtms_rule(&lgtms));
+        }
+
+        if self.no_outstanding_blocks {
+            rules.push(outstanding_blocks_rule(review));
+        }
+
+        if self.require_resolved_threads {
+            let open = review.open_thread_count;
+            rules.push(RuleResult {
+                rule: "require_resolved_threads".to_string(),
+                satisfied: open == 0,
+                detail: match open {
+                    0 => "all threads resolved".to_string(),
+                    1 => "1 open thread".to_string(),
+                    n => format!("{n} open threads"),
+                },
+            });
+        }
+
+        if self.forbid_self_approval {
+            rules.push(self_approval_rule(review, approver));
+        }
+
+        for required in &self.required_reviewers {
+            rules.extend(required_reviewers_rule(required, changed_files, &counted)?);
+        }
+
+        Ok(PolicyEvaluation::new(rules))
+    }
+}
+
+/// At least one LGTM must be on the review's current commit.
+fn stale_lgtms_rule(lgtms: &[&ReviewerVote]) -> RuleResult {
+    let fresh = lgtms.iter().any(|v| !v.stale);
+    let stale: Vec<&str> = lgtms
+        .iter()
+        .filter(|v| v.stale)
+        .map(|v| v.reviewer.as_str())
+        .collect();
+    RuleResult {
+        rule: "ignore_stale_lgtms".to_string(),
+        satisfied: fresh,
+        detail: if fresh {
+            "at least one LGTM is on the current commit".to_string()
+        } else {
+            format!(
+                "all LGTMs are stale, the review changed after: {}",
+                stale.join(", ")
+            )
+        },
+    }
+}
+
+/// No reviewer may have a blocking vote and no blocking thread may be open.
+fn outstanding_blocks_rule(review: &ReviewDetail) -> RuleResult {
+    // An unresolved blocking thread holds up the review like a block vote
+    let blockers: Vec<&str> = review
+        .votes
+        .iter()
+        .filter(|v| v.vote == "block")
+        .map(|v| v.reviewer.as_str())
+        .chain(review.blocking_threads.iter().map(String::as_str))
+        .collect();
+    RuleResult {
+        rule: "no_outstanding_blocks".to_string(),
+        satisfied: blockers.is_empty(),
+        detail: if blockers.is_empty() {
+            "no blocking votes or threads".to_string()
+        } else {
+            format!("blocked by {}", blockers.join(", "))
+        },
+    }
+}
+
+/// The review author can't be the one approving it.
+fn self_approval_rule(review: &ReviewDetail, approver: Option<&str>) -> RuleResult {
+    // Without an approver, look at who approved the review (if anyone)
+    let approved_by = approver.or_else(|| {
+        (review.status == "approved")
+            .then_some(review.status_changed_by.as_deref())
+            .flatten()
+    });
+    let self_approved = approved_by == Some(review.author.as_str());
+    RuleResult {
+        rule: "forbid_self_approval".to_string(),
+        satisfied: !self_approved,
+        detail: if self_approved {
+            format!("{} cannot approve their own review", review.author)
+        } else {
+            format!(
+                "approval must come from someone other than {}",
+                review.author
+            )
+        },
+    }
+}
+
+/// One of `required.reviewers` must have signed off if the review touches
+/// `required.paths`; `None` if it doesn't.
+fn required_reviewers_rule(
+    required: &RequiredReviewers,
+    changed_files: &[String],
+    counted: &[&str],
+) -> Result<Option<RuleResult>> {
+    let matcher = build_matcher(&required.paths)?;
+    let touched: Vec<&str> = changed_files
+        .iter()
+        .map(String::as_str)
+        .filter(|f| matcher.matched_path_or_any_parents(f, false).is_ignore())
+        .collect();
+    if touched.is_empty() {
+        return Ok(None);
+    }
+
+    let signed_off: Vec<&str> = required
+        .reviewers
+        .iter()
+        .map(String::as_str)
+        .filter(|r| counted.contains(r))
+        .collect();
+    Ok(Some(RuleResult {
+        rule: format!("required_reviewers[{}]", required.paths.join(", ")),
+        satisfied: !signed_off.is_empty(),
+        detail: if signed_off.is_empty() {
+            format!(
+                "needs an LGTM from one of {} (touches {})",
+                required.reviewers.join(", "),
+                touched.join(", ")
+            )
+        } else {
+            format!("signed off by {}", signed_off.join(", "))
+        },
+    }))
+}
+
+/// Build a gitignore-style matcher for a set of path patterns.
+fn build_matcher(patterns: &[String]) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new("");
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .with_context(|| format!("Invalid path pattern in review policy: {pattern}"))?;
+    }
+    builder
+        .build()
+        .context("Failed to build path matcher for review policy")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn vote(vote: &str, stale: bool) -> ReviewerVote {
+        vote_by("bob", vote, stale)
+    }
+
+    fn vote_by(reviewer: &str, vote: &str, stale: bool) -> ReviewerVote {
+        ReviewerVote {
+            reviewer: reviewer.to_string(),
+            vote: vote.to_string(),
+            reason: None,
+            voted_at: "2026-01-01T00:00:00Z".to_string(),
+            commit_hash: Some("abc123".to_string()),
+            stale,
+        }
+    }
+
+    #[test]
+    fn test_load_missing_policy_is_default() {
+        let dir = tempdir().unwrap();
+        assert_eq!(Policy::load(dir.path()).unwrap(), Policy::default());
+    }
+
+    #[test]
+    fn test_load_policy_file() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".seal")).unwrap();
+        std::fs::write(
+            Policy::path(dir.path()),
+            "min_lgtms = 2\n\n[[required_reviewers]]\npaths = [\"src/\"]\nreviewers = [\"bob\"]\n",
+        )
+        .unwrap();
+
+        let policy = Policy::load(dir.path()).unwrap();
+        assert_eq!(policy.min_lgtms, 2);
+        assert_eq!(policy.required_reviewers[0].reviewers, vec!["bob"]);
+    }
+
+    #[test]
+    fn test_load_json_policy_when_no_toml() {
+        let dir = tempdir().unwrap();
+        let seal_dir = dir.path().join(".seal");
+        std::fs::create_dir_all(&seal_dir).unwrap();
+        std::fs::write(
+            seal_dir.join(JSON_POLICY_FILE),
+            r#"{"ignore_stale_lgtms": true}"#,
+        )
+        .unwrap();
+        assert!(Policy::load(dir.path()).unwrap().ignore_stale_lgtms);
+
+        // The TOML file wins when both exist
+        std::fs::write(Policy::path(dir.path()), "min_lgtms = 1\n").unwrap();
+        let policy = Policy::load(dir.path()).unwrap();
+        assert!(!policy.ignore_stale_lgtms);
+        assert_eq!(policy.min_lgtms, 1);
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_fields() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".seal")).unwrap();
+        std::fs::write(Policy::path(dir.path()), "ignore_stale_lgtm = true\n").unwrap();
+
+        assert!(Policy::load(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_counts_vote() {
+        let strict = Policy {
+            ignore_stale_lgtms: true,
+            ..Policy::default()
+        };
+        assert!(strict.counts_vote(&vote("lgtm", false)));
+        assert!(!strict.counts_vote(&vote("lgtm", true)));
+        // Stale blocks still count: the concern may still apply
+        assert!(strict.counts_vote(&vote("block", true)));
+
+        assert!(Policy::default().counts_vote(&vote("lgtm", true)));
+    }
+
+    fn review(votes: Vec<ReviewerVote>, open_threads: i64) -> ReviewDetail {
+        ReviewDetail {
+            review_id: "cr-1".to_string(),
+            jj_change_id: "change".to_string(),
+            scm_kind: "git".to_string(),
+            scm_anchor: "abc123".to_string(),
+            initial_commit: "abc123".to_string(),
+            final_commit: None,
+            title: "Test".to_string(),
+            description: None,
+            author: "alice".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            status: "open".to_string(),
+            status_changed_at: None,
+            status_changed_by: None,
+            abandon_reason: None,
+            thread_count: open_threads,
+            open_thread_count: open_threads,
+            reviewers: Vec::new(),
+            votes,
+            revisions: Vec::new(),
+            blocking_threads: Vec::new(),
+            depends_on: None,
+            dependents: Vec::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_default_policy_has_no_rules() {
+        let eval = Policy::default()
+            .evaluate(&review(Vec::new(), 3), &[], Some("alice"))
+            .unwrap();
+        assert!(eval.satisfied);
+        assert!(eval.rules.is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_min_lgtms_blocks_and_threads() {
+        let policy = Policy {
+            min_lgtms: 2,
+            no_outstanding_blocks: true,
+            require_resolved_threads: true,
+            ..Policy::default()
+        };
+        let votes = vec![
+            vote_by("bob", "lgtm", false),
+            vote_by("carol", "block", false),
+        ];
+
+        let eval = policy.evaluate(&review(votes, 1), &[], None).unwrap();
+        assert!(!eval.satisfied);
+        assert_eq!(
+            eval.unmet(),
+            vec![
+                "min_lgtms: 1 of 2 required LGTMs",
+                "no_outstanding_blocks: blocked by carol",
+                "require_resolved_threads: 1 open thread",
+            ]
+        );
+
+        let votes = vec![
+            vote_by("bob", "lgtm", false),
+            vote_by("carol", "lgtm", false),
+        ];
+        let eval = policy.evaluate(&review(votes, 0), &[], None).unwrap();
+        assert!(eval.satisfied);
+        assert_eq!(eval.rules.len(), 3);
+    }
+
+    #[test]
+    fn test_evaluate_blocking_thread_counts_as_block() {
+        let policy = Policy {
+            no_outstanding_blocks: true,
+            ..Policy::default()
+        };
+        let mut blocked = review(vec![vote_by("bob", "lgtm", false)], 1);
+        blocked.blocking_threads = vec!["th-1".to_string()];
+
+        let eval = policy.evaluate(&blocked, &[], None).unwrap();
+        assert_eq!(eval.unmet(), vec!["no_outstanding_blocks: blocked by th-1"]);
+    }
+
+    #[test]
+    fn test_evaluate_stale_lgtms() {
+        let policy = Policy {
+            ignore_stale_lgtms: true,
+            min_lgtms: 1,
+            ..Policy::default()
+        };
+
+        let eval = policy
+            .evaluate(&review(vec![vote("lgtm", true)], 0), &[], None)
+            .unwrap();
+        assert_eq!(
+            eval.unmet(),
+            vec![
+                "min_lgtms: 0 of 1 required LGTMs",
+                "ignore_stale_lgtms: all LGTMs are stale, the review changed after: bob",
+            ]
+        );
+
+        let eval = policy
+            .evaluate(&review(vec![vote("lgtm", false)], 0), &[], None)
+            .unwrap();
+        assert!(eval.satisfied);
+    }
+
+    #[test]
+    fn test_evaluate_forbid_self_approval() {
+        let policy = Policy {
+            forbid_self_approval: true,
+            min_lgtms: 1,
+            ..Policy::default()
+        };
+        let own_lgtm = vec![vote_by("alice", "lgtm", false)];
+
+        // The author's own LGTM doesn't count, and they can't approve
+        let eval = policy
+            .evaluate(&review(own_lgtm, 0), &[], Some("alice"))
+            .unwrap();
+        assert_eq!(eval.unmet().len(), 2);
+        assert!(eval.unmet()[1].contains("alice cannot approve their own review"));
+
+        // An existing approval by the author is caught at merge time
+        let mut approved = review(vec![vote_by("bob", "lgtm", false)], 0);
+        approved.status = "approved".to_string();
+        approved.status_changed_by = Some("alice".to_string());
+        let eval = policy.evaluate(&approved, &[], None).unwrap();
+        assert_eq!(
+            eval.unmet(),
+            vec!["forbid_self_approval: alice cannot approve their own review"]
+        );
+
+        let eval = policy
+            .evaluate(
+                &review(vec![vote_by("bob", "lgtm", false)], 0),
+                &[],
+                Some("bob"),
+            )
+            .unwrap();
+        assert!(eval.satisfied);
+    }
+
+    #[test]
+    fn test_evaluate_required_reviewers_by_path() {
+        let policy = Policy {
+            required_reviewers: vec![
+                RequiredReviewers {
+                    paths: vec!["src/log/".to_string()],
+                    reviewers: vec!["carol".to_string(), "dave".to_string()],
+                },
+                RequiredReviewers {
+                    paths: vec!["*.md".to_string()],
+                    reviewers: vec!["erin".to_string()],
+                },
+            ],
+            ..Policy::default()
+        };
+        let changed = vec!["src/log/mod.rs".to_string(), "src/main.rs".to_string()];
+
+        // Only the rule whose paths match is checked
+        let eval = policy
+            .evaluate(&review(vec![vote("lgtm", false)], 0), &changed, None)
+            .unwrap();
+        assert_eq!(
+            eval.unmet(),
+            vec![
+                "required_reviewers[src/log/]: needs an LGTM from one of carol, dave (touches src/log/mod.rs)"
+            ]
+        );
+
+        let votes = vec![vote("lgtm", false), vote_by("dave", "lgtm", false)];
+        let eval = policy.evaluate(&review(votes, 0), &changed, None).unwrap();
+        assert!(eval.satisfied);
+        assert_eq!(eval.rules[0].detail, "signed off by dave");
+    }
+
+    #[test]
+    fn test_evaluate_rejects_invalid_pattern() {
+        let policy = Policy {
+            required_reviewers: vec![RequiredReviewers {
+                paths: vec!["src/{a,b".to_string()],
+                reviewers: vec!["bob".to_string()],
+            }],
+            ..Policy::default()
+        };
+        let changed = vec!["src/a.rs".to_string()];
+        assert!(policy
+            .evaluate(&review(Vec::new(), 0), &changed, None)
+            .is_err());
+    }
+}
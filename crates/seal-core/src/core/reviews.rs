@@ -1,15 +1,57 @@
-//! Review service — list, get, create, request reviewers, vote, approve, abandon, mark merged.
+//! Review service — list, get, create, edit, update, request/unrequest reviewers, vote,
+//! retract votes, approve, abandon, reopen, mark merged.
+//!
+//! Approving and merging are gated by the repository's review policy (see [`super::policy`]).
+//! Reviews created from a stack of changes depend on the review below them and
+//! can't be merged before it.
+
+use serde::Serialize;
 
 use crate::events::{
     get_agent_identity, new_review_id, Event, EventEnvelope, ReviewAbandoned, ReviewApproved,
-    ReviewCreated, ReviewMerged, ReviewerVoted, ReviewersRequested, VoteType,
+    ReviewCreated, ReviewEdited, ReviewMerged, ReviewReopened, ReviewUpdated,
+    ReviewerVoteRetracted, ReviewerVoted, ReviewersRequested, ReviewersUnrequested, VoteType,
 };
 use crate::log::{open_or_create_review, AppendLog};
-use crate::projection::{ProjectionDb, ReviewDetail, ReviewSummary};
-use crate::scm::ScmRepo;
+use crate::owners::Owners;
+use crate::projection::{
+    sync_from_review_logs, ProjectionDb, ReviewDetail, ReviewRevision, ReviewSummary, ReviewerVote,
+};
+use crate::scm::{ScmRepo, StackEntry};
+use crate::sealignore::SealIgnore;
 
+use super::policy::{Policy, PolicyEvaluation};
 use super::{CoreContext, CoreError, CoreResult};
 
+/// A review created as part of a stack.
+#[derive(Debug, Clone, Serialize)]
+pub struct StackedReview {
+    pub review_id: String,
+    pub scm_anchor: String,
+    pub commit: String,
+    pub title: String,
+    /// The review below this one in the stack (`None` for the bottom review)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<String>,
+}
+
+/// Result of creating reviews for a stack of changes.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewStack {
+    /// Created reviews, bottom of the stack first
+    pub reviews: Vec<StackedReview>,
+    /// Anchors of changes skipped because they have no reviewable files
+    pub skipped: Vec<String>,
+}
+
+/// Whether an LGTM approved its review.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AutoApproval {
+    pub approved: bool,
+    /// Why the review policy wasn't met, if it wasn't
+    pub unmet_policy: Vec<String>,
+}
+
 /// Service for review operations.
 pub struct ReviewService<'a> {
     ctx: &'a CoreContext,
@@ -39,9 +81,10 @@ impl<'a> ReviewService<'a> {
         author: Option<&str>,
         needs_reviewer: Option<&str>,
         has_unresolved: bool,
+        label: Option<&str>,
     ) -> CoreResult<Vec<ReviewSummary>> {
         self.db
-            .list_reviews_filtered(status, author, needs_reviewer, has_unresolved)
+            .list_reviews_filtered(status, author, needs_reviewer, has_unresolved, label)
             .map_err(CoreError::Internal)
     }
 
@@ -57,6 +100,56 @@ impl<'a> ReviewService<'a> {
             })
     }
 
+    /// Get detailed information about a review with stale votes flagged.
+    ///
+    /// A vote is stale when it was cast against a different commit than the
+    /// one the review points at now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review doesn't exist.
+    pub fn get_checked(&self, scm: &dyn ScmRepo, review_id: &str) -> CoreResult<ReviewDetail> {
+        let mut review = self.get(review_id)?;
+        if let Some(current) = current_review_commit(scm, &review) {
+            mark_stale_votes(&mut review.votes, &current);
+        }
+        Ok(review)
+    }
+
+    /// Fill in `stale_votes` for open and approved reviews in a listing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a review's votes can't be read from the projection.
+    pub fn flag_stale_votes(
+        &self,
+        scm: &dyn ScmRepo,
+        reviews: &mut [ReviewSummary],
+    ) -> CoreResult<()> {
+        for review in reviews
+            .iter_mut()
+            .filter(|r| r.status == "open" || r.status == "approved")
+        {
+            let mut votes = self
+                .db
+                .get_votes(&review.review_id)
+                .map_err(CoreError::Internal)?;
+            if votes.iter().all(|v| v.commit_hash.is_none()) {
+                continue;
+            }
+            let Ok(current) = scm.commit_for_anchor(&review.scm_anchor) else {
+                continue;
+            };
+            mark_stale_votes(&mut votes, &current);
+            review.stale_votes = votes
+                .iter()
+                .filter(|v| v.stale)
+                .map(|v| format!("{} ({})", v.reviewer, v.vote))
+                .collect();
+        }
+        Ok(())
+    }
+
     /// Get detailed information about a review, returning `None` if not found.
     pub fn get_optional(&self, review_id: &str) -> CoreResult<Option<ReviewDetail>> {
         self.db.get_review(review_id).map_err(CoreError::Internal)
@@ -65,7 +158,8 @@ impl<'a> ReviewService<'a> {
     /// Create a new review.
     ///
     /// Generates a new review ID, writes a `ReviewCreated` event, and optionally
-    /// writes a `ReviewersRequested` event.
+    /// writes a `ReviewersRequested` event. Owners of the changed files (from
+    /// `.seal/owners`) are requested in a separate `ReviewersRequested` event.
     ///
     /// Returns the new review ID.
     pub fn create(
@@ -82,46 +176,333 @@ impl<'a> ReviewService<'a> {
         let commit_id = scm
             .current_commit()
             .map_err(CoreError::Internal)?;
+        // Load before writing anything so a broken owners file doesn't leave
+        // a half-created review behind
+        let owners = Owners::load(self.ctx.seal_root()).map_err(CoreError::Internal)?;
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
 
-        let review_id = new_review_id();
+        self.create_at(
+            scm,
+            &owners,
+            change_id,
+            &commit_id,
+            title,
+            description,
+            &reviewers.unwrap_or_default(),
+            None,
+            &author_str,
+        )
+    }
+
+    /// Create one review per change in `revset`, each depending on the one below it.
+    ///
+    /// The revset must describe a linear stack (each change a child of the
+    /// previous one). Changes without reviewable files, such as an empty
+    /// working-copy change, are skipped. Reviewers and code owners are
+    /// requested on every review, as with [`Self::create`].
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`CoreError::InvalidStack`] if the revset isn't a linear
+    /// stack or has no reviewable changes.
+    pub fn create_stack(
+        &self,
+        scm: &dyn ScmRepo,
+        revset: &str,
+        reviewers: Option<Vec<String>>,
+        author: Option<&str>,
+    ) -> CoreResult<ReviewStack> {
+        let entries = scm.stack(revset).map_err(CoreError::Internal)?;
+        check_linear_stack(&entries).map_err(|reason| CoreError::InvalidStack {
+            revset: revset.to_string(),
+            reason,
+        })?;
+
+        let mut reviewable = Vec::new();
+        let mut skipped = Vec::new();
+        for entry in entries {
+            if self.changed_files_at(scm, &entry.commit)?.is_empty() {
+                skipped.push(entry.anchor);
+            } else {
+                reviewable.push(entry);
+            }
+        }
+        if reviewable.is_empty() {
+            return Err(CoreError::InvalidStack {
+                revset: revset.to_string(),
+                reason: "no changes with reviewable files".to_string(),
+            });
+        }
+
+        let owners = Owners::load(self.ctx.seal_root()).map_err(CoreError::Internal)?;
         let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+        let reviewers = reviewers.unwrap_or_default();
+
+        let mut reviews: Vec<StackedReview> = Vec::new();
+        for entry in reviewable {
+            let depends_on = reviews.last().map(|below| below.review_id.clone());
+            let title = if entry.title.is_empty() {
+                format!("Change {}", entry.anchor)
+            } else {
+                entry.title
+            };
+            let review_id = self.create_at(
+                scm,
+                &owners,
+                entry.anchor.clone(),
+                &entry.commit,
+                title.clone(),
+                None,
+                &reviewers,
+                depends_on.clone(),
+                &author_str,
+            )?;
+            reviews.push(StackedReview {
+                review_id,
+                scm_anchor: entry.anchor,
+                commit: entry.commit,
+                title,
+                depends_on,
+            });
+        }
+
+        Ok(ReviewStack { reviews, skipped })
+    }
 
+    /// Write the events for a new review of `commit_id` under `change_id`.
+    #[allow(clippy::too_many_arguments)]
+    fn create_at(
+        &self,
+        scm: &dyn ScmRepo,
+        owners: &Owners,
+        change_id: String,
+        commit_id: &str,
+        title: String,
+        description: Option<String>,
+        explicit: &[String],
+        depends_on: Option<String>,
+        author_str: &str,
+    ) -> CoreResult<String> {
+        let review_id = new_review_id();
         let scm_kind = scm.kind().as_str().to_string();
 
-        let event = EventEnvelope::new(
-            &author_str,
+        let mut events = vec![EventEnvelope::new(
+            author_str,
             Event::ReviewCreated(ReviewCreated {
                 review_id: review_id.clone(),
                 jj_change_id: change_id.clone(),
                 scm_kind: Some(scm_kind),
                 scm_anchor: Some(change_id),
-                initial_commit: commit_id,
+                initial_commit: commit_id.to_string(),
                 title,
                 description,
+                depends_on,
             }),
-        );
-
-        let log = open_or_create_review(self.ctx.seal_root(), &review_id)
-            .map_err(CoreError::Internal)?;
-        log.append(&event).map_err(CoreError::Internal)?;
+        )];
 
         // Request reviewers if specified
-        if let Some(reviewers) = reviewers {
-            if !reviewers.is_empty() {
-                let reviewer_event = EventEnvelope::new(
-                    &author_str,
+        if !explicit.is_empty() {
+            events.push(EventEnvelope::new(
+                author_str,
+                Event::ReviewersRequested(ReviewersRequested {
+                    review_id: review_id.clone(),
+                    reviewers: explicit.to_vec(),
+                }),
+            ));
+        }
+
+        // Request code owners of the changed files (see `.seal/owners`)
+        if !owners.is_empty() {
+            let owner_reviewers: Vec<String> = self
+                .changed_files_at(scm, commit_id)
+                .map(|files| owners.owners_for_files(&files))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|owner| owner != author_str && !explicit.contains(owner))
+                .collect();
+            if !owner_reviewers.is_empty() {
+                events.push(EventEnvelope::new(
+                    author_str,
                     Event::ReviewersRequested(ReviewersRequested {
                         review_id: review_id.clone(),
-                        reviewers,
+                        reviewers: owner_reviewers,
                     }),
-                );
-                log.append(&reviewer_event).map_err(CoreError::Internal)?;
+                ));
             }
         }
 
+        let log =
+            open_or_create_review(self.ctx.seal_root(), &review_id).map_err(CoreError::Internal)?;
+        log.append_batch(&events).map_err(CoreError::Internal)?;
+
         Ok(review_id)
     }
 
+    /// Change a review's title, description or labels.
+    ///
+    /// Only the review's author may change the title or description; anyone
+    /// may add or remove labels. An empty `description` clears it. Changes
+    /// that wouldn't alter the review are dropped, and nothing is written if
+    /// none are left.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`CoreError::NotReviewAuthor`] if someone else changes the
+    /// title or description, or if a label is invalid.
+    pub fn edit(
+        &self,
+        review_id: &str,
+        title: Option<String>,
+        description: Option<String>,
+        add_labels: Vec<String>,
+        remove_labels: Vec<String>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        let review = self.get(review_id)?;
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+
+        if (title.is_some() || description.is_some()) && review.author != author_str {
+            return Err(CoreError::NotReviewAuthor {
+                review_id: review_id.to_string(),
+                author: review.author,
+            });
+        }
+        for label in add_labels.iter().chain(&remove_labels) {
+            validate_label(label)?;
+        }
+
+        let title = title.filter(|t| *t != review.title);
+        let description =
+            description.filter(|d| review.description.as_deref().unwrap_or_default() != d);
+        let mut add_labels: Vec<String> = add_labels
+            .into_iter()
+            .filter(|l| !review.labels.contains(l))
+            .collect();
+        add_labels.sort();
+        add_labels.dedup();
+        let mut remove_labels: Vec<String> = remove_labels
+            .into_iter()
+            .filter(|l| review.labels.contains(l) && !add_labels.contains(l))
+            .collect();
+        remove_labels.sort();
+        remove_labels.dedup();
+
+        if title.is_none()
+            && description.is_none()
+            && add_labels.is_empty()
+            && remove_labels.is_empty()
+        {
+            return Ok(());
+        }
+
+        let event = EventEnvelope::new(
+            &author_str,
+            Event::ReviewEdited(ReviewEdited {
+                review_id: review_id.to_string(),
+                title,
+                description,
+                add_labels,
+                remove_labels,
+            }),
+        );
+
+        let log =
+            open_or_create_review(self.ctx.seal_root(), review_id).map_err(CoreError::Internal)?;
+        log.append(&event).map_err(CoreError::Internal)?;
+
+        Ok(())
+    }
+
+    /// Record a new revision of a review.
+    ///
+    /// The revision's commit is `commit` if given, otherwise the commit the
+    /// review's anchor currently resolves to. Writes a `ReviewUpdated` event.
+    ///
+    /// Returns the revision as recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review isn't open or approved, or if its
+    /// latest revision already points at the commit.
+    pub fn update(
+        &self,
+        scm: &dyn ScmRepo,
+        review_id: &str,
+        commit: Option<String>,
+        note: Option<String>,
+        author: Option<&str>,
+    ) -> CoreResult<ReviewRevision> {
+        let review = self.get(review_id)?;
+        if review.status != "open" && review.status != "approved" {
+            return Err(CoreError::InvalidReviewStatus {
+                review_id: review_id.to_string(),
+                actual: review.status,
+                expected: "open or approved".to_string(),
+            });
+        }
+
+        let commit = match commit {
+            Some(commit) => commit,
+            None => scm
+                .commit_for_anchor(&review.scm_anchor)
+                .map_err(CoreError::Internal)?,
+        };
+
+        if let Some(latest) = review.revisions.last() {
+            if latest.commit_hash == commit {
+                return Err(CoreError::ReviewUnchanged {
+                    review_id: review_id.to_string(),
+                    commit,
+                    revision: latest.revision,
+                });
+            }
+        }
+        let revision = review.revisions.last().map_or(1, |r| r.revision) + 1;
+
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+
+        let event = EventEnvelope::new(
+            &author_str,
+            Event::ReviewUpdated(ReviewUpdated {
+                review_id: review_id.to_string(),
+                commit: commit.clone(),
+                note: note.clone(),
+                revision: Some(revision),
+            }),
+        );
+
+        let log =
+            open_or_create_review(self.ctx.seal_root(), review_id).map_err(CoreError::Internal)?;
+        log.append(&event).map_err(CoreError::Internal)?;
+
+        Ok(ReviewRevision {
+            revision,
+            commit_hash: commit,
+            note,
+            author: author_str,
+            created_at: event.ts.to_rfc3339(),
+        })
+    }
+
+    /// Get a single revision of a review.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(CoreError::RevisionNotFound)` if the revision does not exist.
+    pub fn revision(&self, review_id: &str, revision: i64) -> CoreResult<ReviewRevision> {
+        let review = self.get(review_id)?;
+        let latest = review.revisions.last().map_or(1, |r| r.revision);
+        review
+            .revisions
+            .into_iter()
+            .find(|r| r.revision == revision)
+            .ok_or_else(|| CoreError::RevisionNotFound {
+                review_id: review_id.to_string(),
+                revision,
+                latest,
+            })
+    }
+
     /// Request reviewers for an existing review.
     pub fn request_reviewers(
         &self,
@@ -156,9 +537,55 @@ impl<'a> ReviewService<'a> {
         Ok(())
     }
 
+    /// Withdraw review requests from reviewers.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`CoreError::ReviewerNotRequested`] if a reviewer isn't
+    /// currently requested on the review, or if the review isn't open or
+    /// approved.
+    pub fn unrequest_reviewers(
+        &self,
+        review_id: &str,
+        reviewers: Vec<String>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        let review = self.get(review_id)?;
+        if review.status != "open" && review.status != "approved" {
+            return Err(CoreError::InvalidReviewStatus {
+                review_id: review_id.to_string(),
+                actual: review.status,
+                expected: "open or approved".to_string(),
+            });
+        }
+        if let Some(reviewer) = reviewers.iter().find(|r| !review.reviewers.contains(r)) {
+            return Err(CoreError::ReviewerNotRequested {
+                review_id: review_id.to_string(),
+                reviewer: reviewer.clone(),
+            });
+        }
+
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+
+        let event = EventEnvelope::new(
+            &author_str,
+            Event::ReviewersUnrequested(ReviewersUnrequested {
+                review_id: review_id.to_string(),
+                reviewers,
+            }),
+        );
+
+        let log =
+            open_or_create_review(self.ctx.seal_root(), review_id).map_err(CoreError::Internal)?;
+        log.append(&event).map_err(CoreError::Internal)?;
+
+        Ok(())
+    }
+
     /// Vote on a review (LGTM or block).
     pub fn vote(
         &self,
+        scm: &dyn ScmRepo,
         review_id: &str,
         vote: VoteType,
         reason: Option<String>,
@@ -175,12 +602,16 @@ impl<'a> ReviewService<'a> {
 
         let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
 
+        // Record what the reviewer was looking at so later rewrites make the vote stale
+        let commit = scm.commit_for_anchor(&review.scm_anchor).ok();
+
         let event = EventEnvelope::new(
             &author_str,
             Event::ReviewerVoted(ReviewerVoted {
                 review_id: review_id.to_string(),
                 vote,
                 reason,
+                commit,
             }),
         );
 
@@ -191,8 +622,129 @@ impl<'a> ReviewService<'a> {
         Ok(())
     }
 
+    /// Vote on a review; an LGTM on an open review also tries to approve it
+    /// (see [`Self::auto_approve`]).
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Self::vote`] does, or if the approval fails for a reason
+    /// other than the review policy.
+    pub fn vote_and_approve(
+        &self,
+        scm: &dyn ScmRepo,
+        review_id: &str,
+        vote: VoteType,
+        reason: Option<String>,
+        author: Option<&str>,
+    ) -> CoreResult<AutoApproval> {
+        let status = self.get(review_id)?.status;
+        self.vote(scm, review_id, vote, reason, author)?;
+        if vote == VoteType::Lgtm && status == "open" {
+            self.auto_approve(scm, review_id, author)
+        } else {
+            Ok(AutoApproval::default())
+        }
+    }
+
+    /// Approve a review after the author's LGTM, if no one else blocks it,
+    /// there are no open blocking threads, and the review policy is met.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the projection can't be synced, or if approving
+    /// fails for a reason other than the review policy.
+    pub fn auto_approve(
+        &self,
+        scm: &dyn ScmRepo,
+        review_id: &str,
+        author: Option<&str>,
+    ) -> CoreResult<AutoApproval> {
+        // Pick up the vote just written
+        sync_from_review_logs(self.db, self.ctx.seal_root()).map_err(CoreError::Internal)?;
+
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+        let has_blocks = self
+            .db
+            .has_blocking_votes_from_others(review_id, &author_str)
+            .map_err(CoreError::Internal)?
+            || !self
+                .db
+                .get_open_blocking_threads(review_id)
+                .map_err(CoreError::Internal)?
+                .is_empty();
+        if has_blocks {
+            return Ok(AutoApproval::default());
+        }
+        match self.approve(scm, review_id, Some(&author_str)) {
+            Ok(()) => Ok(AutoApproval {
+                approved: true,
+                unmet_policy: Vec::new(),
+            }),
+            Err(CoreError::PolicyNotSatisfied { unmet, .. }) => Ok(AutoApproval {
+                approved: false,
+                unmet_policy: unmet,
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Withdraw the author's vote on a review.
+    ///
+    /// An approved review goes back to open once its last LGTM is withdrawn.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`CoreError::NoVoteToRetract`] if the author hasn't voted, or
+    /// if the review isn't open or approved.
+    pub fn retract_vote(
+        &self,
+        review_id: &str,
+        reason: Option<String>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        let review = self.get(review_id)?;
+        if review.status != "open" && review.status != "approved" {
+            return Err(CoreError::InvalidReviewStatus {
+                review_id: review_id.to_string(),
+                actual: review.status,
+                expected: "open or approved".to_string(),
+            });
+        }
+
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+        if !review.votes.iter().any(|v| v.reviewer == author_str) {
+            return Err(CoreError::NoVoteToRetract {
+                review_id: review_id.to_string(),
+                reviewer: author_str,
+            });
+        }
+
+        let event = EventEnvelope::new(
+            &author_str,
+            Event::ReviewerVoteRetracted(ReviewerVoteRetracted {
+                review_id: review_id.to_string(),
+                reason,
+            }),
+        );
+
+        let log =
+            open_or_create_review(self.ctx.seal_root(), review_id).map_err(CoreError::Internal)?;
+        log.append(&event).map_err(CoreError::Internal)?;
+
+        Ok(())
+    }
+
     /// Approve a review.
-    pub fn approve(&self, review_id: &str, author: Option<&str>) -> CoreResult<()> {
+    ///
+    /// # Errors
+    ///
+    /// Refuses with [`CoreError::PolicyNotSatisfied`] if the review policy isn't met.
+    pub fn approve(
+        &self,
+        scm: &dyn ScmRepo,
+        review_id: &str,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
         let review = self.get(review_id)?;
         if review.status != "open" {
             return Err(CoreError::InvalidReviewStatus {
@@ -203,6 +755,7 @@ impl<'a> ReviewService<'a> {
         }
 
         let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+        self.enforce_policy(scm, review_id, Some(&author_str))?;
 
         let event = EventEnvelope::new(
             &author_str,
@@ -251,9 +804,51 @@ impl<'a> ReviewService<'a> {
         Ok(())
     }
 
+    /// Reopen an abandoned review.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review doesn't exist or isn't abandoned.
+    pub fn reopen(
+        &self,
+        review_id: &str,
+        reason: Option<String>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        let review = self.get(review_id)?;
+        if review.status != "abandoned" {
+            return Err(CoreError::InvalidReviewStatus {
+                review_id: review_id.to_string(),
+                actual: review.status,
+                expected: "abandoned".to_string(),
+            });
+        }
+
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+
+        let event = EventEnvelope::new(
+            &author_str,
+            Event::ReviewReopened(ReviewReopened {
+                review_id: review_id.to_string(),
+                reason,
+            }),
+        );
+
+        let log =
+            open_or_create_review(self.ctx.seal_root(), review_id).map_err(CoreError::Internal)?;
+        log.append(&event).map_err(CoreError::Internal)?;
+
+        Ok(())
+    }
+
     /// Mark a review as merged.
+    ///
+    /// Refuses with [`CoreError::DependencyNotMerged`] while the review it is
+    /// stacked on is still open or approved, and with
+    /// [`CoreError::PolicyNotSatisfied`] if the review policy isn't met.
     pub fn mark_merged(
         &self,
+        scm: &dyn ScmRepo,
         review_id: &str,
         final_commit: String,
         author: Option<&str>,
@@ -266,6 +861,17 @@ impl<'a> ReviewService<'a> {
                 expected: "open or approved".to_string(),
             });
         }
+        if let Some(parent_id) = review.depends_on {
+            let parent = self.get(&parent_id)?;
+            if parent.status == "open" || parent.status == "approved" {
+                return Err(CoreError::DependencyNotMerged {
+                    review_id: review_id.to_string(),
+                    depends_on: parent_id,
+                    status: parent.status,
+                });
+            }
+        }
+        self.enforce_policy(scm, review_id, None)?;
 
         let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
 
@@ -283,4 +889,157 @@ impl<'a> ReviewService<'a> {
 
         Ok(())
     }
+
+    /// Check the repository's review policy against a review.
+    ///
+    /// `approver` is the agent about to approve the review, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review doesn't exist or the policy file is invalid.
+    pub fn evaluate_policy(
+        &self,
+        scm: &dyn ScmRepo,
+        review_id: &str,
+        approver: Option<&str>,
+    ) -> CoreResult<PolicyEvaluation> {
+        let policy = Policy::load(self.ctx.seal_root()).map_err(CoreError::Internal)?;
+        let review = self.get_checked(scm, review_id)?;
+
+        // Only path rules need the changed files, so skip the SCM otherwise
+        let changed_files = if policy.required_reviewers.is_empty() {
+            Vec::new()
+        } else {
+            self.changed_files(scm, &review)?
+        };
+
+        policy
+            .evaluate(&review, &changed_files, approver)
+            .map_err(CoreError::Internal)
+    }
+
+    fn enforce_policy(
+        &self,
+        scm: &dyn ScmRepo,
+        review_id: &str,
+        approver: Option<&str>,
+    ) -> CoreResult<()> {
+        let evaluation = self.evaluate_policy(scm, review_id, approver)?;
+        if evaluation.satisfied {
+            Ok(())
+        } else {
+            Err(CoreError::PolicyNotSatisfied {
+                review_id: review_id.to_string(),
+                unmet: evaluation.unmet(),
+            })
+        }
+    }
+
+    /// Files the review touches (excluding `.sealignore`d paths).
+    fn changed_files(&self, scm: &dyn ScmRepo, review: &ReviewDetail) -> CoreResult<Vec<String>> {
+        let target =
+            current_review_commit(scm, review).unwrap_or_else(|| review.initial_commit.clone());
+        self.changed_files_at(scm, &target)
+    }
+
+    /// Files changed by a commit relative to its parent (excluding `.sealignore`d paths).
+    fn changed_files_at(&self, scm: &dyn ScmRepo, commit: &str) -> CoreResult<Vec<String>> {
+        let base = scm.parent_commit(commit).map_err(CoreError::Internal)?;
+        let files = scm
+            .changed_files_between(&base, commit)
+            .map_err(CoreError::Internal)?;
+        let (files, _) = SealIgnore::load(self.ctx.seal_root()).filter_files(files);
+        Ok(files)
+    }
+}
+
+/// Check that a label is a single word without commas.
+fn validate_label(label: &str) -> CoreResult<()> {
+    if label.is_empty() || label.contains(',') || label.chars().any(char::is_whitespace) {
+        return Err(CoreError::InvalidLabel {
+            label: label.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check that each change in a stack is a child of the one before it.
+///
+/// Returns the reason the stack can't be reviewed in order otherwise.
+fn check_linear_stack(entries: &[StackEntry]) -> Result<(), String> {
+    if entries.is_empty() {
+        return Err("the revset is empty".to_string());
+    }
+    for pair in entries.windows(2) {
+        if !pair[1].parents.contains(&pair[0].commit) {
+            return Err(format!(
+                "{} is not a child of {}; stacks must be linear",
+                pair[1].anchor, pair[0].anchor
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Resolve the commit a review points at now.
+///
+/// Merged reviews use their final commit; others follow the review's anchor.
+pub fn current_review_commit(scm: &dyn ScmRepo, review: &ReviewDetail) -> Option<String> {
+    review
+        .final_commit
+        .clone()
+        .or_else(|| scm.commit_for_anchor(&review.scm_anchor).ok())
+        .or_else(|| scm.commit_for_anchor(&review.jj_change_id).ok())
+}
+
+/// Flag votes cast against a commit other than `current_commit` as stale.
+///
+/// Votes recorded before commits were tracked are never considered stale.
+pub fn mark_stale_votes(votes: &mut [ReviewerVote], current_commit: &str) {
+    for vote in votes {
+        vote.stale = vote
+            .commit_hash
+            .as_deref()
+            .is_some_and(|commit| commit != current_commit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(anchor: &str, commit: &str, parents: &[&str]) -> StackEntry {
+        StackEntry {
+            anchor: anchor.to_string(),
+            commit: commit.to_string(),
+            parents: parents.iter().map(|p| (*p).to_string()).collect(),
+            title: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_linear_stack_accepts_chain() {
+        let stack = [
+            entry("a", "c1", &["c0"]),
+            entry("b", "c2", &["c1"]),
+            entry("c", "c3", &["c2", "c9"]),
+        ];
+        assert!(check_linear_stack(&stack).is_ok());
+    }
+
+    #[test]
+    fn test_check_linear_stack_rejects_siblings_and_empty() {
+        let stack = [entry("a", "c1", &["c0"]), entry("b", "c2", &["c0"])];
+        let err = check_linear_stack(&stack).unwrap_err();
+        assert!(err.contains("b is not a child of a"), "{err}");
+        assert!(check_linear_stack(&[]).is_err());
+    }
+
+    #[test]
+    fn test_validate_label() {
+        assert!(validate_label("needs-docs").is_ok());
+        assert!(validate_label("").is_err());
+        assert!(validate_label("two words").is_err());
+        assert!(validate_label("a,b").is_err());
+    }
 }
@@ -0,0 +1,32 @@
+//! Search service — full-text search over reviews and comments.
+
+use crate::projection::{ProjectionDb, SearchFilter, SearchHit};
+
+use super::{CoreError, CoreResult};
+
+/// Service for search operations.
+pub struct SearchService<'a> {
+    db: &'a ProjectionDb,
+}
+
+impl<'a> SearchService<'a> {
+    pub(crate) const fn new(db: &'a ProjectionDb) -> Self {
+        Self { db }
+    }
+
+    /// Search review titles, descriptions, and comment bodies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the search index can't be queried.
+    pub fn query(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: usize,
+    ) -> CoreResult<Vec<SearchHit>> {
+        self.db
+            .search(query, filter, limit)
+            .map_err(CoreError::Internal)
+    }
+}
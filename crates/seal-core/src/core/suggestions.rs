@@ -0,0 +1,514 @@
+//! Suggestion service — apply a comment's suggested change to the working copy.
+//!
+//! A suggestion replaces the lines a thread was anchored to at its
+//! `commit_hash`. Before patching, the anchored lines are located in the
+//! working copy (they may have moved); if they were edited or can't be found,
+//! the suggestion is refused rather than applied to the wrong code.
+
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+
+use crate::events::{AnchorSnapshot, CodeSelection, Suggestion};
+use crate::jj::reanchor::{capture_anchor, reanchor};
+use crate::projection::ProjectionDb;
+use crate::scm::ScmRepo;
+
+use super::{CoreError, CoreResult};
+
+/// Result of applying a suggestion.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedSuggestion {
+    pub comment_id: String,
+    pub thread_id: String,
+    /// Working-copy path that was patched
+    pub file_path: String,
+    /// First replaced line in the working copy (1-based)
+    pub start_line: u32,
+    /// Last replaced line in the working copy (1-based, inclusive)
+    pub end_line: u32,
+    /// Lines that were replaced
+    pub removed: Vec<String>,
+    /// Lines that were written in their place
+    pub added: Vec<String>,
+}
+
+/// Service for suggestion operations.
+pub struct SuggestionService<'a> {
+    db: &'a ProjectionDb,
+}
+
+impl<'a> SuggestionService<'a> {
+    pub(crate) const fn new(db: &'a ProjectionDb) -> Self {
+        Self { db }
+    }
+
+    /// Patch the working copy with a comment's suggestion.
+    ///
+    /// The thread's anchored lines are followed through renames and line
+    /// drift.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `SuggestionConflict` if the lines were edited since the
+    /// thread was created, and with `CommentRetracted` or `NoSuggestion` if
+    /// the comment has nothing to apply. Paths that leave the working copy
+    /// are refused with `SuggestionConflict`.
+    pub fn apply(&self, scm: &dyn ScmRepo, comment_id: &str) -> CoreResult<AppliedSuggestion> {
+        let target = self.target(scm, comment_id)?;
+
+        let path = target.working_path(scm.root())?;
+        let contents = std::fs::read_to_string(&path).map_err(|_| {
+            target.conflict("the file does not exist in the working copy".to_string())
+        })?;
+
+        let (applied, patched) = target.splice(&contents)?;
+        std::fs::write(&path, patched).map_err(|e| {
+            CoreError::Internal(anyhow::anyhow!("Failed to write {}: {e}", path.display()))
+        })?;
+        Ok(applied)
+    }
+
+    /// Splice a comment's suggestion into `contents`, the current text of the
+    /// thread's file (such as an unsaved editor buffer), without writing it.
+    ///
+    /// Returns what was replaced and the patched contents.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `SuggestionConflict`, `CommentRetracted` or `NoSuggestion`
+    /// as [`Self::apply`] does.
+    pub fn apply_to_contents(
+        &self,
+        scm: &dyn ScmRepo,
+        comment_id: &str,
+        contents: &str,
+    ) -> CoreResult<(AppliedSuggestion, String)> {
+        self.target(scm, comment_id)?.splice(contents)
+    }
+
+    /// Look up a suggestion and the lines it replaces.
+    fn target(&self, scm: &dyn ScmRepo, comment_id: &str) -> CoreResult<SuggestionTarget> {
+        let (thread_id, comment) = self
+            .db
+            .get_comment(comment_id)
+            .map_err(CoreError::Internal)?
+            .ok_or_else(|| CoreError::CommentNotFound {
+                comment_id: comment_id.to_string(),
+            })?;
+        if comment.retracted_at.is_some() {
+            return Err(CoreError::CommentRetracted {
+                comment_id: comment_id.to_string(),
+            });
+        }
+        let suggestion = comment.suggestion.ok_or_else(|| CoreError::NoSuggestion {
+            comment_id: comment_id.to_string(),
+        })?;
+
+        let thread = self
+            .db
+            .get_thread(&thread_id)
+            .map_err(CoreError::Internal)?
+            .ok_or_else(|| CoreError::ThreadNotFound {
+                thread_id: thread_id.clone(),
+            })?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let selection = match thread.selection_end {
+            Some(end) => CodeSelection::range(thread.selection_start as u32, end as u32),
+            None => CodeSelection::line(thread.selection_start as u32),
+        };
+
+        // The file may have been renamed since the thread was created
+        let file_path = scm
+            .current_commit()
+            .ok()
+            .and_then(|current| {
+                scm.renamed_path(&thread.commit_hash, &current, &thread.file_path)
+                    .ok()
+                    .flatten()
+            })
+            .unwrap_or_else(|| thread.file_path.clone());
+
+        let snapshot = match thread.anchor {
+            Some(snapshot) => snapshot,
+            None => capture_anchor(scm, &thread.file_path, &thread.commit_hash, &selection)
+                .map_err(CoreError::Internal)?
+                .ok_or_else(|| CoreError::SuggestionConflict {
+                    comment_id: comment_id.to_string(),
+                    file_path: file_path.clone(),
+                    reason: format!("the selection is out of range at {}", thread.commit_hash),
+                })?,
+        };
+
+        Ok(SuggestionTarget {
+            comment_id: comment_id.to_string(),
+            thread_id,
+            file_path,
+            start_line: selection.start_line(),
+            snapshot,
+            suggestion,
+        })
+    }
+}
+
+/// A suggestion and the anchored lines it replaces.
+struct SuggestionTarget {
+    comment_id: String,
+    thread_id: String,
+    /// Current path of the thread's file
+    file_path: String,
+    /// Where the anchored lines started when the thread was created
+    start_line: u32,
+    snapshot: AnchorSnapshot,
+    suggestion: Suggestion,
+}
+
+impl SuggestionTarget {
+    fn conflict(&self, reason: String) -> CoreError {
+        CoreError::SuggestionConflict {
+            comment_id: self.comment_id.clone(),
+            file_path: self.file_path.clone(),
+            reason,
+        }
+    }
+
+    /// Resolve the thread's file under `root`, refusing paths that escape it.
+    ///
+    /// Thread paths come from events, which anyone with write access to the
+    /// review log can craft.
+    fn working_path(&self, root: &Path) -> CoreResult<PathBuf> {
+        let relative = Path::new(&self.file_path);
+        let outside = || self.conflict("the path is outside the working copy".to_string());
+        if relative.is_absolute()
+            || relative
+                .components()
+                .any(|component| component == Component::ParentDir)
+        {
+            return Err(outside());
+        }
+
+        let path = root.join(relative);
+        let parent = path
+            .parent()
+            .and_then(|parent| parent.canonicalize().ok())
+            .ok_or_else(|| {
+                self.conflict("the file does not exist in the working copy".to_string())
+            })?;
+        let root = root.canonicalize().map_err(|e| {
+            CoreError::Internal(anyhow::anyhow!("Failed to resolve {}: {e}", root.display()))
+        })?;
+        if !parent.starts_with(&root) {
+            return Err(outside());
+        }
+        Ok(path)
+    }
+
+    fn splice(self, contents: &str) -> CoreResult<(AppliedSuggestion, String)> {
+        let spliced = splice(contents, &self.snapshot, self.start_line, &self.suggestion)
+            .map_err(|reason| self.conflict(reason))?;
+        let applied = AppliedSuggestion {
+            comment_id: self.comment_id,
+            thread_id: self.thread_id,
+            file_path: self.file_path,
+            start_line: spliced.start_line,
+            end_line: spliced.end_line,
+            removed: self.snapshot.lines,
+            added: self.suggestion.lines,
+        };
+        Ok((applied, spliced.contents))
+    }
+}
+
+/// File contents with a suggestion spliced in.
+#[derive(Debug)]
+struct Splice {
+    contents: String,
+    start_line: u32,
+    end_line: u32,
+}
+
+/// Replace the anchored lines in `contents` with the suggestion.
+///
+/// Line endings and a trailing newline are preserved. Returns the reason the
+/// suggestion can't be applied if the anchored lines are missing or edited.
+fn splice(
+    contents: &str,
+    snapshot: &AnchorSnapshot,
+    hint_line: u32,
+    suggestion: &Suggestion,
+) -> Result<Splice, String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let located = reanchor(snapshot, contents, hint_line)
+        .filter(|found| {
+            lines[found.start_line as usize - 1..found.end_line as usize] == snapshot.lines
+        })
+        .ok_or_else(|| {
+            if is_applied(contents, snapshot, hint_line, suggestion) {
+                "the suggestion is already applied".to_string()
+            } else {
+                "the selected lines changed since the suggestion was made".to_string()
+            }
+        })?;
+
+    let start_idx = located.start_line as usize - 1;
+    let end_idx = located.end_line as usize;
+
+    let newline = if contents.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut patched: Vec<&str> = Vec::with_capacity(lines.len() + suggestion.lines.len());
+    patched.extend_from_slice(&lines[..start_idx]);
+    patched.extend(suggestion.lines.iter().map(String::as_str));
+    patched.extend_from_slice(&lines[end_idx..]);
+
+    let mut new_contents = patched.join(newline);
+    if contents.ends_with('\n') && !patched.is_empty() {
+        new_contents.push_str(newline);
+    }
+
+    Ok(Splice {
+        contents: new_contents,
+        start_line: located.start_line,
+        end_line: located.end_line,
+    })
+}
+
+/// Check whether the suggested lines already sit between the anchor's context.
+fn is_applied(
+    contents: &str,
+    snapshot: &AnchorSnapshot,
+    hint_line: u32,
+    suggestion: &Suggestion,
+) -> bool {
+    if suggestion.lines.is_empty() {
+        return false;
+    }
+    let applied = AnchorSnapshot {
+        lines: suggestion.lines.clone(),
+        before: snapshot.before.clone(),
+        after: snapshot.after.clone(),
+    };
+    let lines: Vec<&str> = contents.lines().collect();
+    reanchor(&applied, contents, hint_line).is_some_and(|found| {
+        lines[found.start_line as usize - 1..found.end_line as usize] == suggestion.lines
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{
+        CommentAdded, CommentRetracted, Event, EventEnvelope, ReviewCreated, ThreadCreated,
+    };
+    use crate::jj::reanchor::snapshot_from_contents;
+    use crate::projection::apply_event;
+    use crate::scm::git::GitRepo;
+    use std::path::Path;
+    use std::process::Command;
+
+    const FILE: &str = "fn main() {\n    let x = 1;\n    let y = 2;\n    println!(\"{x}\");\n}\n";
+
+    fn suggest(lines: &[&str]) -> Suggestion {
+        Suggestion {
+            lines: lines.iter().map(|l| (*l).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_splice_replaces_selection() {
+        let snapshot = snapshot_from_contents(FILE, 2, 3).unwrap();
+        let patch = splice(FILE, &snapshot, 2, &suggest(&["    let x = 3;"])).unwrap();
+        assert_eq!(
+            patch.contents,
+            "fn main() {\n    let x = 3;\n    println!(\"{x}\");\n}\n"
+        );
+        assert_eq!((patch.start_line, patch.end_line), (2, 3));
+    }
+
+    #[test]
+    fn test_splice_follows_moved_lines() {
+        let snapshot = snapshot_from_contents(FILE, 2, 2).unwrap();
+        let moved = format!("// header\n\n{FILE}");
+        let patch = splice(&moved, &snapshot, 2, &suggest(&["    let x = 9;"])).unwrap();
+        assert_eq!(patch.start_line, 4);
+        assert!(patch.contents.contains("    let x = 9;\n    let y = 2;"));
+    }
+
+    #[test]
+    fn test_splice_refuses_edited_selection() {
+        let snapshot = snapshot_from_contents(FILE, 2, 2).unwrap();
+        let edited = FILE.replace("let x = 1;", "let x = 10;");
+        let err = splice(&edited, &snapshot, 2, &suggest(&["    let x = 3;"])).unwrap_err();
+        assert!(err.contains("changed"), "{err}");
+    }
+
+    #[test]
+    fn test_splice_detects_applied_suggestion() {
+        let snapshot = snapshot_from_contents(FILE, 2, 2).unwrap();
+        let suggestion = suggest(&["    let x = 3;"]);
+        let patch = splice(FILE, &snapshot, 2, &suggestion).unwrap();
+        let err = splice(&patch.contents, &snapshot, 2, &suggestion).unwrap_err();
+        assert_eq!(err, "the suggestion is already applied");
+    }
+
+    #[test]
+    fn test_splice_deletes_lines_and_keeps_crlf() {
+        let crlf = FILE.replace('\n', "\r\n");
+        let snapshot = snapshot_from_contents(&crlf, 3, 3).unwrap();
+        let patch = splice(&crlf, &snapshot, 3, &suggest(&[])).unwrap();
+        assert_eq!(
+            patch.contents,
+            "fn main() {\r\n    let x = 1;\r\n    println!(\"{x}\");\r\n}\r\n"
+        );
+    }
+
+    fn git(root: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(root)
+            .args(args)
+            .output()
+            .expect("failed to run git");
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    fn apply_events(db: &ProjectionDb, events: Vec<Event>) {
+        for event in events {
+            apply_event(db, &EventEnvelope::new("alice", event)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_apply_patches_renamed_file_and_refuses_retracted_comment() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        git(root, &["config", "user.name", "Test User"]);
+        std::fs::write(root.join("main.rs"), FILE).unwrap();
+        git(root, &["add", "main.rs"]);
+        git(root, &["commit", "-qm", "initial"]);
+        let commit = git(root, &["rev-parse", "HEAD"]);
+        git(root, &["mv", "main.rs", "app.rs"]);
+        git(root, &["commit", "-qm", "rename"]);
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+        apply_events(
+            &db,
+            vec![
+                Event::ReviewCreated(ReviewCreated {
+                    review_id: "cr-001".to_string(),
+                    jj_change_id: "change".to_string(),
+                    scm_kind: Some("git".to_string()),
+                    scm_anchor: None,
+                    initial_commit: commit.clone(),
+                    title: "Review".to_string(),
+                    description: None,
+                    depends_on: None,
+                }),
+                Event::ThreadCreated(ThreadCreated {
+                    thread_id: "th-001".to_string(),
+                    review_id: "cr-001".to_string(),
+                    file_path: "main.rs".to_string(),
+                    selection: CodeSelection::line(2),
+                    commit_hash: commit,
+                    anchor: None,
+                    kind: None,
+                }),
+                Event::CommentAdded(CommentAdded {
+                    comment_id: "th-001.1".to_string(),
+                    thread_id: "th-001".to_string(),
+                    body: "Use 3".to_string(),
+                    suggestion: Some(suggest(&["    let x = 3;"])),
+                }),
+                Event::CommentAdded(CommentAdded {
+                    comment_id: "th-001.2".to_string(),
+                    thread_id: "th-001".to_string(),
+                    body: "Use 4".to_string(),
+                    suggestion: Some(suggest(&["    let x = 4;"])),
+                }),
+                Event::CommentRetracted(CommentRetracted {
+                    comment_id: "th-001.2".to_string(),
+                    thread_id: "th-001".to_string(),
+                    reason: None,
+                }),
+            ],
+        );
+
+        let scm = GitRepo::new(root.to_path_buf());
+        let service = SuggestionService::new(&db);
+        let applied = service.apply(&scm, "th-001.1").unwrap();
+        assert_eq!(applied.file_path, "app.rs");
+        assert_eq!((applied.start_line, applied.end_line), (2, 2));
+        assert_eq!(applied.removed, vec!["    let x = 1;"]);
+        assert_eq!(
+            std::fs::read_to_string(root.join("app.rs")).unwrap(),
+            FILE.replace("let x = 1;", "let x = 3;")
+        );
+
+        let err = service.apply(&scm, "th-001.2").unwrap_err();
+        assert!(matches!(err, CoreError::CommentRetracted { .. }), "{err}");
+        assert!(!root.join("main.rs").exists());
+    }
+
+    #[test]
+    fn test_apply_refuses_path_outside_working_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("repo");
+        std::fs::create_dir(&root).unwrap();
+        git(&root, &["init", "-q"]);
+        git(&root, &["config", "user.email", "test@example.com"]);
+        git(&root, &["config", "user.name", "Test User"]);
+        git(&root, &["commit", "-q", "--allow-empty", "-m", "initial"]);
+        let commit = git(&root, &["rev-parse", "HEAD"]);
+        let outside = dir.path().join("outside.rs");
+        std::fs::write(&outside, FILE).unwrap();
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+        apply_events(
+            &db,
+            vec![
+                Event::ReviewCreated(ReviewCreated {
+                    review_id: "cr-001".to_string(),
+                    jj_change_id: "change".to_string(),
+                    scm_kind: Some("git".to_string()),
+                    scm_anchor: None,
+                    initial_commit: commit.clone(),
+                    title: "Review".to_string(),
+                    description: None,
+                    depends_on: None,
+                }),
+                Event::ThreadCreated(ThreadCreated {
+                    thread_id: "th-001".to_string(),
+                    review_id: "cr-001".to_string(),
+                    file_path: "../outside.rs".to_string(),
+                    selection: CodeSelection::line(2),
+                    commit_hash: commit,
+                    anchor: snapshot_from_contents(FILE, 2, 2),
+                    kind: None,
+                }),
+                Event::CommentAdded(CommentAdded {
+                    comment_id: "th-001.1".to_string(),
+                    thread_id: "th-001".to_string(),
+                    body: "Use 3".to_string(),
+                    suggestion: Some(suggest(&["    let x = 3;"])),
+                }),
+            ],
+        );
+
+        let scm = GitRepo::new(root);
+        let err = SuggestionService::new(&db)
+            .apply(&scm, "th-001.1")
+            .unwrap_err();
+        assert!(matches!(err, CoreError::SuggestionConflict { .. }), "{err}");
+        assert!(
+            err.to_string().contains("outside the working copy"),
+            "{err}"
+        );
+        assert_eq!(std::fs::read_to_string(&outside).unwrap(), FILE);
+    }
+}
@@ -0,0 +1,513 @@
+//! Tail service — follow review logs and report newly appended events.
+//!
+//! An [`EventTail`] keeps its own read position in each review log, starting
+//! from the projection's `review_file_state`, so it sees every appended event
+//! whether or not another process syncs it first. A log that shrank or was
+//! rewritten is handled as sync handles it: a merge that interleaved other
+//! events is re-read, skipping events already reported; otherwise the anomaly
+//! is reported, and the tail resynchronises at the file's current end.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use crate::events::{Event, EventEnvelope};
+use crate::log::{list_review_ids, AppendLog, ReviewLog};
+use crate::projection::{
+    event_thread_id, is_merge_of, sync_from_review_logs, AnomalyKind, ProjectionDb,
+    ReviewFileState, SyncAnomaly,
+};
+
+use super::{CoreContext, CoreError, CoreResult};
+
+/// Something the tail read from the review logs.
+#[derive(Debug)]
+pub enum TailItem {
+    /// A newly appended event.
+    Event(Box<TailedEvent>),
+    /// A log that couldn't be followed, e.g. because it was truncated.
+    Anomaly(SyncAnomaly),
+}
+
+/// A newly appended event and the review log it was read from.
+#[derive(Debug, Clone)]
+pub struct TailedEvent {
+    pub review_id: String,
+    pub envelope: EventEnvelope,
+    /// The line as written, including fields the envelope doesn't model
+    /// (such as signatures)
+    pub line: String,
+}
+
+/// Which tailed events to report. Empty fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub review_id: Option<String>,
+    pub author: Option<String>,
+    /// Event type names, e.g. `CommentAdded` (or `comment_added`)
+    pub event_types: Vec<String>,
+    /// Only events written at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only events another agent wrote that concern this agent: on reviews
+    /// they author or are asked to review, or in threads they took part in
+    pub relevant_to: Option<String>,
+}
+
+impl EventFilter {
+    /// Whether an event passes every filter except relevance.
+    #[must_use]
+    pub fn matches_fields(&self, event: &TailedEvent) -> bool {
+        let name = event.envelope.event.name();
+        self.review_id
+            .as_ref()
+            .is_none_or(|id| *id == event.review_id)
+            && self
+                .author
+                .as_ref()
+                .is_none_or(|author| *author == event.envelope.author)
+            && self.since.is_none_or(|since| event.envelope.ts >= since)
+            && (self.event_types.is_empty()
+                || self
+                    .event_types
+                    .iter()
+                    .any(|wanted| Event::parse_name(wanted) == Some(name)))
+    }
+}
+
+/// Read positions in every review log.
+#[derive(Debug)]
+pub struct EventTail {
+    seal_root: PathBuf,
+    positions: HashMap<String, ReviewFileState>,
+    /// IDs of the events before each position, to tell a merge from a rewrite
+    seen: HashMap<String, HashSet<String>>,
+}
+
+impl EventTail {
+    /// Tail the logs under `seal_root`, starting after the given positions.
+    /// `seen` holds the IDs of the events before each position. Logs without
+    /// a position are read from the start.
+    #[must_use]
+    pub fn new(
+        seal_root: &Path,
+        positions: HashMap<String, ReviewFileState>,
+        seen: HashMap<String, HashSet<String>>,
+    ) -> Self {
+        Self {
+            seal_root: seal_root.to_path_buf(),
+            positions,
+            seen,
+        }
+    }
+
+    /// Read events appended since the last call, review by review.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review logs can't be listed or read.
+    pub fn read(&mut self) -> Result<Vec<TailItem>> {
+        let mut items = Vec::new();
+        let review_ids = list_review_ids(&self.seal_root)?;
+        for review_id in &review_ids {
+            let read = ReviewLog::new(&self.seal_root, review_id)
+                .and_then(|log| self.read_log(&log, &mut items));
+            if let Err(e) = read {
+                items.push(anomaly(
+                    review_id,
+                    AnomalyKind::ParseError,
+                    format!("Failed to read review log: {e}"),
+                ));
+            }
+        }
+
+        let on_disk: HashSet<&str> = review_ids.iter().map(String::as_str).collect();
+        self.seen
+            .retain(|review_id, _| on_disk.contains(review_id.as_str()));
+        self.positions.retain(|review_id, _| {
+            let exists = on_disk.contains(review_id.as_str());
+            if !exists {
+                items.push(anomaly(
+                    review_id,
+                    AnomalyKind::Missing,
+                    "file disappeared from disk".to_string(),
+                ));
+            }
+            exists
+        });
+        Ok(items)
+    }
+
+    fn read_log(&mut self, log: &ReviewLog, items: &mut Vec<TailItem>) -> Result<()> {
+        let review_id = log.review_id();
+        let byte_count = log.byte_len()?;
+        let previous = self.positions.get(review_id);
+        if previous.is_some_and(|p| p.byte_count == byte_count) {
+            return Ok(());
+        }
+
+        let line_count = log.total_lines()?;
+        let mut from_line = 0;
+        if let Some(previous) = previous {
+            let rewritten = if line_count < previous.line_count {
+                Some((
+                    AnomalyKind::Shrunk,
+                    format!(
+                        "file shrunk (was {} lines, now {line_count})",
+                        previous.line_count
+                    ),
+                ))
+            } else if previous.line_count > 0
+                && log.prefix_hash(previous.line_count)?.as_deref()
+                    != Some(previous.prefix_hash.as_str())
+            {
+                Some((
+                    AnomalyKind::HashMismatch,
+                    format!(
+                        "content changed (hash mismatch on first {} lines)",
+                        previous.line_count
+                    ),
+                ))
+            } else {
+                None
+            };
+
+            if let Some((kind, detail)) = rewritten {
+                let events = log.read_all()?;
+                let seen = self.seen.entry(review_id.to_string()).or_default();
+                if kind == AnomalyKind::HashMismatch
+                    && is_merge_of(&events, seen.iter().map(String::as_str))
+                {
+                    // A merge interleaves the other side's events into what
+                    // was read; re-read it all, skipping events already seen
+                    from_line = 0;
+                } else {
+                    items.push(anomaly(
+                        review_id,
+                        kind,
+                        format!("{detail}; resuming at the end of the file"),
+                    ));
+                    from_line = line_count;
+                    *seen = events.into_iter().filter_map(|e| e.event_id).collect();
+                }
+            } else {
+                from_line = previous.line_count;
+            }
+        }
+
+        if from_line < line_count {
+            let seen = self.seen.entry(review_id.to_string()).or_default();
+            for (idx, line) in log.read_lines_from(from_line)? {
+                // Appended after the file was measured; read next time
+                if idx >= line_count {
+                    break;
+                }
+                match EventEnvelope::from_json_line(&line) {
+                    Ok(envelope) => {
+                        if let Some(event_id) = &envelope.event_id {
+                            if !seen.insert(event_id.clone()) {
+                                continue;
+                            }
+                        }
+                        items.push(TailItem::Event(Box::new(TailedEvent {
+                            review_id: review_id.to_string(),
+                            envelope,
+                            line,
+                        })));
+                    }
+                    Err(e) => items.push(anomaly(
+                        review_id,
+                        AnomalyKind::ParseError,
+                        format!("line {}: {e}", idx + 1),
+                    )),
+                }
+            }
+        }
+
+        self.positions.insert(
+            review_id.to_string(),
+            ReviewFileState {
+                line_count,
+                byte_count,
+                prefix_hash: log.prefix_hash(line_count)?.unwrap_or_default(),
+            },
+        );
+        Ok(())
+    }
+}
+
+fn anomaly(review_id: &str, kind: AnomalyKind, detail: String) -> TailItem {
+    TailItem::Anomaly(SyncAnomaly {
+        review_id: review_id.to_string(),
+        kind,
+        detail,
+    })
+}
+
+/// Service for following review logs.
+pub struct TailService<'a> {
+    ctx: &'a CoreContext,
+    db: &'a ProjectionDb,
+}
+
+impl<'a> TailService<'a> {
+    pub(crate) const fn new(ctx: &'a CoreContext, db: &'a ProjectionDb) -> Self {
+        Self { ctx, db }
+    }
+
+    /// Start tailing where the projection last synced each log, or at the
+    /// start of every log with `from_start`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the synced positions can't be read from the projection.
+    pub fn start(&self, from_start: bool) -> CoreResult<EventTail> {
+        if from_start {
+            return Ok(EventTail::new(
+                self.ctx.seal_root(),
+                HashMap::new(),
+                HashMap::new(),
+            ));
+        }
+        let positions = self.db.review_file_states().map_err(CoreError::Internal)?;
+        let seen = self.db.applied_event_ids().map_err(CoreError::Internal)?;
+        Ok(EventTail::new(self.ctx.seal_root(), positions, seen))
+    }
+
+    /// Read events appended since the last poll that match `filter`.
+    ///
+    /// Anomalies are always reported, unless they are for a review the
+    /// filter excludes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a log or the projection can't be read.
+    pub fn poll(&self, tail: &mut EventTail, filter: &EventFilter) -> CoreResult<Vec<TailItem>> {
+        let items = tail.read().map_err(CoreError::Internal)?;
+        let has_events = items.iter().any(|item| matches!(item, TailItem::Event(_)));
+        if filter.relevant_to.is_some() && has_events {
+            // Relevance is judged from the projection, so it must include these events
+            sync_from_review_logs(self.db, self.ctx.seal_root()).map_err(CoreError::Internal)?;
+        }
+
+        let mut matching = Vec::new();
+        for item in items {
+            let keep = match &item {
+                TailItem::Event(event) => {
+                    filter.matches_fields(event)
+                        && match &filter.relevant_to {
+                            Some(agent) => self.is_relevant(agent, event)?,
+                            None => true,
+                        }
+                }
+                TailItem::Anomaly(anomaly) => filter
+                    .review_id
+                    .as_ref()
+                    .is_none_or(|id| *id == anomaly.review_id),
+            };
+            if keep {
+                matching.push(item);
+            }
+        }
+        Ok(matching)
+    }
+
+    /// Whether another agent's event concerns `agent`.
+    fn is_relevant(&self, agent: &str, event: &TailedEvent) -> CoreResult<bool> {
+        if event.envelope.author == agent {
+            return Ok(false);
+        }
+        match &event.envelope.event {
+            Event::ReviewersRequested(e) if e.reviewers.iter().any(|r| r == agent) => {
+                return Ok(true)
+            }
+            Event::ReviewersUnrequested(e) if e.reviewers.iter().any(|r| r == agent) => {
+                return Ok(true)
+            }
+            _ => {}
+        }
+
+        let review = self
+            .db
+            .get_review(&event.review_id)
+            .map_err(CoreError::Internal)?;
+        if review.is_some_and(|r| r.author == agent || r.reviewers.iter().any(|r| r == agent)) {
+            return Ok(true);
+        }
+
+        let Some(thread_id) = event_thread_id(&event.envelope.event) else {
+            return Ok(false);
+        };
+        let thread = self.db.get_thread(thread_id).map_err(CoreError::Internal)?;
+        Ok(thread
+            .is_some_and(|t| t.author == agent || t.comments.iter().any(|c| c.author == agent)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{ReviewAbandoned, ReviewReopened};
+    use crate::log::open_or_create_review;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn abandon(author: &str) -> EventEnvelope {
+        EventEnvelope::new(
+            author,
+            Event::ReviewAbandoned(ReviewAbandoned {
+                review_id: "cr-001".to_string(),
+                reason: None,
+            }),
+        )
+    }
+
+    fn reopen(author: &str) -> EventEnvelope {
+        EventEnvelope::new(
+            author,
+            Event::ReviewReopened(ReviewReopened {
+                review_id: "cr-001".to_string(),
+                reason: None,
+            }),
+        )
+    }
+
+    fn events(items: &[TailItem]) -> Vec<&TailedEvent> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                TailItem::Event(event) => Some(event.as_ref()),
+                TailItem::Anomaly(_) => None,
+            })
+            .collect()
+    }
+
+    fn anomalies(items: &[TailItem]) -> Vec<&SyncAnomaly> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                TailItem::Anomaly(anomaly) => Some(anomaly),
+                TailItem::Event(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tail_reads_only_appended_events() {
+        let dir = tempdir().unwrap();
+        let log = open_or_create_review(dir.path(), "cr-001").unwrap();
+        let mut tail = EventTail::new(dir.path(), HashMap::new(), HashMap::new());
+        assert!(tail.read().unwrap().is_empty());
+
+        log.append(&abandon("alice")).unwrap();
+        assert_eq!(events(&tail.read().unwrap()).len(), 1);
+        assert!(tail.read().unwrap().is_empty());
+
+        log.append(&reopen("bob")).unwrap();
+        let items = tail.read().unwrap();
+        let new = events(&items);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].envelope.author, "bob");
+        assert_eq!(new[0].review_id, "cr-001");
+        assert!(new[0].line.contains("ReviewReopened"));
+    }
+
+    #[test]
+    fn test_tail_resynchronises_after_truncation() {
+        let dir = tempdir().unwrap();
+        let log = ReviewLog::new(dir.path(), "cr-001").unwrap();
+        log.append(&abandon("alice")).unwrap();
+        log.append(&reopen("alice")).unwrap();
+
+        let mut tail = EventTail::new(dir.path(), HashMap::new(), HashMap::new());
+        tail.read().unwrap();
+
+        // Restored to an older version, e.g. by a working copy checkout
+        let path = log.path();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, format!("{}\n", content.lines().next().unwrap())).unwrap();
+        let items = tail.read().unwrap();
+        assert!(events(&items).is_empty());
+        assert_eq!(anomalies(&items)[0].kind, AnomalyKind::Shrunk);
+
+        // Appends after the truncation are followed again
+        let bob = reopen("bob");
+        log.append(&bob).unwrap();
+        let items = tail.read().unwrap();
+        assert!(anomalies(&items).is_empty());
+        assert_eq!(events(&items)[0].envelope.author, "bob");
+
+        // Same length, different history
+        let rewritten = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("bob", "eve")
+            .replace(bob.event_id.as_deref().unwrap(), "ev-rewritten");
+        std::fs::write(&path, rewritten).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"\n").unwrap();
+        let items = tail.read().unwrap();
+        assert_eq!(anomalies(&items)[0].kind, AnomalyKind::HashMismatch);
+    }
+
+    #[test]
+    fn test_tail_reads_events_merged_into_the_log() {
+        let dir = tempdir().unwrap();
+        let log = ReviewLog::new(dir.path(), "cr-001").unwrap();
+        log.append(&abandon("alice")).unwrap();
+        log.append(&reopen("alice")).unwrap();
+
+        let mut tail = EventTail::new(dir.path(), HashMap::new(), HashMap::new());
+        assert_eq!(events(&tail.read().unwrap()).len(), 2);
+
+        // A merge puts the other branch's event between ours
+        let path = log.path();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        let theirs = abandon("bob").to_json_line().unwrap();
+        std::fs::write(&path, format!("{}\n{theirs}\n{}\n", lines[0], lines[1])).unwrap();
+
+        let items = tail.read().unwrap();
+        assert!(anomalies(&items).is_empty());
+        let new = events(&items);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].envelope.author, "bob");
+
+        // Appends after the merge are followed as usual
+        log.append(&reopen("carol")).unwrap();
+        let items = tail.read().unwrap();
+        assert!(anomalies(&items).is_empty());
+        assert_eq!(events(&items)[0].envelope.author, "carol");
+    }
+
+    #[test]
+    fn test_filter_fields() {
+        let event = TailedEvent {
+            review_id: "cr-001".to_string(),
+            envelope: reopen("bob"),
+            line: String::new(),
+        };
+        assert!(EventFilter::default().matches_fields(&event));
+
+        let by_type = |types: &[&str]| EventFilter {
+            event_types: types.iter().map(|t| (*t).to_string()).collect(),
+            ..EventFilter::default()
+        };
+        assert!(by_type(&["review_reopened"]).matches_fields(&event));
+        assert!(by_type(&["CommentAdded", "ReviewReopened"]).matches_fields(&event));
+        assert!(!by_type(&["CommentAdded"]).matches_fields(&event));
+
+        let by_author = EventFilter {
+            author: Some("alice".to_string()),
+            ..EventFilter::default()
+        };
+        assert!(!by_author.matches_fields(&event));
+
+        let later = EventFilter {
+            since: Some(event.envelope.ts + chrono::Duration::seconds(1)),
+            ..EventFilter::default()
+        };
+        assert!(!later.matches_fields(&event));
+    }
+}
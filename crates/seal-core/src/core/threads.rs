@@ -1,11 +1,14 @@
-//! Thread service — create, list, resolve, reopen.
+//! Thread service — create, list, resolve, reopen, set kind.
+
+use std::collections::HashMap;
 
 use crate::events::{
-    get_agent_identity, new_thread_id, CodeSelection, Event, EventEnvelope, ThreadCreated,
-    ThreadReopened, ThreadResolved,
+    get_agent_identity, new_thread_id, AnchorSnapshot, CodeSelection, Event, EventEnvelope,
+    ThreadCreated, ThreadKind, ThreadKindChanged, ThreadReopened, ThreadResolved,
 };
 use crate::log::{open_or_create_review, AppendLog};
 use crate::projection::{ProjectionDb, ThreadDetail, ThreadSummary};
+use crate::scm::{FileRename, ScmRepo};
 
 use super::{CoreContext, CoreError, CoreResult};
 
@@ -51,13 +54,19 @@ impl<'a> ThreadService<'a> {
 
     /// Create a new thread on a review.
     ///
+    /// `anchor` is the snapshot of the selected lines at `commit_hash`, used
+    /// later to relocate the thread if those lines are edited.
+    ///
     /// Returns the new thread ID.
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         &self,
         review_id: &str,
         file_path: &str,
         selection: CodeSelection,
         commit_hash: String,
+        anchor: Option<AnchorSnapshot>,
+        kind: Option<ThreadKind>,
         author: Option<&str>,
     ) -> CoreResult<String> {
         let thread_id = new_thread_id();
@@ -71,6 +80,8 @@ impl<'a> ThreadService<'a> {
                 file_path: file_path.to_string(),
                 selection,
                 commit_hash,
+                anchor,
+                kind,
             }),
         );
 
@@ -160,4 +171,199 @@ impl<'a> ThreadService<'a> {
 
         Ok(())
     }
+
+    /// Change a thread's kind, or clear it with `None`.
+    ///
+    /// Setting the kind the thread already has is a no-op. A blocking thread
+    /// holds up a merge like a block vote, so only its author can downgrade it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the thread doesn't exist, its review isn't open or
+    /// approved, someone else is downgrading it from blocking, or the event
+    /// can't be appended.
+    pub fn set_kind(
+        &self,
+        thread_id: &str,
+        kind: Option<ThreadKind>,
+        author: Option<&str>,
+    ) -> CoreResult<()> {
+        let thread = self.get(thread_id)?;
+
+        if thread.kind.as_deref() == kind.map(|k| k.as_str()) {
+            return Ok(());
+        }
+
+        let review = self
+            .db
+            .get_review(&thread.review_id)
+            .map_err(CoreError::Internal)?
+            .ok_or_else(|| CoreError::ReviewNotFound {
+                review_id: thread.review_id.clone(),
+            })?;
+        if review.status != "open" && review.status != "approved" {
+            return Err(CoreError::InvalidReviewStatus {
+                review_id: review.review_id,
+                actual: review.status,
+                expected: "open or approved".to_string(),
+            });
+        }
+
+        let author_str = get_agent_identity(author).map_err(CoreError::Internal)?;
+        if thread.kind.as_deref() == Some(ThreadKind::Blocking.as_str())
+            && thread.author != author_str
+        {
+            return Err(CoreError::NotThreadAuthor {
+                thread_id: thread_id.to_string(),
+                author: thread.author,
+            });
+        }
+
+        let event = EventEnvelope::new(
+            &author_str,
+            Event::ThreadKindChanged(ThreadKindChanged {
+                thread_id: thread_id.to_string(),
+                kind,
+            }),
+        );
+
+        let log = open_or_create_review(self.ctx.seal_root(), &thread.review_id)
+            .map_err(CoreError::Internal)?;
+        log.append(&event).map_err(CoreError::Internal)?;
+
+        Ok(())
+    }
+}
+
+/// Point threads at their file's path in `current_commit` when the file was
+/// renamed after the thread was created.
+///
+/// The path recorded on the thread moves to `moved_from`. Rename detection is
+/// best-effort: threads are left alone when the SCM lookup fails.
+pub fn follow_renames(scm: &dyn ScmRepo, current_commit: &str, threads: &mut [ThreadSummary]) {
+    let mut renames_by_commit: HashMap<String, Vec<FileRename>> = HashMap::new();
+
+    for thread in threads.iter_mut() {
+        if thread.moved_from.is_some() || thread.commit_hash == current_commit {
+            continue;
+        }
+        let renames = renames_by_commit
+            .entry(thread.commit_hash.clone())
+            .or_insert_with(|| {
+                scm.renames_between(&thread.commit_hash, current_commit)
+                    .unwrap_or_default()
+            });
+        if let Some(rename) = renames.iter().find(|r| r.old_path == thread.file_path) {
+            thread.moved_from = Some(std::mem::replace(
+                &mut thread.file_path,
+                rename.new_path.clone(),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{ReviewAbandoned, ReviewCreated};
+    use tempfile::tempdir;
+
+    fn setup() -> (tempfile::TempDir, CoreContext) {
+        let dir = tempdir().unwrap();
+        let log = open_or_create_review(dir.path(), "cr-001").unwrap();
+        log.append(&EventEnvelope::new(
+            "alice",
+            Event::ReviewCreated(ReviewCreated {
+                review_id: "cr-001".to_string(),
+                jj_change_id: "change".to_string(),
+                scm_kind: Some("git".to_string()),
+                scm_anchor: None,
+                initial_commit: "abc123".to_string(),
+                title: "Review".to_string(),
+                description: None,
+                depends_on: None,
+            }),
+        ))
+        .unwrap();
+        log.append(&EventEnvelope::new(
+            "bob",
+            Event::ThreadCreated(ThreadCreated {
+                thread_id: "th-001".to_string(),
+                review_id: "cr-001".to_string(),
+                file_path: "src/main.rs".to_string(),
+                selection: CodeSelection::line(1),
+                commit_hash: "abc123".to_string(),
+                anchor: None,
+                kind: Some(ThreadKind::Blocking),
+            }),
+        ))
+        .unwrap();
+        let ctx = CoreContext::new(dir.path(), &dir.path().join(".seal/index.db")).unwrap();
+        (dir, ctx)
+    }
+
+    fn kind(ctx: &CoreContext) -> Option<String> {
+        ctx.services()
+            .unwrap()
+            .threads()
+            .get("th-001")
+            .unwrap()
+            .kind
+    }
+
+    #[test]
+    fn test_set_kind_only_author_downgrades_blocking() {
+        let (_dir, ctx) = setup();
+
+        let err = ctx
+            .services()
+            .unwrap()
+            .threads()
+            .set_kind("th-001", Some(ThreadKind::Nit), Some("alice"))
+            .unwrap_err();
+        assert!(matches!(err, CoreError::NotThreadAuthor { .. }), "{err}");
+        assert_eq!(kind(&ctx).as_deref(), Some("blocking"));
+
+        ctx.services()
+            .unwrap()
+            .threads()
+            .set_kind("th-001", None, Some("bob"))
+            .unwrap();
+        assert_eq!(kind(&ctx), None);
+
+        // Anyone can raise a thread to blocking
+        ctx.services()
+            .unwrap()
+            .threads()
+            .set_kind("th-001", Some(ThreadKind::Blocking), Some("alice"))
+            .unwrap();
+        assert_eq!(kind(&ctx).as_deref(), Some("blocking"));
+    }
+
+    #[test]
+    fn test_set_kind_refused_on_closed_review() {
+        let (dir, ctx) = setup();
+        open_or_create_review(dir.path(), "cr-001")
+            .unwrap()
+            .append(&EventEnvelope::new(
+                "alice",
+                Event::ReviewAbandoned(ReviewAbandoned {
+                    review_id: "cr-001".to_string(),
+                    reason: None,
+                }),
+            ))
+            .unwrap();
+
+        let err = ctx
+            .services()
+            .unwrap()
+            .threads()
+            .set_kind("th-001", Some(ThreadKind::Nit), Some("bob"))
+            .unwrap_err();
+        assert!(
+            matches!(err, CoreError::InvalidReviewStatus { .. }),
+            "{err}"
+        );
+        assert_eq!(kind(&ctx).as_deref(), Some("blocking"));
+    }
 }
@@ -0,0 +1,203 @@
+//! Local draft comments, published together with a vote.
+//!
+//! Drafts are pending threads and comments an agent has written but not yet
+//! appended to the review's event log. They live at
+//! `.seal/drafts/<review_id>/<agent>.json`, are never committed, and are
+//! flushed in one go by `seal review submit`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::events::{AnchorSnapshot, CodeSelection, Suggestion, ThreadKind};
+use crate::log::validate_review_id;
+use crate::signing::validate_agent_name;
+
+/// Drafts directory inside `.seal/`.
+pub const DRAFTS_DIR: &str = "drafts";
+
+/// Where a draft comment will be posted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DraftTarget {
+    /// A reply to an existing thread.
+    Thread { thread_id: String },
+    /// A comment on lines of a file; joins an open thread there or starts one.
+    Location {
+        file_path: String,
+        selection: CodeSelection,
+        commit_hash: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anchor: Option<AnchorSnapshot>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        kind: Option<ThreadKind>,
+    },
+}
+
+/// A comment that hasn't been published yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Draft {
+    pub created_at: DateTime<Utc>,
+    pub target: DraftTarget,
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<Suggestion>,
+}
+
+/// One agent's drafts for one review.
+#[derive(Debug, Clone)]
+pub struct DraftStore {
+    path: PathBuf,
+}
+
+impl DraftStore {
+    /// Drafts written by `agent` on `review_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review ID or agent name isn't safe to use as a
+    /// path component.
+    pub fn new(seal_root: &Path, review_id: &str, agent: &str) -> Result<Self> {
+        validate_review_id(review_id)?;
+        validate_agent_name(agent)?;
+        Ok(Self {
+            path: drafts_dir(seal_root)
+                .join(review_id)
+                .join(format!("{agent}.json")),
+        })
+    }
+
+    /// Path of the draft file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load all drafts, oldest first. Returns none if the file doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but can't be read or parsed.
+    pub fn load(&self) -> Result<Vec<Draft>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read drafts: {}", self.path.display()))
+            }
+        };
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse drafts: {}", self.path.display()))
+    }
+
+    /// Add a draft after the existing ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drafts can't be loaded or saved.
+    pub fn push(&self, draft: Draft) -> Result<()> {
+        let mut drafts = self.load()?;
+        drafts.push(draft);
+        self.save(&drafts)
+    }
+
+    /// Delete all drafts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but can't be removed.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to remove drafts: {}", self.path.display()))
+            }
+        }
+    }
+
+    fn save(&self, drafts: &[Draft]) -> Result<()> {
+        let dir = self.path.parent().context("Draft path has no parent")?;
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create drafts directory: {}", dir.display()))?;
+        ensure_ignored(dir.parent().unwrap_or(dir))?;
+
+        let json = serde_json::to_string_pretty(drafts).context("Failed to serialize drafts")?;
+        // Write-then-rename so an interrupted save never loses earlier drafts
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, json)
+            .with_context(|| format!("Failed to write drafts: {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write drafts: {}", self.path.display()))
+    }
+}
+
+/// Path to the drafts directory within `.seal/`.
+#[must_use]
+pub fn drafts_dir(seal_root: &Path) -> PathBuf {
+    seal_root.join(".seal").join(DRAFTS_DIR)
+}
+
+/// Keep drafts out of version control even in repos initialized before
+/// `.seal/.gitignore` listed them (jj would otherwise snapshot them).
+fn ensure_ignored(drafts_dir: &Path) -> Result<()> {
+    let gitignore = drafts_dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(&gitignore, "*\n")
+            .with_context(|| format!("Failed to create {}", gitignore.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn reply(thread_id: &str, body: &str) -> Draft {
+        Draft {
+            created_at: Utc::now(),
+            target: DraftTarget::Thread {
+                thread_id: thread_id.to_string(),
+            },
+            body: body.to_string(),
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn test_push_load_clear() {
+        let dir = tempdir().unwrap();
+        let store = DraftStore::new(dir.path(), "cr-abc", "alice").unwrap();
+        assert!(store.load().unwrap().is_empty());
+
+        store.push(reply("th-1", "first")).unwrap();
+        store.push(reply("th-1", "second")).unwrap();
+        let bodies: Vec<String> = store.load().unwrap().into_iter().map(|d| d.body).collect();
+        assert_eq!(bodies, vec!["first", "second"]);
+        assert!(drafts_dir(dir.path()).join(".gitignore").exists());
+
+        // Other agents' drafts are separate
+        assert!(DraftStore::new(dir.path(), "cr-abc", "bob")
+            .unwrap()
+            .load()
+            .unwrap()
+            .is_empty());
+
+        store.clear().unwrap();
+        assert!(store.load().unwrap().is_empty());
+        store.clear().unwrap();
+    }
+
+    #[test]
+    fn test_new_rejects_paths_outside_drafts_dir() {
+        let dir = tempdir().unwrap();
+        assert!(DraftStore::new(dir.path(), "cr-abc", "../../x").is_err());
+        assert!(DraftStore::new(dir.path(), "cr-abc", ".hidden").is_err());
+        assert!(DraftStore::new(dir.path(), "../cr-abc", "alice").is_err());
+        assert!(DraftStore::new(dir.path(), "cr/abc", "alice").is_err());
+    }
+}
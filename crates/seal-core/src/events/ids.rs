@@ -1,7 +1,8 @@
-//! ID generation for reviews and threads.
+//! ID generation for reviews, threads and events.
 //!
 //! Uses short, human-readable slugs: cr-xxx, th-xxx
 //! Comments use thread child IDs: th-xxx.1, th-xxx.2, etc.
+//! Events get longer IDs (ev-xxxxxxxxxxxx) since a repo accumulates many more.
 //! Powered by terseid for adaptive-length, collision-resistant IDs.
 
 use terseid::{IdConfig, IdGenerator, parse_id};
@@ -9,6 +10,10 @@ use terseid::{IdConfig, IdGenerator, parse_id};
 /// Length of the random suffix (in base36 chars)
 const HASH_LENGTH: usize = 4;
 
+/// Length of the random suffix for event IDs. Events are never typed by
+/// hand, so this favors collision resistance over brevity.
+const EVENT_HASH_LENGTH: usize = 12;
+
 fn review_generator() -> IdGenerator {
     IdGenerator::new(IdConfig::new("cr"))
 }
@@ -17,6 +22,10 @@ fn thread_generator() -> IdGenerator {
     IdGenerator::new(IdConfig::new("th"))
 }
 
+fn event_generator() -> IdGenerator {
+    IdGenerator::new(IdConfig::new("ev"))
+}
+
 /// Generate random bytes for seeding ID generation.
 fn random_seed() -> [u8; 16] {
     let mut buf = [0u8; 16];
@@ -26,9 +35,9 @@ fn random_seed() -> [u8; 16] {
 
 /// Generate a valid ID, retrying if the hash doesn't satisfy terseid's
 /// parse rules (e.g., 4+ char hashes must contain at least one digit).
-fn generate_valid_id(gen: &IdGenerator) -> String {
+fn generate_valid_id(gen: &IdGenerator, hash_length: usize) -> String {
     for _ in 0..100 {
-        let id = gen.candidate(&random_seed(), HASH_LENGTH);
+        let id = gen.candidate(random_seed(), hash_length);
         if parse_id(&id).is_ok() {
             return id;
         }
@@ -39,12 +48,18 @@ fn generate_valid_id(gen: &IdGenerator) -> String {
 
 /// Generate a new review ID (e.g., "cr-1d3f")
 pub fn new_review_id() -> String {
-    generate_valid_id(&review_generator())
+    generate_valid_id(&review_generator(), HASH_LENGTH)
 }
 
 /// Generate a new thread ID (e.g., "th-99az")
 pub fn new_thread_id() -> String {
-    generate_valid_id(&thread_generator())
+    generate_valid_id(&thread_generator(), HASH_LENGTH)
+}
+
+/// Generate a new event ID (e.g., "ev-3k9x0a7qzm2c")
+#[must_use]
+pub fn new_event_id() -> String {
+    generate_valid_id(&event_generator(), EVENT_HASH_LENGTH)
 }
 
 /// Generate a comment ID as a child of a thread (e.g., "th-abc.1")
@@ -66,6 +81,12 @@ pub fn is_thread_id(s: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Check if a string looks like a valid event ID
+#[must_use]
+pub fn is_event_id(s: &str) -> bool {
+    parse_id(s).is_ok_and(|parsed| parsed.prefix == "ev" && parsed.hash.len() >= 3)
+}
+
 /// Check if a string looks like a valid comment ID (th-xxx.N format)
 pub fn is_comment_id(s: &str) -> bool {
     // Split on '.' to separate thread ID from comment number
@@ -102,6 +123,15 @@ mod tests {
         assert!(is_thread_id(&id));
     }
 
+    #[test]
+    fn test_event_id_format() {
+        let id = new_event_id();
+        assert!(id.starts_with("ev-"), "ID should start with 'ev-': {}", id);
+        assert_eq!(id.len(), 3 + EVENT_HASH_LENGTH, "unexpected length: {}", id);
+        assert!(is_event_id(&id));
+        assert!(!is_review_id(&id));
+    }
+
     #[test]
     fn test_comment_id_format() {
         // Comment IDs are now thread child IDs: th-xxx.N
@@ -138,6 +168,8 @@ mod tests {
             assert!(is_review_id(&rid), "Generated invalid review ID: {}", rid);
             let tid = new_thread_id();
             assert!(is_thread_id(&tid), "Generated invalid thread ID: {}", tid);
+            let eid = new_event_id();
+            assert!(is_event_id(&eid), "Generated invalid event ID: {}", eid);
         }
     }
 
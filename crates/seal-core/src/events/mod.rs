@@ -6,7 +6,7 @@ pub mod identity;
 pub mod ids;
 
 pub use identity::get_agent_identity;
-pub use ids::{is_review_id, make_comment_id, new_review_id, new_thread_id};
+pub use ids::{is_review_id, make_comment_id, new_event_id, new_review_id, new_thread_id};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,18 @@ use serde::{Deserialize, Serialize};
 pub struct EventEnvelope {
     /// Timestamp when the event was created
     pub ts: DateTime<Utc>,
+    /// Unique ID of this event, used to make replay idempotent.
+    /// Absent on events written before IDs were introduced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    /// Lamport clock: one more than the highest clock in the review log when
+    /// the event was appended. Absent on events written before clocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<u64>,
+    /// IDs of the events at the head of the review log when this one was
+    /// appended (more than one after a merge).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parents: Vec<String>,
     /// Agent/user who created this event
     pub author: String,
     /// The event payload
@@ -31,22 +43,38 @@ pub enum Event {
     ReviewCreated(ReviewCreated),
     /// Reviewers were requested for a review
     ReviewersRequested(ReviewersRequested),
+    /// Review requests were withdrawn from reviewers
+    ReviewersUnrequested(ReviewersUnrequested),
     /// A reviewer voted on a review (LGTM or block)
     ReviewerVoted(ReviewerVoted),
+    /// A reviewer withdrew their vote
+    ReviewerVoteRetracted(ReviewerVoteRetracted),
     /// A review was approved
     ReviewApproved(ReviewApproved),
     /// A review was merged
     ReviewMerged(ReviewMerged),
     /// A review was abandoned
     ReviewAbandoned(ReviewAbandoned),
+    /// An abandoned review was reopened
+    ReviewReopened(ReviewReopened),
+    /// A new revision of a review was pushed for reviewers
+    ReviewUpdated(ReviewUpdated),
+    /// A review's title, description or labels were changed
+    ReviewEdited(ReviewEdited),
     /// A new comment thread was created
     ThreadCreated(ThreadCreated),
     /// A comment was added to a thread
     CommentAdded(CommentAdded),
+    /// A comment body was edited by its author
+    CommentEdited(CommentEdited),
+    /// A comment was retracted by its author
+    CommentRetracted(CommentRetracted),
     /// A thread was resolved
     ThreadResolved(ThreadResolved),
     /// A thread was reopened
     ThreadReopened(ThreadReopened),
+    /// A thread's kind was changed or cleared
+    ThreadKindChanged(ThreadKindChanged),
 }
 
 // ============================================================================
@@ -72,6 +100,9 @@ pub struct ReviewCreated {
     /// Optional description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Review this one is stacked on; it has to be merged first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +111,12 @@ pub struct ReviewersRequested {
     pub reviewers: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewersUnrequested {
+    pub review_id: String,
+    pub reviewers: Vec<String>,
+}
+
 /// Vote type for reviewer decisions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -106,6 +143,17 @@ pub struct ReviewerVoted {
     /// Optional reason (typically used for blocks)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
+    /// Commit the reviewer was looking at when voting (absent on older events)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// The event author's vote on the review is withdrawn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewerVoteRetracted {
+    pub review_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +175,43 @@ pub struct ReviewAbandoned {
     pub reason: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewReopened {
+    pub review_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewUpdated {
+    pub review_id: String,
+    /// Commit hash reviewers should look at for this revision
+    pub commit: String,
+    /// Optional note describing what changed since the previous revision
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Revision number this update creates. Older events leave it out and
+    /// are numbered in log order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<i64>,
+}
+
+/// Changes to a review's metadata. Fields left empty are unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewEdited {
+    pub review_id: String,
+    /// New title (only applied for the review's author)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// New description, or an empty string to clear it (only applied for the review's author)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_labels: Vec<String>,
+}
+
 // ============================================================================
 // Thread Events
 // ============================================================================
@@ -167,6 +252,21 @@ impl CodeSelection {
     }
 }
 
+/// Snapshot of the anchored code, captured when a thread is created.
+///
+/// Lets a thread be relocated after the lines it points at were edited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorSnapshot {
+    /// Text of the selected lines
+    pub lines: Vec<String>,
+    /// Lines immediately above the selection (top to bottom)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<String>,
+    /// Lines immediately below the selection (top to bottom)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreadCreated {
     /// Unique thread identifier (e.g., "th-99a")
@@ -179,6 +279,59 @@ pub struct ThreadCreated {
     pub selection: CodeSelection,
     /// Commit hash where the selection was made
     pub commit_hash: String,
+    /// Anchored line text plus surrounding lines at `commit_hash`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<AnchorSnapshot>,
+    /// Severity of the thread (blocking, nit, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ThreadKind>,
+}
+
+/// Severity of a thread. Unresolved blocking threads hold up a merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThreadKind {
+    /// Must be fixed before merging
+    Blocking,
+    /// Minor or stylistic, safe to ignore
+    Nit,
+    /// Asks for clarification
+    Question,
+    /// Positive feedback
+    Praise,
+}
+
+impl ThreadKind {
+    /// Kind name as stored in the projection.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Blocking => "blocking",
+            Self::Nit => "nit",
+            Self::Question => "question",
+            Self::Praise => "praise",
+        }
+    }
+}
+
+impl std::fmt::Display for ThreadKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ThreadKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocking" => Ok(Self::Blocking),
+            "nit" => Ok(Self::Nit),
+            "question" => Ok(Self::Question),
+            "praise" => Ok(Self::Praise),
+            other => Err(format!("unknown thread kind: {other}")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -195,10 +348,25 @@ pub struct ThreadReopened {
     pub reason: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadKindChanged {
+    pub thread_id: String,
+    /// New kind, or `None` to clear it
+    pub kind: Option<ThreadKind>,
+}
+
 // ============================================================================
 // Comment Events
 // ============================================================================
 
+/// Replacement text proposed for a thread's selection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// Lines that replace the thread's selected lines at its `commit_hash`.
+    /// Empty means the selected lines should be deleted.
+    pub lines: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommentAdded {
     /// Comment identifier as thread child (e.g., "th-abc.1")
@@ -207,17 +375,104 @@ pub struct CommentAdded {
     pub thread_id: String,
     /// Comment body
     pub body: String,
+    /// Suggested change for the thread's selection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<Suggestion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentEdited {
+    /// Comment being edited (e.g., "th-abc.1")
+    pub comment_id: String,
+    /// Parent thread
+    pub thread_id: String,
+    /// Replacement comment body
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentRetracted {
+    /// Comment being retracted (e.g., "th-abc.1")
+    pub comment_id: String,
+    /// Parent thread
+    pub thread_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 // ============================================================================
 // Constructors and helpers
 // ============================================================================
 
+impl Event {
+    /// Every event type name, as returned by [`Self::name`].
+    pub const NAMES: [&'static str; 18] = [
+        "ReviewCreated",
+        "ReviewersRequested",
+        "ReviewersUnrequested",
+        "ReviewerVoted",
+        "ReviewerVoteRetracted",
+        "ReviewApproved",
+        "ReviewMerged",
+        "ReviewAbandoned",
+        "ReviewReopened",
+        "ReviewUpdated",
+        "ReviewEdited",
+        "ThreadCreated",
+        "CommentAdded",
+        "CommentEdited",
+        "CommentRetracted",
+        "ThreadResolved",
+        "ThreadReopened",
+        "ThreadKindChanged",
+    ];
+
+    /// The event type a user-supplied name refers to. Case and underscores
+    /// are ignored, so `comment_added` is `CommentAdded`.
+    #[must_use]
+    pub fn parse_name(name: &str) -> Option<&'static str> {
+        let name = name.replace('_', "");
+        Self::NAMES
+            .into_iter()
+            .find(|known| known.eq_ignore_ascii_case(&name))
+    }
+
+    /// Event type name, as written in the `event` field of the log.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::ReviewCreated(_) => "ReviewCreated",
+            Self::ReviewersRequested(_) => "ReviewersRequested",
+            Self::ReviewersUnrequested(_) => "ReviewersUnrequested",
+            Self::ReviewerVoted(_) => "ReviewerVoted",
+            Self::ReviewerVoteRetracted(_) => "ReviewerVoteRetracted",
+            Self::ReviewApproved(_) => "ReviewApproved",
+            Self::ReviewMerged(_) => "ReviewMerged",
+            Self::ReviewAbandoned(_) => "ReviewAbandoned",
+            Self::ReviewReopened(_) => "ReviewReopened",
+            Self::ReviewUpdated(_) => "ReviewUpdated",
+            Self::ReviewEdited(_) => "ReviewEdited",
+            Self::ThreadCreated(_) => "ThreadCreated",
+            Self::CommentAdded(_) => "CommentAdded",
+            Self::CommentEdited(_) => "CommentEdited",
+            Self::CommentRetracted(_) => "CommentRetracted",
+            Self::ThreadResolved(_) => "ThreadResolved",
+            Self::ThreadReopened(_) => "ThreadReopened",
+            Self::ThreadKindChanged(_) => "ThreadKindChanged",
+        }
+    }
+}
+
 impl EventEnvelope {
-    /// Create a new event envelope with the current timestamp.
+    /// Create a new event envelope with the current timestamp and a fresh ID.
+    ///
+    /// The clock and parents are filled in when it's appended to a review log.
     pub fn new(author: impl Into<String>, event: Event) -> Self {
         Self {
             ts: Utc::now(),
+            event_id: Some(new_event_id()),
+            clock: None,
+            parents: Vec::new(),
             author: author.into(),
             event,
         }
@@ -250,13 +505,18 @@ mod tests {
                 initial_commit: "def456".to_string(),
                 title: "Test Review".to_string(),
                 description: Some("A test".to_string()),
+                depends_on: None,
             }),
         );
 
         let json = event.to_json_line().unwrap();
         let parsed = EventEnvelope::from_json_line(&json).unwrap();
+        assert!(json.contains(&format!(r#""event":"{}""#, event.event.name())));
+        assert!(Event::NAMES.contains(&event.event.name()));
 
         assert_eq!(parsed.author, "test_agent");
+        assert!(parsed.event_id.is_some());
+        assert_eq!(parsed.event_id, event.event_id);
         match parsed.event {
             Event::ReviewCreated(r) => {
                 assert_eq!(r.review_id, "cr-abc");
@@ -266,6 +526,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_name() {
+        assert_eq!(Event::parse_name("CommentAdded"), Some("CommentAdded"));
+        assert_eq!(Event::parse_name("comment_added"), Some("CommentAdded"));
+        assert_eq!(Event::parse_name("reviewervoted"), Some("ReviewerVoted"));
+        assert_eq!(Event::parse_name("CommentAdd"), None);
+    }
+
     #[test]
     fn test_code_selection_line() {
         let sel = CodeSelection::line(42);
@@ -288,6 +556,8 @@ mod tests {
             file_path: "src/main.rs".to_string(),
             selection: CodeSelection::range(10, 15),
             commit_hash: "abc123".to_string(),
+            anchor: None,
+            kind: Some(ThreadKind::Nit),
         });
 
         let envelope = EventEnvelope::new("agent", event);
@@ -297,5 +567,25 @@ mod tests {
         assert!(json.contains("ThreadCreated"));
         assert!(json.contains("th-123"));
         assert!(json.contains("Range"));
+        assert!(json.contains(r#""kind":"nit""#));
+    }
+
+    #[test]
+    fn test_thread_created_without_kind_deserializes() {
+        let json = r#"{"ts":"2025-01-01T00:00:00Z","author":"a","event":"ThreadCreated","data":{"thread_id":"th-1","review_id":"cr-1","file_path":"a.rs","selection":{"type":"Line","line":1},"commit_hash":"abc"}}"#;
+        let parsed = EventEnvelope::from_json_line(json).unwrap();
+        match parsed.event {
+            Event::ThreadCreated(t) => assert_eq!(t.kind, None),
+            _ => panic!("Expected ThreadCreated"),
+        }
+    }
+
+    #[test]
+    fn test_legacy_event_without_id_deserializes() {
+        let json = r#"{"ts":"2025-01-01T00:00:00Z","author":"a","event":"ReviewAbandoned","data":{"review_id":"cr-1","reason":null}}"#;
+        let parsed = EventEnvelope::from_json_line(json).unwrap();
+        assert_eq!(parsed.event_id, None);
+        // Re-serializing a legacy event doesn't invent an ID
+        assert!(!parsed.to_json_line().unwrap().contains("event_id"));
     }
 }
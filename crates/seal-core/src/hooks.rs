@@ -0,0 +1,816 @@
+//! Event hooks: shell commands run when events are appended to a review log.
+//!
+//! `.seal/hooks.toml` lists hooks, each with the events it fires on and
+//! optional filters:
+//!
+//! ```toml
+//! [[hooks]]
+//! events = ["ThreadCreated", "comment_added"]  # absent or empty: every event
+//! author = "alice"                              # events written by alice
+//! reviewer = "storage-agent"                    # reviews requesting storage-agent
+//! path = "crates/seal-core/src/log/"            # threads on matching files
+//! command = "notify-send \"$SEAL_EVENT on $SEAL_REVIEW_ID\""
+//! ```
+//!
+//! The file is checked in, so like git hooks it isn't run just because it is
+//! there: each user trusts its exact contents with `seal hooks trust`, which
+//! records a hash in `$XDG_CONFIG_HOME/.botseal/trusted-hooks`, outside the
+//! repository. Any change to the file, including one pulled from elsewhere,
+//! has to be trusted again before hooks run.
+//!
+//! Commands run with `sh -c` from the repository root. The event's log line
+//! is written to stdin and `SEAL_*` variables describe it. After a write,
+//! commands are started in the background, so a slow hook doesn't hold up
+//! the command or server that wrote the event; their stdout is discarded.
+//! A failing hook is logged and never undoes the event. Hooks run with
+//! `SEAL_HOOK=1` and nothing fires while it is set, so a hook that writes to
+//! seal can't trigger itself.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{Seek, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::events::{Event, EventEnvelope, VoteType};
+use crate::log::{list_review_ids, ReviewLog};
+use crate::projection::event_thread_id;
+use crate::signing::encode_hex;
+
+/// Hooks file name inside `.seal/`.
+pub const HOOKS_FILE: &str = "hooks.toml";
+
+/// Set in the environment of hook commands; hooks don't fire while it is set.
+pub const HOOK_ENV: &str = "SEAL_HOOK";
+
+/// Trusted hooks files, in the user's config directory.
+pub const TRUST_FILE: &str = "trusted-hooks";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HooksFile {
+    #[serde(default)]
+    hooks: Vec<HookEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HookEntry {
+    #[serde(default)]
+    events: Vec<String>,
+    author: Option<String>,
+    reviewer: Option<String>,
+    path: Option<String>,
+    command: String,
+}
+
+/// A single `[[hooks]]` entry.
+#[derive(Debug, Clone)]
+pub struct Hook {
+    /// Event types to fire on, as in `seal events tail --type`; empty for all
+    pub events: Vec<String>,
+    /// Only events written by this agent
+    pub author: Option<String>,
+    /// Only events on reviews this agent is requested on
+    pub reviewer: Option<String>,
+    /// Only thread events on files matching this gitignore-style pattern
+    pub path: Option<String>,
+    /// Shell command to run
+    pub command: String,
+    path_matcher: Option<Gitignore>,
+}
+
+impl Hook {
+    /// Check if the hook fires for an event on a review in the given state.
+    #[must_use]
+    pub fn matches(&self, envelope: &EventEnvelope, review: &ReviewState) -> bool {
+        let name = envelope.event.name();
+        (self.events.is_empty()
+            || self
+                .events
+                .iter()
+                .any(|wanted| Event::parse_name(wanted) == Some(name)))
+            && self
+                .author
+                .as_ref()
+                .is_none_or(|author| *author == envelope.author)
+            && self
+                .reviewer
+                .as_ref()
+                .is_none_or(|reviewer| review.reviewers.contains(reviewer))
+            && self.path_matcher.as_ref().is_none_or(|matcher| {
+                review.file_for(&envelope.event).is_some_and(|file| {
+                    let file = file.strip_prefix("./").unwrap_or(file);
+                    matcher.matched_path_or_any_parents(file, false).is_ignore()
+                })
+            })
+    }
+
+    /// Run the command for one event and wait for it, with `line` (the
+    /// event as written in the log) on stdin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command can't be started or exits unsuccessfully.
+    pub fn run(
+        &self,
+        seal_root: &Path,
+        review_id: &str,
+        line: &str,
+        envelope: &EventEnvelope,
+        review: &ReviewState,
+    ) -> Result<()> {
+        let mut child = self
+            .command(seal_root, review_id, envelope, review)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run hook '{}'", self.command))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // The command may exit without reading its input
+            let _ = writeln!(stdin, "{line}");
+        }
+        let output = child
+            .wait_with_output()
+            .with_context(|| format!("Failed to run hook '{}'", self.command))?;
+        if !output.status.success() {
+            bail!(
+                "Hook '{}' failed ({}): {}",
+                self.command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    /// Start the command for one event without waiting for it. Its exit
+    /// status is logged from a background thread, which lives only as long
+    /// as this process; the command itself keeps running after seal exits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command can't be started.
+    pub fn spawn(
+        &self,
+        seal_root: &Path,
+        review_id: &str,
+        line: &str,
+        envelope: &EventEnvelope,
+        review: &ReviewState,
+    ) -> Result<()> {
+        let mut child = self
+            .command(seal_root, review_id, envelope, review)
+            .stdin(stdin_file(line)?)
+            // Stdout may be a JSON-RPC channel (`seal serve`, `seal lsp`)
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to run hook '{}'", self.command))?;
+
+        let command = self.command.clone();
+        let review_id = review_id.to_string();
+        std::thread::spawn(move || match child.wait() {
+            Ok(status) if status.success() => {}
+            Ok(status) => tracing::warn!(review_id, "Hook '{command}' failed ({status})"),
+            Err(e) => tracing::warn!(review_id, "Hook '{command}' failed: {e}"),
+        });
+        Ok(())
+    }
+
+    /// The command with its `SEAL_*` environment.
+    fn command(
+        &self,
+        seal_root: &Path,
+        review_id: &str,
+        envelope: &EventEnvelope,
+        review: &ReviewState,
+    ) -> Command {
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(&self.command)
+            .current_dir(seal_root)
+            .env(HOOK_ENV, "1")
+            .env("SEAL_ROOT", seal_root)
+            .env("SEAL_REVIEW_ID", review_id)
+            .env("SEAL_EVENT", envelope.event.name())
+            .env("SEAL_AUTHOR", &envelope.author)
+            .env("SEAL_TS", envelope.ts.to_rfc3339());
+        if let Some(event_id) = &envelope.event_id {
+            command.env("SEAL_EVENT_ID", event_id);
+        }
+        if let Some(thread_id) = event_thread_id(&envelope.event) {
+            command.env("SEAL_THREAD_ID", thread_id);
+        }
+        if let Some(file) = review.file_for(&envelope.event) {
+            command.env("SEAL_FILE", file);
+        }
+        if let Event::ReviewerVoted(vote) = &envelope.event {
+            command.env(
+                "SEAL_VOTE",
+                match vote.vote {
+                    VoteType::Lgtm => "lgtm",
+                    VoteType::Block => "block",
+                },
+            );
+        }
+        command
+    }
+}
+
+/// An anonymous temporary file holding `line`, for a background hook's stdin.
+///
+/// A pipe would need someone to keep writing to it after this process exits.
+fn stdin_file(line: &str) -> Result<File> {
+    let mut file = tempfile::tempfile().context("Failed to create hook input file")?;
+    writeln!(file, "{line}").context("Failed to write hook input")?;
+    file.rewind().context("Failed to rewind hook input")?;
+    Ok(file)
+}
+
+/// The current user's record of trusted hooks files, one
+/// `<sha256> <seal root>` line per repository.
+#[derive(Debug, Clone)]
+pub struct TrustStore {
+    path: PathBuf,
+}
+
+impl TrustStore {
+    /// A trust store at an explicit path.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The current user's trust store, if a config directory can be determined.
+    #[must_use]
+    pub fn for_user() -> Option<Self> {
+        let base = if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+            PathBuf::from(xdg)
+        } else if let Ok(home) = std::env::var("HOME") {
+            Path::new(&home).join(".config")
+        } else {
+            return None;
+        };
+        Some(Self::new(base.join(".botseal").join(TRUST_FILE)))
+    }
+
+    /// Path of the trust store file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Check if a hooks file with this hash is trusted for a repository.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trust store exists but cannot be read.
+    pub fn is_trusted(&self, seal_root: &Path, hash: &str) -> Result<bool> {
+        let root = root_key(seal_root);
+        Ok(self
+            .entries()?
+            .iter()
+            .any(|(trusted, trusted_root)| trusted == hash && *trusted_root == root))
+    }
+
+    /// Trust a hooks file with this hash for a repository, replacing any
+    /// hash trusted for it before.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trust store cannot be read or written.
+    pub fn trust(&self, seal_root: &Path, hash: &str) -> Result<()> {
+        let root = root_key(seal_root);
+        let mut lines: Vec<String> = self
+            .entries()?
+            .into_iter()
+            .filter(|(_, trusted_root)| *trusted_root != root)
+            .map(|(trusted, trusted_root)| format!("{trusted} {trusted_root}"))
+            .collect();
+        lines.push(format!("{hash} {root}"));
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(&self.path, lines.join("\n") + "\n")
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    fn entries(&self) -> Result<Vec<(String, String)>> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", self.path.display()))
+            }
+        };
+        Ok(contents
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(hash, root)| (hash.to_string(), root.to_string()))
+            .collect())
+    }
+}
+
+/// How a repository is identified in the trust store.
+fn root_key(seal_root: &Path) -> String {
+    seal_root
+        .canonicalize()
+        .unwrap_or_else(|_| seal_root.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// SHA-256 of a hooks file's contents, as recorded in the trust store.
+#[must_use]
+pub fn hash_contents(contents: &str) -> String {
+    encode_hex(&Sha256::digest(contents.as_bytes()))
+}
+
+/// What hook filters need to know about a review, folded from its events.
+#[derive(Debug, Clone, Default)]
+pub struct ReviewState {
+    /// Currently requested reviewers
+    pub reviewers: BTreeSet<String>,
+    /// File path of each thread
+    pub thread_files: HashMap<String, String>,
+}
+
+impl ReviewState {
+    /// Fold one event into the state.
+    pub fn apply(&mut self, event: &Event) {
+        match event {
+            Event::ReviewersRequested(e) => self.reviewers.extend(e.reviewers.iter().cloned()),
+            Event::ReviewersUnrequested(e) => {
+                for reviewer in &e.reviewers {
+                    self.reviewers.remove(reviewer);
+                }
+            }
+            Event::ThreadCreated(e) => {
+                self.thread_files
+                    .insert(e.thread_id.clone(), e.file_path.clone());
+            }
+            _ => {}
+        }
+    }
+
+    /// File the event's thread is on, if it's a thread event.
+    #[must_use]
+    pub fn file_for(&self, event: &Event) -> Option<&str> {
+        event_thread_id(event)
+            .and_then(|thread_id| self.thread_files.get(thread_id))
+            .map(String::as_str)
+    }
+}
+
+/// One hook run by [`replay`].
+#[derive(Debug, Clone, Serialize)]
+pub struct HookRun {
+    pub review_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    pub event: String,
+    pub ts: DateTime<Utc>,
+    pub command: String,
+    /// Whether the command succeeded; `None` on a dry run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ok: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Parsed `.seal/hooks.toml`.
+#[derive(Debug, Clone, Default)]
+pub struct Hooks {
+    hooks: Vec<Hook>,
+}
+
+impl Hooks {
+    /// Path of the hooks file for a repository.
+    #[must_use]
+    pub fn path(seal_root: &Path) -> PathBuf {
+        seal_root.join(".seal").join(HOOKS_FILE)
+    }
+
+    /// Load the hooks file, returning no hooks if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(seal_root: &Path) -> Result<Self> {
+        let path = Self::path(seal_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Invalid hooks file {}", path.display()))
+    }
+
+    /// Load the hooks file if the user has trusted its current contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but isn't trusted, or cannot be
+    /// read or parsed.
+    pub fn load_trusted(seal_root: &Path, store: Option<&TrustStore>) -> Result<Self> {
+        let path = Self::path(seal_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let hash = hash_contents(&contents);
+        if !store.map_or(Ok(false), |store| store.is_trusted(seal_root, &hash))? {
+            bail!(
+                "{} is new or has changed. Review it, then run `seal hooks trust`.",
+                path.display()
+            );
+        }
+        Self::parse(&contents).with_context(|| format!("Invalid hooks file {}", path.display()))
+    }
+
+    /// Trust the current contents of the hooks file, so its hooks run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no hooks file, it is invalid, or the
+    /// trust store cannot be written.
+    pub fn trust(seal_root: &Path, store: &TrustStore) -> Result<Self> {
+        let path = Self::path(seal_root);
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let hooks = Self::parse(&contents)
+            .with_context(|| format!("Invalid hooks file {}", path.display()))?;
+        store.trust(seal_root, &hash_contents(&contents))?;
+        Ok(hooks)
+    }
+
+    /// Number of hooks.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Parse hooks from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on invalid TOML, unknown keys or event types, an empty
+    /// command or an invalid path pattern.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let file: HooksFile = toml::from_str(contents)?;
+        let mut hooks = Vec::new();
+
+        for (idx, entry) in file.hooks.into_iter().enumerate() {
+            let number = idx + 1;
+            if entry.command.trim().is_empty() {
+                bail!("hook {number}: command is empty");
+            }
+            if let Some(unknown) = entry
+                .events
+                .iter()
+                .find(|name| Event::parse_name(name).is_none())
+            {
+                bail!("hook {number}: unknown event type '{unknown}'");
+            }
+            let path_matcher = entry
+                .path
+                .as_deref()
+                .map(|pattern| {
+                    let mut builder = GitignoreBuilder::new("");
+                    builder.add_line(None, pattern)?;
+                    builder.build()
+                })
+                .transpose()
+                .with_context(|| format!("hook {number}: invalid path pattern"))?;
+
+            hooks.push(Hook {
+                events: entry.events,
+                author: entry.author,
+                reviewer: entry.reviewer,
+                path: entry.path,
+                command: entry.command,
+                path_matcher,
+            });
+        }
+
+        Ok(Self { hooks })
+    }
+
+    /// Check if there are no hooks.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Hooks that fire for an event, in file order.
+    pub fn matching<'a>(
+        &'a self,
+        envelope: &'a EventEnvelope,
+        review: &'a ReviewState,
+    ) -> impl Iterator<Item = &'a Hook> {
+        self.hooks
+            .iter()
+            .filter(move |hook| hook.matches(envelope, review))
+    }
+}
+
+/// Start hooks for lines just appended to a review log.
+///
+/// Called by [`ReviewLog`] after a successful append. Never fails: an
+/// untrusted or invalid hooks file or a command that can't be started is
+/// logged, since the events are already written.
+pub fn run_after_append(seal_root: &Path, review_id: &str, lines: &str) {
+    if std::env::var_os(HOOK_ENV).is_some() {
+        return;
+    }
+    start_hooks(seal_root, TrustStore::for_user().as_ref(), review_id, lines);
+}
+
+fn start_hooks(seal_root: &Path, store: Option<&TrustStore>, review_id: &str, lines: &str) {
+    if !Hooks::path(seal_root).exists() {
+        return;
+    }
+    let hooks = match Hooks::load_trusted(seal_root, store) {
+        Ok(hooks) if hooks.is_empty() => return,
+        Ok(hooks) => hooks,
+        Err(e) => {
+            tracing::warn!("Hooks not run: {e:#}");
+            return;
+        }
+    };
+
+    // The log now ends with the appended events, so folding all of it gives
+    // the review as of the last of them
+    let mut review = ReviewState::default();
+    match ReviewLog::new(seal_root, review_id).and_then(|log| crate::log::AppendLog::read_all(&log))
+    {
+        Ok(events) => events.iter().for_each(|e| review.apply(&e.event)),
+        Err(e) => tracing::warn!(review_id, "Hook filters may be incomplete: {e:#}"),
+    }
+
+    for line in lines.lines().filter(|line| !line.trim().is_empty()) {
+        let envelope = match EventEnvelope::from_json_line(line) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                tracing::warn!(review_id, "Hooks not run for unparseable event: {e:#}");
+                continue;
+            }
+        };
+        for hook in hooks.matching(&envelope, &review) {
+            if let Err(e) = hook.spawn(seal_root, review_id, line, &envelope, &review) {
+                tracing::warn!(review_id, "{e:#}");
+            }
+        }
+    }
+}
+
+/// Run hooks for every event written at or after `since`, oldest log line
+/// first within each review, as if each had just been appended.
+///
+/// With `dry_run`, reports the hooks that would run without running them.
+/// Failing commands are recorded in the result rather than stopping replay.
+///
+/// # Errors
+///
+/// Returns an error if the hooks file isn't trusted or is invalid, or a
+/// review log can't be read.
+pub fn replay(
+    seal_root: &Path,
+    store: Option<&TrustStore>,
+    since: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<Vec<HookRun>> {
+    let hooks = Hooks::load_trusted(seal_root, store)?;
+    let mut runs = Vec::new();
+    if hooks.is_empty() {
+        return Ok(runs);
+    }
+
+    for review_id in list_review_ids(seal_root)? {
+        let log = ReviewLog::new(seal_root, &review_id)?;
+        let mut review = ReviewState::default();
+        for (idx, line) in log.read_lines_from(0)? {
+            let envelope = match EventEnvelope::from_json_line(&line) {
+                Ok(envelope) => envelope,
+                Err(e) => {
+                    tracing::warn!(review_id, "Skipping line {}: {e:#}", idx + 1);
+                    continue;
+                }
+            };
+            review.apply(&envelope.event);
+            if envelope.ts < since {
+                continue;
+            }
+
+            for hook in hooks.matching(&envelope, &review) {
+                let result =
+                    (!dry_run).then(|| hook.run(seal_root, &review_id, &line, &envelope, &review));
+                runs.push(HookRun {
+                    review_id: review_id.clone(),
+                    event_id: envelope.event_id.clone(),
+                    event: envelope.event.name().to_string(),
+                    ts: envelope.ts,
+                    command: hook.command.clone(),
+                    ok: result.as_ref().map(Result::is_ok),
+                    error: result.and_then(Result::err).map(|e| format!("{e:#}")),
+                });
+            }
+        }
+    }
+
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{CodeSelection, CommentAdded, ReviewersRequested, ThreadCreated};
+    use crate::log::AppendLog;
+    use tempfile::TempDir;
+
+    fn envelope(author: &str, event: Event) -> EventEnvelope {
+        EventEnvelope::new(author, event)
+    }
+
+    fn thread(thread_id: &str, file_path: &str) -> Event {
+        Event::ThreadCreated(ThreadCreated {
+            thread_id: thread_id.to_string(),
+            review_id: "cr-1".to_string(),
+            file_path: file_path.to_string(),
+            selection: CodeSelection::line(1),
+            commit_hash: "abc".to_string(),
+            anchor: None,
+            kind: None,
+        })
+    }
+
+    fn comment(thread_id: &str) -> Event {
+        Event::CommentAdded(CommentAdded {
+            comment_id: format!("{thread_id}.1"),
+            thread_id: thread_id.to_string(),
+            body: "note".to_string(),
+            suggestion: None,
+        })
+    }
+
+    fn write_hooks(root: &Path, contents: &str) {
+        std::fs::create_dir_all(root.join(".seal")).unwrap();
+        std::fs::write(Hooks::path(root), contents).unwrap();
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_config() {
+        assert!(Hooks::parse("").unwrap().is_empty());
+        assert!(Hooks::parse("[[hooks]]\nevents = []\n").is_err());
+        assert!(Hooks::parse("[[hooks]]\ncommand = \"true\"\nwhen = \"x\"\n").is_err());
+        let err = Hooks::parse("[[hooks]]\ncommand = \"true\"\n[[hooks]]\ncommand = \" \"\n")
+            .unwrap_err();
+        assert!(err.to_string().contains("hook 2"));
+        let err =
+            Hooks::parse("[[hooks]]\nevents = [\"CommentAdd\"]\ncommand = \"true\"\n").unwrap_err();
+        assert!(err.to_string().contains("unknown event type 'CommentAdd'"));
+    }
+
+    #[test]
+    fn test_filters() {
+        let hooks = Hooks::parse(
+            r#"
+[[hooks]]
+events = ["comment_added"]
+path = "src/log/"
+reviewer = "storage"
+command = "true"
+"#,
+        )
+        .unwrap();
+        let hook = &hooks.hooks[0];
+
+        let mut review = ReviewState::default();
+        review.apply(&thread("th-1", "src/log/mod.rs"));
+        review.apply(&thread("th-2", "README.md"));
+        let on_log = envelope("alice", comment("th-1"));
+        assert!(!hook.matches(&on_log, &review));
+
+        review.apply(&Event::ReviewersRequested(ReviewersRequested {
+            review_id: "cr-1".to_string(),
+            reviewers: vec!["storage".to_string()],
+        }));
+        assert!(hook.matches(&on_log, &review));
+        assert!(!hook.matches(&envelope("alice", comment("th-2")), &review));
+        assert!(!hook.matches(
+            &envelope("alice", thread("th-1", "src/log/mod.rs")),
+            &review
+        ));
+    }
+
+    /// Wait for a file written by a background hook.
+    fn wait_for(path: &Path) -> String {
+        for _ in 0..100 {
+            if let Ok(contents) = std::fs::read_to_string(path) {
+                if contents.ends_with('\n') {
+                    return contents;
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        panic!("{} was not written", path.display());
+    }
+
+    #[test]
+    fn test_hooks_run_once_trusted() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let store = TrustStore::new(root.join("config").join(TRUST_FILE));
+        write_hooks(
+            root,
+            r#"
+[[hooks]]
+events = ["CommentAdded"]
+command = "cat > comment.json; echo \"$SEAL_EVENT $SEAL_THREAD_ID $SEAL_FILE\" > env.txt"
+
+[[hooks]]
+command = "exit 3"
+"#,
+        );
+
+        let log = ReviewLog::new(root, "cr-1").unwrap();
+        log.append_batch(&[
+            envelope("alice", thread("th-1", "src/lib.rs")),
+            envelope("alice", comment("th-1")),
+        ])
+        .unwrap();
+        let lines = std::fs::read_to_string(log.path()).unwrap();
+
+        // A checked-in hooks file does nothing until it is trusted
+        start_hooks(root, Some(&store), "cr-1", &lines);
+        assert!(Hooks::load_trusted(root, Some(&store)).is_err());
+        assert!(Hooks::load_trusted(root, None).is_err());
+
+        Hooks::trust(root, &store).unwrap();
+        assert_eq!(Hooks::load_trusted(root, Some(&store)).unwrap().len(), 2);
+        start_hooks(root, Some(&store), "cr-1", &lines);
+
+        assert_eq!(
+            wait_for(&root.join("env.txt")),
+            "CommentAdded th-1 src/lib.rs\n"
+        );
+        let stdin = std::fs::read_to_string(root.join("comment.json")).unwrap();
+        let written = EventEnvelope::from_json_line(stdin.trim()).unwrap();
+        assert!(matches!(written.event, Event::CommentAdded(_)));
+        assert!(written.clock.is_some());
+        // The failing hook doesn't undo the events
+        assert_eq!(log.len().unwrap(), 2);
+
+        // Any change has to be trusted again
+        write_hooks(root, "[[hooks]]\ncommand = \"touch pwned\"\n");
+        assert!(Hooks::load_trusted(root, Some(&store)).is_err());
+    }
+
+    #[test]
+    fn test_replay_since() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let log = ReviewLog::new(root, "cr-1").unwrap();
+        let mut old = envelope("alice", thread("th-1", "src/lib.rs"));
+        old.ts = DateTime::UNIX_EPOCH;
+        log.append_batch(&[old, envelope("alice", comment("th-1"))])
+            .unwrap();
+
+        write_hooks(
+            root,
+            r#"
+[[hooks]]
+path = "src/"
+command = "echo \"$SEAL_EVENT\" >> replayed.txt"
+"#,
+        );
+        let store = TrustStore::new(root.join(TRUST_FILE));
+        Hooks::trust(root, &store).unwrap();
+        let since = DateTime::UNIX_EPOCH + chrono::Duration::days(1);
+
+        let planned = replay(root, Some(&store), since, true).unwrap();
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].ok, None);
+        assert!(!root.join("replayed.txt").exists());
+
+        let runs = replay(root, Some(&store), since, false).unwrap();
+        assert_eq!(runs[0].ok, Some(true));
+        assert_eq!(
+            std::fs::read_to_string(root.join("replayed.txt")).unwrap(),
+            "CommentAdded\n"
+        );
+    }
+}
@@ -5,8 +5,12 @@
 //!
 //! This module parses unified diffs and tracks how insertions/deletions shift line numbers.
 
+use std::fmt;
+
 use anyhow::{bail, Result};
+use serde::Serialize;
 
+use crate::events::CodeSelection;
 use crate::scm::ScmRepo;
 
 /// Result of drift detection for a line anchor.
@@ -260,8 +264,11 @@ pub fn calculate_drift(
             hunk.header.old_start + hunk.header.old_count - 1
         };
 
-        if hunk.header.old_start > original_line {
-            // Hunk is entirely after our line - no effect
+        if hunk.header.old_start > original_line
+            || (hunk.header.old_count == 0 && hunk.header.old_start == original_line)
+        {
+            // Hunk is entirely after our line - no effect. A pure addition
+            // inserts after old_start, so it doesn't move old_start itself.
             continue;
         }
 
@@ -329,6 +336,338 @@ pub fn calculate_drift(
     }
 }
 
+/// An inclusive, 1-indexed range of lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LineRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl LineRange {
+    #[must_use]
+    pub const fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    /// Number of lines in the range.
+    #[must_use]
+    pub const fn line_count(&self) -> u32 {
+        self.end - self.start + 1
+    }
+
+    /// Whether `line` falls inside the range.
+    #[must_use]
+    pub const fn contains(&self, line: u32) -> bool {
+        line >= self.start && line <= self.end
+    }
+}
+
+impl From<&CodeSelection> for LineRange {
+    fn from(selection: &CodeSelection) -> Self {
+        Self::new(selection.start_line(), selection.end_line())
+    }
+}
+
+impl fmt::Display for LineRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+/// Overall outcome of drift detection for a selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RangeDriftKind {
+    /// Every line is intact and at the same position.
+    Unchanged,
+    /// Every line is intact but the selection moved.
+    Shifted,
+    /// Some lines were edited, removed, or inserted inside the selection.
+    PartiallyModified,
+    /// Every line was replaced by new content.
+    Modified,
+    /// Every line was removed and nothing replaced them.
+    Deleted,
+}
+
+/// Result of drift detection for a whole selection (single line or range).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RangeDrift {
+    pub kind: RangeDriftKind,
+    /// The selection when the anchor was created.
+    pub original: LineRange,
+    /// Where the selection lives now, including any replacement lines.
+    /// `None` when the whole selection was deleted.
+    pub current: Option<LineRange>,
+    /// Original lines that were edited or removed.
+    pub changed: Vec<LineRange>,
+    /// Number of new lines inserted within the selection.
+    pub added_lines: u32,
+    /// New path of the file, when it was renamed since the original commit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_to: Option<String>,
+}
+
+impl RangeDrift {
+    /// Drift result for a selection that is known to be untouched.
+    #[must_use]
+    pub const fn unchanged(original: LineRange) -> Self {
+        Self {
+            kind: RangeDriftKind::Unchanged,
+            original,
+            current: Some(original),
+            changed: Vec::new(),
+            added_lines: 0,
+            renamed_to: None,
+        }
+    }
+
+    /// Human-readable description, e.g. "lines 12-14 of 10-20 changed".
+    #[must_use]
+    pub fn summary(&self) -> String {
+        match self.kind {
+            RangeDriftKind::Unchanged => "unchanged".to_string(),
+            RangeDriftKind::Shifted => self.current.map_or_else(
+                || "shifted".to_string(),
+                |current| format!("moved from {} to {current}", self.original),
+            ),
+            RangeDriftKind::PartiallyModified => {
+                let mut parts = Vec::new();
+                if !self.changed.is_empty() {
+                    let changed_count: u32 = self.changed.iter().map(LineRange::line_count).sum();
+                    let list: Vec<String> = self.changed.iter().map(ToString::to_string).collect();
+                    parts.push(format!(
+                        "{} {} of {} changed",
+                        if changed_count == 1 { "line" } else { "lines" },
+                        list.join(", "),
+                        self.original
+                    ));
+                }
+                if self.added_lines > 0 {
+                    parts.push(format!(
+                        "{} {} added",
+                        self.added_lines,
+                        if self.added_lines == 1 {
+                            "line"
+                        } else {
+                            "lines"
+                        }
+                    ));
+                }
+                parts.join(", ")
+            }
+            RangeDriftKind::Modified => format!("{} rewritten", self.original),
+            RangeDriftKind::Deleted => format!("{} deleted", self.original),
+        }
+    }
+}
+
+/// Where a single original line ended up while walking the hunks.
+#[derive(Debug, Clone, Copy)]
+enum LineFate {
+    Pending,
+    Kept(u32),
+    Removed,
+}
+
+/// Calculate drift for a whole selection from parsed hunks.
+///
+/// Unlike [`calculate_drift`], which only follows a single line, this tracks
+/// every line of `original`, reports which ones were edited or removed, and
+/// returns the span the selection covers now (including replacement lines).
+#[must_use]
+pub fn range_drift_from_hunks(hunks: &[Hunk], original: LineRange) -> RangeDrift {
+    let mut fates = vec![LineFate::Pending; original.line_count() as usize];
+    let idx = |line: u32| (line - original.start) as usize;
+
+    // Resolve still-pending lines before `limit` by shifting them `offset` lines
+    let settle = |fates: &mut [LineFate], limit: u32, offset: i64| {
+        for line in original.start..=original.end.min(limit.saturating_sub(1)) {
+            let fate = &mut fates[idx(line)];
+            if matches!(fate, LineFate::Pending) {
+                let shifted = (i64::from(line) + offset).max(1);
+                *fate = LineFate::Kept(u32::try_from(shifted).unwrap_or(u32::MAX));
+            }
+        }
+    };
+
+    let mut added_lines = 0u32;
+    let mut added_span: Option<LineRange> = None;
+    let mut offset = 0i64;
+
+    for hunk in hunks {
+        // An empty side's start is the line *before* the change, so
+        // `@@ -4,0 +5,3 @@` inserts after old line 4
+        let mut old_line = hunk.header.old_start + u32::from(hunk.header.old_count == 0);
+        let mut new_line = hunk.header.new_start + u32::from(hunk.header.new_count == 0);
+        settle(&mut fates, old_line, offset);
+
+        // Inside a run of deletions that started within the selection
+        let mut replacing = false;
+
+        for diff_line in &hunk.lines {
+            match diff_line {
+                DiffLine::Context => {
+                    if original.contains(old_line) {
+                        fates[idx(old_line)] = LineFate::Kept(new_line);
+                    }
+                    replacing = false;
+                    old_line += 1;
+                    new_line += 1;
+                }
+                DiffLine::Deleted => {
+                    if original.contains(old_line) {
+                        fates[idx(old_line)] = LineFate::Removed;
+                        replacing = true;
+                    }
+                    old_line += 1;
+                }
+                DiffLine::Added => {
+                    // Lines added between two selected lines, or replacing the
+                    // last selected line, belong to the selection.
+                    let inside = old_line > original.start && old_line <= original.end;
+                    if inside || (replacing && old_line == original.end + 1) {
+                        added_lines += 1;
+                        added_span = Some(added_span.map_or_else(
+                            || LineRange::new(new_line, new_line),
+                            |span| LineRange::new(span.start, new_line),
+                        ));
+                    }
+                    new_line += 1;
+                }
+            }
+        }
+
+        offset = i64::from(new_line) - i64::from(old_line);
+    }
+    settle(&mut fates, u32::MAX, offset);
+
+    let mut changed: Vec<LineRange> = Vec::new();
+    let mut kept: Option<LineRange> = None;
+    for (line, fate) in (original.start..=original.end).zip(&fates) {
+        match *fate {
+            LineFate::Kept(new) => {
+                kept = Some(kept.map_or_else(
+                    || LineRange::new(new, new),
+                    |span| LineRange::new(span.start, new),
+                ));
+            }
+            LineFate::Removed => match changed.last_mut() {
+                Some(last) if last.end + 1 == line => last.end = line,
+                _ => changed.push(LineRange::new(line, line)),
+            },
+            LineFate::Pending => {}
+        }
+    }
+
+    let current = match (kept, added_span) {
+        (Some(a), Some(b)) => Some(LineRange::new(a.start.min(b.start), a.end.max(b.end))),
+        (span, None) | (None, span) => span,
+    };
+
+    let kind = match kept {
+        None if added_lines > 0 => RangeDriftKind::Modified,
+        None => RangeDriftKind::Deleted,
+        Some(_) if !changed.is_empty() || added_lines > 0 => RangeDriftKind::PartiallyModified,
+        Some(_) if current == Some(original) => RangeDriftKind::Unchanged,
+        Some(_) => RangeDriftKind::Shifted,
+    };
+
+    RangeDrift {
+        kind,
+        original,
+        current,
+        changed,
+        added_lines,
+        renamed_to: None,
+    }
+}
+
+/// Calculate drift for a whole selection between two commits.
+///
+/// If `file` was renamed, the selection is followed to the new path and
+/// [`RangeDrift::renamed_to`] is set. See [`range_drift_from_hunks`] for how
+/// the rest of the result is derived.
+///
+/// # Errors
+///
+/// Returns an error if the diff cannot be produced or parsed.
+pub fn calculate_range_drift(
+    repo: &dyn ScmRepo,
+    file: &str,
+    original: LineRange,
+    original_commit: &str,
+    current_commit: &str,
+) -> Result<RangeDrift> {
+    Ok(FileDrift::between(repo, file, original_commit, current_commit)?.range(original))
+}
+
+/// How one file changed between two commits.
+///
+/// Diffs the file once, so every selection in it can be followed without
+/// running another diff.
+#[derive(Debug, Clone)]
+pub struct FileDrift {
+    hunks: Vec<Hunk>,
+    renamed_to: Option<String>,
+}
+
+impl FileDrift {
+    /// Diff `file` between two commits, following it if it was renamed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the diff cannot be produced or parsed.
+    pub fn between(
+        repo: &dyn ScmRepo,
+        file: &str,
+        original_commit: &str,
+        current_commit: &str,
+    ) -> Result<Self> {
+        let mut diff = repo.diff_git_file(original_commit, current_commit, file)?;
+
+        // A rename looks like a deletion of the old path; diff against the new one
+        let mut renamed_to = None;
+        if is_file_deletion(&diff) {
+            if let Some(new_path) = repo.renamed_path(original_commit, current_commit, file)? {
+                diff =
+                    repo.diff_git_renamed_file(original_commit, current_commit, file, &new_path)?;
+                renamed_to = Some(new_path);
+            }
+        }
+
+        let hunks = if diff.trim().is_empty() {
+            Vec::new()
+        } else {
+            parse_hunks(&diff)?
+        };
+        Ok(Self { hunks, renamed_to })
+    }
+
+    /// Where `original` lives in the newer commit.
+    #[must_use]
+    pub fn range(&self, original: LineRange) -> RangeDrift {
+        let mut drift = if self.hunks.is_empty() {
+            RangeDrift::unchanged(original)
+        } else {
+            range_drift_from_hunks(&self.hunks, original)
+        };
+        drift.renamed_to.clone_from(&self.renamed_to);
+        drift
+    }
+}
+
+/// Whether a single-file git diff removes the file entirely.
+fn is_file_deletion(diff: &str) -> bool {
+    diff.lines()
+        .take_while(|line| !line.starts_with("@@"))
+        .any(|line| line.starts_with("deleted file mode"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -731,4 +1070,161 @@ new file mode 100644
         // So hunk_old_end = 5 - 1 = 4
         // This means the hunk is "before" lines >= 5, so lines at 5+ shift by +3
     }
+
+    /// Parse hunks from a diff body, adding the file headers.
+    fn hunks_for(diff_body: &str) -> Vec<Hunk> {
+        parse_hunks(&format!("--- a/f.rs\n+++ b/f.rs\n{diff_body}")).unwrap()
+    }
+
+    #[test]
+    fn test_range_drift_unchanged_when_hunk_is_after() {
+        let hunks = hunks_for("@@ -30,0 +31 @@ l30\n+b\n");
+        let drift = range_drift_from_hunks(&hunks, LineRange::new(10, 20));
+        assert_eq!(drift, RangeDrift::unchanged(LineRange::new(10, 20)));
+    }
+
+    #[test]
+    fn test_range_drift_shifted_by_insertion_above() {
+        let hunks = hunks_for("@@ -1,0 +2,2 @@ l1\n+x\n+y\n");
+        let drift = range_drift_from_hunks(&hunks, LineRange::new(10, 20));
+        assert_eq!(drift.kind, RangeDriftKind::Shifted);
+        assert_eq!(drift.current, Some(LineRange::new(12, 22)));
+        assert!(drift.changed.is_empty());
+        assert_eq!(drift.summary(), "moved from 10-20 to 12-22");
+    }
+
+    #[test]
+    fn test_range_drift_partial_modification() {
+        // Lines 12-14 of 10-20 rewritten as two lines
+        let hunks = hunks_for("@@ -12,3 +12,2 @@ l11\n-l12\n-l13\n-l14\n+n12\n+n13\n");
+        let drift = range_drift_from_hunks(&hunks, LineRange::new(10, 20));
+        assert_eq!(drift.kind, RangeDriftKind::PartiallyModified);
+        assert_eq!(drift.changed, vec![LineRange::new(12, 14)]);
+        assert_eq!(drift.added_lines, 2);
+        assert_eq!(drift.current, Some(LineRange::new(10, 19)));
+        assert_eq!(
+            drift.summary(),
+            "lines 12-14 of 10-20 changed, 2 lines added"
+        );
+    }
+
+    #[test]
+    fn test_range_drift_insertion_inside_range() {
+        let hunks = hunks_for("@@ -4,0 +5 @@ l4\n+new\n");
+        let drift = range_drift_from_hunks(&hunks, LineRange::new(3, 6));
+        assert_eq!(drift.kind, RangeDriftKind::PartiallyModified);
+        assert!(drift.changed.is_empty());
+        assert_eq!(drift.current, Some(LineRange::new(3, 7)));
+        assert_eq!(drift.summary(), "1 line added");
+    }
+
+    #[test]
+    fn test_range_drift_insertion_at_edges_not_included() {
+        let hunks = hunks_for("@@ -2,0 +3 @@ l2\n+above\n@@ -4,0 +6 @@ l4\n+below\n");
+        let drift = range_drift_from_hunks(&hunks, LineRange::new(3, 4));
+        assert_eq!(drift.kind, RangeDriftKind::Shifted);
+        assert_eq!(drift.current, Some(LineRange::new(4, 5)));
+    }
+
+    #[test]
+    fn test_range_drift_replaced_last_line_extends_span() {
+        let hunks = hunks_for("@@ -4 +4,2 @@ l3\n-l4\n+n4a\n+n4b\n");
+        let drift = range_drift_from_hunks(&hunks, LineRange::new(3, 4));
+        assert_eq!(drift.kind, RangeDriftKind::PartiallyModified);
+        assert_eq!(drift.changed, vec![LineRange::new(4, 4)]);
+        assert_eq!(drift.current, Some(LineRange::new(3, 5)));
+        assert_eq!(drift.summary(), "line 4 of 3-4 changed, 2 lines added");
+    }
+
+    #[test]
+    fn test_range_drift_fully_rewritten() {
+        let hunks = hunks_for("@@ -2,2 +2 @@ l1\n-l2\n-l3\n+n2\n");
+        let drift = range_drift_from_hunks(&hunks, LineRange::new(2, 3));
+        assert_eq!(drift.kind, RangeDriftKind::Modified);
+        assert_eq!(drift.changed, vec![LineRange::new(2, 3)]);
+        assert_eq!(drift.current, Some(LineRange::new(2, 2)));
+    }
+
+    #[test]
+    fn test_range_drift_deleted() {
+        let hunks = hunks_for("@@ -2,2 +1,0 @@ l1\n-l2\n-l3\n");
+        let drift = range_drift_from_hunks(&hunks, LineRange::new(2, 3));
+        assert_eq!(drift.kind, RangeDriftKind::Deleted);
+        assert_eq!(drift.current, None);
+        assert_eq!(drift.summary(), "2-3 deleted");
+
+        // Lines below the deletion move up by its size
+        let below = range_drift_from_hunks(&hunks, LineRange::new(5, 5));
+        assert_eq!(below.current, Some(LineRange::new(3, 3)));
+    }
+
+    #[test]
+    fn test_is_file_deletion() {
+        let deleted = "diff --git a/f.rs b/f.rs\ndeleted file mode 100644\n--- a/f.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n";
+        assert!(is_file_deletion(deleted));
+        assert!(!is_file_deletion(
+            "--- a/f.rs\n+++ b/f.rs\n@@ -1 +1 @@\n-deleted file mode\n+y\n"
+        ));
+    }
+
+    #[test]
+    fn test_range_drift_pure_insertion_goes_after_old_start() {
+        // `git diff -U0` output for three lines inserted after line 4
+        let hunks = hunks_for("@@ -4,0 +5,3 @@ l4\n+a\n+b\n+c\n");
+        let at = range_drift_from_hunks(&hunks, LineRange::new(4, 4));
+        assert_eq!(at, RangeDrift::unchanged(LineRange::new(4, 4)));
+        let below = range_drift_from_hunks(&hunks, LineRange::new(5, 5));
+        assert_eq!(below.kind, RangeDriftKind::Shifted);
+        assert_eq!(below.current, Some(LineRange::new(8, 8)));
+    }
+
+    #[test]
+    fn test_range_drift_matches_calculate_drift_on_insertion() {
+        use crate::scm::git::GitRepo;
+        use std::process::Command;
+
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .current_dir(dir.path())
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?} failed");
+            String::from_utf8(output.stdout).unwrap().trim().to_string()
+        };
+        let lines: Vec<String> = (1..=8).map(|i| format!("l{i}")).collect();
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test User"]);
+        std::fs::write(dir.path().join("f.rs"), lines.join("\n") + "\n").unwrap();
+        git(&["add", "f.rs"]);
+        git(&["commit", "-qm", "initial"]);
+        let before = git(&["rev-parse", "HEAD"]);
+        let mut inserted = lines.clone();
+        inserted.splice(4..4, ["a", "b", "c"].map(String::from));
+        std::fs::write(dir.path().join("f.rs"), inserted.join("\n") + "\n").unwrap();
+        git(&["commit", "-qam", "insert"]);
+        let after = git(&["rev-parse", "HEAD"]);
+
+        let repo = GitRepo::new(dir.path().to_path_buf());
+        let unified = git(&["diff", "-U0", &before, &after, "--", "f.rs"]);
+        let hunks = parse_hunks(&unified).unwrap();
+        assert_eq!(hunks[0].header.old_start, 4);
+        assert_eq!(hunks[0].header.old_count, 0);
+        let file = FileDrift::between(&repo, "f.rs", &before, &after).unwrap();
+        for line in 1..=8 {
+            let expected = calculate_drift(&repo, "f.rs", line, &before, &after)
+                .unwrap()
+                .current_line();
+            let range = LineRange::new(line, line);
+            let from_hunks = range_drift_from_hunks(&hunks, range).current;
+            assert_eq!(from_hunks.map(|r| r.start), expected, "line {line} (-U0)");
+            assert_eq!(
+                file.range(range).current.map(|r| r.start),
+                expected,
+                "line {line}"
+            );
+        }
+    }
 }
@@ -4,15 +4,22 @@
 
 pub mod context;
 pub mod drift;
+pub mod reanchor;
 
 pub use context::{extract_context, format_context, CodeContext, ContextLine};
-pub use drift::{calculate_drift, DriftResult};
+pub use drift::{
+    calculate_drift, calculate_range_drift, DriftResult, FileDrift, LineRange, RangeDrift,
+    RangeDriftKind,
+};
+pub use reanchor::{capture_anchor, reanchor, reanchor_thread, Reanchor};
 
 use anyhow::{bail, Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::scm::FileRename;
+
 /// Resolve the local jj repo/workspace root from any path within the repo.
 ///
 /// This returns the directory containing `.jj/`, WITHOUT following workspace pointers.
@@ -316,6 +323,29 @@ impl JjRepo {
             .with_context(|| format!("Failed to get diff for file {file} from {from} to {to}"))
     }
 
+    /// Get a git-format diff for a file that was renamed between two revisions.
+    ///
+    /// Passing both paths lets jj pair the deletion and addition as a rename,
+    /// so hunks are relative to the old file's contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the jj command fails or the revsets/files are invalid.
+    pub fn diff_git_renamed_file(
+        &self,
+        from: &str,
+        to: &str,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<String> {
+        self.run_jj(&[
+            "diff", "--from", from, "--to", to, "--git", old_path, new_path,
+        ])
+        .with_context(|| {
+            format!("Failed to get diff for {old_path} -> {new_path} from {from} to {to}")
+        })
+    }
+
     /// Check if a file exists at a given revision.
     ///
     /// Note: jj file list returns exit code 0 even for non-existent files,
@@ -375,6 +405,63 @@ impl JjRepo {
             .map(ToString::to_string)
             .collect())
     }
+
+    /// List the changes in a revset, oldest first.
+    ///
+    /// Each line is `change_id\tcommit_id\tparent commit ids\tfirst description line`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the jj command fails or the revset is invalid.
+    pub fn log_stack(&self, revset: &str) -> Result<String> {
+        const TEMPLATE: &str = r#"change_id ++ "\t" ++ commit_id ++ "\t" ++ parents.map(|c| c.commit_id()).join(" ") ++ "\t" ++ description.first_line() ++ "\n""#;
+        self.run_jj(&[
+            "log",
+            "-r",
+            revset,
+            "--no-graph",
+            "--reversed",
+            "-T",
+            TEMPLATE,
+        ])
+        .with_context(|| format!("Failed to list changes in {revset}"))
+    }
+
+    /// List files renamed between two revisions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the jj command fails or the revsets are invalid.
+    pub fn renames_between(&self, from: &str, to: &str) -> Result<Vec<FileRename>> {
+        let output = self
+            .run_jj(&["diff", "--from", from, "--to", to, "--summary"])
+            .with_context(|| format!("Failed to list renamed files from {from} to {to}"))?;
+
+        Ok(output
+            .lines()
+            .filter_map(|line| line.strip_prefix("R "))
+            .filter_map(parse_summary_rename)
+            .collect())
+    }
+}
+
+/// Parse the path part of a `jj diff --summary` rename line.
+///
+/// jj factors out the common prefix and suffix, e.g. `src/{old.rs => new.rs}`
+/// or `{a => b}/mod.rs`.
+fn parse_summary_rename(paths: &str) -> Option<FileRename> {
+    let open = paths.find('{')?;
+    let close = paths.rfind('}')?;
+    let (old, new) = paths.get(open + 1..close)?.split_once(" => ")?;
+    let prefix = &paths[..open];
+    let suffix = &paths[close + 1..];
+
+    // An empty side (`src/{ => sub}/a.rs`) leaves a doubled separator
+    let join = |middle: &str| format!("{prefix}{middle}{suffix}").replace("//", "/");
+    Some(FileRename {
+        old_path: join(old),
+        new_path: join(new),
+    })
 }
 
 #[cfg(test)]
@@ -521,4 +608,26 @@ mod tests {
             .to_string()
             .contains("Not in a jj repository"));
     }
+
+    #[test]
+    fn test_parse_summary_rename() {
+        let rename = |old: &str, new: &str| FileRename {
+            old_path: old.to_string(),
+            new_path: new.to_string(),
+        };
+
+        assert_eq!(
+            parse_summary_rename("src/{old.rs => new.rs}"),
+            Some(rename("src/old.rs", "src/new.rs"))
+        );
+        assert_eq!(
+            parse_summary_rename("{a => b}/mod.rs"),
+            Some(rename("a/mod.rs", "b/mod.rs"))
+        );
+        assert_eq!(
+            parse_summary_rename("src/{ => nested}/lib.rs"),
+            Some(rename("src/lib.rs", "src/nested/lib.rs"))
+        );
+        assert_eq!(parse_summary_rename("src/plain.rs"), None);
+    }
 }
@@ -0,0 +1,335 @@
+//! Fuzzy re-anchoring of threads whose anchored lines were edited.
+//!
+//! `calculate_drift` follows a line exactly through a diff and gives up once the
+//! anchored line itself changes. This module uses the [`AnchorSnapshot`] stored
+//! at thread creation (the selected text plus a few surrounding lines) to find
+//! the most similar location in the current file, and reports how confident
+//! that match is.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::events::{AnchorSnapshot, CodeSelection};
+use crate::scm::ScmRepo;
+
+/// Number of lines captured above and below the selection.
+pub const ANCHOR_CONTEXT_LINES: usize = 3;
+
+/// Matches scoring below this are not reported as relocations.
+pub const MIN_REANCHOR_CONFIDENCE: f64 = 0.6;
+
+/// Weight of the selected lines vs. surrounding context in the match score.
+const ANCHOR_WEIGHT: f64 = 0.7;
+
+/// A relocated anchor in the current version of a file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Reanchor {
+    /// New start line (1-based).
+    pub start_line: u32,
+    /// New end line (1-based, inclusive).
+    pub end_line: u32,
+    /// Similarity between the snapshot and the new location, in `0.0..=1.0`.
+    pub confidence: f64,
+}
+
+/// Build an anchor snapshot from file contents for a 1-based inclusive range.
+///
+/// Returns `None` if the range is empty or out of bounds.
+#[must_use]
+pub fn snapshot_from_contents(contents: &str, start: u32, end: u32) -> Option<AnchorSnapshot> {
+    let file_lines: Vec<&str> = contents.lines().collect();
+    if start == 0 || end < start || end as usize > file_lines.len() {
+        return None;
+    }
+
+    let start_idx = start as usize - 1;
+    let end_idx = end as usize;
+    let before_idx = start_idx.saturating_sub(ANCHOR_CONTEXT_LINES);
+    let after_idx = (end_idx + ANCHOR_CONTEXT_LINES).min(file_lines.len());
+
+    let to_owned = |lines: &[&str]| lines.iter().map(|l| (*l).to_string()).collect();
+    Some(AnchorSnapshot {
+        lines: to_owned(&file_lines[start_idx..end_idx]),
+        before: to_owned(&file_lines[before_idx..start_idx]),
+        after: to_owned(&file_lines[end_idx..after_idx]),
+    })
+}
+
+/// Capture an anchor snapshot for a selection at a given commit.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read at `commit`.
+pub fn capture_anchor(
+    repo: &dyn ScmRepo,
+    file: &str,
+    commit: &str,
+    selection: &CodeSelection,
+) -> Result<Option<AnchorSnapshot>> {
+    let contents = repo.show_file(commit, file)?;
+    Ok(snapshot_from_contents(
+        &contents,
+        selection.start_line(),
+        selection.end_line(),
+    ))
+}
+
+/// Relocate a thread's selection in `current_commit` using fuzzy matching.
+///
+/// Uses the stored `anchor` snapshot when present; threads created before
+/// snapshots existed fall back to a snapshot taken from `original_commit`.
+/// `current_file` is where the file lives in `current_commit` (it differs
+/// from `file` when the file was renamed).
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read at either commit.
+pub fn reanchor_thread(
+    repo: &dyn ScmRepo,
+    file: &str,
+    current_file: &str,
+    selection: &CodeSelection,
+    anchor: Option<&AnchorSnapshot>,
+    original_commit: &str,
+    current_commit: &str,
+) -> Result<Option<Reanchor>> {
+    let snapshot = match anchor {
+        Some(snapshot) => snapshot.clone(),
+        None => match capture_anchor(repo, file, original_commit, selection)? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        },
+    };
+
+    let contents = repo.show_file(current_commit, current_file)?;
+    Ok(reanchor(&snapshot, &contents, selection.start_line()))
+}
+
+/// Find the location in `contents` that best matches `snapshot`.
+///
+/// `hint_line` is where the anchor is expected to be (usually the original
+/// line); it only breaks ties between equally good matches. Returns `None`
+/// when no location reaches [`MIN_REANCHOR_CONFIDENCE`].
+#[must_use]
+pub fn reanchor(snapshot: &AnchorSnapshot, contents: &str, hint_line: u32) -> Option<Reanchor> {
+    let anchor_len = snapshot.lines.len();
+    let current: Vec<LineFingerprint> = contents.lines().map(LineFingerprint::new).collect();
+    if anchor_len == 0 || current.len() < anchor_len {
+        return None;
+    }
+
+    let anchor: Vec<LineFingerprint> = snapshot
+        .lines
+        .iter()
+        .map(|l| LineFingerprint::new(l))
+        .collect();
+    let before: Vec<LineFingerprint> = snapshot
+        .before
+        .iter()
+        .map(|l| LineFingerprint::new(l))
+        .collect();
+    let after: Vec<LineFingerprint> = snapshot
+        .after
+        .iter()
+        .map(|l| LineFingerprint::new(l))
+        .collect();
+
+    let mut best: Option<(f64, usize)> = None;
+    let hint_idx = hint_line.saturating_sub(1) as usize;
+
+    for start in 0..=current.len() - anchor_len {
+        let anchor_score = mean(
+            anchor
+                .iter()
+                .enumerate()
+                .map(|(i, line)| line.similarity(&current[start + i])),
+        );
+
+        let context_scores = before
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, line)| {
+                start
+                    .checked_sub(i + 1)
+                    .map_or(0.0, |idx| line.similarity(&current[idx]))
+            })
+            .chain(after.iter().enumerate().map(|(i, line)| {
+                current
+                    .get(start + anchor_len + i)
+                    .map_or(0.0, |other| line.similarity(other))
+            }));
+
+        let score = if before.is_empty() && after.is_empty() {
+            anchor_score
+        } else {
+            ANCHOR_WEIGHT.mul_add(anchor_score, (1.0 - ANCHOR_WEIGHT) * mean(context_scores))
+        };
+
+        let better = match best {
+            None => true,
+            Some((best_score, best_start)) => {
+                score > best_score + f64::EPSILON
+                    || ((score - best_score).abs() <= f64::EPSILON
+                        && start.abs_diff(hint_idx) < best_start.abs_diff(hint_idx))
+            }
+        };
+        if better {
+            best = Some((score, start));
+        }
+    }
+
+    let (score, start) = best?;
+    if score < MIN_REANCHOR_CONFIDENCE {
+        return None;
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    Some(Reanchor {
+        start_line: start as u32 + 1,
+        end_line: (start + anchor_len) as u32,
+        confidence: (score * 100.0).round() / 100.0,
+    })
+}
+
+/// Precomputed comparison data for a single line.
+struct LineFingerprint {
+    trimmed: String,
+    bigrams: HashSet<(char, char)>,
+}
+
+impl LineFingerprint {
+    fn new(line: &str) -> Self {
+        // Whitespace changes (reindentation, reflowing) shouldn't lower the score.
+        let trimmed: String = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        let chars: Vec<char> = trimmed.chars().collect();
+        let bigrams = chars.windows(2).map(|w| (w[0], w[1])).collect();
+        Self { trimmed, bigrams }
+    }
+
+    /// Dice coefficient over character bigrams (1.0 for identical lines).
+    #[allow(clippy::cast_precision_loss)]
+    fn similarity(&self, other: &Self) -> f64 {
+        if self.trimmed == other.trimmed {
+            return 1.0;
+        }
+        if self.bigrams.is_empty() || other.bigrams.is_empty() {
+            return 0.0;
+        }
+        let shared = self.bigrams.intersection(&other.bigrams).count();
+        (2 * shared) as f64 / (self.bigrams.len() + other.bigrams.len()) as f64
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = "\
+use std::io;
+
+fn main() {
+    let config = load_config();
+    let result = process(config, 42);
+    println!(\"{result}\");
+}
+
+fn helper() {}
+";
+
+    #[test]
+    fn test_snapshot_from_contents_captures_context() {
+        let snap = snapshot_from_contents(ORIGINAL, 5, 5).unwrap();
+        assert_eq!(snap.lines, vec!["    let result = process(config, 42);"]);
+        assert_eq!(snap.before.len(), 3);
+        assert_eq!(snap.before[2], "    let config = load_config();");
+        assert_eq!(snap.after[0], "    println!(\"{result}\");");
+    }
+
+    #[test]
+    fn test_snapshot_from_contents_clamps_at_file_edges() {
+        let snap = snapshot_from_contents(ORIGINAL, 1, 2).unwrap();
+        assert!(snap.before.is_empty());
+        assert_eq!(snap.lines.len(), 2);
+        assert_eq!(snap.after.len(), 3);
+    }
+
+    #[test]
+    fn test_snapshot_from_contents_out_of_bounds() {
+        assert!(snapshot_from_contents(ORIGINAL, 0, 1).is_none());
+        assert!(snapshot_from_contents(ORIGINAL, 5, 4).is_none());
+        assert!(snapshot_from_contents(ORIGINAL, 9, 50).is_none());
+    }
+
+    #[test]
+    fn test_reanchor_exact_match_after_shift() {
+        let snap = snapshot_from_contents(ORIGINAL, 5, 5).unwrap();
+        let current = format!("// header\n// more\n{ORIGINAL}");
+
+        let found = reanchor(&snap, &current, 5).unwrap();
+        assert_eq!(found.start_line, 7);
+        assert_eq!(found.end_line, 7);
+        assert!((found.confidence - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_reanchor_edited_line_lower_confidence() {
+        let snap = snapshot_from_contents(ORIGINAL, 5, 5).unwrap();
+        let current = ORIGINAL.replace("process(config, 42)", "process(config, 43)");
+
+        let found = reanchor(&snap, &current, 5).unwrap();
+        assert_eq!(found.start_line, 5);
+        assert!(found.confidence < 1.0);
+        assert!(found.confidence >= MIN_REANCHOR_CONFIDENCE);
+    }
+
+    #[test]
+    fn test_reanchor_moved_block() {
+        let snap = snapshot_from_contents(ORIGINAL, 4, 6).unwrap();
+        let current = "\
+fn helper() {}
+
+use std::io;
+
+fn main() {
+    let config = load_config();
+    let result = process(config, 42);
+    println!(\"{result}\");
+}
+";
+        let found = reanchor(&snap, current, 4).unwrap();
+        assert_eq!(found.start_line, 6);
+        assert_eq!(found.end_line, 8);
+    }
+
+    #[test]
+    fn test_reanchor_no_match() {
+        let snap = snapshot_from_contents(ORIGINAL, 5, 5).unwrap();
+        let current = "completely\ndifferent\ncontent\nhere\n";
+        assert!(reanchor(&snap, current, 5).is_none());
+    }
+
+    #[test]
+    fn test_reanchor_prefers_location_near_hint() {
+        let snap = AnchorSnapshot {
+            lines: vec!["}".to_string()],
+            before: Vec::new(),
+            after: Vec::new(),
+        };
+        let current = "fn a() {\n}\nfn b() {\n}\n";
+        assert_eq!(reanchor(&snap, current, 4).unwrap().start_line, 4);
+        assert_eq!(reanchor(&snap, current, 1).unwrap().start_line, 2);
+    }
+}
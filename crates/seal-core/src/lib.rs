@@ -4,10 +4,14 @@
 //! SCM abstraction, and shared domain types.
 
 pub mod core;
+pub mod drafts;
 pub mod sealignore;
 pub mod events;
+pub mod hooks;
 pub mod jj;
 pub mod log;
+pub mod owners;
 pub mod projection;
 pub mod scm;
+pub mod signing;
 pub mod version;
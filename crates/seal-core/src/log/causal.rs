@@ -0,0 +1,358 @@
+//! Causal ordering of review log events.
+//!
+//! Wall-clock timestamps can't order events from agents with skewed clocks,
+//! so each event appended to a review log records a Lamport `clock` and its
+//! `parents`: the events at the head of the log when it was written. Sorting
+//! by clock respects causality; the parent links tell genuinely concurrent
+//! events (written on different branches or clones) apart from ordered ones.
+//!
+//! Events written before clocks existed have neither; they keep their place
+//! in the file relative to clocked events.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::events::{new_event_id, Event, EventEnvelope};
+
+/// Position of an event in causal order: clock, then timestamp, then event
+/// ID so every clone orders concurrent events the same way.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CausalKey {
+    clock: u64,
+    /// Unclocked events sort after the clocked event they followed
+    unclocked: bool,
+    ts: DateTime<Utc>,
+    event_id: Option<String>,
+}
+
+/// Causal keys for one log's events, given in file order.
+///
+/// An event without a clock takes the highest clock before it in the file.
+#[must_use]
+pub fn causal_keys(events: &[EventEnvelope]) -> Vec<CausalKey> {
+    let mut last_clock = 0;
+    events
+        .iter()
+        .map(|event| {
+            if let Some(clock) = event.clock {
+                last_clock = last_clock.max(clock);
+            }
+            CausalKey {
+                clock: event.clock.unwrap_or(last_clock),
+                unclocked: event.clock.is_none(),
+                ts: event.ts,
+                event_id: event.event_id.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Sort one log's events, given in file order, into causal order.
+pub fn sort_causally(events: &mut Vec<EventEnvelope>) {
+    let mut keyed: Vec<(CausalKey, EventEnvelope)> = causal_keys(events)
+        .into_iter()
+        .zip(events.drain(..))
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    events.extend(keyed.into_iter().map(|(_, event)| event));
+}
+
+/// The head of a review log: its highest clock and the events no other
+/// event lists as a parent.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Frontier {
+    clock: u64,
+    heads: Vec<String>,
+}
+
+impl Frontier {
+    /// Frontier of a log's events, in file order. Legacy events without a
+    /// clock don't count.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn of<'a>(events: impl IntoIterator<Item = &'a EventEnvelope>) -> Self {
+        let mut frontier = Self::default();
+        for event in events {
+            frontier.observe(event);
+        }
+        frontier
+    }
+
+    /// Advance past an event that follows the frontier's events in the log.
+    ///
+    /// Logs list parents before their children, so an event observed here
+    /// can't already be some other event's parent.
+    pub(crate) fn observe(&mut self, event: &EventEnvelope) {
+        let (Some(clock), Some(id)) = (event.clock, &event.event_id) else {
+            return;
+        };
+        self.clock = self.clock.max(clock);
+        self.heads.retain(|head| !event.parents.contains(head));
+        if !self.heads.contains(id) {
+            self.heads.push(id.clone());
+        }
+    }
+
+    /// Stamp an event being appended after the frontier, and advance past it.
+    pub(crate) fn stamp(&mut self, event: &mut EventEnvelope) {
+        let id = event.event_id.get_or_insert_with(new_event_id).clone();
+        self.clock += 1;
+        event.clock = Some(self.clock);
+        event.parents = std::mem::replace(&mut self.heads, vec![id]);
+    }
+}
+
+/// Two concurrent events that set conflicting state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcurrentConflict {
+    /// Event IDs of the two events.
+    pub event_ids: (String, String),
+    /// What the events disagree about.
+    pub detail: String,
+}
+
+/// Find pairs of concurrent events that set conflicting state: resolving and
+/// reopening the same thread, or one reviewer voting LGTM and block.
+///
+/// Only pairs involving at least one event in `new_ids` are reported, so a
+/// conflict isn't reported again every time its log grows.
+#[must_use]
+pub fn concurrent_conflicts<S: BuildHasher>(
+    events: &[EventEnvelope],
+    new_ids: &HashSet<&str, S>,
+) -> Vec<ConcurrentConflict> {
+    let graph = CausalGraph::new(events);
+
+    // Events that set some state, grouped by what they set
+    let mut by_subject: HashMap<String, Vec<(&EventEnvelope, String)>> = HashMap::new();
+    for event in events {
+        if let Some((subject, value)) = state_change(event) {
+            by_subject.entry(subject).or_default().push((event, value));
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    for (subject, changes) in &by_subject {
+        for (i, (a, a_value)) in changes.iter().enumerate() {
+            for (b, b_value) in &changes[i + 1..] {
+                if a_value == b_value {
+                    continue;
+                }
+                let (Some(a_id), Some(b_id)) = (a.event_id.as_deref(), b.event_id.as_deref())
+                else {
+                    continue;
+                };
+                if !new_ids.contains(a_id) && !new_ids.contains(b_id) {
+                    continue;
+                }
+                if graph.concurrent(a, b) {
+                    conflicts.push(ConcurrentConflict {
+                        event_ids: (a_id.to_string(), b_id.to_string()),
+                        detail: format!(
+                            "{subject}: {a_value} by {} and {b_value} by {} concurrently",
+                            a.author, b.author
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    conflicts.sort_by(|a, b| a.detail.cmp(&b.detail));
+    conflicts
+}
+
+/// The state an event sets, as (subject, value). Events with the same
+/// subject and different values conflict.
+fn state_change(event: &EventEnvelope) -> Option<(String, String)> {
+    match &event.event {
+        Event::ThreadResolved(e) => Some((format!("thread {}", e.thread_id), "resolve".into())),
+        Event::ThreadReopened(e) => Some((format!("thread {}", e.thread_id), "reopen".into())),
+        Event::ReviewerVoted(e) => Some((
+            format!("{}'s vote on {}", event.author, e.review_id),
+            e.vote.to_string(),
+        )),
+        _ => None,
+    }
+}
+
+/// Parent links between clocked events.
+struct CausalGraph<'a> {
+    parents: HashMap<&'a str, &'a [String]>,
+    clocks: HashMap<&'a str, u64>,
+}
+
+impl<'a> CausalGraph<'a> {
+    fn new(events: &'a [EventEnvelope]) -> Self {
+        let mut parents = HashMap::new();
+        let mut clocks = HashMap::new();
+        for event in events {
+            if let (Some(id), Some(clock)) = (event.event_id.as_deref(), event.clock) {
+                parents.insert(id, event.parents.as_slice());
+                clocks.insert(id, clock);
+            }
+        }
+        Self { parents, clocks }
+    }
+
+    /// Neither event was written by an agent that had seen the other.
+    /// Events without a clock can't be compared and are never concurrent.
+    fn concurrent(&self, a: &EventEnvelope, b: &EventEnvelope) -> bool {
+        let (Some(a_id), Some(b_id)) = (a.event_id.as_deref(), b.event_id.as_deref()) else {
+            return false;
+        };
+        if a.clock.is_none() || b.clock.is_none() {
+            return false;
+        }
+        !self.happened_before(a_id, b_id) && !self.happened_before(b_id, a_id)
+    }
+
+    /// Whether `ancestor` is reachable from `descendant` through parents.
+    fn happened_before(&self, ancestor: &str, descendant: &str) -> bool {
+        let Some(&ancestor_clock) = self.clocks.get(ancestor) else {
+            return false;
+        };
+        let mut stack = vec![descendant];
+        let mut visited = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            for parent in self.parents.get(id).copied().unwrap_or_default() {
+                if parent == ancestor {
+                    return true;
+                }
+                // Clocks only grow along parent links, so lower clocks can't lead back
+                if self
+                    .clocks
+                    .get(parent.as_str())
+                    .is_some_and(|&c| c > ancestor_clock)
+                {
+                    stack.push(parent);
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{ThreadReopened, ThreadResolved};
+
+    fn resolve(author: &str) -> EventEnvelope {
+        EventEnvelope::new(
+            author,
+            Event::ThreadResolved(ThreadResolved {
+                thread_id: "th-1".to_string(),
+                reason: None,
+            }),
+        )
+    }
+
+    fn reopen(author: &str) -> EventEnvelope {
+        EventEnvelope::new(
+            author,
+            Event::ThreadReopened(ThreadReopened {
+                thread_id: "th-1".to_string(),
+                reason: None,
+            }),
+        )
+    }
+
+    fn ids(events: &[EventEnvelope]) -> HashSet<&str> {
+        events
+            .iter()
+            .filter_map(|e| e.event_id.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_stamp_chains_clocks_and_parents() {
+        let mut frontier = Frontier::default();
+        let mut first = resolve("alice");
+        let mut second = reopen("bob");
+        frontier.stamp(&mut first);
+        frontier.stamp(&mut second);
+
+        assert_eq!(first.clock, Some(1));
+        assert!(first.parents.is_empty());
+        assert_eq!(second.clock, Some(2));
+        assert_eq!(second.parents, vec![first.event_id.clone().unwrap()]);
+
+        // The frontier of the log is its last event
+        let log = [first, second.clone()];
+        let mut next = resolve("alice");
+        Frontier::of(&log).stamp(&mut next);
+        assert_eq!(next.clock, Some(3));
+        assert_eq!(next.parents, vec![second.event_id.unwrap()]);
+    }
+
+    #[test]
+    fn test_sort_causally_ignores_skewed_timestamps() {
+        let mut frontier = Frontier::default();
+        let mut first = resolve("alice");
+        let mut second = reopen("bob");
+        frontier.stamp(&mut first);
+        frontier.stamp(&mut second);
+        // Bob's clock runs an hour behind
+        second.ts = first.ts - chrono::Duration::hours(1);
+
+        let mut events = vec![second.clone(), first.clone()];
+        sort_causally(&mut events);
+        assert_eq!(events[0].event_id, first.event_id);
+
+        // An unclocked event written after them stays after them
+        let mut legacy = resolve("old-seal");
+        legacy.event_id = None;
+        legacy.ts = second.ts - chrono::Duration::hours(1);
+        let mut events = vec![first.clone(), second.clone(), legacy];
+        sort_causally(&mut events);
+        assert_eq!(events[1].event_id, second.event_id);
+        assert_eq!(events[2].event_id, None);
+    }
+
+    #[test]
+    fn test_concurrent_resolve_and_reopen_conflict() {
+        let mut base = Frontier::default();
+        let mut root = resolve("alice");
+        base.stamp(&mut root);
+
+        // Two clones append on top of the same log
+        let mut ours = reopen("alice");
+        let mut theirs = resolve("bob");
+        Frontier::of([&root]).stamp(&mut ours);
+        Frontier::of([&root]).stamp(&mut theirs);
+        let merged = vec![root.clone(), ours.clone(), theirs];
+
+        let conflicts = concurrent_conflicts(&merged, &ids(&merged));
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].detail.contains("thread th-1"));
+        assert!(conflicts[0].detail.contains("concurrently"));
+
+        // Only reported while one of the pair is new
+        let old_only: HashSet<&str> = [root.event_id.as_deref().unwrap()].into();
+        assert!(concurrent_conflicts(&merged, &old_only).is_empty());
+
+        // After the merge, a reopen that saw both is ordered, not concurrent
+        let mut after = reopen("carol");
+        Frontier::of(&merged).stamp(&mut after);
+        assert_eq!(after.parents.len(), 2);
+        let mut all = merged.clone();
+        all.push(after.clone());
+        let new: HashSet<&str> = [after.event_id.as_deref().unwrap()].into();
+        assert!(concurrent_conflicts(&all, &new).is_empty());
+    }
+
+    #[test]
+    fn test_sequential_resolve_and_reopen_do_not_conflict() {
+        let mut frontier = Frontier::default();
+        let mut events = vec![resolve("alice"), reopen("bob"), resolve("alice")];
+        for event in &mut events {
+            frontier.stamp(event);
+        }
+        assert!(concurrent_conflicts(&events, &ids(&events)).is_empty());
+    }
+}
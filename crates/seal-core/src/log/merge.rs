@@ -0,0 +1,134 @@
+//! Union merge of event logs, for the git merge driver and jj merge tool.
+//!
+//! Event logs are append-only, so two branches that both appended to the
+//! same review never really conflict: the merge is every event from either
+//! side, each once. Our side is kept as-is so the local projection sees the
+//! merge as plain growth; events only on their side follow in causal order.
+//! None of those can precede one of ours causally: had our writer seen it, it
+//! would be on our side too.
+
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+
+use super::causal::{causal_keys, CausalKey};
+use crate::events::EventEnvelope;
+
+/// Merge two versions of an event log.
+///
+/// Events are de-duplicated by `event_id`, or by exact line for legacy events
+/// without one.
+///
+/// # Errors
+///
+/// Fails if either side has a line that isn't a valid event (e.g. conflict
+/// markers from an earlier merge), so the caller can leave the conflict for
+/// a human.
+pub fn union_merge(ours: &str, theirs: &str) -> Result<String> {
+    let ours = parse_side(ours, "ours")?;
+    let theirs = parse_side(theirs, "theirs")?;
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut merged: Vec<&str> = Vec::with_capacity(ours.len() + theirs.len());
+    for (key, _, line) in &ours {
+        if seen.insert(key.clone()) {
+            merged.push(line);
+        }
+    }
+
+    let their_events: Vec<EventEnvelope> = theirs.iter().map(|(_, e, _)| e.clone()).collect();
+    let mut added: Vec<(CausalKey, &str)> = causal_keys(&their_events)
+        .into_iter()
+        .zip(&theirs)
+        .filter(|(_, (key, _, _))| seen.insert(key.clone()))
+        .map(|(causal, (_, _, line))| (causal, *line))
+        .collect();
+    // Stable, so legacy events with equal timestamps keep their relative order
+    added.sort_by(|(a, _), (b, _)| a.cmp(b));
+    merged.extend(added.into_iter().map(|(_, line)| line));
+
+    let mut out = merged.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Whether a log contains conflict markers left by git or jj.
+#[must_use]
+pub fn has_conflict_markers(content: &str) -> bool {
+    content
+        .lines()
+        .any(|line| line.starts_with("<<<<<<<") || line.starts_with(">>>>>>>"))
+}
+
+/// Parse one side into (dedup key, event, line) triples.
+fn parse_side<'a>(content: &'a str, side: &str) -> Result<Vec<(String, EventEnvelope, &'a str)>> {
+    let mut events = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let envelope = match EventEnvelope::from_json_line(line) {
+            Ok(envelope) => envelope,
+            Err(e) => bail!("{side} line {} is not a valid event: {e}", idx + 1),
+        };
+        let key = envelope
+            .event_id
+            .clone()
+            .unwrap_or_else(|| line.to_string());
+        events.push((key, envelope, line));
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{Event, ReviewAbandoned};
+    use chrono::DateTime;
+
+    fn line_at(secs: i64) -> String {
+        let mut envelope = EventEnvelope::new(
+            "agent",
+            Event::ReviewAbandoned(ReviewAbandoned {
+                review_id: "cr-abc".to_string(),
+                reason: None,
+            }),
+        );
+        envelope.ts = DateTime::from_timestamp(secs, 0).unwrap();
+        envelope.to_json_line().unwrap()
+    }
+
+    #[test]
+    fn test_union_merge_keeps_ours_and_appends_theirs_by_time() {
+        let base = line_at(1);
+        let ours_new = line_at(5);
+        let theirs_late = line_at(4);
+        let theirs_early = line_at(2);
+
+        let ours = format!("{base}\n{ours_new}\n");
+        let theirs = format!("{base}\n{theirs_late}\n{theirs_early}\n");
+        let merged = union_merge(&ours, &theirs).unwrap();
+
+        let expected = format!("{base}\n{ours_new}\n{theirs_early}\n{theirs_late}\n");
+        assert_eq!(merged, expected);
+        // Merging again is a no-op
+        assert_eq!(union_merge(&merged, &theirs).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_union_merge_dedups_legacy_lines_by_content() {
+        let legacy = r#"{"ts":"2025-01-01T00:00:00Z","author":"a","event":"ReviewAbandoned","data":{"review_id":"cr-1"}}"#;
+        let merged = union_merge(&format!("{legacy}\n"), &format!("{legacy}\n")).unwrap();
+        assert_eq!(merged, format!("{legacy}\n"));
+    }
+
+    #[test]
+    fn test_union_merge_rejects_conflict_markers() {
+        let conflicted = format!("<<<<<<< ours\n{}\n=======\n>>>>>>> theirs\n", line_at(1));
+        assert!(has_conflict_markers(&conflicted));
+        assert!(union_merge(&conflicted, "").is_err());
+        assert!(!has_conflict_markers(&line_at(1)));
+    }
+}
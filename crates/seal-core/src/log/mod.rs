@@ -8,16 +8,23 @@
 //! - **v2**: Per-review event logs at `.seal/reviews/{review_id}/events.jsonl`
 //!
 //! v2 eliminates merge conflicts between concurrent reviews in different
-//! workspaces, as each review has its own isolated event log.
+//! workspaces, as each review has its own isolated event log. Concurrent
+//! appends to the same review are reconciled by [`merge::union_merge`].
+
+pub mod causal;
+pub mod merge;
 
 use std::fs::{self, File, OpenOptions};
-use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Take, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
 use fs2::FileExt;
+use serde::{Deserialize, Serialize};
 
 use crate::events::EventEnvelope;
+use crate::signing;
+use causal::Frontier;
 
 /// FNV-1a hash over byte slices. Output is stable across Rust versions
 /// (unlike `DefaultHasher` which uses randomized SipHash keys).
@@ -32,11 +39,154 @@ fn fnv1a_hash(data: &[u8]) -> u64 {
     hash
 }
 
+/// Byte length of a log file's intact content.
+///
+/// A final line without a newline that isn't valid JSON is a torn write left
+/// by an interrupted append and is excluded. An unterminated final line that
+/// is a complete JSON object (e.g. after a hand edit or a merge) is kept, even
+/// if it's an event this version doesn't know.
+fn intact_len(file: &mut File) -> Result<u64> {
+    const CHUNK: u64 = 4096;
+
+    let len = file.metadata().context("Failed to stat log file")?.len();
+    if len == 0 {
+        return Ok(0);
+    }
+
+    // Scan backwards for the start of the final line
+    let mut tail = Vec::new();
+    let mut end = len;
+    let line_start = loop {
+        let start = end.saturating_sub(CHUNK);
+        let mut chunk = vec![0; usize::try_from(end - start)?];
+        file.seek(SeekFrom::Start(start))
+            .context("Failed to seek in log file")?;
+        file.read_exact(&mut chunk)
+            .context("Failed to read log file tail")?;
+        if let Some(pos) = chunk.iter().rposition(|&b| b == b'\n') {
+            tail.splice(0..0, chunk[pos + 1..].iter().copied());
+            break start + pos as u64 + 1;
+        }
+        tail.splice(0..0, chunk);
+        if start == 0 {
+            break 0;
+        }
+        end = start;
+    };
+
+    let complete = std::str::from_utf8(&tail).is_ok_and(|line| {
+        line.trim().is_empty()
+            || serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(line).is_ok()
+    });
+    Ok(if complete { len } else { line_start })
+}
+
+/// Append newline-terminated lines to a log file under one exclusive lock,
+/// then fsync once.
+///
+/// A torn final line from an earlier interrupted append is cut off first; it
+/// can't be a write in progress because we hold the exclusive lock.
+/// `render` produces the lines from the file and its intact length, so they
+/// can depend on what's already in the log.
+///
+/// Returns the byte offset where the appended lines end.
+fn append_lines(path: &Path, render: impl FnOnce(&mut File, u64) -> Result<String>) -> Result<u64> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+
+    file.lock_exclusive()
+        .context("Failed to acquire exclusive lock")?;
+
+    let len = file.metadata().context("Failed to stat log file")?.len();
+    let intact = intact_len(&mut file)?;
+    if intact < len {
+        tracing::warn!(
+            "dropping torn write at end of {} ({} bytes)",
+            path.display(),
+            len - intact
+        );
+        file.set_len(intact)
+            .context("Failed to truncate torn write")?;
+    }
+
+    // Terminate a complete final event that lacks its newline
+    let needs_newline = if intact > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::Start(intact - 1))
+            .context("Failed to seek in log file")?;
+        file.read_exact(&mut last)
+            .context("Failed to read log file tail")?;
+        last[0] != b'\n'
+    } else {
+        false
+    };
+
+    let lines = render(&mut file, intact)?;
+
+    // Seek to end (append mode writes there anyway, but be explicit)
+    file.seek(SeekFrom::End(0))
+        .context("Failed to seek to end of file")?;
+
+    if needs_newline {
+        file.write_all(b"\n")
+            .context("Failed to write event to log")?;
+    }
+    file.write_all(lines.as_bytes())
+        .context("Failed to write event to log")?;
+
+    file.sync_data().context("Failed to sync log file")?;
+    let end = intact + u64::from(needs_newline) + lines.len() as u64;
+
+    // Lock is automatically released when file is dropped
+    Ok(end)
+}
+
+/// Directory inside `.seal/` for local caches that are never checked in.
+pub const CACHE_DIR: &str = "cache";
+
+/// Create `.seal/cache/`, with a `.gitignore` that ignores all of it.
+fn ensure_cache_dir(seal_root: &Path) -> Result<()> {
+    let dir = seal_root.join(".seal").join(CACHE_DIR);
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        fs::write(&gitignore, "*\n")
+            .with_context(|| format!("Failed to write {}", gitignore.display()))?;
+    }
+    Ok(())
+}
+
+/// A review log's frontier as of a byte offset.
+#[derive(Debug, Serialize, Deserialize)]
+struct FrontierCache {
+    /// Offset the frontier is valid up to
+    len: u64,
+    /// Length and hash of the line ending at `len`, to notice a log that was
+    /// replaced rather than appended to
+    last_line_len: u64,
+    last_line_hash: u64,
+    #[serde(flatten)]
+    frontier: Frontier,
+}
+
 /// Trait for append-only event log operations.
 pub trait AppendLog {
     /// Append an event to the log.
     fn append(&self, event: &EventEnvelope) -> Result<()>;
 
+    /// Append several events atomically: one exclusive lock, one write, one fsync.
+    ///
+    /// Readers never see some of the events without the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log can't be locked, written or synced.
+    fn append_batch(&self, events: &[EventEnvelope]) -> Result<()>;
+
     /// Read all events from the log.
     fn read_all(&self) -> Result<Vec<EventEnvelope>>;
 
@@ -97,30 +247,21 @@ impl FileLog {
 
 impl AppendLog for FileLog {
     fn append(&self, event: &EventEnvelope) -> Result<()> {
-        let json_line = event.to_json_line().context("Failed to serialize event")?;
-
-        // Open file for appending
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&self.path)
-            .with_context(|| format!("Failed to open log file: {}", self.path.display()))?;
-
-        // Acquire exclusive lock for writing
-        file.lock_exclusive()
-            .context("Failed to acquire exclusive lock")?;
-
-        // Seek to end (should already be there due to append mode, but be explicit)
-        file.seek(SeekFrom::End(0))
-            .context("Failed to seek to end of file")?;
+        self.append_batch(std::slice::from_ref(event))
+    }
 
-        // Write the JSON line with newline
-        writeln!(file, "{}", json_line).context("Failed to write event to log")?;
+    fn append_batch(&self, events: &[EventEnvelope]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
 
-        // Flush to ensure data is written
-        file.flush().context("Failed to flush log file")?;
+        let mut lines = String::new();
+        for event in events {
+            lines.push_str(&event.to_json_line().context("Failed to serialize event")?);
+            lines.push('\n');
+        }
 
-        // Lock is automatically released when file is dropped
+        append_lines(&self.path, |_, _| Ok(lines))?;
         Ok(())
     }
 
@@ -294,7 +435,7 @@ pub fn review_events_path(seal_root: &Path, review_id: &str) -> PathBuf {
 ///
 /// Rejects IDs containing path separators, traversal sequences, or
 /// characters outside the expected alphanumeric-plus-dash set.
-fn validate_review_id(review_id: &str) -> Result<()> {
+pub(crate) fn validate_review_id(review_id: &str) -> Result<()> {
     if review_id.is_empty() {
         bail!("review ID must not be empty");
     }
@@ -347,18 +488,184 @@ impl ReviewLog {
         &self.review_id
     }
 
-    /// Get the file size in bytes via `fs::metadata`.
+    /// Get the size in bytes of the file's intact content.
     ///
     /// Returns 0 if the file does not exist. This is a cheap fast-path check
-    /// to skip unchanged files without hashing.
+    /// to skip unchanged files without hashing; only the final line is read.
+    /// A torn final line is not counted, so the file looks changed once the
+    /// write is repaired or completed.
     pub fn byte_len(&self) -> Result<u64> {
         let path = self.path();
-        match fs::metadata(&path) {
-            Ok(meta) => Ok(meta.len()),
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        let mut file = match File::open(&path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
             Err(e) => {
-                Err(e).with_context(|| format!("Failed to stat review log: {}", path.display()))
+                return Err(e)
+                    .with_context(|| format!("Failed to stat review log: {}", path.display()))
             }
+        };
+        file.lock_shared()
+            .context("Failed to acquire shared lock")?;
+        intact_len(&mut file)
+    }
+
+    /// Open the log for reading under a shared lock, limited to its intact
+    /// content so a torn final line is never parsed.
+    fn open_intact(&self) -> Result<BufReader<Take<File>>> {
+        let path = self.path();
+        let mut file = File::open(&path)
+            .with_context(|| format!("Failed to open review log: {}", path.display()))?;
+
+        file.lock_shared()
+            .context("Failed to acquire shared lock")?;
+
+        let len = intact_len(&mut file)?;
+        file.seek(SeekFrom::Start(0))
+            .context("Failed to seek in review log")?;
+        Ok(BufReader::new(file.take(len)))
+    }
+
+    /// Read raw, non-empty lines starting from a line offset (0-indexed).
+    ///
+    /// Returns `(line_index, line)` pairs. Used where the exact bytes matter,
+    /// such as signature verification.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log exists but can't be read.
+    pub fn read_lines_from(&self, line: usize) -> Result<Vec<(usize, String)>> {
+        let path = self.path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let reader = self.open_intact()?;
+        let mut lines = Vec::new();
+
+        for (idx, line_result) in reader.lines().enumerate().skip(line) {
+            let line_content =
+                line_result.with_context(|| format!("Failed to read line {idx} from log file"))?;
+            if !line_content.trim().is_empty() {
+                lines.push((idx, line_content));
+            }
+        }
+
+        Ok(lines)
+    }
+
+    /// Insert events among the log's existing events without stamping clocks,
+    /// so causal replay orders them by their place in the file.
+    ///
+    /// Each `(index, event)` goes before the existing event at `index`
+    /// (counting non-empty lines), or at the end if there is none; `events`
+    /// must be sorted by index. Events appended after the indices were
+    /// worked out stay after the inserted ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log can't be locked, read or rewritten.
+    pub fn insert_unclocked(&self, events: &[(usize, EventEnvelope)]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.ensure_dir()?;
+
+        let path = self.path();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+        file.lock_exclusive()
+            .context("Failed to acquire exclusive lock")?;
+
+        let intact = intact_len(&mut file)?;
+        let mut existing = String::new();
+        file.seek(SeekFrom::Start(0))
+            .context("Failed to seek in log file")?;
+        Read::by_ref(&mut file)
+            .take(intact)
+            .read_to_string(&mut existing)
+            .context("Failed to read log file")?;
+
+        let mut contents = String::new();
+        let push_event = |contents: &mut String, event: &EventEnvelope| -> Result<()> {
+            contents.push_str(&event.to_json_line().context("Failed to serialize event")?);
+            contents.push('\n');
+            Ok(())
+        };
+        let mut pending = events.iter().peekable();
+        let lines = existing.lines().filter(|line| !line.trim().is_empty());
+        for (idx, line) in lines.enumerate() {
+            while let Some((_, event)) = pending.next_if(|(at, _)| *at <= idx) {
+                push_event(&mut contents, event)?;
+            }
+            contents.push_str(line);
+            contents.push('\n');
+        }
+        for (_, event) in pending {
+            push_event(&mut contents, event)?;
+        }
+
+        file.set_len(0).context("Failed to truncate log file")?;
+        file.seek(SeekFrom::Start(0))
+            .context("Failed to seek in log file")?;
+        file.write_all(contents.as_bytes())
+            .context("Failed to write log file")?;
+        file.sync_data().context("Failed to sync log file")?;
+
+        // The cached frontier's offset no longer points at the last line
+        let _ = fs::remove_file(self.frontier_cache_path());
+        Ok(())
+    }
+
+    /// Where the frontier after the last append is cached. `.seal/cache/`
+    /// ignores itself, so it is never checked in.
+    fn frontier_cache_path(&self) -> PathBuf {
+        self.seal_root
+            .join(".seal")
+            .join(CACHE_DIR)
+            .join("frontiers")
+            .join(format!("{}.json", self.review_id))
+    }
+
+    /// The cached frontier and the offset it is valid up to, if the log still
+    /// has the line the cache was written after.
+    fn cached_frontier(&self, file: &mut File, intact: u64) -> Option<(Frontier, u64)> {
+        let contents = fs::read_to_string(self.frontier_cache_path()).ok()?;
+        let cache: FrontierCache = serde_json::from_str(&contents).ok()?;
+        let start = cache.len.checked_sub(cache.last_line_len)?;
+        if cache.len > intact {
+            return None;
+        }
+        let mut last_line = vec![0; usize::try_from(cache.last_line_len).ok()?];
+        file.seek(SeekFrom::Start(start)).ok()?;
+        file.read_exact(&mut last_line).ok()?;
+        (fnv1a_hash(&last_line) == cache.last_line_hash).then_some((cache.frontier, cache.len))
+    }
+
+    /// Cache the frontier after appending `lines`, which end at `end`. Best
+    /// effort: without a cache the next append reads the whole log.
+    fn cache_frontier(&self, frontier: Frontier, end: u64, lines: &str) {
+        let Some(last_line) = lines.trim_end_matches('\n').rsplit('\n').next() else {
+            return;
+        };
+        let last_line = format!("{last_line}\n");
+        let cache = FrontierCache {
+            len: end,
+            last_line_len: last_line.len() as u64,
+            last_line_hash: fnv1a_hash(last_line.as_bytes()),
+            frontier,
+        };
+        let path = self.frontier_cache_path();
+        let written = ensure_cache_dir(&self.seal_root).and_then(|()| {
+            fs::create_dir_all(path.parent().unwrap_or(&path))?;
+            Ok(fs::write(&path, serde_json::to_string(&cache)?)?)
+        });
+        if let Err(e) = written {
+            tracing::debug!("failed to cache frontier at {}: {e:#}", path.display());
         }
     }
 
@@ -375,27 +682,47 @@ impl ReviewLog {
 
 impl AppendLog for ReviewLog {
     fn append(&self, event: &EventEnvelope) -> Result<()> {
-        self.ensure_dir()?;
-
-        let path = self.path();
-        let json_line = event.to_json_line().context("Failed to serialize event")?;
-
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&path)
-            .with_context(|| format!("Failed to open review log: {}", path.display()))?;
-
-        file.lock_exclusive()
-            .context("Failed to acquire exclusive lock")?;
-
-        file.seek(SeekFrom::End(0))
-            .context("Failed to seek to end of file")?;
+        self.append_batch(std::slice::from_ref(event))
+    }
 
-        writeln!(file, "{}", json_line).context("Failed to write event to log")?;
+    fn append_batch(&self, events: &[EventEnvelope]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.ensure_dir()?;
 
-        file.flush().context("Failed to flush log file")?;
+        let mut written = String::new();
+        let mut stamped = Frontier::default();
+        let end = append_lines(&self.path(), |file, intact| {
+            // Stamp clocks and parents from the log as it is under the lock,
+            // reading only what was appended since the cached frontier
+            let (mut frontier, from) = self.cached_frontier(file, intact).unwrap_or_default();
+            file.seek(SeekFrom::Start(from))
+                .context("Failed to seek in log file")?;
+            BufReader::new(Read::by_ref(file).take(intact - from))
+                .lines()
+                .map_while(std::result::Result::ok)
+                .filter_map(|line| EventEnvelope::from_json_line(&line).ok())
+                .for_each(|event| frontier.observe(&event));
+
+            let mut lines = String::new();
+            for event in events {
+                let mut event = event.clone();
+                frontier.stamp(&mut event);
+                let json_line = event.to_json_line().context("Failed to serialize event")?;
+                // Signed only when the author has a private key (see `signing`)
+                lines.push_str(&signing::sign_line_as(&json_line, &event.author)?);
+                lines.push('\n');
+            }
+            written.clone_from(&lines);
+            stamped = frontier;
+            Ok(lines)
+        })
+        .with_context(|| format!("Failed to append to review {}", self.review_id))?;
+        self.cache_frontier(stamped, end, &written);
 
+        // After the lock is released, so hooks can read (or write) the log
+        crate::hooks::run_after_append(&self.seal_root, &self.review_id, &written);
         Ok(())
     }
 
@@ -405,13 +732,7 @@ impl AppendLog for ReviewLog {
             return Ok(Vec::new());
         }
 
-        let file = File::open(&path)
-            .with_context(|| format!("Failed to open review log: {}", path.display()))?;
-
-        file.lock_shared()
-            .context("Failed to acquire shared lock")?;
-
-        let reader = BufReader::new(file);
+        let reader = self.open_intact()?;
         let mut events = Vec::new();
 
         for (idx, line_result) in reader.lines().enumerate() {
@@ -437,13 +758,7 @@ impl AppendLog for ReviewLog {
             return Ok(Vec::new());
         }
 
-        let file = File::open(&path)
-            .with_context(|| format!("Failed to open review log: {}", path.display()))?;
-
-        file.lock_shared()
-            .context("Failed to acquire shared lock")?;
-
-        let reader = BufReader::new(file);
+        let reader = self.open_intact()?;
         let mut events = Vec::new();
 
         for (idx, line_result) in reader.lines().enumerate() {
@@ -473,13 +788,7 @@ impl AppendLog for ReviewLog {
             return Ok(0);
         }
 
-        let file = File::open(&path)
-            .with_context(|| format!("Failed to open review log: {}", path.display()))?;
-
-        file.lock_shared()
-            .context("Failed to acquire shared lock")?;
-
-        let reader = BufReader::new(file);
+        let reader = self.open_intact()?;
         let count = reader
             .lines()
             .filter_map(|l| l.ok())
@@ -495,13 +804,7 @@ impl AppendLog for ReviewLog {
             return Ok(0);
         }
 
-        let file = File::open(&path)
-            .with_context(|| format!("Failed to open review log: {}", path.display()))?;
-
-        file.lock_shared()
-            .context("Failed to acquire shared lock")?;
-
-        let reader = BufReader::new(file);
+        let reader = self.open_intact()?;
         let count = reader.lines().filter_map(|l| l.ok()).count();
 
         Ok(count)
@@ -517,13 +820,7 @@ impl AppendLog for ReviewLog {
             return Ok(None);
         }
 
-        let file = File::open(&path)
-            .with_context(|| format!("Failed to open review log: {}", path.display()))?;
-
-        file.lock_shared()
-            .context("Failed to acquire shared lock")?;
-
-        let reader = BufReader::new(file);
+        let reader = self.open_intact()?;
         let mut hash: u64 = 0xcbf29ce484222325; // FNV offset basis
 
         for (idx, line_result) in reader.lines().enumerate() {
@@ -569,7 +866,7 @@ pub fn list_review_ids(seal_root: &Path) -> Result<Vec<String>> {
     Ok(review_ids)
 }
 
-/// Read all events from all reviews, sorted by timestamp.
+/// Read all events from all reviews, in causal order (see [`causal`]).
 pub fn read_all_reviews(seal_root: &Path) -> Result<Vec<EventEnvelope>> {
     let review_ids = list_review_ids(seal_root)?;
     let mut all_events = Vec::new();
@@ -577,13 +874,11 @@ pub fn read_all_reviews(seal_root: &Path) -> Result<Vec<EventEnvelope>> {
     for review_id in review_ids {
         let log = ReviewLog::new(seal_root, &review_id)?;
         let events = log.read_all()?;
-        all_events.extend(events);
+        all_events.extend(causal::causal_keys(&events).into_iter().zip(events));
     }
 
-    // Sort by timestamp
-    all_events.sort_by(|a, b| a.ts.cmp(&b.ts));
-
-    Ok(all_events)
+    all_events.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(all_events.into_iter().map(|(_, event)| event).collect())
 }
 
 /// Open or create a review log (v2 format).
@@ -618,6 +913,7 @@ mod tests {
                 initial_commit: "commit456".to_string(),
                 title: format!("Test Review {}", id),
                 description: None,
+                depends_on: None,
             }),
         )
     }
@@ -836,6 +1132,122 @@ mod tests {
         assert_eq!(events.len(), 2);
     }
 
+    #[test]
+    fn test_review_log_append_batch() {
+        let dir = tempdir().unwrap();
+        let log = ReviewLog::new(dir.path(), "cr-001").unwrap();
+
+        log.append_batch(&[make_test_event("cr-001"), make_test_event("cr-001")])
+            .unwrap();
+        log.append_batch(&[]).unwrap();
+        log.append(&make_test_event("cr-001")).unwrap();
+
+        assert_eq!(log.len().unwrap(), 3);
+
+        // Appends are stamped with a Lamport clock and the previous event
+        let events = log.read_all().unwrap();
+        let clocks: Vec<Option<u64>> = events.iter().map(|e| e.clock).collect();
+        assert_eq!(clocks, vec![Some(1), Some(2), Some(3)]);
+        assert!(events[0].parents.is_empty());
+        assert_eq!(events[2].parents, vec![events[1].event_id.clone().unwrap()]);
+    }
+
+    #[test]
+    fn test_review_log_frontier_cache() {
+        let dir = tempdir().unwrap();
+        let log = ReviewLog::new(dir.path(), "cr-001").unwrap();
+        log.append_batch(&[make_test_event("cr-001"), make_test_event("cr-001")])
+            .unwrap();
+        let cache_dir = dir.path().join(".seal").join(CACHE_DIR);
+        assert_eq!(
+            fs::read_to_string(cache_dir.join(".gitignore")).unwrap(),
+            "*\n"
+        );
+        assert!(log.frontier_cache_path().exists());
+
+        // Another clone's event, merged in after one of ours
+        let base = log.read_all().unwrap();
+        log.append(&make_test_event("cr-001")).unwrap();
+        let mut theirs = make_test_event("cr-001");
+        Frontier::of(&base).stamp(&mut theirs);
+        let mut file = OpenOptions::new().append(true).open(log.path()).unwrap();
+        writeln!(file, "{}", theirs.to_json_line().unwrap()).unwrap();
+        drop(file);
+
+        log.append(&make_test_event("cr-001")).unwrap();
+        let events = log.read_all().unwrap();
+        assert_eq!(events[4].clock, Some(4));
+        assert_eq!(
+            events[4].parents,
+            vec![
+                events[2].event_id.clone().unwrap(),
+                theirs.event_id.unwrap()
+            ]
+        );
+
+        // A log replaced wholesale isn't mistaken for the cached one
+        let mut replaced = make_test_event("cr-001");
+        replaced.clock = Some(7);
+        fs::write(
+            log.path(),
+            format!("{}\n", replaced.to_json_line().unwrap()),
+        )
+        .unwrap();
+        log.append(&make_test_event("cr-001")).unwrap();
+        let events = log.read_all().unwrap();
+        assert_eq!(events[1].clock, Some(8));
+        assert_eq!(events[1].parents, vec![replaced.event_id.unwrap()]);
+    }
+
+    #[test]
+    fn test_review_log_ignores_and_repairs_torn_write() {
+        let dir = tempdir().unwrap();
+        let log = ReviewLog::new(dir.path(), "cr-001").unwrap();
+        log.append(&make_test_event("cr-001")).unwrap();
+        let intact = log.byte_len().unwrap();
+
+        // An interrupted append leaves half a line behind
+        let mut file = OpenOptions::new().append(true).open(log.path()).unwrap();
+        file.write_all(br#"{"ts":"2026-01-01T00:00:00Z","author":"te"#)
+            .unwrap();
+        drop(file);
+
+        assert_eq!(log.read_all().unwrap().len(), 1);
+        assert_eq!(log.total_lines().unwrap(), 1);
+        assert_eq!(log.byte_len().unwrap(), intact);
+
+        // The next append cuts it off instead of extending it
+        log.append(&make_test_event("cr-001")).unwrap();
+        assert_eq!(log.read_all().unwrap().len(), 2);
+        assert!(!fs::read_to_string(log.path())
+            .unwrap()
+            .contains(r#""author":"te{"#));
+    }
+
+    #[test]
+    fn test_review_log_keeps_unterminated_complete_line() {
+        let dir = tempdir().unwrap();
+        let log = ReviewLog::new(dir.path(), "cr-001").unwrap();
+        log.ensure_dir().unwrap();
+        let line = make_test_event("cr-001").to_json_line().unwrap();
+        fs::write(log.path(), &line).unwrap();
+
+        assert_eq!(log.read_all().unwrap().len(), 1);
+
+        log.append(&make_test_event("cr-001")).unwrap();
+        assert_eq!(log.read_all().unwrap().len(), 2);
+
+        // An event from a newer version is complete too, so it isn't cut off
+        let newer = r#"{"ts":"2026-01-01T00:00:00Z","author":"a","event":"FutureEvent","data":{}}"#;
+        let mut file = OpenOptions::new().append(true).open(log.path()).unwrap();
+        file.write_all(newer.as_bytes()).unwrap();
+        drop(file);
+        log.append(&make_test_event("cr-001")).unwrap();
+        let content = fs::read_to_string(log.path()).unwrap();
+        assert!(content.contains(&format!("\n{newer}\n")));
+        assert_eq!(content.lines().count(), 4);
+    }
+
     #[test]
     fn test_review_log_isolation() {
         let dir = tempdir().unwrap();
@@ -896,8 +1308,9 @@ mod tests {
         let all_events = read_all_reviews(seal_root).unwrap();
         assert_eq!(all_events.len(), 3);
 
-        // Should be sorted by timestamp
+        // Should be in causal order (here, also timestamp order)
         for i in 0..all_events.len() - 1 {
+            assert!(all_events[i].clock <= all_events[i + 1].clock);
             assert!(all_events[i].ts <= all_events[i + 1].ts);
         }
     }
@@ -999,6 +1412,7 @@ mod tests {
                 initial_commit: "other_commit".to_string(),
                 title: "Another review".to_string(),
                 description: Some("with description".to_string()),
+                depends_on: None,
             }),
         );
         log.append(&e1).unwrap();
@@ -0,0 +1,229 @@
+//! Code owners file support for automatic reviewer assignment.
+//!
+//! `.seal/owners` maps gitignore-style path patterns to the agents who own
+//! them, one rule per line, CODEOWNERS-style:
+//!
+//! ```text
+//! # pattern              owners
+//! *.md                   docs-bot
+//! crates/seal-core/src/log/   storage-agent alice
+//! ```
+//!
+//! As in CODEOWNERS, when several rules match a path the last one wins.
+
+use anyhow::{bail, Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Owners file name inside `.seal/`.
+pub const OWNERS_FILE: &str = "owners";
+
+/// A single line of the owners file.
+#[derive(Debug, Clone, Serialize)]
+pub struct OwnerRule {
+    /// 1-based line number in the owners file
+    pub line: usize,
+    /// Path pattern as written
+    pub pattern: String,
+    /// Agents owning matching paths
+    pub owners: Vec<String>,
+    #[serde(skip)]
+    matcher: Gitignore,
+}
+
+impl OwnerRule {
+    /// Check if this rule matches a repository-relative path.
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        let is_dir = path.ends_with('/');
+        // Directory patterns like "src/log/" should match "src/log/mod.rs"
+        self.matcher
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+    }
+}
+
+/// Parsed `.seal/owners` file.
+#[derive(Debug, Clone, Default)]
+pub struct Owners {
+    rules: Vec<OwnerRule>,
+}
+
+impl Owners {
+    /// Path of the owners file for a repository.
+    #[must_use]
+    pub fn path(seal_root: &Path) -> PathBuf {
+        seal_root.join(".seal").join(OWNERS_FILE)
+    }
+
+    /// Load the owners file, returning no rules if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(seal_root: &Path) -> Result<Self> {
+        let path = Self::path(seal_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Invalid owners file {}", path.display()))
+    }
+
+    /// Parse owners rules. Blank lines and `#` comments are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a rule has no owners or an invalid pattern.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+
+        for (idx, raw) in contents.lines().enumerate() {
+            let line = idx + 1;
+            let text = raw.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = text.split_whitespace();
+            let pattern = fields.next().unwrap_or_default().to_string();
+            let owners: Vec<String> = fields
+                .map(|owner| owner.trim_start_matches('@').to_string())
+                .filter(|owner| !owner.is_empty())
+                .collect();
+            if owners.is_empty() {
+                bail!("line {line}: pattern '{pattern}' has no owners");
+            }
+
+            let mut builder = GitignoreBuilder::new("");
+            builder
+                .add_line(None, &pattern)
+                .with_context(|| format!("line {line}: invalid pattern '{pattern}'"))?;
+            let matcher = builder
+                .build()
+                .with_context(|| format!("line {line}: invalid pattern '{pattern}'"))?;
+
+            rules.push(OwnerRule {
+                line,
+                pattern,
+                owners,
+                matcher,
+            });
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Check if there are no rules.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// All rules matching a path, in file order. The last one is in effect.
+    #[must_use]
+    pub fn matching_rules(&self, path: &str) -> Vec<&OwnerRule> {
+        let path = normalize(path);
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(path))
+            .collect()
+    }
+
+    /// The rule in effect for a path, if any.
+    #[must_use]
+    pub fn rule_for(&self, path: &str) -> Option<&OwnerRule> {
+        let path = normalize(path);
+        self.rules.iter().rev().find(|rule| rule.matches(path))
+    }
+
+    /// Owners of a set of files, deduplicated in order of first appearance.
+    #[must_use]
+    pub fn owners_for_files(&self, files: &[String]) -> Vec<String> {
+        let mut owners: Vec<String> = Vec::new();
+        for file in files {
+            if let Some(rule) = self.rule_for(file) {
+                for owner in &rule.owners {
+                    if !owners.contains(owner) {
+                        owners.push(owner.clone());
+                    }
+                }
+            }
+        }
+        owners
+    }
+}
+
+/// Strip a leading `./` so paths typed on the command line match.
+fn normalize(path: &str) -> &str {
+    path.strip_prefix("./").unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const OWNERS: &str = "\
+# Docs
+*.md            docs-bot
+
+src/log/        @storage alice
+src/log/tui.md  tui-agent
+";
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let temp = TempDir::new().unwrap();
+        assert!(Owners::load(temp.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let owners = Owners::parse(OWNERS).unwrap();
+        assert_eq!(owners.rules.len(), 3);
+        assert_eq!(owners.rules[1].line, 4);
+        assert_eq!(owners.rules[1].owners, vec!["storage", "alice"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_rule_without_owners() {
+        let err = Owners::parse("*.md docs\nsrc/\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let owners = Owners::parse(OWNERS).unwrap();
+
+        assert_eq!(
+            owners.rule_for("README.md").unwrap().owners,
+            vec!["docs-bot"]
+        );
+        assert_eq!(owners.rule_for("./src/log/mod.rs").unwrap().line, 4);
+        assert_eq!(
+            owners.rule_for("src/log/tui.md").unwrap().owners,
+            vec!["tui-agent"]
+        );
+        assert_eq!(owners.matching_rules("src/log/tui.md").len(), 3);
+        assert!(owners.rule_for("src/main.rs").is_none());
+    }
+
+    #[test]
+    fn test_owners_for_files_dedups() {
+        let owners = Owners::parse(OWNERS).unwrap();
+        let files = vec![
+            "src/log/mod.rs".to_string(),
+            "src/log/append.rs".to_string(),
+            "README.md".to_string(),
+            "src/main.rs".to_string(),
+        ];
+        assert_eq!(
+            owners.owners_for_files(&files),
+            vec!["storage", "alice", "docs-bot"]
+        );
+    }
+}
@@ -12,8 +12,9 @@
 mod query;
 
 pub use query::{
-    Comment, InboxSummary, OpenThreadOnMyReview, ReviewAwaitingVote, ReviewDetail, ReviewSummary,
-    ReviewerVote, ThreadDetail, ThreadSummary, ThreadWithNewResponses,
+    Comment, CommentEdit, InboxSummary, OpenThreadOnMyReview, ReviewAwaitingVote, ReviewDetail,
+    ReviewRevision, ReviewSummary, ReviewerVote, SearchFilter, SearchHit, StaleVote, ThreadDetail,
+    ThreadSummary, ThreadWithNewResponses,
 };
 
 use std::collections::{HashMap, HashSet};
@@ -26,12 +27,15 @@ use rusqlite::{params, Connection, OptionalExtension};
 use serde::Serialize;
 
 use crate::events::{
-    CodeSelection, CommentAdded, Event, EventEnvelope, ReviewAbandoned, ReviewApproved,
-    ReviewCreated, ReviewMerged, ReviewerVoted, ReviewersRequested, ThreadCreated, ThreadReopened,
-    ThreadResolved,
+    CodeSelection, CommentAdded, CommentEdited, CommentRetracted, Event, EventEnvelope,
+    ReviewAbandoned, ReviewApproved, ReviewCreated, ReviewEdited, ReviewMerged, ReviewReopened,
+    ReviewUpdated, ReviewerVoteRetracted, ReviewerVoted, ReviewersRequested, ReviewersUnrequested,
+    ThreadCreated, ThreadKindChanged, ThreadReopened, ThreadResolved,
 };
+use crate::log::causal::{concurrent_conflicts, sort_causally};
 use crate::log::{list_review_ids, read_all_reviews, AppendLog, ReviewLog};
 use crate::scm::BackendDetection;
+use crate::signing::{PublicKeys, Verification};
 
 static EMITTED_WARNING_KEYS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
 
@@ -119,7 +123,65 @@ fn emit_orphaned_reviews_warning(seal_root: Option<&Path>, review_ids: &[String]
     }
 }
 
+fn emit_invalid_signatures_warning(seal_root: &Path, anomalies: &[&SyncAnomaly]) {
+    if anomalies.is_empty() {
+        return;
+    }
+
+    let lines: Vec<String> = anomalies
+        .iter()
+        .map(|anomaly| format!("{}: {}", anomaly.review_id, anomaly.detail))
+        .collect();
+    let key = format!(
+        "invalid-signatures:{}:{}",
+        seal_root.display(),
+        lines.join("|")
+    );
+    if !should_emit_warning_once(key) {
+        return;
+    }
+
+    tracing::warn!("review event(s) have invalid or missing signatures (possible forged author)");
+    for line in lines {
+        tracing::warn!("  {line}");
+    }
+    tracing::warn!("Run `seal doctor` to check all signatures");
+}
+
+fn emit_concurrent_conflicts_warning(seal_root: &Path, anomalies: &[&SyncAnomaly]) {
+    if anomalies.is_empty() {
+        return;
+    }
+
+    let lines: Vec<String> = anomalies
+        .iter()
+        .map(|anomaly| format!("{}: {}", anomaly.review_id, anomaly.detail))
+        .collect();
+    let key = format!(
+        "concurrent-conflicts:{}:{}",
+        seal_root.display(),
+        lines.join("|")
+    );
+    if !should_emit_warning_once(key) {
+        return;
+    }
+
+    tracing::warn!("concurrent conflicting change(s) merged into review logs");
+    for line in lines {
+        tracing::warn!("  {line}");
+    }
+    tracing::warn!("Check the resulting state and resolve, reopen or vote again if needed");
+}
+
 fn emit_stale_review_logs_warning(seal_root: &Path, anomalies: &[SyncAnomaly]) {
+    let (signature_anomalies, anomalies): (Vec<&SyncAnomaly>, Vec<&SyncAnomaly>) = anomalies
+        .iter()
+        .partition(|anomaly| anomaly.kind == AnomalyKind::InvalidSignature);
+    emit_invalid_signatures_warning(seal_root, &signature_anomalies);
+    let (conflict_anomalies, anomalies): (Vec<&SyncAnomaly>, Vec<&SyncAnomaly>) = anomalies
+        .into_iter()
+        .partition(|anomaly| anomaly.kind == AnomalyKind::ConcurrentConflict);
+    emit_concurrent_conflicts_warning(seal_root, &conflict_anomalies);
     if anomalies.is_empty() {
         return;
     }
@@ -201,6 +263,11 @@ pub enum AnomalyKind {
     Missing,
     /// File could not be parsed or applying events failed.
     ParseError,
+    /// An event's signature doesn't match its author's public key, or is
+    /// missing although the author has one.
+    InvalidSignature,
+    /// Concurrent events set conflicting state (e.g. resolve vs reopen).
+    ConcurrentConflict,
 }
 
 /// Database for projected state from events.
@@ -313,6 +380,9 @@ impl ProjectionDb {
                 .context("Failed to add scm_anchor column to reviews")?;
         }
 
+        self.migrate_comment_columns()?;
+        self.migrate_text_columns()?;
+
         self.conn
             .execute(
                 "UPDATE reviews SET scm_anchor = jj_change_id WHERE scm_anchor IS NULL OR scm_anchor = ''",
@@ -350,6 +420,96 @@ impl ProjectionDb {
             )
             .context("Failed to create review_file_state table")?;
 
+        self.conn
+            .execute(
+                "CREATE INDEX IF NOT EXISTS idx_applied_events_review ON applied_events(review_id)",
+                [],
+            )
+            .context("Failed to create idx_applied_events_review index")?;
+
+        // The search index was added after reviews and comments existed;
+        // populate it once from the projected rows
+        let needs_search_backfill: bool = self
+            .conn
+            .query_row(
+                "SELECT NOT EXISTS (SELECT 1 FROM search_index)
+                    AND EXISTS (SELECT 1 FROM reviews)",
+                [],
+                |row| row.get(0),
+            )
+            .context("Failed to check search index")?;
+        if needs_search_backfill {
+            self.conn
+                .execute_batch(&format!("{INDEX_REVIEW_SQL};{INDEX_COMMENT_SQL};"))
+                .context("Failed to backfill search index")?;
+        }
+
+        Ok(())
+    }
+
+    /// Add nullable text columns to databases created before they existed.
+    fn migrate_text_columns(&self) -> Result<()> {
+        for (table, column) in [
+            ("threads", "anchor_snapshot"),
+            ("threads", "kind"),
+            ("reviews", "depends_on"),
+            ("reviewer_votes", "commit_hash"),
+            // Recognizes merged review logs
+            ("applied_events", "review_id"),
+        ] {
+            let has_column: bool = self
+                .conn
+                .query_row(
+                    "SELECT COUNT(*) > 0 FROM pragma_table_info(?) WHERE name = ?",
+                    params![table, column],
+                    |row| row.get(0),
+                )
+                .with_context(|| format!("Failed to check for {table}.{column} column"))?;
+
+            if !has_column {
+                self.conn
+                    .execute(&format!("ALTER TABLE {table} ADD COLUMN {column} TEXT"), [])
+                    .with_context(|| format!("Failed to add {column} column to {table}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add comment edit/retract columns to databases created before they existed,
+    /// and redact comments retracted before retraction cleared their text.
+    fn migrate_comment_columns(&self) -> Result<()> {
+        for column in ["edited_at", "retracted_at", "retract_reason", "suggestion"] {
+            let has_comment_column: bool = self
+                .conn
+                .query_row(
+                    "SELECT COUNT(*) > 0 FROM pragma_table_info('comments') WHERE name = ?",
+                    params![column],
+                    |row| row.get(0),
+                )
+                .with_context(|| format!("Failed to check for comments.{column} column"))?;
+
+            if !has_comment_column {
+                self.conn
+                    .execute(
+                        &format!("ALTER TABLE comments ADD COLUMN {column} TEXT"),
+                        [],
+                    )
+                    .with_context(|| format!("Failed to add {column} column to comments"))?;
+            }
+        }
+
+        // Databases synced before retraction redacted comments still hold their text
+        self.conn
+            .execute_batch(
+                "UPDATE comment_edits SET previous_body = '', body = ''
+                 WHERE comment_id IN (
+                     SELECT comment_id FROM comments WHERE retracted_at IS NOT NULL
+                 );
+                 UPDATE comments SET body = '', suggestion = NULL
+                 WHERE retracted_at IS NOT NULL AND (body != '' OR suggestion IS NOT NULL);",
+            )
+            .context("Failed to redact retracted comments")?;
+
         Ok(())
     }
 
@@ -406,6 +566,55 @@ impl ProjectionDb {
         &self.conn
     }
 
+    /// How far each review file has been synced, by review ID.
+    pub fn review_file_states(&self) -> Result<HashMap<String, ReviewFileState>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT review_id, line_count, byte_count, prefix_hash FROM review_file_state")
+            .context("Failed to prepare review_file_state query")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ReviewFileState {
+                        line_count: row.get::<_, i64>(1)? as usize,
+                        byte_count: row.get::<_, i64>(2)? as u64,
+                        prefix_hash: row.get::<_, String>(3)?,
+                    },
+                ))
+            })
+            .context("Failed to query review_file_state")?;
+        let mut states = HashMap::new();
+        for row in rows {
+            let (review_id, state) = row.context("Failed to read review_file_state row")?;
+            states.insert(review_id, state);
+        }
+        Ok(states)
+    }
+
+    /// IDs of the events applied so far, by review ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the applied events can't be queried.
+    pub fn applied_event_ids(&self) -> Result<HashMap<String, HashSet<String>>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT review_id, event_id FROM applied_events WHERE review_id IS NOT NULL")
+            .context("Failed to query applied events")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .context("Failed to query applied events")?;
+        let mut ids: HashMap<String, HashSet<String>> = HashMap::new();
+        for row in rows {
+            let (review_id, event_id) = row.context("Failed to read applied events")?;
+            ids.entry(review_id).or_default().insert(event_id);
+        }
+        Ok(ids)
+    }
+
     /// Delete the file state for a specific review (for --accept-regression).
     pub fn delete_review_file_state(&self, review_id: &str) -> Result<()> {
         self.conn.execute(
@@ -550,10 +759,15 @@ fn rebuild_projection(db: &ProjectionDb, log: &impl AppendLog) -> Result<usize>
 
     // Wipe all projection data (order matters for foreign keys)
     tx.execute_batch(
-        "DELETE FROM comments;
+        "DELETE FROM comment_edits;
+         DELETE FROM comments;
          DELETE FROM threads;
          DELETE FROM reviewer_votes;
          DELETE FROM review_reviewers;
+         DELETE FROM review_revisions;
+         DELETE FROM review_labels;
+         DELETE FROM search_index;
+         DELETE FROM applied_events;
          DELETE FROM reviews;",
     )
     .context("Failed to wipe projection tables")?;
@@ -693,10 +907,15 @@ fn rebuild_projection_with_orphan_detection(
         .context("Failed to begin rebuild transaction")?;
 
     tx.execute_batch(
-        "DELETE FROM comments;
+        "DELETE FROM comment_edits;
+         DELETE FROM comments;
          DELETE FROM threads;
          DELETE FROM reviewer_votes;
          DELETE FROM review_reviewers;
+         DELETE FROM review_revisions;
+         DELETE FROM review_labels;
+         DELETE FROM search_index;
+         DELETE FROM applied_events;
          DELETE FROM reviews;",
     )
     .context("Failed to wipe projection tables")?;
@@ -731,23 +950,36 @@ fn event_review_id(event: &Event) -> Option<&str> {
     match event {
         Event::ReviewCreated(e) => Some(&e.review_id),
         Event::ReviewersRequested(e) => Some(&e.review_id),
+        Event::ReviewersUnrequested(e) => Some(&e.review_id),
         Event::ReviewerVoted(e) => Some(&e.review_id),
+        Event::ReviewerVoteRetracted(e) => Some(&e.review_id),
         Event::ReviewApproved(e) => Some(&e.review_id),
         Event::ReviewMerged(e) => Some(&e.review_id),
         Event::ReviewAbandoned(e) => Some(&e.review_id),
+        Event::ReviewReopened(e) => Some(&e.review_id),
+        Event::ReviewUpdated(e) => Some(&e.review_id),
+        Event::ReviewEdited(e) => Some(&e.review_id),
         Event::ThreadCreated(e) => Some(&e.review_id),
         // These only carry thread_id:
-        Event::ThreadResolved(_) | Event::ThreadReopened(_) | Event::CommentAdded(_) => None,
+        Event::ThreadResolved(_)
+        | Event::ThreadReopened(_)
+        | Event::ThreadKindChanged(_)
+        | Event::CommentAdded(_)
+        | Event::CommentEdited(_)
+        | Event::CommentRetracted(_) => None,
     }
 }
 
 /// Extract thread_id from an event, if it carries one.
-fn event_thread_id(event: &Event) -> Option<&str> {
+pub(crate) fn event_thread_id(event: &Event) -> Option<&str> {
     match event {
         Event::ThreadCreated(e) => Some(&e.thread_id),
         Event::ThreadResolved(e) => Some(&e.thread_id),
         Event::ThreadReopened(e) => Some(&e.thread_id),
+        Event::ThreadKindChanged(e) => Some(&e.thread_id),
         Event::CommentAdded(e) => Some(&e.thread_id),
+        Event::CommentEdited(e) => Some(&e.thread_id),
+        Event::CommentRetracted(e) => Some(&e.thread_id),
         _ => None,
     }
 }
@@ -859,11 +1091,16 @@ fn filter_orphaned_events_for_repo(
 // v2: Per-review event log sync
 // ============================================================================
 
-/// Stored state for a review file from the `review_file_state` table.
-struct StoredFileState {
-    line_count: usize,
-    byte_count: u64,
-    prefix_hash: String,
+/// Stored state for a review file from the `review_file_state` table: how
+/// much of the file has been read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewFileState {
+    /// Lines read, including empty lines
+    pub line_count: usize,
+    /// Bytes read
+    pub byte_count: u64,
+    /// `prefix_hash` of the lines read, to detect rewrites
+    pub prefix_hash: String,
 }
 
 /// Sync the projection from per-review event logs (v2 format).
@@ -881,29 +1118,7 @@ pub fn sync_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<Sync
     };
 
     // Step 1: Load all review_file_state rows into a HashMap
-    let mut stored_states: HashMap<String, StoredFileState> = HashMap::new();
-    {
-        let mut stmt = db
-            .conn
-            .prepare("SELECT review_id, line_count, byte_count, prefix_hash FROM review_file_state")
-            .context("Failed to prepare review_file_state query")?;
-        let rows = stmt
-            .query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    StoredFileState {
-                        line_count: row.get::<_, i64>(1)? as usize,
-                        byte_count: row.get::<_, i64>(2)? as u64,
-                        prefix_hash: row.get::<_, String>(3)?,
-                    },
-                ))
-            })
-            .context("Failed to query review_file_state")?;
-        for row in rows {
-            let (review_id, state) = row.context("Failed to read review_file_state row")?;
-            stored_states.insert(review_id, state);
-        }
-    }
+    let mut stored_states = db.review_file_states()?;
 
     // Step 1b: Bootstrap — if projection has data but review_file_state is empty,
     // seed review_file_state from current on-disk files without replaying events.
@@ -933,7 +1148,7 @@ pub fn sync_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<Sync
                         .ok();
                     stored_states.insert(
                         review_id.clone(),
-                        StoredFileState {
+                        ReviewFileState {
                             line_count,
                             byte_count,
                             prefix_hash,
@@ -944,6 +1159,12 @@ pub fn sync_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<Sync
         }
     }
 
+    // Public keys for signature checks on newly synced lines
+    let public_keys = PublicKeys::load(seal_root).unwrap_or_else(|e| {
+        tracing::warn!("ignoring .seal/keys: {e:#}");
+        PublicKeys::default()
+    });
+
     // Step 2: Discover files on disk
     let on_disk_ids = list_review_ids(seal_root)?;
     let on_disk_set: HashSet<&str> = on_disk_ids.iter().map(|s| s.as_str()).collect();
@@ -979,6 +1200,7 @@ pub fn sync_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<Sync
             None => {
                 // NEW file: read all events, apply
                 sync_new_file(db, &log, review_id, seal_root, &mut report)?;
+                verify_signatures(&public_keys, &log, 0, &mut report);
             }
             Some(stored) => {
                 if current_byte_count == stored.byte_count {
@@ -1020,6 +1242,7 @@ pub fn sync_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<Sync
                     Ok(None) => {
                         // Empty prefix — treat as new file
                         sync_new_file(db, &log, review_id, seal_root, &mut report)?;
+                        verify_signatures(&public_keys, &log, 0, &mut report);
                         continue;
                     }
                     Err(e) => {
@@ -1033,6 +1256,14 @@ pub fn sync_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<Sync
                 };
 
                 if current_prefix_hash != stored.prefix_hash {
+                    // A merge interleaves the other side's events into the
+                    // synced prefix; replaying the file only applies new ones
+                    if is_merged_log(db, &log, review_id)? {
+                        sync_grew_file(db, &log, review_id, 0, seal_root, &mut report)?;
+                        verify_signatures(&public_keys, &log, 0, &mut report);
+                        continue;
+                    }
+
                     // HASH MISMATCH: skip file, record anomaly
                     report.files_skipped += 1;
                     report.anomalies.push(SyncAnomaly {
@@ -1055,6 +1286,7 @@ pub fn sync_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<Sync
                     seal_root,
                     &mut report,
                 )?;
+                verify_signatures(&public_keys, &log, stored.line_count, &mut report);
             }
         }
     }
@@ -1076,6 +1308,134 @@ pub fn sync_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<Sync
     Ok(report)
 }
 
+/// Check signatures on lines synced from a review log, starting at `from_line`.
+///
+/// Events are still applied; a bad signature is reported as an anomaly. So is
+/// a missing one from an author with a registered key, or leaving `sig` off
+/// would get around signing. Unsigned events from other authors are fine,
+/// since signing is optional.
+fn verify_signatures(
+    keys: &PublicKeys,
+    log: &ReviewLog,
+    from_line: usize,
+    report: &mut SyncReport,
+) {
+    if keys.is_empty() {
+        return;
+    }
+    let Ok(lines) = log.read_lines_from(from_line) else {
+        return;
+    };
+    for (idx, line) in lines {
+        let reason = match keys.verify_line(&line) {
+            Ok(Verification::Invalid(reason)) => reason,
+            Ok(Verification::Unsigned) => {
+                let author = EventEnvelope::from_json_line(&line)
+                    .map(|e| e.author)
+                    .unwrap_or_default();
+                if !keys.contains(&author) {
+                    continue;
+                }
+                format!("unsigned, but {author} has a registered signing key")
+            }
+            _ => continue,
+        };
+        report.anomalies.push(SyncAnomaly {
+            review_id: log.review_id().to_string(),
+            kind: AnomalyKind::InvalidSignature,
+            detail: format!("line {}: {reason}", idx + 1),
+        });
+    }
+}
+
+/// Whether a review log whose synced prefix changed is a merge of what we
+/// synced with other events, rather than different history.
+///
+/// That holds when every event has an ID (so replay is idempotent) and every
+/// event already applied for the review is still in the file.
+fn is_merged_log(db: &ProjectionDb, log: &ReviewLog, review_id: &str) -> Result<bool> {
+    let Ok(events) = log.read_all() else {
+        return Ok(false);
+    };
+
+    let mut stmt = db
+        .conn
+        .prepare("SELECT event_id FROM applied_events WHERE review_id = ?")
+        .context("Failed to query applied events")?;
+    let applied = stmt
+        .query_map(params![review_id], |row| row.get::<_, String>(0))
+        .context("Failed to query applied events")?
+        .collect::<rusqlite::Result<Vec<String>>>()
+        .context("Failed to read applied events")?;
+
+    Ok(is_merge_of(&events, applied.iter().map(String::as_str)))
+}
+
+/// Whether a log's events are a merge of the `seen` events with others:
+/// every event has an ID, and at least one event was seen and all of them
+/// are still there.
+pub(crate) fn is_merge_of<'a>(
+    events: &[EventEnvelope],
+    seen: impl IntoIterator<Item = &'a str>,
+) -> bool {
+    let Some(ids) = events
+        .iter()
+        .map(|e| e.event_id.as_deref())
+        .collect::<Option<HashSet<&str>>>()
+    else {
+        return false;
+    };
+    let mut seen = seen.into_iter().peekable();
+    seen.peek().is_some() && seen.all(|id| ids.contains(id))
+}
+
+/// How to resolve a review log left with merge conflict markers, if it has them.
+fn conflict_hint(seal_root: &Path, log: &ReviewLog) -> String {
+    let has_markers = std::fs::read_to_string(log.path())
+        .is_ok_and(|content| crate::log::merge::has_conflict_markers(&content));
+    if !has_markers {
+        return String::new();
+    }
+    let path = review_log_history_path(Some(log.review_id()));
+    let command = match history_backend(Some(seal_root)) {
+        HistoryBackend::Jj => format!("jj resolve --tool seal {path}"),
+        HistoryBackend::Git | HistoryBackend::Unknown => {
+            format!("git mergetool --tool seal {path}")
+        }
+    };
+    format!(
+        " (unresolved merge conflict; after `seal init --install-merge-driver`, run `{command}`)"
+    )
+}
+
+/// Report concurrent conflicting changes involving newly synced events.
+///
+/// They've already been applied in causal order; the anomaly tells agents
+/// one of the changes may have been silently overridden.
+fn report_concurrent_conflicts(
+    log: &ReviewLog,
+    new_events: &[EventEnvelope],
+    report: &mut SyncReport,
+) {
+    let new_ids: HashSet<&str> = new_events
+        .iter()
+        .filter_map(|e| e.event_id.as_deref())
+        .collect();
+    if new_ids.is_empty() {
+        return;
+    }
+    let Ok(events) = log.read_all() else {
+        return;
+    };
+    for conflict in concurrent_conflicts(&events, &new_ids) {
+        report.anomalies.push(SyncAnomaly {
+            review_id: log.review_id().to_string(),
+            kind: AnomalyKind::ConcurrentConflict,
+            detail: conflict.detail,
+        });
+    }
+}
+
 /// Sync a new review file (no prior state).
 ///
 /// Reads all events, applies them in a savepoint, and records file state on success.
@@ -1086,13 +1446,16 @@ fn sync_new_file(
     seal_root: &Path,
     report: &mut SyncReport,
 ) -> Result<()> {
-    let events = match log.read_all() {
+    let mut events = match log.read_all() {
         Ok(e) => e,
         Err(e) => {
             report.anomalies.push(SyncAnomaly {
                 review_id: review_id.to_string(),
                 kind: AnomalyKind::ParseError,
-                detail: format!("Failed to read events: {e}"),
+                detail: format!(
+                    "Failed to read events: {e}{}",
+                    conflict_hint(seal_root, log)
+                ),
             });
             return Ok(());
         }
@@ -1101,6 +1464,7 @@ fn sync_new_file(
     if events.is_empty() {
         return Ok(());
     }
+    sort_causally(&mut events);
 
     // Filter orphaned events for this file
     let (events, _orphaned) = filter_orphaned_events_for_repo(events, Some(seal_root));
@@ -1160,6 +1524,7 @@ fn sync_new_file(
         .context("Failed to release savepoint")?;
 
     report.applied += event_count;
+    report_concurrent_conflicts(log, &events, report);
     report.files_synced += 1;
 
     Ok(())
@@ -1174,16 +1539,19 @@ fn sync_grew_file(
     log: &ReviewLog,
     review_id: &str,
     old_line_count: usize,
-    _seal_root: &Path,
+    seal_root: &Path,
     report: &mut SyncReport,
 ) -> Result<()> {
-    let new_events = match log.read_from(old_line_count) {
+    let mut new_events = match log.read_from(old_line_count) {
         Ok(e) => e,
         Err(e) => {
             report.anomalies.push(SyncAnomaly {
                 review_id: review_id.to_string(),
                 kind: AnomalyKind::ParseError,
-                detail: format!("Failed to read new events: {e}"),
+                detail: format!(
+                    "Failed to read new events: {e}{}",
+                    conflict_hint(seal_root, log)
+                ),
             });
             return Ok(());
         }
@@ -1210,6 +1578,8 @@ fn sync_grew_file(
         return Ok(());
     }
 
+    sort_causally(&mut new_events);
+
     // Filter orphaned events for new events (need context from existing projection)
     // For grew files, we trust events that reference reviews already in the projection
     let (new_events, _orphaned) = filter_orphaned_events_with_projection(db, new_events);
@@ -1269,6 +1639,7 @@ fn sync_grew_file(
         .context("Failed to release savepoint")?;
 
     report.applied += event_count;
+    report_concurrent_conflicts(log, &new_events, report);
     report.files_synced += 1;
 
     Ok(())
@@ -1402,10 +1773,15 @@ pub fn rebuild_from_review_logs(db: &ProjectionDb, seal_root: &Path) -> Result<u
 
     // Wipe all projection data (order matters for foreign keys)
     tx.execute_batch(
-        "DELETE FROM comments;
+        "DELETE FROM comment_edits;
+         DELETE FROM comments;
          DELETE FROM threads;
          DELETE FROM reviewer_votes;
          DELETE FROM review_reviewers;
+         DELETE FROM review_revisions;
+         DELETE FROM review_labels;
+         DELETE FROM search_index;
+         DELETE FROM applied_events;
          DELETE FROM reviews;
          DELETE FROM review_file_state;",
     )
@@ -1439,21 +1815,50 @@ pub fn apply_event(db: &ProjectionDb, event: &EventEnvelope) -> Result<()> {
 }
 
 /// Internal event application using a generic connection/transaction.
+///
+/// Events with an `event_id` are applied at most once, so replaying a log
+/// that contains the same event twice (e.g. after a merge) is a no-op.
 fn apply_event_inner(conn: &Connection, envelope: &EventEnvelope) -> Result<()> {
+    if let Some(event_id) = &envelope.event_id {
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO applied_events (event_id, review_id)
+                 VALUES (?1, COALESCE(?2, (SELECT review_id FROM threads WHERE thread_id = ?3)))",
+                params![
+                    event_id,
+                    event_review_id(&envelope.event),
+                    event_thread_id(&envelope.event)
+                ],
+            )
+            .context("Failed to record applied event")?;
+        if inserted == 0 {
+            tracing::debug!(event_id = %event_id, "Skipping already-applied event");
+            return Ok(());
+        }
+    }
+
     let ts = &envelope.ts;
     let author = &envelope.author;
 
     match &envelope.event {
         Event::ReviewCreated(e) => apply_review_created(conn, e, author, ts),
         Event::ReviewersRequested(e) => apply_reviewers_requested(conn, e, author, ts),
+        Event::ReviewersUnrequested(e) => apply_reviewers_unrequested(conn, e),
         Event::ReviewerVoted(e) => apply_reviewer_voted(conn, e, author, ts),
+        Event::ReviewerVoteRetracted(e) => apply_reviewer_vote_retracted(conn, e, author, ts),
         Event::ReviewApproved(e) => apply_review_approved(conn, e, author, ts),
         Event::ReviewMerged(e) => apply_review_merged(conn, e, author, ts),
         Event::ReviewAbandoned(e) => apply_review_abandoned(conn, e, author, ts),
+        Event::ReviewReopened(e) => apply_review_reopened(conn, e, author, ts),
+        Event::ReviewUpdated(e) => apply_review_updated(conn, e, author, ts),
+        Event::ReviewEdited(e) => apply_review_edited(conn, e, author, ts),
         Event::ThreadCreated(e) => apply_thread_created(conn, e, author, ts),
         Event::ThreadResolved(e) => apply_thread_resolved(conn, e, author, ts),
         Event::ThreadReopened(e) => apply_thread_reopened(conn, e, author, ts),
+        Event::ThreadKindChanged(e) => apply_thread_kind_changed(conn, e, author),
         Event::CommentAdded(e) => apply_comment_added(conn, e, author, ts),
+        Event::CommentEdited(e) => apply_comment_edited(conn, e, author, ts),
+        Event::CommentRetracted(e) => apply_comment_retracted(conn, e, author, ts),
     }
 }
 
@@ -1470,8 +1875,8 @@ fn apply_review_created(
     conn.execute(
         "INSERT OR IGNORE INTO reviews (
             review_id, jj_change_id, scm_kind, scm_anchor, initial_commit, title, description,
-            author, created_at, status
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'open')",
+            author, created_at, status, depends_on
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'open', ?)",
         params![
             event.review_id,
             event.jj_change_id,
@@ -1482,9 +1887,10 @@ fn apply_review_created(
             event.description,
             author,
             ts.to_rfc3339(),
+            event.depends_on,
         ],
     )?;
-    Ok(())
+    index_review(conn, &event.review_id)
 }
 
 fn apply_reviewers_requested(
@@ -1498,7 +1904,8 @@ fn apply_reviewers_requested(
         conn.execute(
             "INSERT INTO review_reviewers (
                 review_id, reviewer, requested_at, requested_by
-            ) VALUES (?, ?, ?, ?)
+            )
+            VALUES (?1, ?2, ?3, ?4)
             ON CONFLICT (review_id, reviewer) DO UPDATE SET
                 requested_at = excluded.requested_at,
                 requested_by = excluded.requested_by",
@@ -1508,31 +1915,69 @@ fn apply_reviewers_requested(
     Ok(())
 }
 
+fn apply_reviewers_unrequested(conn: &Connection, event: &ReviewersUnrequested) -> Result<()> {
+    for reviewer in &event.reviewers {
+        conn.execute(
+            "DELETE FROM review_reviewers WHERE review_id = ? AND reviewer = ?",
+            params![event.review_id, reviewer],
+        )?;
+    }
+    Ok(())
+}
+
 fn apply_reviewer_voted(
     conn: &Connection,
     event: &ReviewerVoted,
     author: &str,
     ts: &DateTime<Utc>,
 ) -> Result<()> {
-    // Insert or replace vote (a reviewer can change their vote)
+    // Insert or replace vote (a reviewer can change their vote). Events are
+    // applied in causal order, so the last one applied is the latest.
     conn.execute(
-        "INSERT INTO reviewer_votes (review_id, reviewer, vote, reason, voted_at)
-         VALUES (?, ?, ?, ?, ?)
+        "INSERT INTO reviewer_votes (review_id, reviewer, vote, reason, voted_at, commit_hash)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
          ON CONFLICT (review_id, reviewer) DO UPDATE SET
              vote = excluded.vote,
              reason = excluded.reason,
-             voted_at = excluded.voted_at",
+             voted_at = excluded.voted_at,
+             commit_hash = excluded.commit_hash",
         params![
             event.review_id,
             author,
             event.vote.to_string(),
             event.reason,
             ts.to_rfc3339(),
+            event.commit,
         ],
     )?;
     Ok(())
 }
 
+fn apply_reviewer_vote_retracted(
+    conn: &Connection,
+    event: &ReviewerVoteRetracted,
+    author: &str,
+    ts: &DateTime<Utc>,
+) -> Result<()> {
+    conn.execute(
+        "DELETE FROM reviewer_votes WHERE review_id = ? AND reviewer = ?",
+        params![event.review_id, author],
+    )?;
+    // Approval rested on the LGTMs, so without any left the review is open again
+    conn.execute(
+        "UPDATE reviews SET
+            status = 'open',
+            status_changed_at = ?1,
+            status_changed_by = ?2
+        WHERE review_id = ?3 AND status = 'approved'
+          AND NOT EXISTS (
+            SELECT 1 FROM reviewer_votes WHERE review_id = ?3 AND vote = 'lgtm'
+          )",
+        params![ts.to_rfc3339(), author, event.review_id],
+    )?;
+    Ok(())
+}
+
 fn apply_review_approved(
     conn: &Connection,
     event: &ReviewApproved,
@@ -1577,15 +2022,100 @@ fn apply_review_abandoned(
     conn.execute(
         "UPDATE reviews SET
             status = 'abandoned',
-            status_changed_at = ?,
-            status_changed_by = ?,
-            abandon_reason = ?
-        WHERE review_id = ? AND status IN ('open', 'approved')",
+            status_changed_at = ?1,
+            status_changed_by = ?2,
+            abandon_reason = ?3
+        WHERE review_id = ?4 AND status IN ('open', 'approved')",
         params![ts.to_rfc3339(), author, event.reason, event.review_id],
     )?;
     Ok(())
 }
 
+fn apply_review_reopened(
+    conn: &Connection,
+    event: &ReviewReopened,
+    author: &str,
+    ts: &DateTime<Utc>,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE reviews SET
+            status = 'open',
+            status_changed_at = ?1,
+            status_changed_by = ?2,
+            abandon_reason = NULL
+        WHERE review_id = ?3 AND status = 'abandoned'",
+        params![ts.to_rfc3339(), author, event.review_id],
+    )?;
+    Ok(())
+}
+
+fn apply_review_updated(
+    conn: &Connection,
+    event: &ReviewUpdated,
+    author: &str,
+    ts: &DateTime<Utc>,
+) -> Result<()> {
+    // Revision 1 is the review's initial_commit, so pushed revisions start at 2.
+    // Keep the number the event was written with, so a rebuild or merged log
+    // can't renumber revisions. Only when it's missing, or a concurrent update
+    // already took it, does this one go after the latest.
+    conn.execute(
+        "INSERT INTO review_revisions (
+            review_id, revision, commit_hash, note, author, created_at
+        )
+        SELECT ?1,
+               CASE WHEN ?6 > 1 AND NOT EXISTS (
+                   SELECT 1 FROM review_revisions WHERE review_id = ?1 AND revision = ?6
+               ) THEN ?6 ELSE COALESCE(MAX(revision), 1) + 1 END,
+               ?2, ?3, ?4, ?5
+        FROM review_revisions WHERE review_id = ?1",
+        params![
+            event.review_id,
+            event.commit,
+            event.note,
+            author,
+            ts.to_rfc3339(),
+            event.revision
+        ],
+    )?;
+    Ok(())
+}
+
+fn apply_review_edited(
+    conn: &Connection,
+    event: &ReviewEdited,
+    author: &str,
+    ts: &DateTime<Utc>,
+) -> Result<()> {
+    // Only the review's author may retitle or redescribe it
+    if event.title.is_some() || event.description.is_some() {
+        conn.execute(
+            "UPDATE reviews SET
+                title = COALESCE(?1, title),
+                description = CASE WHEN ?2 IS NULL THEN description ELSE NULLIF(?2, '') END
+             WHERE review_id = ?3 AND author = ?4",
+            params![event.title, event.description, event.review_id, author],
+        )?;
+    }
+
+    let ts_str = ts.to_rfc3339();
+    for label in &event.add_labels {
+        conn.execute(
+            "INSERT OR IGNORE INTO review_labels (review_id, label, added_by, added_at)
+             VALUES (?, ?, ?, ?)",
+            params![event.review_id, label, author, ts_str],
+        )?;
+    }
+    for label in &event.remove_labels {
+        conn.execute(
+            "DELETE FROM review_labels WHERE review_id = ? AND label = ?",
+            params![event.review_id, label],
+        )?;
+    }
+
+    index_review(conn, &event.review_id)
+}
+
 // ============================================================================
 // Thread Event Handlers
 // ============================================================================
@@ -1601,12 +2131,19 @@ fn apply_thread_created(
         CodeSelection::Range { start, end } => ("range", *start, Some(*end)),
     };
 
+    let anchor_snapshot = event
+        .anchor
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .context("Failed to serialize anchor snapshot")?;
+
     conn.execute(
         "INSERT OR IGNORE INTO threads (
             thread_id, review_id, file_path,
             selection_type, selection_start, selection_end,
-            commit_hash, author, created_at, status
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'open')",
+            commit_hash, author, created_at, status, anchor_snapshot, kind
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'open', ?, ?)",
         params![
             event.thread_id,
             event.review_id,
@@ -1617,6 +2154,8 @@ fn apply_thread_created(
             event.commit_hash,
             author,
             ts.to_rfc3339(),
+            anchor_snapshot,
+            event.kind.map(|k| k.as_str()),
         ],
     )?;
     Ok(())
@@ -1658,6 +2197,25 @@ fn apply_thread_reopened(
     Ok(())
 }
 
+fn apply_thread_kind_changed(
+    conn: &Connection,
+    event: &ThreadKindChanged,
+    author: &str,
+) -> Result<()> {
+    // Only the thread's author may downgrade it from blocking, and only while
+    // the review is still open or approved
+    conn.execute(
+        "UPDATE threads SET kind = ?1
+         WHERE thread_id = ?2
+           AND (kind IS NOT 'blocking' OR ?1 IS 'blocking' OR author = ?3)
+           AND review_id IN (
+               SELECT review_id FROM reviews WHERE status IN ('open', 'approved')
+           )",
+        params![event.kind.map(|k| k.as_str()), event.thread_id, author],
+    )?;
+    Ok(())
+}
+
 // ============================================================================
 // Comment Event Handlers
 // ============================================================================
@@ -1668,17 +2226,25 @@ fn apply_comment_added(
     author: &str,
     ts: &DateTime<Utc>,
 ) -> Result<()> {
+    let suggestion = event
+        .suggestion
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .context("Failed to serialize suggestion")?;
+
     // Insert the comment
     conn.execute(
         "INSERT OR IGNORE INTO comments (
-            comment_id, thread_id, body, author, created_at
-        ) VALUES (?, ?, ?, ?, ?)",
+            comment_id, thread_id, body, author, created_at, suggestion
+        ) VALUES (?, ?, ?, ?, ?, ?)",
         params![
             event.comment_id,
             event.thread_id,
             event.body,
             author,
             ts.to_rfc3339(),
+            suggestion,
         ],
     )?;
     // Increment the thread's next_comment_number for future comments
@@ -1686,6 +2252,124 @@ fn apply_comment_added(
         "UPDATE threads SET next_comment_number = next_comment_number + 1 WHERE thread_id = ?",
         params![event.thread_id],
     )?;
+    index_comment(conn, &event.comment_id)
+}
+
+fn apply_comment_edited(
+    conn: &Connection,
+    event: &CommentEdited,
+    author: &str,
+    ts: &DateTime<Utc>,
+) -> Result<()> {
+    // Only the original author may edit, and retracted comments stay retracted.
+    let previous_body: Option<String> = conn
+        .query_row(
+            "SELECT body FROM comments
+             WHERE comment_id = ? AND author = ? AND retracted_at IS NULL",
+            params![event.comment_id, author],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    let Some(previous_body) = previous_body else {
+        return Ok(());
+    };
+
+    // Keyed on (comment_id, edited_at) so re-applying an edit is a no-op
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO comment_edits (
+            comment_id, previous_body, body, edited_by, edited_at
+        ) VALUES (?, ?, ?, ?, ?)",
+        params![
+            event.comment_id,
+            previous_body,
+            event.body,
+            author,
+            ts.to_rfc3339(),
+        ],
+    )?;
+    if inserted == 0 {
+        return Ok(());
+    }
+
+    conn.execute(
+        "UPDATE comments SET body = ?, edited_at = ? WHERE comment_id = ?",
+        params![event.body, ts.to_rfc3339(), event.comment_id],
+    )?;
+    index_comment(conn, &event.comment_id)
+}
+
+fn apply_comment_retracted(
+    conn: &Connection,
+    event: &CommentRetracted,
+    author: &str,
+    ts: &DateTime<Utc>,
+) -> Result<()> {
+    // The text stays in the event log, but the projection stops serving it
+    let retracted = conn.execute(
+        "UPDATE comments SET
+            retracted_at = ?,
+            retract_reason = ?,
+            body = '',
+            suggestion = NULL
+        WHERE comment_id = ? AND author = ? AND retracted_at IS NULL",
+        params![ts.to_rfc3339(), event.reason, event.comment_id, author],
+    )?;
+    if retracted > 0 {
+        conn.execute(
+            "UPDATE comment_edits SET previous_body = '', body = '' WHERE comment_id = ?",
+            params![event.comment_id],
+        )?;
+    }
+    index_comment(conn, &event.comment_id)
+}
+
+// ============================================================================
+// Search index
+// ============================================================================
+
+const INDEX_REVIEW_SQL: &str = "
+INSERT INTO search_index (title, body, kind, review_id, author, created_at)
+SELECT title,
+       COALESCE(description, '') || COALESCE(
+           (SELECT ' ' || group_concat(label, ' ') FROM review_labels l
+            WHERE l.review_id = reviews.review_id), ''),
+       'review', review_id, author, created_at
+FROM reviews";
+
+const INDEX_COMMENT_SQL: &str = "
+INSERT INTO search_index (
+    title, body, kind, review_id, thread_id, comment_id, author, file_path, created_at
+)
+SELECT '', c.body, 'comment', t.review_id, c.thread_id, c.comment_id, c.author,
+       t.file_path, c.created_at
+FROM comments c
+JOIN threads t ON t.thread_id = c.thread_id
+WHERE c.retracted_at IS NULL";
+
+/// Re-derive a review's search row from the `reviews` table.
+fn index_review(conn: &Connection, review_id: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM search_index WHERE kind = 'review' AND review_id = ?",
+        params![review_id],
+    )?;
+    conn.execute(
+        &format!("{INDEX_REVIEW_SQL} WHERE review_id = ?"),
+        params![review_id],
+    )?;
+    Ok(())
+}
+
+/// Re-derive a comment's search row (retracted comments are dropped).
+fn index_comment(conn: &Connection, comment_id: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM search_index WHERE kind = 'comment' AND comment_id = ?",
+        params![comment_id],
+    )?;
+    conn.execute(
+        &format!("{INDEX_COMMENT_SQL} AND c.comment_id = ?"),
+        params![comment_id],
+    )?;
     Ok(())
 }
 
@@ -1697,14 +2381,22 @@ const fn event_type_name(event: &Event) -> &'static str {
     match event {
         Event::ReviewCreated(_) => "ReviewCreated",
         Event::ReviewersRequested(_) => "ReviewersRequested",
+        Event::ReviewersUnrequested(_) => "ReviewersUnrequested",
         Event::ReviewerVoted(_) => "ReviewerVoted",
+        Event::ReviewerVoteRetracted(_) => "ReviewerVoteRetracted",
         Event::ReviewApproved(_) => "ReviewApproved",
         Event::ReviewMerged(_) => "ReviewMerged",
         Event::ReviewAbandoned(_) => "ReviewAbandoned",
+        Event::ReviewReopened(_) => "ReviewReopened",
+        Event::ReviewUpdated(_) => "ReviewUpdated",
+        Event::ReviewEdited(_) => "ReviewEdited",
         Event::ThreadCreated(_) => "ThreadCreated",
         Event::ThreadResolved(_) => "ThreadResolved",
         Event::ThreadReopened(_) => "ThreadReopened",
+        Event::ThreadKindChanged(_) => "ThreadKindChanged",
         Event::CommentAdded(_) => "CommentAdded",
+        Event::CommentEdited(_) => "CommentEdited",
+        Event::CommentRetracted(_) => "CommentRetracted",
     }
 }
 
@@ -1739,7 +2431,8 @@ CREATE TABLE IF NOT EXISTS reviews (
         CHECK (status IN ('open', 'approved', 'merged', 'abandoned')),
     status_changed_at TEXT,
     status_changed_by TEXT,
-    abandon_reason TEXT
+    abandon_reason TEXT,
+    depends_on TEXT
 );
 
 CREATE INDEX IF NOT EXISTS idx_reviews_status ON reviews(status);
@@ -1757,18 +2450,48 @@ CREATE TABLE IF NOT EXISTS review_reviewers (
 
 CREATE INDEX IF NOT EXISTS idx_reviewers_reviewer ON review_reviewers(reviewer);
 
+-- APPLIED EVENTS
+-- IDs of events already projected, so duplicated log lines are applied once
+CREATE TABLE IF NOT EXISTS applied_events (
+    event_id TEXT PRIMARY KEY,
+    review_id TEXT
+);
+
 -- REVIEWER VOTES
 CREATE TABLE IF NOT EXISTS reviewer_votes (
     review_id TEXT NOT NULL REFERENCES reviews(review_id),
-    reviewer TEXT NOT NULL,
-    vote TEXT NOT NULL CHECK (vote IN ('lgtm', 'block')),
-    reason TEXT,
-    voted_at TEXT NOT NULL,
-    PRIMARY KEY (review_id, reviewer)
+    reviewer TEXT NOT NULL,
+    vote TEXT NOT NULL CHECK (vote IN ('lgtm', 'block')),
+    reason TEXT,
+    voted_at TEXT NOT NULL,
+    commit_hash TEXT,
+    PRIMARY KEY (review_id, reviewer)
+);
+
+CREATE INDEX IF NOT EXISTS idx_votes_review ON reviewer_votes(review_id);
+CREATE INDEX IF NOT EXISTS idx_votes_vote ON reviewer_votes(vote);
+
+-- REVIEW REVISIONS (revision 1 is the review's initial_commit and is not stored)
+CREATE TABLE IF NOT EXISTS review_revisions (
+    review_id TEXT NOT NULL REFERENCES reviews(review_id),
+    revision INTEGER NOT NULL,
+    commit_hash TEXT NOT NULL,
+    note TEXT,
+    author TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (review_id, revision)
+);
+
+-- REVIEW LABELS
+CREATE TABLE IF NOT EXISTS review_labels (
+    review_id TEXT NOT NULL REFERENCES reviews(review_id),
+    label TEXT NOT NULL,
+    added_by TEXT NOT NULL,
+    added_at TEXT NOT NULL,
+    PRIMARY KEY (review_id, label)
 );
 
-CREATE INDEX IF NOT EXISTS idx_votes_review ON reviewer_votes(review_id);
-CREATE INDEX IF NOT EXISTS idx_votes_vote ON reviewer_votes(vote);
+CREATE INDEX IF NOT EXISTS idx_review_labels_label ON review_labels(label);
 
 -- THREADS
 CREATE TABLE IF NOT EXISTS threads (
@@ -1787,7 +2510,9 @@ CREATE TABLE IF NOT EXISTS threads (
     status_changed_by TEXT,
     resolve_reason TEXT,
     reopen_reason TEXT,
-    next_comment_number INTEGER NOT NULL DEFAULT 1
+    next_comment_number INTEGER NOT NULL DEFAULT 1,
+    anchor_snapshot TEXT,
+    kind TEXT CHECK (kind IN ('blocking', 'nit', 'question', 'praise'))
 );
 
 CREATE INDEX IF NOT EXISTS idx_threads_review_id ON threads(review_id);
@@ -1801,11 +2526,40 @@ CREATE TABLE IF NOT EXISTS comments (
     thread_id TEXT NOT NULL REFERENCES threads(thread_id),
     body TEXT NOT NULL,
     author TEXT NOT NULL,
-    created_at TEXT NOT NULL
+    created_at TEXT NOT NULL,
+    edited_at TEXT,
+    retracted_at TEXT,
+    retract_reason TEXT,
+    suggestion TEXT
 );
 
 CREATE INDEX IF NOT EXISTS idx_comments_thread_id ON comments(thread_id);
 
+-- COMMENT EDIT HISTORY
+CREATE TABLE IF NOT EXISTS comment_edits (
+    comment_id TEXT NOT NULL REFERENCES comments(comment_id),
+    previous_body TEXT NOT NULL,
+    body TEXT NOT NULL,
+    edited_by TEXT NOT NULL,
+    edited_at TEXT NOT NULL,
+    PRIMARY KEY (comment_id, edited_at)
+);
+
+-- FULL-TEXT SEARCH over review titles/descriptions and comment bodies.
+-- Rows are derived from the tables above (see index_review/index_comment).
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+    title,
+    body,
+    kind UNINDEXED,
+    review_id UNINDEXED,
+    thread_id UNINDEXED,
+    comment_id UNINDEXED,
+    author UNINDEXED,
+    file_path UNINDEXED,
+    created_at UNINDEXED,
+    tokenize = 'porter unicode61'
+);
+
 -- VIEWS
 -- Note: open_thread_count only counts threads that are truly actionable.
 -- Threads on merged/abandoned reviews are NOT counted as open, even if
@@ -1911,6 +2665,7 @@ mod tests {
                 initial_commit: "commit456".to_string(),
                 title: format!("Review {review_id}"),
                 description: Some("Test description".to_string()),
+                depends_on: None,
             }),
         )
     }
@@ -1924,6 +2679,8 @@ mod tests {
                 file_path: "src/main.rs".to_string(),
                 selection: CodeSelection::range(10, 20),
                 commit_hash: "abc123".to_string(),
+                anchor: None,
+                kind: None,
             }),
         )
     }
@@ -2112,6 +2869,106 @@ mod tests {
         assert_eq!(reason, Some("No longer needed".to_string()));
     }
 
+    #[test]
+    fn test_apply_review_updated_numbers_revisions() {
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+
+        apply_event(&db, &make_review_created("cr-001")).unwrap();
+
+        for (commit, note) in [
+            ("rev2commit", Some("address feedback")),
+            ("rev3commit", None),
+        ] {
+            apply_event(
+                &db,
+                &EventEnvelope::new(
+                    "author",
+                    Event::ReviewUpdated(ReviewUpdated {
+                        review_id: "cr-001".to_string(),
+                        commit: commit.to_string(),
+                        note: note.map(str::to_string),
+                        revision: None,
+                    }),
+                ),
+            )
+            .unwrap();
+        }
+
+        let mut stmt = db
+            .conn()
+            .prepare(
+                "SELECT revision, commit_hash, note FROM review_revisions
+                 WHERE review_id = ? ORDER BY revision",
+            )
+            .unwrap();
+        let rows: Vec<(i64, String, Option<String>)> = stmt
+            .query_map(params!["cr-001"], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    2,
+                    "rev2commit".to_string(),
+                    Some("address feedback".to_string())
+                ),
+                (3, "rev3commit".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_review_updated_keeps_event_revision() {
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+
+        apply_event(&db, &make_review_created("cr-001")).unwrap();
+
+        // Revision 3 arrives first (e.g. merged from another branch), and a
+        // concurrent push also claims 3
+        for (commit, revision) in [("rev3", 3), ("rev2", 2), ("rev3-concurrent", 3)] {
+            apply_event(
+                &db,
+                &EventEnvelope::new(
+                    "author",
+                    Event::ReviewUpdated(ReviewUpdated {
+                        review_id: "cr-001".to_string(),
+                        commit: commit.to_string(),
+                        note: None,
+                        revision: Some(revision),
+                    }),
+                ),
+            )
+            .unwrap();
+        }
+
+        let rows: Vec<(i64, String)> = db
+            .conn()
+            .prepare(
+                "SELECT revision, commit_hash FROM review_revisions
+                 WHERE review_id = 'cr-001' ORDER BY revision",
+            )
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (2, "rev2".to_string()),
+                (3, "rev3".to_string()),
+                (4, "rev3-concurrent".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_apply_thread_lifecycle() {
         let db = ProjectionDb::open_in_memory().unwrap();
@@ -2332,6 +3189,8 @@ mod tests {
                 file_path: "src/lib.rs".to_string(),
                 selection: CodeSelection::line(42),
                 commit_hash: "abc123".to_string(),
+                anchor: None,
+                kind: None,
             }),
         );
         apply_event(&db, &event).unwrap();
@@ -2434,6 +3293,7 @@ mod tests {
                 review_id: review_id.to_string(),
                 vote: VoteType::Block,
                 reason: Some(reason.to_string()),
+                commit: None,
             }),
         )
     }
@@ -2446,6 +3306,7 @@ mod tests {
                 review_id: review_id.to_string(),
                 vote: VoteType::Lgtm,
                 reason: Some("Looks good".to_string()),
+                commit: None,
             }),
         )
     }
@@ -3243,6 +4104,7 @@ mod tests {
                     review_id: "cr-001".to_string(),
                     vote: VoteType::Block,
                     reason: Some("Needs fixes".to_string()),
+                    commit: None,
                 }),
             ),
         )
@@ -3269,6 +4131,7 @@ mod tests {
                     review_id: "cr-001".to_string(),
                     vote: VoteType::Lgtm,
                     reason: Some("Looks good now".to_string()),
+                    commit: None,
                 }),
             ),
         )
@@ -3309,6 +4172,111 @@ mod tests {
         assert_eq!(has_blocks, 0);
     }
 
+    #[test]
+    fn test_causally_later_changes_win_despite_clock_skew() {
+        use crate::events::VoteType;
+
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+        let log = crate::log::ReviewLog::new(seal_root, "cr-001").unwrap();
+        // Each event is written by an agent whose clock is further behind
+        let mut hours_behind = 0;
+        let mut append = |author: &str, event: Event| {
+            let mut envelope = EventEnvelope::new(author, event);
+            envelope.ts -= chrono::Duration::hours(hours_behind);
+            hours_behind += 1;
+            log.append(&envelope).unwrap();
+        };
+        let vote = |vote| {
+            Event::ReviewerVoted(ReviewerVoted {
+                review_id: "cr-001".to_string(),
+                vote,
+                reason: None,
+                commit: None,
+            })
+        };
+
+        append("author", make_review_created("cr-001").event);
+        append(
+            "author",
+            Event::ReviewersRequested(ReviewersRequested {
+                review_id: "cr-001".to_string(),
+                reviewers: vec!["alice".to_string(), "bob".to_string()],
+            }),
+        );
+        append("alice", vote(VoteType::Block));
+        append("alice", vote(VoteType::Lgtm));
+        append("bob", vote(VoteType::Lgtm));
+        append(
+            "bob",
+            Event::ReviewerVoteRetracted(ReviewerVoteRetracted {
+                review_id: "cr-001".to_string(),
+                reason: None,
+            }),
+        );
+        append(
+            "author",
+            Event::ReviewersUnrequested(ReviewersUnrequested {
+                review_id: "cr-001".to_string(),
+                reviewers: vec!["bob".to_string()],
+            }),
+        );
+        append(
+            "author",
+            Event::ReviewAbandoned(ReviewAbandoned {
+                review_id: "cr-001".to_string(),
+                reason: None,
+            }),
+        );
+        append(
+            "author",
+            Event::ReviewReopened(ReviewReopened {
+                review_id: "cr-001".to_string(),
+                reason: Some("Still needed".to_string()),
+            }),
+        );
+
+        let check = |db: &ProjectionDb| {
+            let votes: Vec<(String, String)> = db
+                .conn()
+                .prepare("SELECT reviewer, vote FROM reviewer_votes WHERE review_id = 'cr-001'")
+                .unwrap()
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .unwrap()
+                .collect::<rusqlite::Result<_>>()
+                .unwrap();
+            assert_eq!(votes, vec![("alice".to_string(), "lgtm".to_string())]);
+            let reviewers: Vec<String> = db
+                .conn()
+                .prepare("SELECT reviewer FROM review_reviewers WHERE review_id = 'cr-001'")
+                .unwrap()
+                .query_map([], |row| row.get(0))
+                .unwrap()
+                .collect::<rusqlite::Result<_>>()
+                .unwrap();
+            assert_eq!(reviewers, vec!["alice".to_string()]);
+            let status: String = db
+                .conn()
+                .query_row(
+                    "SELECT status FROM reviews WHERE review_id = 'cr-001'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(status, "open");
+        };
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+        rebuild_from_review_logs(&db, seal_root).unwrap();
+        check(&db);
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+        sync_from_review_logs(&db, seal_root).unwrap();
+        check(&db);
+    }
+
     /// bd-2m6: Test orphan detection saves lost reviews to backup file
     /// when truncation is detected and seal_dir is provided.
     #[test]
@@ -3524,6 +4492,34 @@ mod tests {
                 )
                 .unwrap();
             assert_eq!(next_num, 1, "Existing row should have default value 1");
+
+            // Comment edit/retract columns are added to the old comments table
+            let comment_columns: i64 = db
+                .conn()
+                .query_row(
+                    "SELECT COUNT(*) FROM pragma_table_info('comments')
+                     WHERE name IN ('edited_at', 'retracted_at', 'retract_reason')",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(
+                comment_columns, 3,
+                "Migration should add comment edit columns"
+            );
+
+            let has_vote_commit: bool = db
+                .conn()
+                .query_row(
+                    "SELECT COUNT(*) > 0 FROM pragma_table_info('reviewer_votes') WHERE name = 'commit_hash'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert!(
+                has_vote_commit,
+                "Migration should add reviewer_votes.commit_hash"
+            );
         }
 
         // Verify migration is idempotent (can run again without error)
@@ -3544,6 +4540,7 @@ mod tests {
                 comment_id: comment_id.to_string(),
                 thread_id: thread_id.to_string(),
                 body: "Test comment".to_string(),
+                suggestion: None,
             }),
         )
     }
@@ -4033,6 +5030,108 @@ mod tests {
         assert_eq!(comment_count, 1, "should have 1 comment");
     }
 
+    #[test]
+    fn test_per_file_sync_skips_torn_write() {
+        use std::io::Write;
+
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+
+        let log = crate::log::ReviewLog::new(seal_root, "cr-torn").unwrap();
+        log.append(&make_review_created("cr-torn")).unwrap();
+
+        // Half-written final line from an interrupted append
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(log.path())
+            .unwrap();
+        file.write_all(br#"{"ts":"2026-01-01T00:00:00Z","event":"Thre"#)
+            .unwrap();
+        drop(file);
+
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert_eq!(report.applied, 1);
+        assert!(
+            report.anomalies.is_empty(),
+            "torn line is not a parse error"
+        );
+
+        // The next append repairs the log and sync picks it up as growth
+        log.append(&make_thread_created("th-torn", "cr-torn"))
+            .unwrap();
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert_eq!(report.applied, 1);
+        assert!(report.anomalies.is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_event_ids_applied_once() {
+        use std::io::Write;
+
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+
+        let log = crate::log::ReviewLog::new(seal_root, "cr-dup").unwrap();
+        log.append(&make_review_created("cr-dup")).unwrap();
+        log.append(&EventEnvelope::new(
+            "test_author",
+            Event::ReviewUpdated(ReviewUpdated {
+                review_id: "cr-dup".to_string(),
+                commit: "rev2".to_string(),
+                note: None,
+                revision: Some(2),
+            }),
+        ))
+        .unwrap();
+
+        // The same line again, as a union merge of two branches would leave it,
+        // plus a legacy event without an ID
+        let content = std::fs::read_to_string(log.path()).unwrap();
+        let duplicate = content.lines().last().unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(log.path())
+            .unwrap();
+        writeln!(file, "{duplicate}").unwrap();
+        writeln!(
+            file,
+            r#"{{"ts":"2099-01-01T00:00:00Z","author":"legacy","event":"ReviewUpdated","data":{{"review_id":"cr-dup","commit":"rev3"}}}}"#
+        )
+        .unwrap();
+        drop(file);
+
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert!(report.anomalies.is_empty());
+
+        let revisions: Vec<String> = db
+            .conn()
+            .prepare("SELECT commit_hash FROM review_revisions WHERE review_id = 'cr-dup' ORDER BY revision")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(revisions, vec!["rev2", "rev3"]);
+
+        // A full rebuild gives the same result
+        rebuild_from_review_logs(&db, seal_root).unwrap();
+        let count: i64 = db
+            .conn()
+            .query_row(
+                "SELECT COUNT(*) FROM review_revisions WHERE review_id = 'cr-dup'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
     #[test]
     fn test_per_file_sync_shrunk() {
         // Truncate file, re-sync, projection data preserved, anomaly recorded
@@ -4087,6 +5186,188 @@ mod tests {
         assert_eq!(comment_count, 1, "comment preserved");
     }
 
+    #[test]
+    fn test_per_file_sync_reports_invalid_signature() {
+        use ed25519_dalek::SigningKey;
+
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+
+        // Register a public key for the test author
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let keys_dir = crate::signing::public_keys_dir(seal_root);
+        std::fs::create_dir_all(&keys_dir).unwrap();
+        let public_hex = crate::signing::encode_hex(key.verifying_key().as_bytes());
+        std::fs::write(keys_dir.join("test_author.pub"), public_hex).unwrap();
+
+        // One correctly signed event, one whose payload changed after signing
+        let sign = |event: &EventEnvelope| {
+            crate::signing::sign_line(&event.to_json_line().unwrap(), &key).unwrap()
+        };
+        let good = sign(&make_review_created("cr-sig"));
+        let forged =
+            sign(&make_thread_created("th-sig", "cr-sig")).replace("src/main.rs", "src/lib.rs");
+        let log = crate::log::ReviewLog::new(seal_root, "cr-sig").unwrap();
+        std::fs::create_dir_all(log.path().parent().unwrap()).unwrap();
+        std::fs::write(log.path(), format!("{good}\n{forged}\n")).unwrap();
+
+        // Events are still applied; the bad signature is reported
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert_eq!(report.applied, 2);
+        assert_eq!(report.anomalies.len(), 1);
+        assert_eq!(report.anomalies[0].kind, AnomalyKind::InvalidSignature);
+        assert!(report.anomalies[0].detail.starts_with("line 2:"));
+    }
+
+    #[test]
+    fn test_per_file_sync_reports_unsigned_event_from_key_holder() {
+        use ed25519_dalek::SigningKey;
+
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let keys_dir = crate::signing::public_keys_dir(seal_root);
+        std::fs::create_dir_all(&keys_dir).unwrap();
+        let public_hex = crate::signing::encode_hex(key.verifying_key().as_bytes());
+        std::fs::write(keys_dir.join("test_author.pub"), public_hex).unwrap();
+
+        // The key holder's event with `sig` left off, and one from an agent
+        // without a key, which may be unsigned
+        let created = make_review_created("cr-unsigned").to_json_line().unwrap();
+        let mut thread = make_thread_created("th-unsigned", "cr-unsigned");
+        thread.author = "keyless".to_string();
+        let thread = thread.to_json_line().unwrap();
+        let log = crate::log::ReviewLog::new(seal_root, "cr-unsigned").unwrap();
+        std::fs::create_dir_all(log.path().parent().unwrap()).unwrap();
+        std::fs::write(log.path(), format!("{created}\n{thread}\n")).unwrap();
+
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert_eq!(report.applied, 2);
+        assert_eq!(report.anomalies.len(), 1, "{:?}", report.anomalies);
+        assert_eq!(report.anomalies[0].kind, AnomalyKind::InvalidSignature);
+        assert_eq!(
+            report.anomalies[0].detail,
+            "line 1: unsigned, but test_author has a registered signing key"
+        );
+    }
+
+    #[test]
+    fn test_per_file_sync_replays_merged_log() {
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+
+        let created = make_review_created("cr-merge");
+        let ours = make_thread_created("th-ours", "cr-merge");
+        let log = crate::log::ReviewLog::new(seal_root, "cr-merge").unwrap();
+        log.append_batch(&[created.clone(), ours.clone()]).unwrap();
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert_eq!(report.applied, 2);
+
+        // A merge brings in the other side's thread ahead of ours
+        let theirs = make_thread_created("th-theirs", "cr-merge");
+        let content: String = [&created, &theirs, &ours]
+            .iter()
+            .map(|e| format!("{}\n", e.to_json_line().unwrap()))
+            .collect();
+        std::fs::write(log.path(), content).unwrap();
+
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert!(report.anomalies.is_empty(), "{:?}", report.anomalies);
+        let threads: i64 = db
+            .conn()
+            .query_row(
+                "SELECT COUNT(*) FROM threads WHERE review_id = 'cr-merge'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(threads, 2);
+    }
+
+    #[test]
+    fn test_rebuild_orders_by_clock_despite_clock_skew() {
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+        let log = crate::log::ReviewLog::new(seal_root, "cr-skew").unwrap();
+
+        log.append(&make_review_created("cr-skew")).unwrap();
+        log.append(&make_thread_created("th-skew", "cr-skew"))
+            .unwrap();
+        // Written by an agent whose clock is an hour behind
+        let mut comment = make_comment_added("th-skew.1", "th-skew");
+        comment.ts -= chrono::Duration::hours(1);
+        log.append(&comment).unwrap();
+
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+        assert_eq!(rebuild_from_review_logs(&db, seal_root).unwrap(), 3);
+        let comments: i64 = db
+            .conn()
+            .query_row("SELECT COUNT(*) FROM comments", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(comments, 1);
+    }
+
+    #[test]
+    fn test_per_file_sync_reports_concurrent_conflict() {
+        use crate::log::causal::Frontier;
+        use std::io::Write;
+
+        let dir = tempdir().unwrap();
+        let seal_root = dir.path();
+        let db = ProjectionDb::open_in_memory().unwrap();
+        db.init_schema().unwrap();
+
+        let log = crate::log::ReviewLog::new(seal_root, "cr-conc").unwrap();
+        log.append(&make_review_created("cr-conc")).unwrap();
+        log.append(&make_thread_created("th-conc", "cr-conc"))
+            .unwrap();
+        log.append(&make_thread_resolved("th-conc")).unwrap();
+        sync_from_review_logs(&db, seal_root).unwrap();
+
+        // Two clones answer the same resolved thread without seeing each other
+        let base = log.read_all().unwrap();
+        let mut reopen = EventEnvelope::new(
+            "alice",
+            Event::ThreadReopened(ThreadReopened {
+                thread_id: "th-conc".to_string(),
+                reason: None,
+            }),
+        );
+        let mut resolve = make_thread_resolved("th-conc");
+        resolve.author = "bob".to_string();
+        Frontier::of(&base).stamp(&mut reopen);
+        Frontier::of(&base).stamp(&mut resolve);
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(log.path())
+            .unwrap();
+        for event in [&reopen, &resolve] {
+            writeln!(file, "{}", event.to_json_line().unwrap()).unwrap();
+        }
+        drop(file);
+
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert_eq!(report.applied, 2);
+        assert_eq!(report.anomalies.len(), 1, "{:?}", report.anomalies);
+        assert_eq!(report.anomalies[0].kind, AnomalyKind::ConcurrentConflict);
+        assert!(report.anomalies[0].detail.contains("th-conc"));
+
+        // The original resolve and the reopen were ordered, so only one pair
+        // conflicts; a later event that saw both doesn't add another
+        log.append(&make_thread_resolved("th-conc")).unwrap();
+        let report = sync_from_review_logs(&db, seal_root).unwrap();
+        assert!(report.anomalies.is_empty(), "{:?}", report.anomalies);
+    }
+
     #[test]
     fn test_per_file_sync_hash_mismatch() {
         // Replace content, re-sync, projection preserved, anomaly recorded
@@ -9,6 +9,7 @@ use rusqlite::{params, OptionalExtension, Row};
 use serde::Serialize;
 
 use super::ProjectionDb;
+use crate::events::{AnchorSnapshot, Suggestion};
 
 // ============================================================================
 // Query Result Types
@@ -27,6 +28,15 @@ pub struct ReviewSummary {
     pub thread_count: i64,
     pub open_thread_count: i64,
     pub reviewers: Vec<String>,
+    /// Reviewers whose vote was cast against an older commit, e.g. "bob (lgtm)".
+    /// Only populated when the caller checks votes against the SCM.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stale_votes: Vec<String>,
+    /// Review this one is stacked on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
 }
 
 /// Full details of a review.
@@ -50,6 +60,26 @@ pub struct ReviewDetail {
     pub open_thread_count: i64,
     pub reviewers: Vec<String>,
     pub votes: Vec<ReviewerVote>,
+    pub revisions: Vec<ReviewRevision>,
+    /// Open threads marked as blocking; these hold up a merge like a block vote
+    pub blocking_threads: Vec<String>,
+    /// Review this one is stacked on; it has to be merged first
+    pub depends_on: Option<String>,
+    /// Reviews stacked directly on this one
+    pub dependents: Vec<String>,
+    pub labels: Vec<String>,
+}
+
+/// A revision (pushed iteration) of a review.
+///
+/// Revision 1 is always the review's initial commit.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewRevision {
+    pub revision: i64,
+    pub commit_hash: String,
+    pub note: Option<String>,
+    pub author: String,
+    pub created_at: String,
 }
 
 /// A reviewer's vote on a review.
@@ -59,6 +89,11 @@ pub struct ReviewerVote {
     pub vote: String,
     pub reason: Option<String>,
     pub voted_at: String,
+    /// Commit the vote was cast against (absent for votes recorded before this was tracked)
+    pub commit_hash: Option<String>,
+    /// The review has moved to a different commit since this vote was cast.
+    /// Only set when the caller checks votes against the SCM.
+    pub stale: bool,
 }
 
 /// Summary of a thread for list views.
@@ -70,6 +105,14 @@ pub struct ThreadSummary {
     pub selection_end: Option<i64>,
     pub status: String,
     pub comment_count: i64,
+    /// Commit the selection refers to
+    pub commit_hash: String,
+    /// Path recorded on the thread, when `file_path` was updated to follow a rename
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moved_from: Option<String>,
+    /// Severity: "blocking", "nit", "question" or "praise"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
 }
 
 /// Full details of a thread with comments.
@@ -89,6 +132,10 @@ pub struct ThreadDetail {
     pub status_changed_by: Option<String>,
     pub resolve_reason: Option<String>,
     pub reopen_reason: Option<String>,
+    /// Anchored text captured at thread creation (absent for older threads).
+    pub anchor: Option<AnchorSnapshot>,
+    /// Severity: "blocking", "nit", "question" or "praise"
+    pub kind: Option<String>,
     pub comments: Vec<Comment>,
 }
 
@@ -97,8 +144,27 @@ pub struct ThreadDetail {
 pub struct Comment {
     pub comment_id: String,
     pub author: String,
+    /// Empty once the comment is retracted.
     pub body: String,
     pub created_at: String,
+    /// Timestamp of the most recent edit, if the comment was edited.
+    pub edited_at: Option<String>,
+    /// Timestamp of retraction, if the author retracted the comment.
+    pub retracted_at: Option<String>,
+    pub retract_reason: Option<String>,
+    /// Suggested replacement for the thread's selection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<Suggestion>,
+}
+
+/// A single revision in a comment's edit history.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentEdit {
+    pub comment_id: String,
+    pub previous_body: String,
+    pub body: String,
+    pub edited_by: String,
+    pub edited_at: String,
 }
 
 /// A review awaiting the agent's vote.
@@ -123,6 +189,8 @@ pub struct ThreadWithNewResponses {
     pub file_path: String,
     pub selection_start: i64,
     pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
     pub my_last_comment_at: String,
     pub new_response_count: i64,
     pub latest_response_at: String,
@@ -137,16 +205,71 @@ pub struct OpenThreadOnMyReview {
     pub file_path: String,
     pub selection_start: i64,
     pub thread_author: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
     pub comment_count: i64,
     pub latest_comment_at: String,
 }
 
+/// A vote cast against a commit the review may since have moved away from.
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleVote {
+    pub review_id: String,
+    pub title: String,
+    pub author: String,
+    pub vote: String,
+    pub voted_at: String,
+    /// Commit the vote was cast against
+    pub voted_commit: String,
+    /// Commit the review points at now (filled in by the caller from the SCM)
+    pub current_commit: String,
+    /// Anchor used to resolve the review's current commit
+    #[serde(skip)]
+    pub scm_anchor: String,
+}
+
 /// Complete inbox summary for an agent.
 #[derive(Debug, Clone, Serialize)]
 pub struct InboxSummary {
     pub reviews_awaiting_vote: Vec<ReviewAwaitingVote>,
     pub threads_with_new_responses: Vec<ThreadWithNewResponses>,
     pub open_threads_on_my_reviews: Vec<OpenThreadOnMyReview>,
+    /// The agent's votes on reviews that changed since they voted.
+    /// Only populated when the caller checks votes against the SCM.
+    pub stale_votes: Vec<StaleVote>,
+}
+
+/// Filters applied to a full-text search.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilter {
+    /// Only hits written by this agent
+    pub author: Option<String>,
+    /// Only hits on reviews with this status
+    pub status: Option<String>,
+    /// Only comments on this file, or files under this directory
+    pub file: Option<String>,
+    /// Only hits created at or after this RFC 3339 timestamp
+    pub since: Option<String>,
+}
+
+/// A full-text search match on a review or comment.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    /// "review" or "comment"
+    pub kind: String,
+    pub review_id: String,
+    pub review_title: String,
+    pub review_status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_id: Option<String>,
+    pub author: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    pub created_at: String,
+    /// Matching excerpt with hits wrapped in `[` `]`
+    pub snippet: String,
 }
 
 // ============================================================================
@@ -162,23 +285,27 @@ impl ProjectionDb {
         status: Option<&str>,
         author: Option<&str>,
     ) -> Result<Vec<ReviewSummary>> {
-        self.list_reviews_filtered(status, author, None, false)
+        self.list_reviews_filtered(status, author, None, false, None)
     }
 
     /// List reviews with extended filtering options.
     ///
     /// - `needs_reviewer`: Only return reviews where this agent is a requested reviewer
     /// - `has_unresolved`: Only return reviews with open_thread_count > 0
+    /// - `label`: Only return reviews carrying this label
     pub fn list_reviews_filtered(
         &self,
         status: Option<&str>,
         author: Option<&str>,
         needs_reviewer: Option<&str>,
         has_unresolved: bool,
+        label: Option<&str>,
     ) -> Result<Vec<ReviewSummary>> {
         let mut sql = String::from(
-            "SELECT DISTINCT v.review_id, v.jj_change_id, v.scm_kind, v.scm_anchor, v.title, v.author, v.status, v.thread_count, v.open_thread_count
-             FROM v_reviews_summary v",
+            "SELECT DISTINCT v.review_id, v.jj_change_id, v.scm_kind, v.scm_anchor, v.title, v.author, v.status, v.thread_count, v.open_thread_count,
+                    r.depends_on
+             FROM v_reviews_summary v
+             JOIN reviews r ON r.review_id = v.review_id",
         );
         let mut param_values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
@@ -204,10 +331,16 @@ impl ProjectionDb {
         if has_unresolved {
             sql.push_str(" AND v.open_thread_count > 0");
         }
+        if let Some(l) = label {
+            sql.push_str(
+                " AND EXISTS (SELECT 1 FROM review_labels l WHERE l.review_id = v.review_id AND l.label = ?)",
+            );
+            param_values.push(Box::new(l.to_string()));
+        }
 
         sql.push_str(" ORDER BY v.created_at DESC");
 
-        let params: Vec<&dyn rusqlite::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
+        let params: Vec<&dyn rusqlite::ToSql> = param_values.iter().map(Box::as_ref).collect();
 
         let mut stmt = self
             .conn
@@ -227,6 +360,9 @@ impl ProjectionDb {
                     thread_count: row.get(7)?,
                     open_thread_count: row.get(8)?,
                     reviewers: Vec::new(), // populated below
+                    stale_votes: Vec::new(),
+                    depends_on: row.get(9)?,
+                    labels: Vec::new(),
                 })
             })
             .context("Failed to execute list_reviews query")?;
@@ -252,6 +388,7 @@ impl ProjectionDb {
                     .collect::<Result<Vec<_>, _>>()
                     .context("Failed to read reviewers")?;
                 review.reviewers = reviewers;
+                review.labels = self.get_labels(&review.review_id)?;
             }
         }
 
@@ -270,7 +407,7 @@ impl ProjectionDb {
                     r.review_id, r.jj_change_id, r.scm_kind, r.scm_anchor, r.initial_commit, r.final_commit,
                     r.title, r.description, r.author, r.created_at, r.status,
                     r.status_changed_at, r.status_changed_by, r.abandon_reason,
-                    COALESCE(s.thread_count, 0), COALESCE(s.open_thread_count, 0)
+                    COALESCE(s.thread_count, 0), COALESCE(s.open_thread_count, 0), r.depends_on
                  FROM reviews r
                  LEFT JOIN v_reviews_summary s ON s.review_id = r.review_id
                  WHERE r.review_id = ?",
@@ -301,6 +438,15 @@ impl ProjectionDb {
         // Get the votes
         let votes = self.get_votes(review_id)?;
 
+        // Get the revisions
+        let revisions = self.get_revisions(review_id)?;
+
+        let blocking_threads = self.get_open_blocking_threads(review_id)?;
+
+        let dependents = self.get_dependent_reviews(review_id)?;
+
+        let labels = self.get_labels(review_id)?;
+
         Ok(Some(ReviewDetail {
             review_id: row.review_id,
             jj_change_id: row.jj_change_id,
@@ -320,6 +466,11 @@ impl ProjectionDb {
             open_thread_count: row.open_thread_count,
             reviewers,
             votes,
+            revisions,
+            blocking_threads,
+            depends_on: row.depends_on,
+            dependents,
+            labels,
         }))
     }
 
@@ -333,7 +484,8 @@ impl ProjectionDb {
         file: Option<&str>,
     ) -> Result<Vec<ThreadSummary>> {
         let mut sql = String::from(
-            "SELECT thread_id, file_path, selection_start, selection_end, effective_status, comment_count
+            "SELECT thread_id, file_path, selection_start, selection_end, effective_status, comment_count,
+                    commit_hash, kind
              FROM v_threads_detail
              WHERE review_id = ?",
         );
@@ -351,7 +503,7 @@ impl ProjectionDb {
 
         sql.push_str(" ORDER BY file_path, selection_start");
 
-        let params: Vec<&dyn rusqlite::ToSql> = param_values.iter().map(|p| p.as_ref()).collect();
+        let params: Vec<&dyn rusqlite::ToSql> = param_values.iter().map(Box::as_ref).collect();
 
         let mut stmt = self
             .conn
@@ -367,6 +519,9 @@ impl ProjectionDb {
                     selection_end: row.get(3)?,
                     status: row.get(4)?,
                     comment_count: row.get(5)?,
+                    commit_hash: row.get(6)?,
+                    moved_from: None,
+                    kind: row.get(7)?,
                 })
             })
             .context("Failed to execute list_threads query")?;
@@ -418,7 +573,7 @@ impl ProjectionDb {
                     thread_id, review_id, file_path, selection_type,
                     selection_start, selection_end, commit_hash, author,
                     created_at, status, status_changed_at, status_changed_by,
-                    resolve_reason, reopen_reason
+                    resolve_reason, reopen_reason, anchor_snapshot, kind
                  FROM threads
                  WHERE thread_id = ?",
                 params![thread_id],
@@ -449,6 +604,10 @@ impl ProjectionDb {
             status_changed_by: row.status_changed_by,
             resolve_reason: row.resolve_reason,
             reopen_reason: row.reopen_reason,
+            anchor: row
+                .anchor_snapshot
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            kind: row.kind,
             comments,
         }))
     }
@@ -460,7 +619,7 @@ impl ProjectionDb {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT reviewer, vote, reason, voted_at
+                "SELECT reviewer, vote, reason, voted_at, commit_hash
                  FROM reviewer_votes
                  WHERE review_id = ?
                  ORDER BY voted_at ASC",
@@ -474,6 +633,8 @@ impl ProjectionDb {
                     vote: row.get(1)?,
                     reason: row.get(2)?,
                     voted_at: row.get(3)?,
+                    commit_hash: row.get(4)?,
+                    stale: false,
                 })
             })
             .context("Failed to execute get_votes query")?;
@@ -485,6 +646,45 @@ impl ProjectionDb {
         Ok(results)
     }
 
+    /// Get all revisions of a review, oldest first.
+    ///
+    /// Revision 1 is synthesized from the review's initial commit; later
+    /// revisions come from `ReviewUpdated` events. Returns an empty list if the
+    /// review doesn't exist.
+    pub fn get_revisions(&self, review_id: &str) -> Result<Vec<ReviewRevision>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT 1, initial_commit, NULL, author, created_at
+                 FROM reviews
+                 WHERE review_id = ?1
+                 UNION ALL
+                 SELECT revision, commit_hash, note, author, created_at
+                 FROM review_revisions
+                 WHERE review_id = ?1
+                 ORDER BY 1 ASC",
+            )
+            .context("Failed to prepare get_revisions query")?;
+
+        let rows = stmt
+            .query_map(params![review_id], |row| {
+                Ok(ReviewRevision {
+                    revision: row.get(0)?,
+                    commit_hash: row.get(1)?,
+                    note: row.get(2)?,
+                    author: row.get(3)?,
+                    created_at: row.get(4)?,
+                })
+            })
+            .context("Failed to execute get_revisions query")?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.context("Failed to read revision row")?);
+        }
+        Ok(results)
+    }
+
     /// Check if a review has any blocking votes.
     ///
     /// Returns true if there is at least one "block" vote.
@@ -501,6 +701,63 @@ impl ProjectionDb {
         Ok(count > 0)
     }
 
+    /// Get the ids of a review's open threads marked as blocking.
+    ///
+    /// Returns thread ids sorted by creation time (oldest first).
+    pub fn get_open_blocking_threads(&self, review_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT thread_id FROM threads
+                 WHERE review_id = ? AND status = 'open' AND kind = 'blocking'
+                 ORDER BY created_at ASC",
+            )
+            .context("Failed to prepare open_blocking_threads query")?;
+
+        let thread_ids = stmt
+            .query_map(params![review_id], |row| row.get(0))
+            .context("Failed to query open blocking threads")?
+            .collect::<Result<Vec<String>, _>>()
+            .context("Failed to read open blocking threads")?;
+        Ok(thread_ids)
+    }
+
+    /// Get the ids of reviews stacked directly on a review.
+    ///
+    /// Returns review ids sorted by creation time (oldest first).
+    pub fn get_dependent_reviews(&self, review_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT review_id FROM reviews
+                 WHERE depends_on = ?
+                 ORDER BY created_at ASC",
+            )
+            .context("Failed to prepare dependent_reviews query")?;
+
+        let review_ids = stmt
+            .query_map(params![review_id], |row| row.get(0))
+            .context("Failed to query dependent reviews")?
+            .collect::<Result<Vec<String>, _>>()
+            .context("Failed to read dependent reviews")?;
+        Ok(review_ids)
+    }
+
+    /// Get a review's labels, sorted alphabetically.
+    pub fn get_labels(&self, review_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT label FROM review_labels WHERE review_id = ? ORDER BY label")
+            .context("Failed to prepare labels query")?;
+
+        let labels = stmt
+            .query_map(params![review_id], |row| row.get(0))
+            .context("Failed to query labels")?
+            .collect::<Result<Vec<String>, _>>()
+            .context("Failed to read labels")?;
+        Ok(labels)
+    }
+
     /// Check if a review has blocking votes from reviewers other than the specified one.
     ///
     /// Used for auto-approval logic: when a reviewer votes LGTM, we only auto-approve
@@ -529,7 +786,8 @@ impl ProjectionDb {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT comment_id, author, body, created_at
+                "SELECT comment_id, author, body, created_at,
+                        edited_at, retracted_at, retract_reason, suggestion
                  FROM comments
                  WHERE thread_id = ?
                  ORDER BY created_at ASC",
@@ -537,19 +795,62 @@ impl ProjectionDb {
             .context("Failed to prepare list_comments query")?;
 
         let rows = stmt
-            .query_map(params![thread_id], |row| {
-                Ok(Comment {
+            .query_map(params![thread_id], Comment::from_row)
+            .context("Failed to execute list_comments query")?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.context("Failed to read comment row")?);
+        }
+        Ok(results)
+    }
+
+    /// Get a single comment along with the ID of its thread.
+    ///
+    /// Returns `None` if the comment doesn't exist.
+    pub fn get_comment(&self, comment_id: &str) -> Result<Option<(String, Comment)>> {
+        self.conn
+            .query_row(
+                "SELECT comment_id, author, body, created_at,
+                        edited_at, retracted_at, retract_reason, suggestion, thread_id
+                 FROM comments
+                 WHERE comment_id = ?",
+                params![comment_id],
+                |row| Ok((row.get(8)?, Comment::from_row(row)?)),
+            )
+            .optional()
+            .context("Failed to query comment")
+    }
+
+    /// List the edit history for a comment.
+    ///
+    /// Returns edits sorted by edit time (oldest first).
+    pub fn list_comment_edits(&self, comment_id: &str) -> Result<Vec<CommentEdit>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT comment_id, previous_body, body, edited_by, edited_at
+                 FROM comment_edits
+                 WHERE comment_id = ?
+                 ORDER BY edited_at ASC",
+            )
+            .context("Failed to prepare list_comment_edits query")?;
+
+        let rows = stmt
+            .query_map(params![comment_id], |row| {
+                Ok(CommentEdit {
                     comment_id: row.get(0)?,
-                    author: row.get(1)?,
+                    previous_body: row.get(1)?,
                     body: row.get(2)?,
-                    created_at: row.get(3)?,
+                    edited_by: row.get(3)?,
+                    edited_at: row.get(4)?,
                 })
             })
-            .context("Failed to execute list_comments query")?;
+            .context("Failed to execute list_comment_edits query")?;
 
         let mut results = Vec::new();
         for row in rows {
-            results.push(row.context("Failed to read comment row")?);
+            results.push(row.context("Failed to read comment edit row")?);
         }
         Ok(results)
     }
@@ -582,6 +883,7 @@ impl ProjectionDb {
             reviews_awaiting_vote: self.get_reviews_awaiting_vote(agent)?,
             threads_with_new_responses: self.get_threads_with_new_responses(agent)?,
             open_threads_on_my_reviews: self.get_open_threads_on_my_reviews(agent)?,
+            stale_votes: Vec::new(),
         })
     }
 
@@ -634,6 +936,47 @@ impl ProjectionDb {
         Ok(results)
     }
 
+    /// Get the agent's votes on open/approved reviews that recorded a commit.
+    ///
+    /// These are candidates for staleness: the caller compares `voted_commit`
+    /// against the commit `scm_anchor` resolves to now and fills in `current_commit`.
+    pub fn get_stale_vote_candidates(&self, agent: &str) -> Result<Vec<StaleVote>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT r.review_id, r.title, r.author, v.vote, v.voted_at, v.commit_hash,
+                        COALESCE(r.scm_anchor, r.jj_change_id)
+                 FROM reviewer_votes v
+                 JOIN reviews r ON r.review_id = v.review_id
+                 WHERE v.reviewer = ?
+                   AND v.commit_hash IS NOT NULL
+                   AND r.status IN ('open', 'approved')
+                 ORDER BY v.voted_at DESC",
+            )
+            .context("Failed to prepare stale_vote_candidates query")?;
+
+        let rows = stmt
+            .query_map(params![agent], |row| {
+                Ok(StaleVote {
+                    review_id: row.get(0)?,
+                    title: row.get(1)?,
+                    author: row.get(2)?,
+                    vote: row.get(3)?,
+                    voted_at: row.get(4)?,
+                    voted_commit: row.get(5)?,
+                    current_commit: String::new(),
+                    scm_anchor: row.get(6)?,
+                })
+            })
+            .context("Failed to execute stale_vote_candidates query")?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.context("Failed to read vote row")?);
+        }
+        Ok(results)
+    }
+
     /// Get threads where the agent has commented but there are newer comments from others.
     ///
     /// Only includes open threads on open/approved reviews.
@@ -663,7 +1006,7 @@ impl ProjectionDb {
                 SELECT 
                     t.thread_id, t.review_id, r.title, t.file_path,
                     t.selection_start, t.status,
-                    m.last_at, n.new_count, n.latest_at
+                    m.last_at, n.new_count, n.latest_at, t.kind
                 FROM threads t
                 JOIN reviews r ON r.review_id = t.review_id
                 JOIN my_last_comment m ON m.thread_id = t.thread_id
@@ -683,6 +1026,7 @@ impl ProjectionDb {
                     file_path: row.get(3)?,
                     selection_start: row.get(4)?,
                     status: row.get(5)?,
+                    kind: row.get(9)?,
                     my_last_comment_at: row.get(6)?,
                     new_response_count: row.get(7)?,
                     latest_response_at: row.get(8)?,
@@ -709,7 +1053,7 @@ impl ProjectionDb {
                     t.thread_id, t.review_id, r.title, t.file_path,
                     t.selection_start, t.author,
                     COUNT(c.comment_id) as comment_count,
-                    MAX(c.created_at) as latest_comment_at
+                    MAX(c.created_at) as latest_comment_at, t.kind
                  FROM threads t
                  JOIN reviews r ON r.review_id = t.review_id
                  LEFT JOIN comments c ON c.thread_id = t.thread_id
@@ -730,6 +1074,7 @@ impl ProjectionDb {
                     file_path: row.get(3)?,
                     selection_start: row.get(4)?,
                     thread_author: row.get(5)?,
+                    kind: row.get(8)?,
                     comment_count: row.get(6)?,
                     latest_comment_at: row.get::<_, Option<String>>(7)?.unwrap_or_default(),
                 })
@@ -742,6 +1087,116 @@ impl ProjectionDb {
         }
         Ok(results)
     }
+
+    /// Full-text search over review titles, descriptions, and comment bodies.
+    ///
+    /// Each whitespace-separated term must match (terms are quoted, so FTS
+    /// operators are treated as text); a trailing `*` makes a term a prefix
+    /// match. Results are ordered by relevance.
+    pub fn search(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>> {
+        let Some(match_expr) = fts_match_expr(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut sql = String::from(
+            "SELECT s.kind, s.review_id, r.title, r.status, s.thread_id, s.comment_id, s.author, s.file_path, s.created_at,
+                    snippet(search_index, -1, '[', ']', '…', 12)
+             FROM search_index s
+             JOIN reviews r ON r.review_id = s.review_id
+             WHERE search_index MATCH ?",
+        );
+        let mut param_values: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(match_expr)];
+
+        if let Some(a) = &filter.author {
+            sql.push_str(" AND s.author = ?");
+            param_values.push(Box::new(a.clone()));
+        }
+        if let Some(st) = &filter.status {
+            sql.push_str(" AND r.status = ?");
+            param_values.push(Box::new(st.clone()));
+        }
+        if let Some(f) = &filter.file {
+            let dir = f.trim_end_matches('/');
+            sql.push_str(" AND (s.file_path = ? OR s.file_path LIKE ? ESCAPE '\\')");
+            param_values.push(Box::new(dir.to_string()));
+            param_values.push(Box::new(format!("{}/%", escape_like(dir))));
+        }
+        if let Some(since) = &filter.since {
+            sql.push_str(" AND s.created_at >= ?");
+            param_values.push(Box::new(since.clone()));
+        }
+
+        sql.push_str(" ORDER BY rank LIMIT ?");
+        param_values.push(Box::new(i64::try_from(limit).unwrap_or(i64::MAX)));
+
+        let params: Vec<&dyn rusqlite::ToSql> = param_values.iter().map(Box::as_ref).collect();
+
+        let mut stmt = self
+            .conn
+            .prepare(&sql)
+            .context("Failed to prepare search query")?;
+
+        let rows = stmt
+            .query_map(params.as_slice(), |row| {
+                Ok(SearchHit {
+                    kind: row.get(0)?,
+                    review_id: row.get(1)?,
+                    review_title: row.get(2)?,
+                    review_status: row.get(3)?,
+                    thread_id: row.get(4)?,
+                    comment_id: row.get(5)?,
+                    author: row.get(6)?,
+                    file_path: row.get(7)?,
+                    created_at: row.get(8)?,
+                    snippet: row.get(9)?,
+                })
+            })
+            .context("Failed to execute search query")?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.context("Failed to read search row")?);
+        }
+        Ok(results)
+    }
+}
+
+/// Build an FTS5 MATCH expression from user input.
+///
+/// Every term is quoted so punctuation and FTS keywords (`AND`, `NEAR`, `-`)
+/// are matched literally. Returns `None` if there are no terms.
+fn fts_match_expr(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .filter_map(|term| {
+            let (word, prefix) = term
+                .strip_suffix('*')
+                .map_or((term, false), |word| (word, true));
+            if word.is_empty() {
+                return None;
+            }
+            let quoted = format!("\"{}\"", word.replace('"', "\"\""));
+            Some(if prefix { format!("{quoted}*") } else { quoted })
+        })
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Escape `%`, `_` and `\` for a LIKE pattern using `ESCAPE '\'`.
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
 
 // ============================================================================
@@ -766,6 +1221,7 @@ struct ReviewDetailRow {
     abandon_reason: Option<String>,
     thread_count: i64,
     open_thread_count: i64,
+    depends_on: Option<String>,
 }
 
 impl ReviewDetailRow {
@@ -787,6 +1243,7 @@ impl ReviewDetailRow {
             abandon_reason: row.get(13)?,
             thread_count: row.get(14)?,
             open_thread_count: row.get(15)?,
+            depends_on: row.get(16)?,
         })
     }
 }
@@ -807,6 +1264,8 @@ struct ThreadDetailRow {
     status_changed_by: Option<String>,
     resolve_reason: Option<String>,
     reopen_reason: Option<String>,
+    anchor_snapshot: Option<String>,
+    kind: Option<String>,
 }
 
 impl ThreadDetailRow {
@@ -826,6 +1285,25 @@ impl ThreadDetailRow {
             status_changed_by: row.get(11)?,
             resolve_reason: row.get(12)?,
             reopen_reason: row.get(13)?,
+            anchor_snapshot: row.get(14)?,
+            kind: row.get(15)?,
+        })
+    }
+}
+
+impl Comment {
+    fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            comment_id: row.get(0)?,
+            author: row.get(1)?,
+            body: row.get(2)?,
+            created_at: row.get(3)?,
+            edited_at: row.get(4)?,
+            retracted_at: row.get(5)?,
+            retract_reason: row.get(6)?,
+            suggestion: row
+                .get::<_, Option<String>>(7)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
         })
     }
 }
@@ -838,8 +1316,9 @@ impl ThreadDetailRow {
 mod tests {
     use super::*;
     use crate::events::{
-        CodeSelection, CommentAdded, Event, EventEnvelope, ReviewAbandoned, ReviewCreated,
-        ReviewMerged, ReviewerVoted, ReviewersRequested, ThreadCreated, ThreadResolved, VoteType,
+        CodeSelection, CommentAdded, CommentEdited, CommentRetracted, Event, EventEnvelope,
+        ReviewAbandoned, ReviewCreated, ReviewEdited, ReviewMerged, ReviewUpdated, ReviewerVoted,
+        ReviewersRequested, ThreadCreated, ThreadKind, ThreadKindChanged, ThreadResolved, VoteType,
     };
     use crate::projection::apply_event;
     use chrono::{DateTime, Duration, Utc};
@@ -861,6 +1340,7 @@ mod tests {
                 initial_commit: format!("commit-{review_id}"),
                 title: title.to_string(),
                 description: Some(format!("Description for {review_id}")),
+                depends_on: None,
             }),
         )
     }
@@ -874,6 +1354,8 @@ mod tests {
                 file_path: file.to_string(),
                 selection: CodeSelection::line(line),
                 commit_hash: "abc123".to_string(),
+                anchor: None,
+                kind: None,
             }),
         )
     }
@@ -893,6 +1375,8 @@ mod tests {
                 file_path: file.to_string(),
                 selection: CodeSelection::range(start, end),
                 commit_hash: "abc123".to_string(),
+                anchor: None,
+                kind: None,
             }),
         )
     }
@@ -904,6 +1388,7 @@ mod tests {
                 comment_id: comment_id.to_string(),
                 thread_id: thread_id.to_string(),
                 body: body.to_string(),
+                suggestion: None,
             }),
         )
     }
@@ -1136,6 +1621,40 @@ mod tests {
         assert_eq!(review.open_thread_count, 2);
     }
 
+    #[test]
+    fn test_get_review_revisions() {
+        let db = setup_db();
+
+        apply_event(&db, &make_review("cr-001", "alice", "Review")).unwrap();
+
+        let review = db.get_review("cr-001").unwrap().unwrap();
+        assert_eq!(review.revisions.len(), 1);
+        assert_eq!(review.revisions[0].revision, 1);
+        assert_eq!(review.revisions[0].commit_hash, "commit-cr-001");
+
+        apply_event(
+            &db,
+            &EventEnvelope::new(
+                "alice",
+                Event::ReviewUpdated(ReviewUpdated {
+                    review_id: "cr-001".to_string(),
+                    commit: "commit-rev2".to_string(),
+                    note: Some("Addressed feedback".to_string()),
+                    revision: Some(2),
+                }),
+            ),
+        )
+        .unwrap();
+
+        let revisions = db.get_revisions("cr-001").unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[1].revision, 2);
+        assert_eq!(revisions[1].commit_hash, "commit-rev2");
+        assert_eq!(revisions[1].note.as_deref(), Some("Addressed feedback"));
+
+        assert!(db.get_revisions("nonexistent").unwrap().is_empty());
+    }
+
     // ========================================================================
     // list_threads tests
     // ========================================================================
@@ -1243,6 +1762,168 @@ mod tests {
         assert_eq!(threads[2].selection_start, 100);
     }
 
+    #[test]
+    fn test_thread_kind_and_open_blocking_threads() {
+        let db = setup_db();
+        apply_event(&db, &make_review("cr-001", "author", "Review")).unwrap();
+        let mut blocking = make_thread("th-001", "cr-001", "src/main.rs", 10);
+        if let Event::ThreadCreated(thread) = &mut blocking.event {
+            thread.kind = Some(ThreadKind::Blocking);
+        }
+        apply_event(&db, &blocking).unwrap();
+        apply_event(&db, &make_thread("th-002", "cr-001", "src/main.rs", 20)).unwrap();
+
+        let threads = db.list_threads("cr-001", None, None).unwrap();
+        assert_eq!(threads[0].kind.as_deref(), Some("blocking"));
+        assert_eq!(threads[1].kind, None);
+        assert_eq!(
+            db.get_open_blocking_threads("cr-001").unwrap(),
+            vec!["th-001"]
+        );
+
+        // Promoting a thread to blocking, then resolving the original one
+        apply_event(
+            &db,
+            &EventEnvelope::new(
+                "reviewer",
+                Event::ThreadKindChanged(ThreadKindChanged {
+                    thread_id: "th-002".to_string(),
+                    kind: Some(ThreadKind::Blocking),
+                }),
+            ),
+        )
+        .unwrap();
+        apply_event(
+            &db,
+            &EventEnvelope::new(
+                "resolver",
+                Event::ThreadResolved(ThreadResolved {
+                    thread_id: "th-001".to_string(),
+                    reason: None,
+                }),
+            ),
+        )
+        .unwrap();
+
+        let review = db.get_review("cr-001").unwrap().unwrap();
+        assert_eq!(review.blocking_threads, vec!["th-002"]);
+
+        // Only the thread's author can downgrade it; clearing the kind unblocks the review
+        let clear = |author: &str| {
+            EventEnvelope::new(
+                author,
+                Event::ThreadKindChanged(ThreadKindChanged {
+                    thread_id: "th-002".to_string(),
+                    kind: None,
+                }),
+            )
+        };
+        apply_event(&db, &clear("reviewer")).unwrap();
+        assert_eq!(
+            db.get_open_blocking_threads("cr-001").unwrap(),
+            vec!["th-002"]
+        );
+        apply_event(&db, &clear("thread_author")).unwrap();
+        let thread = db.get_thread("th-002").unwrap().unwrap();
+        assert_eq!(thread.kind, None);
+        assert!(db.get_open_blocking_threads("cr-001").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_thread_kind_frozen_once_review_closes() {
+        let db = setup_db();
+        apply_event(&db, &make_review("cr-001", "author", "Review")).unwrap();
+        apply_event(&db, &make_thread("th-001", "cr-001", "src/main.rs", 10)).unwrap();
+        apply_event(
+            &db,
+            &EventEnvelope::new(
+                "author",
+                Event::ReviewAbandoned(ReviewAbandoned {
+                    review_id: "cr-001".to_string(),
+                    reason: None,
+                }),
+            ),
+        )
+        .unwrap();
+
+        apply_event(
+            &db,
+            &EventEnvelope::new(
+                "thread_author",
+                Event::ThreadKindChanged(ThreadKindChanged {
+                    thread_id: "th-001".to_string(),
+                    kind: Some(ThreadKind::Nit),
+                }),
+            ),
+        )
+        .unwrap();
+        assert_eq!(db.get_thread("th-001").unwrap().unwrap().kind, None);
+    }
+
+    #[test]
+    fn test_stacked_review_dependencies() {
+        let db = setup_db();
+        apply_event(&db, &make_review("cr-001", "author", "Bottom")).unwrap();
+        for (review_id, parent) in [("cr-002", "cr-001"), ("cr-003", "cr-002")] {
+            let mut review = make_review(review_id, "author", "Stacked");
+            if let Event::ReviewCreated(created) = &mut review.event {
+                created.depends_on = Some(parent.to_string());
+            }
+            apply_event(&db, &review).unwrap();
+        }
+
+        let middle = db.get_review("cr-002").unwrap().unwrap();
+        assert_eq!(middle.depends_on.as_deref(), Some("cr-001"));
+        assert_eq!(middle.dependents, vec!["cr-003"]);
+
+        let bottom = db.get_review("cr-001").unwrap().unwrap();
+        assert_eq!(bottom.depends_on, None);
+        assert_eq!(bottom.dependents, vec!["cr-002"]);
+
+        let reviews = db.list_reviews(None, None).unwrap();
+        let top = reviews.iter().find(|r| r.review_id == "cr-003").unwrap();
+        assert_eq!(top.depends_on.as_deref(), Some("cr-002"));
+    }
+
+    #[test]
+    fn test_review_edited_updates_metadata_and_labels() {
+        let db = setup_db();
+        apply_event(&db, &make_review("cr-001", "alice", "Draft title")).unwrap();
+        apply_event(&db, &make_review("cr-002", "alice", "Other")).unwrap();
+
+        let edit = |author: &str, title: &str, add: &[&str], remove: &[&str]| {
+            EventEnvelope::new(
+                author,
+                Event::ReviewEdited(ReviewEdited {
+                    review_id: "cr-001".to_string(),
+                    title: Some(title.to_string()),
+                    description: Some(String::new()),
+                    add_labels: add.iter().map(|l| (*l).to_string()).collect(),
+                    remove_labels: remove.iter().map(|l| (*l).to_string()).collect(),
+                }),
+            )
+        };
+        apply_event(&db, &edit("alice", "Final title", &["bug", "docs"], &[])).unwrap();
+        // Someone else's title change is ignored, but their labels still apply
+        apply_event(&db, &edit("bob", "Hijacked", &[], &["docs"])).unwrap();
+
+        let review = db.get_review("cr-001").unwrap().unwrap();
+        assert_eq!(review.title, "Final title");
+        assert_eq!(review.description, None);
+        assert_eq!(review.labels, vec!["bug"]);
+
+        let labelled = db
+            .list_reviews_filtered(None, None, None, false, Some("bug"))
+            .unwrap();
+        assert_eq!(labelled.len(), 1);
+        assert_eq!(labelled[0].review_id, "cr-001");
+        assert_eq!(labelled[0].labels, vec!["bug"]);
+        assert!(db
+            .list_reviews_filtered(None, None, None, false, Some("docs"))
+            .unwrap()
+            .is_empty());
+    }
+
     // ========================================================================
     // get_thread tests
     // ========================================================================
@@ -1285,6 +1966,31 @@ mod tests {
         assert_eq!(thread.comments[1].body, "Second comment");
     }
 
+    #[test]
+    fn test_get_thread_with_anchor_snapshot() {
+        let db = setup_db();
+
+        apply_event(&db, &make_review("cr-001", "alice", "Review")).unwrap();
+        let mut thread = make_thread("th-001", "cr-001", "src/main.rs", 10);
+        if let Event::ThreadCreated(ref mut created) = thread.event {
+            created.anchor = Some(AnchorSnapshot {
+                lines: vec!["let x = 1;".to_string()],
+                before: vec!["fn main() {".to_string()],
+                after: vec!["}".to_string()],
+            });
+        }
+        apply_event(&db, &thread).unwrap();
+        apply_event(&db, &make_thread("th-002", "cr-001", "src/main.rs", 20)).unwrap();
+
+        let with_anchor = db.get_thread("th-001").unwrap().unwrap();
+        let snapshot = with_anchor.anchor.unwrap();
+        assert_eq!(snapshot.lines, vec!["let x = 1;"]);
+        assert_eq!(snapshot.before, vec!["fn main() {"]);
+
+        let legacy = db.get_thread("th-002").unwrap().unwrap();
+        assert!(legacy.anchor.is_none());
+    }
+
     #[test]
     fn test_get_thread_resolved() {
         let db = setup_db();
@@ -1360,6 +2066,180 @@ mod tests {
         assert_eq!(comments_2[0].body, "Thread 2 comment");
     }
 
+    // ========================================================================
+    // Comment edit / retract tests
+    // ========================================================================
+
+    fn make_comment_edit(
+        author: &str,
+        comment_id: &str,
+        thread_id: &str,
+        body: &str,
+    ) -> EventEnvelope {
+        EventEnvelope::new(
+            author,
+            Event::CommentEdited(CommentEdited {
+                comment_id: comment_id.to_string(),
+                thread_id: thread_id.to_string(),
+                body: body.to_string(),
+            }),
+        )
+    }
+
+    fn make_comment_retract(author: &str, comment_id: &str, thread_id: &str) -> EventEnvelope {
+        EventEnvelope::new(
+            author,
+            Event::CommentRetracted(CommentRetracted {
+                comment_id: comment_id.to_string(),
+                thread_id: thread_id.to_string(),
+                reason: Some("wrong file".to_string()),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_comment_edit_updates_body_and_history() {
+        let db = setup_db();
+
+        apply_event(&db, &make_review("cr-001", "alice", "Review")).unwrap();
+        apply_event(&db, &make_thread("th-001", "cr-001", "src/main.rs", 10)).unwrap();
+        apply_event(&db, &make_comment("th-001.1", "th-001", "Frist")).unwrap();
+
+        let edit = make_comment_edit("commenter", "th-001.1", "th-001", "First");
+        apply_event(&db, &edit).unwrap();
+        // Re-applying the same event must not duplicate history
+        apply_event(&db, &edit).unwrap();
+
+        let (thread_id, comment) = db.get_comment("th-001.1").unwrap().unwrap();
+        assert_eq!(thread_id, "th-001");
+        assert_eq!(comment.body, "First");
+        assert!(comment.edited_at.is_some());
+        assert!(comment.retracted_at.is_none());
+
+        let edits = db.list_comment_edits("th-001.1").unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].previous_body, "Frist");
+        assert_eq!(edits[0].body, "First");
+        assert_eq!(edits[0].edited_by, "commenter");
+    }
+
+    #[test]
+    fn test_comment_edit_by_non_author_ignored() {
+        let db = setup_db();
+
+        apply_event(&db, &make_review("cr-001", "alice", "Review")).unwrap();
+        apply_event(&db, &make_thread("th-001", "cr-001", "src/main.rs", 10)).unwrap();
+        apply_event(&db, &make_comment("th-001.1", "th-001", "Original")).unwrap();
+        apply_event(
+            &db,
+            &make_comment_edit("mallory", "th-001.1", "th-001", "Tampered"),
+        )
+        .unwrap();
+        apply_event(&db, &make_comment_retract("mallory", "th-001.1", "th-001")).unwrap();
+
+        let (_, comment) = db.get_comment("th-001.1").unwrap().unwrap();
+        assert_eq!(comment.body, "Original");
+        assert!(comment.edited_at.is_none());
+        assert!(comment.retracted_at.is_none());
+        assert!(db.list_comment_edits("th-001.1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_comment_retract_blocks_later_edits() {
+        let db = setup_db();
+
+        apply_event(&db, &make_review("cr-001", "alice", "Review")).unwrap();
+        apply_event(&db, &make_thread("th-001", "cr-001", "src/main.rs", 10)).unwrap();
+        apply_event(&db, &make_comment("th-001.1", "th-001", "Original")).unwrap();
+        apply_event(
+            &db,
+            &make_comment_retract("commenter", "th-001.1", "th-001"),
+        )
+        .unwrap();
+        apply_event(
+            &db,
+            &make_comment_edit("commenter", "th-001.1", "th-001", "Too late"),
+        )
+        .unwrap();
+
+        let comments = db.list_comments("th-001").unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].body, "");
+        assert!(comments[0].retracted_at.is_some());
+        assert_eq!(comments[0].retract_reason.as_deref(), Some("wrong file"));
+        assert!(comments[0].edited_at.is_none());
+    }
+
+    #[test]
+    fn test_comment_retract_redacts_body_and_history() {
+        let db = setup_db();
+
+        apply_event(&db, &make_review("cr-001", "alice", "Review")).unwrap();
+        apply_event(&db, &make_thread("th-001", "cr-001", "src/main.rs", 10)).unwrap();
+        apply_event(&db, &make_comment("th-001.1", "th-001", "api_key=hunter2")).unwrap();
+        apply_event(
+            &db,
+            &make_comment_edit("commenter", "th-001.1", "th-001", "Oops"),
+        )
+        .unwrap();
+        apply_event(
+            &db,
+            &make_comment_retract("commenter", "th-001.1", "th-001"),
+        )
+        .unwrap();
+
+        let listed = serde_json::to_string(&db.list_comments("th-001").unwrap()).unwrap();
+        let shown = serde_json::to_string(&db.get_comment("th-001.1").unwrap()).unwrap();
+        let history = db.list_comment_edits("th-001.1").unwrap();
+        assert_eq!(history.len(), 1);
+        let history = serde_json::to_string(&history).unwrap();
+        for json in [listed, shown, history] {
+            assert!(json.contains("th-001.1"), "{json}");
+            assert!(!json.contains("hunter2"), "{json}");
+            assert!(!json.contains("Oops"), "{json}");
+        }
+    }
+
+    // ========================================================================
+    // Vote commit tracking tests
+    // ========================================================================
+
+    #[test]
+    fn test_votes_record_commit() {
+        let db = setup_db();
+        apply_event(&db, &make_review("cr-001", "alice", "Review")).unwrap();
+        apply_event(&db, &make_review("cr-002", "alice", "Legacy votes")).unwrap();
+
+        apply_event(
+            &db,
+            &EventEnvelope::new(
+                "bob",
+                Event::ReviewerVoted(ReviewerVoted {
+                    review_id: "cr-001".to_string(),
+                    vote: VoteType::Lgtm,
+                    reason: None,
+                    commit: Some("commit-a".to_string()),
+                }),
+            ),
+        )
+        .unwrap();
+        apply_event(&db, &make_vote("bob", "cr-002", VoteType::Lgtm)).unwrap();
+
+        let votes = db.get_votes("cr-001").unwrap();
+        assert_eq!(votes[0].commit_hash.as_deref(), Some("commit-a"));
+        assert!(
+            !votes[0].stale,
+            "Staleness is only set by callers that check the SCM"
+        );
+
+        // Only votes that recorded a commit can go stale
+        let candidates = db.get_stale_vote_candidates("bob").unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].review_id, "cr-001");
+        assert_eq!(candidates[0].voted_commit, "commit-a");
+        assert_eq!(candidates[0].scm_anchor, "change-cr-001");
+    }
+
     // ========================================================================
     // has_blocking_votes_from_others tests
     // ========================================================================
@@ -1371,6 +2251,7 @@ mod tests {
                 review_id: review_id.to_string(),
                 vote,
                 reason: None,
+                commit: None,
             }),
         )
     }
@@ -1463,6 +2344,9 @@ mod tests {
     ) -> EventEnvelope {
         EventEnvelope {
             ts,
+            event_id: None,
+            clock: None,
+            parents: Vec::new(),
             author: author.to_string(),
             event: Event::ReviewersRequested(ReviewersRequested {
                 review_id: review_id.to_string(),
@@ -1479,11 +2363,15 @@ mod tests {
     ) -> EventEnvelope {
         EventEnvelope {
             ts,
+            event_id: None,
+            clock: None,
+            parents: Vec::new(),
             author: reviewer.to_string(),
             event: Event::ReviewerVoted(ReviewerVoted {
                 review_id: review_id.to_string(),
                 vote,
                 reason: None,
+                commit: None,
             }),
         }
     }
@@ -1865,4 +2753,144 @@ mod tests {
             .unwrap();
         assert_eq!(not_found, None);
     }
+
+    // ========================================================================
+    // search tests
+    // ========================================================================
+
+    #[test]
+    fn test_search_matches_reviews_and_comments() {
+        let db = setup_db();
+        apply_event(&db, &make_review("cr-001", "alice", "Add caching layer")).unwrap();
+        apply_event(&db, &make_review("cr-002", "bob", "Fix parser")).unwrap();
+        apply_event(
+            &db,
+            &make_thread("th-001", "cr-002", "src/parse/lexer.rs", 3),
+        )
+        .unwrap();
+        apply_event(
+            &db,
+            &make_comment("th-001.1", "th-001", "Should this be cached?"),
+        )
+        .unwrap();
+
+        // Porter stemming: "cache" matches "caching" and "cached"
+        let hits = db.search("cache", &SearchFilter::default(), 50).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits
+            .iter()
+            .any(|h| h.kind == "review" && h.review_id == "cr-001"));
+        let comment = hits.iter().find(|h| h.kind == "comment").unwrap();
+        assert_eq!(comment.review_id, "cr-002");
+        assert_eq!(comment.comment_id.as_deref(), Some("th-001.1"));
+        assert!(comment.snippet.contains("[cached]"));
+
+        // Descriptions are indexed too
+        assert_eq!(
+            db.search("description", &SearchFilter::default(), 50)
+                .unwrap()
+                .len(),
+            2
+        );
+        // Prefix terms and literal punctuation
+        assert_eq!(
+            db.search("pars*", &SearchFilter::default(), 50)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(db
+            .search("\"AND( -", &SearchFilter::default(), 50)
+            .unwrap()
+            .is_empty());
+        assert!(db
+            .search("   ", &SearchFilter::default(), 50)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_search_filters() {
+        let db = setup_db();
+        apply_event(&db, &make_review("cr-001", "alice", "Tune cache size")).unwrap();
+        apply_event(&db, &make_thread("th-001", "cr-001", "src/cache/mod.rs", 1)).unwrap();
+        apply_event(
+            &db,
+            &make_comment("th-001.1", "th-001", "cache eviction looks off"),
+        )
+        .unwrap();
+        apply_event(
+            &db,
+            &make_thread("th-002", "cr-001", "src/cache_util.rs", 1),
+        )
+        .unwrap();
+        apply_event(&db, &make_comment("th-002.1", "th-002", "cache helper")).unwrap();
+
+        let by_author = SearchFilter {
+            author: Some("commenter".to_string()),
+            ..SearchFilter::default()
+        };
+        assert_eq!(db.search("cache", &by_author, 50).unwrap().len(), 2);
+
+        let by_dir = SearchFilter {
+            file: Some("src/cache/".to_string()),
+            ..SearchFilter::default()
+        };
+        let hits = db.search("cache", &by_dir, 50).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].thread_id.as_deref(), Some("th-001"));
+
+        let by_status = SearchFilter {
+            status: Some("merged".to_string()),
+            ..SearchFilter::default()
+        };
+        assert!(db.search("cache", &by_status, 50).unwrap().is_empty());
+
+        let future = SearchFilter {
+            since: Some((Utc::now() + Duration::days(1)).to_rfc3339()),
+            ..SearchFilter::default()
+        };
+        assert!(db.search("cache", &future, 50).unwrap().is_empty());
+
+        assert_eq!(
+            db.search("cache", &SearchFilter::default(), 1)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_search_tracks_edits_and_retractions() {
+        let db = setup_db();
+        apply_event(&db, &make_review("cr-001", "alice", "Review")).unwrap();
+        apply_event(&db, &make_thread("th-001", "cr-001", "src/main.rs", 10)).unwrap();
+        apply_event(&db, &make_comment("th-001.1", "th-001", "typo in banner")).unwrap();
+
+        apply_event(
+            &db,
+            &make_comment_edit("commenter", "th-001.1", "th-001", "typo in footer"),
+        )
+        .unwrap();
+        assert!(db
+            .search("banner", &SearchFilter::default(), 50)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            db.search("footer", &SearchFilter::default(), 50)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        apply_event(
+            &db,
+            &make_comment_retract("commenter", "th-001.1", "th-001"),
+        )
+        .unwrap();
+        assert!(db
+            .search("footer", &SearchFilter::default(), 50)
+            .unwrap()
+            .is_empty());
+    }
 }
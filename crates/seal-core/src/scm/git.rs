@@ -2,7 +2,10 @@ use anyhow::{bail, Context, Result};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::scm::{validate_anchor, validate_repo_relative_path, ScmKind, ScmRepo};
+use crate::scm::{
+    parse_stack_entries, validate_anchor, validate_repo_relative_path, FileRename, ScmKind,
+    ScmRepo, StackEntry,
+};
 
 #[derive(Debug, Clone)]
 pub struct GitRepo {
@@ -170,6 +173,43 @@ impl ScmRepo for GitRepo {
             .collect())
     }
 
+    fn renames_between(&self, from: &str, to: &str) -> Result<Vec<FileRename>> {
+        validate_anchor(from)?;
+        validate_anchor(to)?;
+        let range = format!("{from}..{to}");
+        let output = self
+            .run_git(&["diff", "-z", "--name-status", "--find-renames", &range])
+            .with_context(|| format!("Failed to list renamed files from {from} to {to}"))?;
+
+        Ok(parse_name_status_renames(&output))
+    }
+
+    fn diff_git_renamed_file(
+        &self,
+        from: &str,
+        to: &str,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<String> {
+        validate_anchor(from)?;
+        validate_anchor(to)?;
+        validate_repo_relative_path(old_path)?;
+        validate_repo_relative_path(new_path)?;
+        let range = format!("{from}..{to}");
+        self.run_git(&[
+            "diff",
+            "--no-color",
+            "--find-renames",
+            &range,
+            "--",
+            old_path,
+            new_path,
+        ])
+        .with_context(|| {
+            format!("Failed to diff renamed file {old_path} -> {new_path} from {from} to {to}")
+        })
+    }
+
     fn file_exists(&self, rev: &str, path: &str) -> Result<bool> {
         validate_repo_relative_path(path)?;
         let commit = self.commit_for_anchor(rev)?;
@@ -196,6 +236,64 @@ impl ScmRepo for GitRepo {
         self.run_git(&["show", "--end-of-options", &spec])
             .with_context(|| format!("Failed to show file {path} at {rev}"))
     }
+
+    fn stack(&self, revset: &str) -> Result<Vec<StackEntry>> {
+        validate_anchor(revset)?;
+        let output = self
+            .run_git(&[
+                "log",
+                "--reverse",
+                "--topo-order",
+                "--format=%H%x09%H%x09%P%x09%s",
+                "--end-of-options",
+                revset,
+            ])
+            .with_context(|| format!("Failed to list commits in {revset}"))?;
+
+        // Review each commit under the branch that points at it, if any
+        let mut entries = parse_stack_entries(&output);
+        for entry in &mut entries {
+            let branches = self.run_git(&[
+                "for-each-ref",
+                "--points-at",
+                &entry.commit,
+                "--format=%(refname)",
+                "refs/heads/",
+            ])?;
+            entry.anchor = branches
+                .lines()
+                .next()
+                .map_or_else(|| format!("detached:{}", entry.commit), ToString::to_string);
+        }
+        Ok(entries)
+    }
+}
+
+/// Extract renames from `git diff -z --name-status --find-renames` output.
+///
+/// Entries are NUL-separated: a status (`R087`), then one path, or two for
+/// renames and copies.
+fn parse_name_status_renames(output: &str) -> Vec<FileRename> {
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    let mut renames = Vec::new();
+
+    while let Some(status) = fields.next() {
+        if status.starts_with('R') || status.starts_with('C') {
+            let (Some(old_path), Some(new_path)) = (fields.next(), fields.next()) else {
+                break;
+            };
+            if status.starts_with('R') {
+                renames.push(FileRename {
+                    old_path: old_path.to_string(),
+                    new_path: new_path.to_string(),
+                });
+            }
+        } else {
+            fields.next();
+        }
+    }
+
+    renames
 }
 
 #[cfg(test)]
@@ -239,4 +337,68 @@ mod tests {
         let anchor = repo.current_anchor().expect("anchor");
         assert!(anchor.starts_with("refs/heads/"));
     }
+
+    #[test]
+    fn test_stack_lists_branch_chain() {
+        let repo_path = setup_git_repo();
+        let repo = GitRepo::new(repo_path.clone());
+        let base = repo.current_commit().expect("commit");
+
+        run_git_at(&repo_path, &["checkout", "-b", "part-1"]);
+        std::fs::write(repo_path.join("a.txt"), "a\n").expect("write file");
+        run_git_at(&repo_path, &["add", "a.txt"]);
+        run_git_at(&repo_path, &["commit", "-m", "Add a"]);
+        std::fs::write(repo_path.join("b.txt"), "b\n").expect("write file");
+        run_git_at(&repo_path, &["add", "b.txt"]);
+        run_git_at(&repo_path, &["commit", "-m", "Add b"]);
+
+        let stack = repo.stack(&format!("{base}..HEAD")).expect("stack");
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack[0].title, "Add a");
+        assert_eq!(stack[0].parents, vec![base]);
+        assert!(stack[0].anchor.starts_with("detached:"));
+        assert_eq!(stack[1].title, "Add b");
+        assert_eq!(stack[1].parents, vec![stack[0].commit.clone()]);
+        assert_eq!(stack[1].anchor, "refs/heads/part-1");
+    }
+
+    #[test]
+    fn test_parse_name_status_renames() {
+        let output = "M\0src/lib.rs\0R087\0src/old.rs\0src/new.rs\0C100\0a.rs\0b.rs\0D\0gone.rs\0";
+        assert_eq!(
+            parse_name_status_renames(output),
+            vec![FileRename {
+                old_path: "src/old.rs".to_string(),
+                new_path: "src/new.rs".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_renamed_path_follows_git_mv() {
+        let repo_path = setup_git_repo();
+        let repo = GitRepo::new(repo_path.clone());
+        let before = repo.current_commit().expect("commit");
+
+        std::fs::create_dir(repo_path.join("src")).expect("mkdir");
+        run_git_at(&repo_path, &["mv", "file.txt", "src/moved.txt"]);
+        run_git_at(&repo_path, &["commit", "-m", "move"]);
+        let after = repo.current_commit().expect("commit");
+
+        assert_eq!(
+            repo.renamed_path(&before, &after, "file.txt")
+                .expect("renames"),
+            Some("src/moved.txt".to_string())
+        );
+        assert_eq!(
+            repo.renamed_path(&before, &after, "other.txt")
+                .expect("renames"),
+            None
+        );
+
+        let diff = repo
+            .diff_git_renamed_file(&before, &after, "file.txt", "src/moved.txt")
+            .expect("diff");
+        assert!(diff.contains("rename from file.txt"));
+    }
 }
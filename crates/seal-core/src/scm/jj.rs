@@ -2,7 +2,10 @@ use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
 use crate::jj::{resolve_workspace_root, JjRepo};
-use crate::scm::{validate_anchor, validate_repo_relative_path, ScmKind, ScmRepo};
+use crate::scm::{
+    parse_stack_entries, validate_anchor, validate_repo_relative_path, FileRename, ScmKind,
+    ScmRepo, StackEntry,
+};
 
 #[derive(Debug, Clone)]
 pub struct JjScmRepo {
@@ -71,6 +74,27 @@ impl ScmRepo for JjScmRepo {
         self.inner.changed_files_between(from, to)
     }
 
+    fn renames_between(&self, from: &str, to: &str) -> Result<Vec<FileRename>> {
+        validate_anchor(from)?;
+        validate_anchor(to)?;
+        self.inner.renames_between(from, to)
+    }
+
+    fn diff_git_renamed_file(
+        &self,
+        from: &str,
+        to: &str,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<String> {
+        validate_anchor(from)?;
+        validate_anchor(to)?;
+        validate_repo_relative_path(old_path)?;
+        validate_repo_relative_path(new_path)?;
+        self.inner
+            .diff_git_renamed_file(from, to, old_path, new_path)
+    }
+
     fn file_exists(&self, rev: &str, path: &str) -> Result<bool> {
         validate_anchor(rev)?;
         validate_repo_relative_path(path)?;
@@ -84,4 +108,10 @@ impl ScmRepo for JjScmRepo {
             .show_file(rev, path)
             .with_context(|| format!("Failed to show file {path} at {rev}"))
     }
+
+    fn stack(&self, revset: &str) -> Result<Vec<StackEntry>> {
+        validate_anchor(revset)?;
+        let output = self.inner.log_stack(revset)?;
+        Ok(parse_stack_entries(&output))
+    }
 }
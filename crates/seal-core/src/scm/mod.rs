@@ -28,6 +28,24 @@ impl ScmKind {
     }
 }
 
+/// A file that was renamed (or moved) between two revisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRename {
+    pub old_path: String,
+    pub new_path: String,
+}
+
+/// One change in a stack of changes, as listed by [`ScmRepo::stack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackEntry {
+    /// Anchor to review the change under (jj change id, git branch ref or `detached:<commit>`)
+    pub anchor: String,
+    pub commit: String,
+    pub parents: Vec<String>,
+    /// First line of the change's description
+    pub title: String,
+}
+
 pub trait ScmRepo {
     fn kind(&self) -> ScmKind;
     fn root(&self) -> &Path;
@@ -41,8 +59,47 @@ pub trait ScmRepo {
     fn diff_git_file(&self, from: &str, to: &str, file: &str) -> Result<String>;
     fn changed_files_between(&self, from: &str, to: &str) -> Result<Vec<String>>;
 
+    /// Files renamed between two revisions, using the backend's rename detection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either revision can't be resolved or the diff fails.
+    fn renames_between(&self, from: &str, to: &str) -> Result<Vec<FileRename>>;
+    /// Diff a renamed file, with hunks relative to `old_path` at `from`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either revision can't be resolved or the diff fails.
+    fn diff_git_renamed_file(
+        &self,
+        from: &str,
+        to: &str,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<String>;
+
+    /// Follow `file` from `from` to `to`, returning its new path if it was renamed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the renames can't be listed.
+    fn renamed_path(&self, from: &str, to: &str, file: &str) -> Result<Option<String>> {
+        Ok(self
+            .renames_between(from, to)?
+            .into_iter()
+            .find(|rename| rename.old_path == file)
+            .map(|rename| rename.new_path))
+    }
+
     fn file_exists(&self, rev: &str, path: &str) -> Result<bool>;
     fn show_file(&self, rev: &str, path: &str) -> Result<String>;
+
+    /// List the changes in `revset`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the revset can't be resolved.
+    fn stack(&self, revset: &str) -> Result<Vec<StackEntry>>;
 }
 
 #[derive(Debug, Clone)]
@@ -185,6 +242,33 @@ pub fn validate_anchor(anchor: &str) -> Result<()> {
     Ok(())
 }
 
+/// Parse stack listings with one `anchor\tcommit\tparents\ttitle` line per
+/// change, where `parents` is space-separated.
+pub(crate) fn parse_stack_entries(output: &str) -> Vec<StackEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\t');
+            let anchor = fields.next()?.trim();
+            let commit = fields.next()?.trim();
+            if anchor.is_empty() || commit.is_empty() {
+                return None;
+            }
+            Some(StackEntry {
+                anchor: anchor.to_string(),
+                commit: commit.to_string(),
+                parents: fields
+                    .next()
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(ToString::to_string)
+                    .collect(),
+                title: fields.next().unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect()
+}
+
 pub fn validate_repo_relative_path(path: &str) -> Result<()> {
     if path.trim().is_empty() {
         bail!("Path cannot be empty");
@@ -247,6 +331,28 @@ mod tests {
         assert!(validate_anchor("@").is_ok());
     }
 
+    #[test]
+    fn test_parse_stack_entries() {
+        let output = "ch-a\tc1\tc0\tFirst change\nch-b\tc2\tc1 c9\t\n\nbad line\n";
+        assert_eq!(
+            parse_stack_entries(output),
+            vec![
+                StackEntry {
+                    anchor: "ch-a".to_string(),
+                    commit: "c1".to_string(),
+                    parents: vec!["c0".to_string()],
+                    title: "First change".to_string(),
+                },
+                StackEntry {
+                    anchor: "ch-b".to_string(),
+                    commit: "c2".to_string(),
+                    parents: vec!["c1".to_string(), "c9".to_string()],
+                    title: String::new(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_validate_repo_relative_path() {
         assert!(validate_repo_relative_path("src/main.rs").is_ok());
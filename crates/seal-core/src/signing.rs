@@ -0,0 +1,440 @@
+//! Optional ed25519 signatures on event envelopes.
+//!
+//! An agent's private key lives outside the repository (in
+//! `$XDG_CONFIG_HOME/.botseal/keys/<agent>.key`, or `$SEAL_KEYS_DIR`), and its
+//! public key is checked in at `.seal/keys/<agent>.pub`. When a private key
+//! exists for an event's author, [`ReviewLog`](crate::log::ReviewLog) appends
+//! signed lines.
+//!
+//! The signature goes in a `sig` field of the JSON line itself, so readers
+//! that don't care about signatures parse the envelope unchanged. It covers
+//! the line's JSON with `sig` removed, re-serialized by `serde_json`; working
+//! from the raw line (rather than a parsed `EventEnvelope`) keeps signatures
+//! valid when a newer seal adds fields an older one doesn't know about.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Field holding the hex-encoded signature in a signed log line.
+pub const SIG_FIELD: &str = "sig";
+
+/// Directory of public keys inside `.seal/`.
+pub const KEYS_DIR: &str = "keys";
+
+/// Environment variable overriding where private keys are stored.
+pub const KEYS_DIR_VAR: &str = "SEAL_KEYS_DIR";
+
+const PUBLIC_KEY_EXT: &str = "pub";
+const PRIVATE_KEY_EXT: &str = "key";
+
+/// Outcome of checking one log line's signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum Verification {
+    /// Signed by the author's registered key.
+    Valid,
+    /// No `sig` field.
+    Unsigned,
+    /// Signed, but the author has no public key in `.seal/keys/`.
+    UnknownKey,
+    /// Signature doesn't verify against the author's public key.
+    Invalid(String),
+}
+
+/// Public keys checked into `.seal/keys/`, by agent name.
+#[derive(Debug, Clone, Default)]
+pub struct PublicKeys {
+    keys: HashMap<String, VerifyingKey>,
+}
+
+impl PublicKeys {
+    /// Load every `<agent>.pub` file in `.seal/keys/`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a key file cannot be read or isn't a valid key.
+    pub fn load(seal_root: &Path) -> Result<Self> {
+        let dir = public_keys_dir(seal_root);
+        let mut keys = HashMap::new();
+        if !dir.exists() {
+            return Ok(Self { keys });
+        }
+
+        for entry in fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read keys directory: {}", dir.display()))?
+        {
+            let path = entry.context("Failed to read directory entry")?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(PUBLIC_KEY_EXT) {
+                continue;
+            }
+            let Some(agent) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let bytes = read_hex_key::<32>(&path)?;
+            let key = VerifyingKey::from_bytes(&bytes)
+                .with_context(|| format!("Invalid public key: {}", path.display()))?;
+            keys.insert(agent.to_string(), key);
+        }
+
+        Ok(Self { keys })
+    }
+
+    /// Check if no public keys are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Check if an agent has a registered public key.
+    #[must_use]
+    pub fn contains(&self, agent: &str) -> bool {
+        self.keys.contains_key(agent)
+    }
+
+    /// Verify the signature on a raw log line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the line isn't a JSON object with an `author`.
+    pub fn verify_line(&self, line: &str) -> Result<Verification> {
+        let mut value: Value = serde_json::from_str(line).context("Failed to parse event line")?;
+        let Some(object) = value.as_object_mut() else {
+            bail!("event line is not a JSON object");
+        };
+        let sig = object.remove(SIG_FIELD);
+        let Some(author) = object
+            .get("author")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+        else {
+            bail!("event line has no author");
+        };
+
+        let Some(sig) = sig else {
+            return Ok(Verification::Unsigned);
+        };
+        let Some(key) = self.keys.get(&author) else {
+            return Ok(Verification::UnknownKey);
+        };
+        let Some(sig_bytes) = sig.as_str().and_then(decode_hex::<64>) else {
+            return Ok(Verification::Invalid("malformed signature".to_string()));
+        };
+
+        let payload = serde_json::to_string(&value).context("Failed to serialize event")?;
+        Ok(
+            match key.verify(payload.as_bytes(), &Signature::from_bytes(&sig_bytes)) {
+                Ok(()) => Verification::Valid,
+                Err(_) => Verification::Invalid(format!("signature does not match {author}'s key")),
+            },
+        )
+    }
+}
+
+/// Directory of checked-in public keys.
+#[must_use]
+pub fn public_keys_dir(seal_root: &Path) -> PathBuf {
+    seal_root.join(".seal").join(KEYS_DIR)
+}
+
+/// Directory of private keys for the current user, if one can be determined.
+#[must_use]
+pub fn private_keys_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var(KEYS_DIR_VAR) {
+        return Some(PathBuf::from(dir));
+    }
+    let base = if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+        PathBuf::from(xdg)
+    } else if let Ok(home) = std::env::var("HOME") {
+        Path::new(&home).join(".config")
+    } else {
+        return None;
+    };
+    Some(base.join(".botseal").join(KEYS_DIR))
+}
+
+/// Paths written by [`generate_key`].
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedKey {
+    pub agent: String,
+    pub public_key: String,
+    pub public_key_path: PathBuf,
+    pub private_key_path: PathBuf,
+}
+
+/// Generate a signing key for an agent.
+///
+/// Writes the private key to [`private_keys_dir`] (mode 0600 on Unix) and the
+/// public key to `.seal/keys/<agent>.pub`. Refuses to replace an existing
+/// private key unless `force` is set.
+///
+/// # Errors
+///
+/// Returns an error if the agent name can't be used as a file name, a key
+/// already exists, or the files cannot be written.
+pub fn generate_key(seal_root: &Path, agent: &str, force: bool) -> Result<GeneratedKey> {
+    validate_agent_name(agent)?;
+    let Some(private_dir) = private_keys_dir() else {
+        bail!("Cannot determine where to store private keys. Set {KEYS_DIR_VAR} or HOME.");
+    };
+
+    let private_key_path = private_dir.join(format!("{agent}.{PRIVATE_KEY_EXT}"));
+    if private_key_path.exists() && !force {
+        bail!(
+            "A signing key for {agent} already exists at {}. Use --force to replace it.",
+            private_key_path.display()
+        );
+    }
+
+    let mut seed = [0u8; 32];
+    getrandom::fill(&mut seed).map_err(|e| anyhow::anyhow!("Failed to generate key: {e}"))?;
+    let signing_key = SigningKey::from_bytes(&seed);
+    let public_key = encode_hex(signing_key.verifying_key().as_bytes());
+
+    fs::create_dir_all(&private_dir)
+        .with_context(|| format!("Failed to create {}", private_dir.display()))?;
+    write_private(&private_key_path, &encode_hex(&seed))?;
+
+    let public_dir = public_keys_dir(seal_root);
+    fs::create_dir_all(&public_dir)
+        .with_context(|| format!("Failed to create {}", public_dir.display()))?;
+    let public_key_path = public_dir.join(format!("{agent}.{PUBLIC_KEY_EXT}"));
+    fs::write(&public_key_path, format!("{public_key}\n"))
+        .with_context(|| format!("Failed to write {}", public_key_path.display()))?;
+
+    Ok(GeneratedKey {
+        agent: agent.to_string(),
+        public_key,
+        public_key_path,
+        private_key_path,
+    })
+}
+
+/// Load an agent's private signing key, if they have one.
+///
+/// # Errors
+///
+/// Returns an error if the key file exists but is unreadable or malformed.
+pub fn load_signing_key(agent: &str) -> Result<Option<SigningKey>> {
+    if validate_agent_name(agent).is_err() {
+        return Ok(None);
+    }
+    let Some(dir) = private_keys_dir() else {
+        return Ok(None);
+    };
+    let path = dir.join(format!("{agent}.{PRIVATE_KEY_EXT}"));
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(SigningKey::from_bytes(&read_hex_key::<32>(&path)?)))
+}
+
+/// Add a `sig` field to a serialized envelope.
+///
+/// # Errors
+///
+/// Returns an error if `json_line` isn't a JSON object.
+pub fn sign_line(json_line: &str, key: &SigningKey) -> Result<String> {
+    let mut value: Value = serde_json::from_str(json_line).context("Failed to parse event line")?;
+    let Some(object) = value.as_object_mut() else {
+        bail!("event line is not a JSON object");
+    };
+    object.remove(SIG_FIELD);
+
+    let payload = serde_json::to_string(&value).context("Failed to serialize event")?;
+    let signature = key.sign(payload.as_bytes());
+    if let Some(object) = value.as_object_mut() {
+        object.insert(
+            SIG_FIELD.to_string(),
+            Value::String(encode_hex(&signature.to_bytes())),
+        );
+    }
+    serde_json::to_string(&value).context("Failed to serialize signed event")
+}
+
+/// Sign a serialized envelope with the author's key, if they have one.
+///
+/// # Errors
+///
+/// Returns an error if the author's key is malformed or signing fails.
+pub fn sign_line_as(json_line: &str, author: &str) -> Result<String> {
+    load_signing_key(author)?.map_or_else(
+        || Ok(json_line.to_string()),
+        |key| sign_line(json_line, &key),
+    )
+}
+
+/// Agent names become key and draft file names, so keep them to a safe
+/// character set.
+pub(crate) fn validate_agent_name(agent: &str) -> Result<()> {
+    if agent.is_empty()
+        || agent.starts_with('.')
+        || !agent
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        bail!("agent name '{agent}' can't be used in a file name (allowed: alphanumeric, '-', '_', '.')");
+    }
+    Ok(())
+}
+
+fn write_private(path: &Path, contents: &str) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        writeln!(file, "{contents}").with_context(|| format!("Failed to write {}", path.display()))
+    }
+    #[cfg(not(unix))]
+    {
+        fs::write(path, format!("{contents}\n"))
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn read_hex_key<const N: usize>(path: &Path) -> Result<[u8; N]> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    decode_hex(contents.trim()).with_context(|| {
+        format!(
+            "Malformed key file (expected {N} hex bytes): {}",
+            path.display()
+        )
+    })
+}
+
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+fn decode_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const LINE: &str = r#"{"ts":"2026-01-01T00:00:00Z","author":"alice","event":"ReviewApproved","data":{"review_id":"cr-1"}}"#;
+
+    fn keys_for(agent: &str, key: &SigningKey) -> PublicKeys {
+        PublicKeys {
+            keys: HashMap::from([(agent.to_string(), key.verifying_key())]),
+        }
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let bytes = [0u8, 1, 0xab, 0xff];
+        assert_eq!(encode_hex(&bytes), "0001abff");
+        assert_eq!(decode_hex::<4>("0001abff"), Some(bytes));
+        assert_eq!(decode_hex::<4>("0001ab"), None);
+        assert_eq!(decode_hex::<2>("zz00"), None);
+    }
+
+    #[test]
+    fn test_sign_and_verify_line() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let signed = sign_line(LINE, &key).unwrap();
+
+        let keys = keys_for("alice", &key);
+        assert_eq!(keys.verify_line(&signed).unwrap(), Verification::Valid);
+        assert_eq!(keys.verify_line(LINE).unwrap(), Verification::Unsigned);
+
+        // Signed lines still parse as envelopes
+        assert!(crate::events::EventEnvelope::from_json_line(&signed).is_ok());
+    }
+
+    #[test]
+    fn test_verify_detects_forgery() {
+        let alice = SigningKey::from_bytes(&[7u8; 32]);
+        let mallory = SigningKey::from_bytes(&[9u8; 32]);
+        let keys = keys_for("alice", &alice);
+
+        // Signed with someone else's key
+        let forged = sign_line(LINE, &mallory).unwrap();
+        assert!(matches!(
+            keys.verify_line(&forged).unwrap(),
+            Verification::Invalid(_)
+        ));
+
+        // Tampered after signing
+        let tampered = sign_line(LINE, &alice).unwrap().replace("cr-1", "cr-2");
+        assert!(matches!(
+            keys.verify_line(&tampered).unwrap(),
+            Verification::Invalid(_)
+        ));
+
+        // Signed by an agent without a registered key
+        let bob_line = LINE.replace("alice", "bob");
+        let signed = sign_line(&bob_line, &mallory).unwrap();
+        assert_eq!(keys.verify_line(&signed).unwrap(), Verification::UnknownKey);
+    }
+
+    #[test]
+    fn test_signature_survives_unknown_fields() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let newer = LINE.replace(r#""cr-1"}"#, r#""cr-1","added_later":true}"#);
+        let signed = sign_line(&newer, &key).unwrap();
+        assert_eq!(
+            keys_for("alice", &key).verify_line(&signed).unwrap(),
+            Verification::Valid
+        );
+    }
+
+    #[test]
+    fn test_load_public_keys() {
+        let temp = TempDir::new().unwrap();
+        let dir = public_keys_dir(temp.path());
+        fs::create_dir_all(&dir).unwrap();
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        fs::write(
+            dir.join("alice.pub"),
+            encode_hex(key.verifying_key().as_bytes()),
+        )
+        .unwrap();
+        fs::write(dir.join("README"), "not a key").unwrap();
+
+        let keys = PublicKeys::load(temp.path()).unwrap();
+        assert!(keys.contains("alice"));
+        assert!(!keys.contains("README"));
+
+        fs::write(dir.join("bob.pub"), "nothex").unwrap();
+        assert!(PublicKeys::load(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_validate_agent_name() {
+        assert!(validate_agent_name("alice").is_ok());
+        assert!(validate_agent_name("bot-1.ci_x").is_ok());
+        assert!(validate_agent_name("../etc").is_err());
+        assert!(validate_agent_name("a/b").is_err());
+        assert!(validate_agent_name("").is_err());
+    }
+}
@@ -8,8 +8,12 @@ pub enum CommandId {
     SelectTheme,
     ToggleDiffView,
     ToggleDiffWrap,
+    CycleDiffBase,
+    CycleThreadKindFilter,
     ToggleSidebar,
     OpenFileInEditor,
+    StackParent,
+    StackChild,
 }
 
 #[derive(Clone)]
@@ -43,6 +47,22 @@ pub fn get_commands() -> Vec<CommandSpec> {
             shortcut: Some("w"),
             active: false,
         },
+        CommandSpec {
+            name: "Diff since revision",
+            description: "Show only changes since an earlier revision",
+            id: CommandId::CycleDiffBase,
+            category: "View",
+            shortcut: Some("R"),
+            active: false,
+        },
+        CommandSpec {
+            name: "Filter threads by kind",
+            description: "Show only blocking, nit, question or praise threads",
+            id: CommandId::CycleThreadKindFilter,
+            category: "View",
+            shortcut: Some("K"),
+            active: false,
+        },
         CommandSpec {
             name: "Toggle sidebar",
             description: "Show or hide the file sidebar",
@@ -59,6 +79,23 @@ pub fn get_commands() -> Vec<CommandSpec> {
             shortcut: None,
             active: false,
         },
+        // --- Navigation ---
+        CommandSpec {
+            name: "Parent review",
+            description: "Open the review this one is stacked on",
+            id: CommandId::StackParent,
+            category: "Navigation",
+            shortcut: Some("{"),
+            active: false,
+        },
+        CommandSpec {
+            name: "Child review",
+            description: "Open the first review stacked on this one",
+            id: CommandId::StackChild,
+            category: "Navigation",
+            shortcut: Some("}"),
+            active: false,
+        },
         // --- Session ---
         CommandSpec {
             name: "Open in editor",
@@ -86,7 +123,11 @@ pub const fn command_id_to_message(id: CommandId) -> Message {
         CommandId::SelectTheme => Message::ShowThemePicker,
         CommandId::ToggleDiffView => Message::ToggleDiffView,
         CommandId::ToggleDiffWrap => Message::ToggleDiffWrap,
+        CommandId::CycleDiffBase => Message::CycleDiffBase,
+        CommandId::CycleThreadKindFilter => Message::CycleThreadKindFilter,
         CommandId::ToggleSidebar => Message::ToggleSidebar,
         CommandId::OpenFileInEditor => Message::OpenFileInEditor,
+        CommandId::StackParent => Message::StackParent,
+        CommandId::StackChild => Message::StackChild,
     }
 }
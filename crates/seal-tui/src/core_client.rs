@@ -5,16 +5,24 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
+use seal_core::core::reviews::mark_stale_votes;
+use seal_core::core::threads::follow_renames;
 use seal_core::core::{CoreContext, SealServices};
 use seal_core::events::CodeSelection;
-use seal_core::scm::{resolve_backend, ScmPreference};
+use seal_core::jj::drift::{FileDrift, LineRange};
+use seal_core::jj::reanchor::capture_anchor;
+use seal_core::projection::SearchFilter;
+use seal_core::scm::{resolve_backend, ScmPreference, ScmRepo};
 use seal_core::sealignore::SealIgnore;
 
 use crate::db::{
-    Comment, FileContentData, FileData, ReviewData, ReviewDetail, ReviewSummary, SealClient,
-    ThreadSummary,
+    Comment, FileContentData, FileData, ReviewData, ReviewDetail, ReviewRevision, ReviewSummary,
+    ReviewVote, SealClient, SearchHit, SuggestionDiff, ThreadSummary,
 };
 
+/// Maximum full-text hits fetched per query in the review list.
+const SEARCH_LIMIT: usize = 200;
+
 /// Client that calls seal-core services directly (no subprocess).
 pub struct CoreClient {
     ctx: CoreContext,
@@ -53,6 +61,7 @@ fn convert_review_summary(r: &seal_core::projection::ReviewSummary) -> ReviewSum
         thread_count: r.thread_count,
         open_thread_count: r.open_thread_count,
         reviewers: r.reviewers.clone(),
+        labels: r.labels.clone(),
     }
 }
 
@@ -74,10 +83,35 @@ fn convert_review_detail(r: &seal_core::projection::ReviewDetail) -> ReviewDetai
         abandon_reason: r.abandon_reason.clone(),
         thread_count: r.thread_count,
         open_thread_count: r.open_thread_count,
+        depends_on: r.depends_on.clone(),
+        dependents: r.dependents.clone(),
+        revisions: r
+            .revisions
+            .iter()
+            .map(|rev| ReviewRevision {
+                revision: rev.revision,
+                commit_hash: rev.commit_hash.clone(),
+                note: rev.note.clone(),
+                author: rev.author.clone(),
+                created_at: rev.created_at.clone(),
+            })
+            .collect(),
+        votes: r
+            .votes
+            .iter()
+            .map(|v| ReviewVote {
+                reviewer: v.reviewer.clone(),
+                vote: v.vote.clone(),
+                stale: v.stale,
+            })
+            .collect(),
     }
 }
 
-fn convert_thread_summary(t: &seal_core::projection::ThreadSummary) -> ThreadSummary {
+fn convert_thread_summary(
+    t: &seal_core::projection::ThreadSummary,
+    current: Option<&LineRange>,
+) -> ThreadSummary {
     ThreadSummary {
         thread_id: t.thread_id.clone(),
         file_path: t.file_path.clone(),
@@ -85,15 +119,27 @@ fn convert_thread_summary(t: &seal_core::projection::ThreadSummary) -> ThreadSum
         selection_end: t.selection_end,
         status: t.status.clone(),
         comment_count: t.comment_count,
+        current_start: current.map(|span| i64::from(span.start)),
+        current_end: current.map(|span| i64::from(span.end)),
+        moved_from: t.moved_from.clone(),
+        kind: t.kind.clone(),
     }
 }
 
-fn convert_comment(c: &seal_core::projection::Comment) -> Comment {
+/// `anchor_lines` are the thread's originally selected lines, used as the
+/// removed side of a suggestion.
+fn convert_comment(c: &seal_core::projection::Comment, anchor_lines: &[String]) -> Comment {
     Comment {
         comment_id: c.comment_id.clone(),
         author: c.author.clone(),
         body: c.body.clone(),
         created_at: c.created_at.clone(),
+        edited_at: c.edited_at.clone(),
+        retracted_at: c.retracted_at.clone(),
+        suggestion: c.suggestion.as_ref().map(|s| SuggestionDiff {
+            removed: anchor_lines.to_vec(),
+            added: s.lines.clone(),
+        }),
     }
 }
 
@@ -107,33 +153,44 @@ impl SealClient for CoreClient {
         Ok(reviews.iter().map(convert_review_summary).collect())
     }
 
-    fn load_review_data(&self, review_id: &str) -> Result<Option<ReviewData>> {
+    fn load_review_data(
+        &self,
+        review_id: &str,
+        since_revision: Option<i64>,
+    ) -> Result<Option<ReviewData>> {
         let services = self.services()?;
 
-        let detail = match services
+        let Some(mut detail) = services
             .reviews()
             .get_optional(review_id)
             .map_err(|e| anyhow::anyhow!("{e}"))?
-        {
-            Some(d) => d,
-            None => return Ok(None),
+        else {
+            return Ok(None);
         };
 
-        let core_threads = services
+        let mut core_threads = services
             .threads()
             .list(review_id, None, None)
             .map_err(|e| anyhow::anyhow!("{e}"))?;
+        // Show threads on renamed files under the file's current path, and
+        // flag votes cast before the review's latest changes
+        if let Ok(scm) = resolve_backend(&self.repo_root, ScmPreference::Auto) {
+            let target_commit = resolve_target_commit(scm.as_ref(), &detail);
+            follow_renames(scm.as_ref(), &target_commit, &mut core_threads);
+            mark_stale_votes(&mut detail.votes, &target_commit);
+        }
         let sealignore = SealIgnore::load(&self.repo_root);
         let visible_threads: Vec<_> = core_threads
             .into_iter()
             .filter(|thread| !sealignore.is_ignored(&thread.file_path))
             .collect();
 
+        let spans = self.current_spans(&services, &detail, &visible_threads);
         let mut threads = Vec::with_capacity(visible_threads.len());
         let mut comments: HashMap<String, Vec<Comment>> = HashMap::new();
 
         for t in &visible_threads {
-            threads.push(convert_thread_summary(t));
+            threads.push(convert_thread_summary(t, spans.get(&t.thread_id)));
 
             let core_comments = services
                 .comments()
@@ -141,9 +198,25 @@ impl SealClient for CoreClient {
                 .map_err(|e| anyhow::anyhow!("{e}"))?;
 
             if !core_comments.is_empty() {
+                // Suggestions replace the anchored lines; only look them up when needed
+                let anchor_lines = if core_comments.iter().any(|c| c.suggestion.is_some()) {
+                    services
+                        .threads()
+                        .get_optional(&t.thread_id)
+                        .ok()
+                        .flatten()
+                        .and_then(|detail| detail.anchor)
+                        .map(|anchor| anchor.lines)
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
                 comments.insert(
                     t.thread_id.clone(),
-                    core_comments.iter().map(convert_comment).collect(),
+                    core_comments
+                        .iter()
+                        .map(|c| convert_comment(c, &anchor_lines))
+                        .collect(),
                 );
             }
         }
@@ -151,7 +224,7 @@ impl SealClient for CoreClient {
         let review_detail = convert_review_detail(&detail);
 
         // Build file diffs using SCM
-        let files = self.build_file_diffs(&detail, &visible_threads);
+        let files = self.build_file_diffs(&detail, &visible_threads, since_revision);
 
         Ok(Some(ReviewData {
             detail: review_detail,
@@ -184,6 +257,15 @@ impl SealClient for CoreClient {
             _ => CodeSelection::line(start_line as u32),
         };
 
+        // Snapshot the anchored lines (best effort) for later re-anchoring
+        let anchor = resolve_backend(&self.repo_root, ScmPreference::Auto)
+            .ok()
+            .and_then(|scm| {
+                capture_anchor(scm.as_ref(), file_path, &review.initial_commit, &selection)
+                    .ok()
+                    .flatten()
+            });
+
         services
             .comments()
             .add_to_review(
@@ -191,7 +273,10 @@ impl SealClient for CoreClient {
                 file_path,
                 selection,
                 body,
+                None,
                 review.initial_commit.clone(),
+                anchor,
+                None,
                 Some(&agent),
             )
             .map_err(|e| anyhow::anyhow!("{e}"))?;
@@ -205,37 +290,151 @@ impl SealClient for CoreClient {
 
         services
             .comments()
-            .add_to_thread(thread_id, body, Some(&agent))
+            .add_to_thread(thread_id, body, None, Some(&agent))
             .map_err(|e| anyhow::anyhow!("{e}"))?;
 
         Ok(())
     }
+
+    fn save_draft(
+        &self,
+        review_id: &str,
+        file_path: &str,
+        start_line: i64,
+        end_line: Option<i64>,
+        thread_id: Option<&str>,
+        body: &str,
+    ) -> Result<()> {
+        let services = self.services()?;
+        let agent = Self::comment_agent();
+
+        if let Some(thread_id) = thread_id {
+            return services
+                .drafts()
+                .save_reply(thread_id, body, None, Some(&agent))
+                .map_err(|e| anyhow::anyhow!("{e}"));
+        }
+
+        let review = services
+            .reviews()
+            .get(review_id)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+        #[allow(clippy::cast_sign_loss)]
+        let selection = match end_line {
+            Some(end) if end != start_line => CodeSelection::range(start_line as u32, end as u32),
+            _ => CodeSelection::line(start_line as u32),
+        };
+
+        let anchor = resolve_backend(&self.repo_root, ScmPreference::Auto)
+            .ok()
+            .and_then(|scm| {
+                capture_anchor(scm.as_ref(), file_path, &review.initial_commit, &selection)
+                    .ok()
+                    .flatten()
+            });
+
+        services
+            .drafts()
+            .save_comment(
+                review_id,
+                file_path,
+                selection,
+                body,
+                None,
+                review.initial_commit,
+                anchor,
+                None,
+                Some(&agent),
+            )
+            .map_err(|e| anyhow::anyhow!("{e}"))
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<SearchHit>> {
+        let services = self.services()?;
+        let hits = services
+            .search()
+            .query(query, &SearchFilter::default(), SEARCH_LIMIT)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+        Ok(hits
+            .into_iter()
+            .map(|hit| SearchHit {
+                review_id: hit.review_id,
+                snippet: hit.snippet,
+            })
+            .collect())
+    }
 }
 
 // -- Diff assembly (mirrors CLI `build_file_diffs` logic) --
 
 impl CoreClient {
+    /// Where each thread's selection lives in the review's target commit.
+    ///
+    /// Threads whose drift can't be computed (or whose lines were deleted)
+    /// are left out and keep their original position.
+    fn current_spans(
+        &self,
+        services: &SealServices,
+        review: &seal_core::projection::ReviewDetail,
+        threads: &[seal_core::projection::ThreadSummary],
+    ) -> HashMap<String, LineRange> {
+        let Ok(scm) = resolve_backend(&self.repo_root, ScmPreference::Auto) else {
+            return HashMap::new();
+        };
+        let target_commit = resolve_target_commit(scm.as_ref(), review);
+
+        // Threads on the same file and commit share one diff
+        let mut files: HashMap<(String, String), Option<FileDrift>> = HashMap::new();
+        threads
+            .iter()
+            .filter_map(|t| {
+                let detail = services.threads().get_optional(&t.thread_id).ok()??;
+                let path = t.moved_from.as_deref().unwrap_or(&t.file_path);
+                let file = files
+                    .entry((path.to_string(), detail.commit_hash.clone()))
+                    .or_insert_with(|| {
+                        FileDrift::between(scm.as_ref(), path, &detail.commit_hash, &target_commit)
+                            .ok()
+                    })
+                    .as_ref()?;
+                let original = LineRange::new(
+                    t.selection_start as u32,
+                    t.selection_end.unwrap_or(t.selection_start) as u32,
+                );
+                file.range(original)
+                    .current
+                    .map(|span| (t.thread_id.clone(), span))
+            })
+            .collect()
+    }
+
     fn build_file_diffs(
         &self,
         review: &seal_core::projection::ReviewDetail,
         threads: &[seal_core::projection::ThreadSummary],
+        since_revision: Option<i64>,
     ) -> Vec<FileData> {
         let scm = match resolve_backend(&self.repo_root, ScmPreference::Auto) {
             Ok(s) => s,
             Err(_) => return Vec::new(),
         };
 
-        // Resolve target commit
-        let target_commit = review
-            .final_commit
-            .clone()
-            .or_else(|| scm.commit_for_anchor(&review.scm_anchor).ok())
-            .or_else(|| scm.commit_for_anchor(&review.jj_change_id).ok())
-            .unwrap_or_else(|| review.initial_commit.clone());
+        let target_commit = resolve_target_commit(scm.as_ref(), review);
 
-        let base_commit = scm
-            .parent_commit(&target_commit)
-            .unwrap_or_else(|_| review.initial_commit.clone());
+        // Interdiffs start from the requested revision; otherwise show the whole change
+        let since_commit = since_revision.and_then(|rev| {
+            review
+                .revisions
+                .iter()
+                .find(|r| r.revision == rev)
+                .map(|r| r.commit_hash.clone())
+        });
+        let base_commit = since_commit.unwrap_or_else(|| {
+            scm.parent_commit(&target_commit)
+                .unwrap_or_else(|_| review.initial_commit.clone())
+        });
 
         // Get full diff and split by file
         let full_diff = scm
@@ -306,6 +505,19 @@ impl CoreClient {
     }
 }
 
+/// Resolve the commit a review currently points at.
+fn resolve_target_commit(
+    scm: &dyn ScmRepo,
+    review: &seal_core::projection::ReviewDetail,
+) -> String {
+    review
+        .final_commit
+        .clone()
+        .or_else(|| scm.commit_for_anchor(&review.scm_anchor).ok())
+        .or_else(|| scm.commit_for_anchor(&review.jj_change_id).ok())
+        .unwrap_or_else(|| review.initial_commit.clone())
+}
+
 /// Split a full git-format diff into per-file sections.
 fn split_diff_by_file(full_diff: &str) -> HashMap<&str, &str> {
     let mut result = HashMap::new();
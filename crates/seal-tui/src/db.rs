@@ -16,6 +16,16 @@ pub struct ReviewSummary {
     pub open_thread_count: i64,
     #[serde(default)]
     pub reviewers: Vec<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// A full-text search match, reduced to the review it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub review_id: String,
+    /// Matching excerpt with hits wrapped in `[` `]`
+    pub snippet: String,
 }
 
 /// Full details of a review.
@@ -37,6 +47,44 @@ pub struct ReviewDetail {
     pub abandon_reason: Option<String>,
     pub thread_count: i64,
     pub open_thread_count: i64,
+    #[serde(default)]
+    pub revisions: Vec<ReviewRevision>,
+    #[serde(default)]
+    pub votes: Vec<ReviewVote>,
+    /// Review this one is stacked on
+    #[serde(default)]
+    pub depends_on: Option<String>,
+    /// Reviews stacked directly on this one
+    #[serde(default)]
+    pub dependents: Vec<String>,
+}
+
+impl ReviewDetail {
+    /// Number of the most recent revision (1 if the review was never updated).
+    #[must_use]
+    pub fn latest_revision(&self) -> i64 {
+        self.revisions.last().map_or(1, |r| r.revision)
+    }
+}
+
+/// A pushed iteration of a review. Revision 1 is the initial commit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewRevision {
+    pub revision: i64,
+    pub commit_hash: String,
+    pub note: Option<String>,
+    pub author: String,
+    pub created_at: String,
+}
+
+/// A reviewer's vote on a review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewVote {
+    pub reviewer: String,
+    pub vote: String,
+    /// Cast against an older commit than the review's current one
+    #[serde(default)]
+    pub stale: bool,
 }
 
 /// Summary of a thread for list views.
@@ -48,6 +96,34 @@ pub struct ThreadSummary {
     pub selection_end: Option<i64>,
     pub status: String,
     pub comment_count: i64,
+    /// First line of the selection in the review's current commit, after drift
+    #[serde(default)]
+    pub current_start: Option<i64>,
+    /// Last line of the selection in the review's current commit, after drift
+    #[serde(default)]
+    pub current_end: Option<i64>,
+    /// Path the thread was created on, if the file has since been renamed
+    #[serde(default)]
+    pub moved_from: Option<String>,
+    /// Severity: "blocking", "nit", "question" or "praise"
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
+impl ThreadSummary {
+    /// Inclusive line span to highlight in the current diff.
+    ///
+    /// Falls back to the original selection when drift is unknown.
+    #[must_use]
+    pub fn current_range(&self) -> (i64, i64) {
+        let start = self.current_start.unwrap_or(self.selection_start);
+        let end = self
+            .current_end
+            .or(self.selection_end)
+            .unwrap_or(self.selection_start)
+            .max(start);
+        (start, end)
+    }
 }
 
 /// Full details of a thread.
@@ -76,6 +152,43 @@ pub struct Comment {
     pub author: String,
     pub body: String,
     pub created_at: String,
+    #[serde(default)]
+    pub edited_at: Option<String>,
+    #[serde(default)]
+    pub retracted_at: Option<String>,
+    #[serde(default)]
+    pub suggestion: Option<SuggestionDiff>,
+}
+
+/// A suggested change attached to a comment, shown as a mini-diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestionDiff {
+    /// Lines the suggestion replaces (empty if the thread has no anchor snapshot)
+    pub removed: Vec<String>,
+    /// Replacement lines
+    pub added: Vec<String>,
+}
+
+impl Comment {
+    /// Author label shown above the comment body, with an "(edited)" marker.
+    #[must_use]
+    pub fn author_label(&self) -> String {
+        if self.edited_at.is_some() && self.retracted_at.is_none() {
+            format!("@{} (edited)", self.author)
+        } else {
+            format!("@{}", self.author)
+        }
+    }
+
+    /// Markdown body to render; retracted comments show a marker instead.
+    #[must_use]
+    pub fn display_body(&self) -> &str {
+        if self.retracted_at.is_some() {
+            "_comment retracted_"
+        } else {
+            &self.body
+        }
+    }
 }
 
 /// Per-file diff and content data from seal.
@@ -114,10 +227,17 @@ pub trait SealClient {
 
     /// Load full review data (detail, threads, comments) for a review.
     ///
+    /// With `since_revision`, file diffs are interdiffs against that
+    /// revision's commit instead of the full change.
+    ///
     /// # Errors
     ///
     /// Returns an error if the backend query fails.
-    fn load_review_data(&self, review_id: &str) -> Result<Option<ReviewData>>;
+    fn load_review_data(
+        &self,
+        review_id: &str,
+        since_revision: Option<i64>,
+    ) -> Result<Option<ReviewData>>;
 
     /// Add a comment to a review on specific lines (auto-creates thread).
     ///
@@ -139,4 +259,31 @@ pub trait SealClient {
     ///
     /// Returns an error if the CLI call fails.
     fn reply(&self, thread_id: &str, body: &str) -> Result<()>;
+
+    /// Save a comment as a draft instead of posting it.
+    ///
+    /// Drafts reply to `thread_id` if given, otherwise they target the lines.
+    /// They are published later by `seal review submit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the draft can't be saved.
+    fn save_draft(
+        &self,
+        review_id: &str,
+        file_path: &str,
+        start_line: i64,
+        end_line: Option<i64>,
+        thread_id: Option<&str>,
+        body: &str,
+    ) -> Result<()>;
+
+    /// Full-text search over review titles, descriptions, and comments.
+    ///
+    /// Hits are ordered by relevance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend query fails.
+    fn search(&self, query: &str) -> Result<Vec<SearchHit>>;
 }
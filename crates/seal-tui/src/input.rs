@@ -59,6 +59,7 @@ fn map_review_list_key(key: KeyCode, modifiers: KeyModifiers, model: &Model) ->
         return match key {
             KeyCode::Esc => Message::SearchClear,
             KeyCode::Backspace => Message::SearchBackspace,
+            KeyCode::Tab => Message::SearchToggleMode,
             KeyCode::Enter => {
                 // Select current review from filtered results
                 let reviews = model.filtered_reviews();
@@ -277,6 +278,9 @@ fn map_review_detail_key(model: &Model, key: KeyCode, modifiers: KeyModifiers) -
             KeyCode::Char('G') | KeyCode::End => Message::SidebarBottom,
             KeyCode::Enter => Message::SidebarSelect,
             KeyCode::Char('s') => Message::ToggleSidebar,
+            KeyCode::Char('K') => Message::CycleThreadKindFilter,
+            KeyCode::Char('{') => Message::StackParent,
+            KeyCode::Char('}') => Message::StackChild,
             _ => Message::Noop,
         },
         Focus::DiffPane if model.visual_mode => match key {
@@ -327,6 +331,8 @@ fn map_review_detail_key(model: &Model, key: KeyCode, modifiers: KeyModifiers) -
                 KeyCode::Char('p' | 'N') => Message::PrevThread,
                 KeyCode::Char('v') => Message::ToggleDiffView,
                 KeyCode::Char('w') => Message::ToggleDiffWrap,
+                KeyCode::Char('R') => Message::CycleDiffBase,
+                KeyCode::Char('K') => Message::CycleThreadKindFilter,
                 KeyCode::Char('o') => Message::OpenFileInEditor,
                 KeyCode::Char('u') => Message::ScrollHalfPageUp,
                 KeyCode::Char('d') => Message::ScrollHalfPageDown,
@@ -342,6 +348,8 @@ fn map_review_detail_key(model: &Model, key: KeyCode, modifiers: KeyModifiers) -
                 KeyCode::Char('V') => Message::VisualToggle,
                 KeyCode::Char('[') => Message::PrevFile,
                 KeyCode::Char(']') => Message::NextFile,
+                KeyCode::Char('{') => Message::StackParent,
+                KeyCode::Char('}') => Message::StackChild,
                 _ => Message::Noop,
             }
         }
@@ -361,6 +369,7 @@ fn map_review_detail_key(model: &Model, key: KeyCode, modifiers: KeyModifiers) -
             if modifiers.contains(KeyModifiers::CTRL) {
                 return match key {
                     KeyCode::Char('s') => Message::SaveComment,
+                    KeyCode::Char('d') => Message::SaveDraft,
                     KeyCode::Char('w') => Message::CommentDeleteWord,
                     KeyCode::Char('u') => Message::CommentClearLine,
                     KeyCode::Char('a') => Message::CommentHome,
@@ -447,11 +456,170 @@ mod tests {
             abandon_reason: None,
             thread_count: 0,
             open_thread_count: 0,
+            revisions: Vec::new(),
+            votes: Vec::new(),
+            depends_on: None,
+            dependents: Vec::new(),
         });
 
         let msg = map_review_detail_key(&model, KeyCode::Char('j'), KeyModifiers::empty());
         assert!(matches!(msg, Message::ScrollDown));
     }
+
+    #[test]
+    fn diff_pane_braces_open_neighbouring_stacked_reviews() {
+        let mut model = Model::new(120, 40, UiConfig::default());
+        model.screen = Screen::ReviewDetail;
+        model.focus = Focus::DiffPane;
+        model.current_review = Some(crate::db::ReviewDetail {
+            review_id: "cr-2".to_string(),
+            jj_change_id: "main".to_string(),
+            scm_kind: "git".to_string(),
+            scm_anchor: "main".to_string(),
+            initial_commit: "abc123".to_string(),
+            final_commit: None,
+            title: "Title".to_string(),
+            description: None,
+            author: "alice".to_string(),
+            created_at: "2026-03-10T00:00:00Z".to_string(),
+            status: "open".to_string(),
+            status_changed_at: None,
+            status_changed_by: None,
+            abandon_reason: None,
+            thread_count: 0,
+            open_thread_count: 0,
+            revisions: Vec::new(),
+            votes: Vec::new(),
+            depends_on: Some("cr-1".to_string()),
+            dependents: Vec::new(),
+        });
+
+        let msg = map_review_detail_key(&model, KeyCode::Char('}'), KeyModifiers::empty());
+        assert!(matches!(msg, Message::StackChild));
+        crate::update::update(&mut model, msg);
+        assert!(model.current_review.is_some());
+        assert!(model.flash_message.is_some());
+
+        let msg = map_review_detail_key(&model, KeyCode::Char('{'), KeyModifiers::empty());
+        assert!(matches!(msg, Message::StackParent));
+        crate::update::update(&mut model, msg);
+        assert_eq!(model.screen, Screen::ReviewDetail);
+        // Cleared so the parent review gets loaded
+        assert!(model.current_review.is_none());
+    }
+
+    #[test]
+    fn search_tab_toggles_full_text_and_filters_by_hits() {
+        let mut model = Model::new(120, 40, UiConfig::default());
+        model.search_active = true;
+        model.reviews = ["cr-1", "cr-2"]
+            .iter()
+            .map(|id| crate::db::ReviewSummary {
+                review_id: (*id).to_string(),
+                title: "Title".to_string(),
+                author: "alice".to_string(),
+                status: "open".to_string(),
+                thread_count: 0,
+                open_thread_count: 0,
+                reviewers: Vec::new(),
+                labels: Vec::new(),
+            })
+            .collect();
+
+        let msg = map_review_list_key(KeyCode::Tab, KeyModifiers::empty(), &model);
+        assert!(matches!(msg, Message::SearchToggleMode));
+        crate::update::update(&mut model, msg);
+        assert!(model.search_full_text);
+
+        crate::update::update(&mut model, Message::SearchInput("cache".to_string()));
+        assert!(model.pending_search);
+        model
+            .search_hits
+            .insert("cr-2".to_string(), "[cache] miss".to_string());
+
+        let reviews = model.filtered_reviews();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].review_id, "cr-2");
+    }
+
+    #[test]
+    fn search_label_tokens_filter_by_label() {
+        let mut model = Model::new(120, 40, UiConfig::default());
+        model.reviews = [
+            ("cr-1", "Fix cache", vec!["bug"]),
+            ("cr-2", "Fix docs", vec![]),
+        ]
+        .iter()
+        .map(|(id, title, labels)| crate::db::ReviewSummary {
+            review_id: (*id).to_string(),
+            title: (*title).to_string(),
+            author: "alice".to_string(),
+            status: "open".to_string(),
+            thread_count: 0,
+            open_thread_count: 0,
+            reviewers: Vec::new(),
+            labels: labels.iter().map(|l| (*l).to_string()).collect(),
+        })
+        .collect();
+
+        model.search_input = "label:bug".to_string();
+        let reviews = model.filtered_reviews();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].review_id, "cr-1");
+
+        model.search_input = "docs label:bug".to_string();
+        assert!(model.filtered_reviews().is_empty());
+
+        model.search_input = "fix".to_string();
+        assert_eq!(model.filtered_reviews().len(), 2);
+    }
+
+    #[test]
+    fn sidebar_k_cycles_thread_kind_filter() {
+        let mut model = Model::new(120, 40, UiConfig::default());
+        model.screen = Screen::ReviewDetail;
+        model.focus = Focus::FileSidebar;
+        model.threads = [
+            ("th-1", Some("blocking")),
+            ("th-2", Some("nit")),
+            ("th-3", None),
+        ]
+        .iter()
+        .map(|(id, kind)| crate::db::ThreadSummary {
+            thread_id: (*id).to_string(),
+            file_path: "src/lib.rs".to_string(),
+            selection_start: 1,
+            selection_end: None,
+            status: "open".to_string(),
+            comment_count: 1,
+            current_start: None,
+            current_end: None,
+            moved_from: None,
+            kind: kind.map(String::from),
+        })
+        .collect();
+
+        let msg = map_review_detail_key(&model, KeyCode::Char('K'), KeyModifiers::empty());
+        assert!(matches!(msg, Message::CycleThreadKindFilter));
+        crate::update::update(&mut model, msg);
+        assert_eq!(model.thread_kind_filter, Some("blocking"));
+
+        let threads: Vec<String> = model
+            .sidebar_items()
+            .into_iter()
+            .filter_map(|item| match item {
+                crate::model::SidebarItem::Thread { thread_id, .. } => Some(thread_id),
+                crate::model::SidebarItem::File { .. } => None,
+            })
+            .collect();
+        assert_eq!(threads, vec!["th-1"]);
+
+        for _ in 0..4 {
+            crate::update::update(&mut model, Message::CycleThreadKindFilter);
+        }
+        assert_eq!(model.thread_kind_filter, None);
+        assert_eq!(model.sidebar_items().len(), 4);
+    }
 }
 
 fn map_command_palette_key(key: KeyCode, modifiers: KeyModifiers) -> Message {
@@ -47,7 +47,7 @@ use anyhow::{Context, Result};
 
 use crate::config::{load_ui_config, save_ui_config};
 use crate::input::map_event_to_message;
-use crate::model::{CommentRequest, DiffViewMode, EditorRequest};
+use crate::model::{split_label_filters, CommentRequest, DiffViewMode, EditorRequest};
 use crate::render_backend::{enable_raw_mode, Event, RawModeGuard, Renderer, RendererOptions};
 use crate::render_backend::{event_from_ftui, rgba_to_packed, OptimizedBuffer};
 use crate::render_backend::{
@@ -339,6 +339,18 @@ fn process_event(event: &Event, model: &mut Model, ctx: &mut EventContext<'_>) -
         model.needs_redraw = true;
     }
 
+    if std::mem::take(&mut model.pending_reload) {
+        if let Some(client) = ctx.client.as_ref() {
+            reload_review_data(model, *client, ctx.repo_path);
+        }
+    }
+
+    if std::mem::take(&mut model.pending_search) {
+        if let Some(client) = ctx.client.as_ref() {
+            run_full_text_search(model, *client);
+        }
+    }
+
     if let Some(request) = model.pending_editor_request.take() {
         ctx.terminal_session.take();
         let (prev_width, prev_height) = ctx.renderer.size();
@@ -435,16 +447,35 @@ fn process_event(event: &Event, model: &mut Model, ctx: &mut EventContext<'_>) -
     // Handle inline editor submission
     if let Some(submission) = model.pending_comment_submission.take() {
         if let Some(client) = ctx.client.as_ref() {
-            let persist_result = persist_comment(
-                *client,
-                ctx.repo_path,
-                &submission.request,
-                &submission.body,
-            );
-            match persist_result {
-                Ok(()) => reload_review_data(model, *client, ctx.repo_path),
-                Err(e) => {
-                    model.flash_message = Some(format!("Comment failed: {e}"));
+            if submission.draft {
+                let request = &submission.request;
+                let draft_result = client.save_draft(
+                    &request.review_id,
+                    &request.file_path,
+                    request.start_line,
+                    request.end_line,
+                    request.thread_id.as_deref(),
+                    &submission.body,
+                );
+                model.flash_message = Some(match draft_result {
+                    Ok(()) => format!(
+                        "Draft saved; publish with `seal review submit {}`",
+                        request.review_id
+                    ),
+                    Err(e) => format!("Draft failed: {e}"),
+                });
+            } else {
+                let persist_result = persist_comment(
+                    *client,
+                    ctx.repo_path,
+                    &submission.request,
+                    &submission.body,
+                );
+                match persist_result {
+                    Ok(()) => reload_review_data(model, *client, ctx.repo_path),
+                    Err(e) => {
+                        model.flash_message = Some(format!("Comment failed: {e}"));
+                    }
                 }
             }
         }
@@ -707,7 +738,7 @@ fn reload_review_data(model: &mut Model, client: &dyn SealClient, _repo_path: Op
         return;
     };
     let review_id = review.review_id.clone();
-    if let Ok(Some(data)) = client.load_review_data(&review_id) {
+    if let Ok(Some(data)) = client.load_review_data(&review_id, model.diff_since_revision) {
         model.current_review = Some(data.detail);
         model.threads = data.threads;
         model.all_comments = data.comments;
@@ -715,12 +746,34 @@ fn reload_review_data(model: &mut Model, client: &dyn SealClient, _repo_path: Op
     }
 }
 
+fn run_full_text_search(model: &mut Model, client: &dyn SealClient) {
+    model.search_hits.clear();
+    let (_, query) = split_label_filters(&model.search_input);
+    if query.is_empty() {
+        return;
+    }
+    match client.search(&query) {
+        Ok(hits) => {
+            // Hits are ranked, so the first one per review is its best match
+            for hit in hits {
+                model
+                    .search_hits
+                    .entry(hit.review_id)
+                    .or_insert(hit.snippet);
+            }
+        }
+        Err(e) => {
+            model.flash_message = Some(format!("Search failed: {e}"));
+        }
+    }
+}
+
 fn handle_data_loading(model: &mut Model, client: &dyn SealClient, _repo_path: Option<&Path>) {
     if model.screen == Screen::ReviewDetail && model.current_review.is_none() {
         let reviews = model.filtered_reviews();
         if let Some(review) = reviews.get(model.list_index) {
             let review_id = review.review_id.clone();
-            if let Ok(Some(data)) = client.load_review_data(&review_id) {
+            if let Ok(Some(data)) = client.load_review_data(&review_id, model.diff_since_revision) {
                 model.current_review = Some(data.detail);
                 model.threads = data.threads;
                 model.all_comments = data.comments;
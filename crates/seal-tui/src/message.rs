@@ -8,6 +8,10 @@ pub enum Message {
     SelectReview(String),
     /// Go back to previous screen
     Back,
+    /// Open the review the current one is stacked on
+    StackParent,
+    /// Open the first review stacked on the current one
+    StackChild,
 
     // === List Navigation ===
     /// Move selection up in list
@@ -104,12 +108,18 @@ pub enum Message {
     SearchClearLine,
     /// Clear and deactivate search
     SearchClear,
+    /// Switch search between title filtering and full-text search
+    SearchToggleMode,
     /// Toggle between unified and side-by-side diff view
     ToggleDiffView,
     /// Toggle file sidebar visibility
     ToggleSidebar,
     /// Toggle diff line wrapping
     ToggleDiffWrap,
+    /// Step the diff base back through earlier revisions (interdiff), then back to the full diff
+    CycleDiffBase,
+    /// Cycle the sidebar thread filter through each thread kind, then back to all threads
+    CycleThreadKindFilter,
     /// Open current file in editor
     OpenFileInEditor,
 
@@ -143,6 +153,7 @@ pub enum Message {
     CommentDeleteWord,
     CommentClearLine,
     SaveComment,
+    SaveDraft,
     CancelComment,
 
     // === Theme Selection ===
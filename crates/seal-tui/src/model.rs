@@ -112,6 +112,8 @@ pub struct CommentRequest {
 pub struct PendingCommentSubmission {
     pub request: CommentRequest,
     pub body: String,
+    /// Save as a draft instead of posting now
+    pub draft: bool,
 }
 
 /// In-TUI multi-line comment editor state.
@@ -337,6 +339,9 @@ impl LayoutMode {
     }
 }
 
+/// Thread kinds, in the order the sidebar filter cycles through them
+pub const THREAD_KINDS: [&str; 4] = ["blocking", "nit", "question", "praise"];
+
 /// Filter for review list
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ReviewFilter {
@@ -392,12 +397,18 @@ pub struct Model {
     pub expanded_thread: Option<String>,
     /// Review list filter
     pub filter: ReviewFilter,
+    /// Only list threads of this kind in the sidebar and thread navigation
+    pub thread_kind_filter: Option<&'static str>,
     /// Show sidebar in overlay mode
     pub sidebar_visible: bool,
     /// Diff view mode (unified or side-by-side)
     pub diff_view_mode: DiffViewMode,
     /// Wrap diff lines when enabled
     pub diff_wrap: bool,
+    /// Show only changes since this revision (interdiff); `None` shows the full change
+    pub diff_since_revision: Option<i64>,
+    /// Review data must be reloaded (e.g. after changing the diff base)
+    pub pending_reload: bool,
     /// Pending editor launch request
     pub pending_editor_request: Option<EditorRequest>,
     /// Pending comment-via-$EDITOR request (Shift+A)
@@ -449,6 +460,13 @@ pub struct Model {
     // === Review list search ===
     pub search_input: String,
     pub search_active: bool,
+    /// Search titles, descriptions, and comments through the index instead of
+    /// filtering titles locally
+    pub search_full_text: bool,
+    /// Full-text matches for `search_input`: `review_id` → best snippet
+    pub search_hits: HashMap<String, String>,
+    /// Full-text query must be re-run against the client
+    pub pending_search: bool,
 
     // === Repo path for display ===
     pub repo_path: Option<String>,
@@ -503,9 +521,12 @@ impl Model {
             diff_cursor: 0,
             expanded_thread: None,
             filter: ReviewFilter::default(),
+            thread_kind_filter: None,
             sidebar_visible: true,
             diff_view_mode: DiffViewMode::default(),
             diff_wrap: true,
+            diff_since_revision: None,
+            pending_reload: false,
             pending_editor_request: None,
             pending_comment_request: None,
             inline_editor: None,
@@ -530,6 +551,9 @@ impl Model {
             cursor_stops: RefCell::new(Vec::new()),
             search_input: String::new(),
             search_active: false,
+            search_full_text: false,
+            search_hits: HashMap::new(),
+            pending_search: false,
             repo_path: None,
             editor_name: std::env::var("EDITOR")
                 .or_else(|_| std::env::var("VISUAL"))
@@ -558,9 +582,20 @@ impl Model {
         if self.search_input.is_empty() {
             return status_filtered;
         }
-        let query = self.search_input.to_lowercase();
-        status_filtered
+        let (labels, query) = split_label_filters(&self.search_input);
+        let status_filtered = status_filtered
             .into_iter()
+            .filter(|r| labels.iter().all(|l| r.labels.contains(l)));
+        if query.is_empty() {
+            return status_filtered.collect();
+        }
+        if self.search_full_text {
+            return status_filtered
+                .filter(|r| self.search_hits.contains_key(&r.review_id))
+                .collect();
+        }
+        let query = query.to_lowercase();
+        status_filtered
             .filter(|r| {
                 r.title.to_lowercase().contains(&query)
                     || r.review_id.to_lowercase().contains(&query)
@@ -617,10 +652,19 @@ impl Model {
             .collect()
     }
 
-    /// Get threads that are visible in the current diff (all threads for the file)
+    /// Get threads for the current file that pass the thread kind filter
     #[must_use]
     pub fn visible_threads_for_current_file(&self) -> Vec<&ThreadSummary> {
-        self.threads_for_current_file()
+        let mut threads = self.threads_for_current_file();
+        threads.retain(|t| self.matches_thread_kind_filter(t));
+        threads
+    }
+
+    /// Whether a thread passes the thread kind filter
+    #[must_use]
+    pub fn matches_thread_kind_filter(&self, thread: &ThreadSummary) -> bool {
+        self.thread_kind_filter
+            .is_none_or(|kind| thread.kind.as_deref() == Some(kind))
     }
 
     /// Build a flat list of sidebar items: files with their threads as children
@@ -645,7 +689,7 @@ impl Model {
                 let mut file_threads: Vec<&ThreadSummary> = self
                     .threads
                     .iter()
-                    .filter(|t| t.file_path == file.path)
+                    .filter(|t| t.file_path == file.path && self.matches_thread_kind_filter(t))
                     .collect();
                 file_threads
                     .sort_by_key(|t| positions.get(&t.thread_id).copied().unwrap_or(usize::MAX));
@@ -654,6 +698,7 @@ impl Model {
                     items.push(SidebarItem::Thread {
                         thread_id: thread.thread_id.clone(),
                         status: thread.status.clone(),
+                        kind: thread.kind.clone(),
                         comment_count: thread.comment_count,
                         file_idx,
                     });
@@ -723,8 +768,26 @@ pub enum SidebarItem {
     Thread {
         thread_id: String,
         status: String,
+        kind: Option<String>,
         comment_count: i64,
         /// Parent file index for selection matching
         file_idx: usize,
     },
 }
+
+/// Split `label:<name>` tokens out of a search query.
+///
+/// Returns the requested labels and the remaining query text, which is
+/// matched against titles (or sent to full-text search) as before.
+#[must_use]
+pub fn split_label_filters(input: &str) -> (Vec<String>, String) {
+    let mut labels = Vec::new();
+    let mut rest = Vec::new();
+    for word in input.split_whitespace() {
+        match word.strip_prefix("label:") {
+            Some(label) if !label.is_empty() => labels.push(label.to_string()),
+            _ => rest.push(word),
+        }
+    }
+    (labels, rest.join(" "))
+}
@@ -549,6 +549,10 @@ mod tests {
             selection_end: end,
             status: "open".to_string(),
             comment_count: 1,
+            current_start: None,
+            current_end: None,
+            moved_from: None,
+            kind: None,
         }
     }
 
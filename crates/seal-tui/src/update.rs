@@ -5,7 +5,7 @@ use crate::layout::visible_stream_rows;
 use crate::message::Message;
 use crate::model::{
     CommentRequest, DiffViewMode, EditorRequest, Focus, InlineEditor, Model, PaletteMode,
-    PendingCommentSubmission, ReviewFilter, Screen,
+    PendingCommentSubmission, ReviewFilter, Screen, THREAD_KINDS,
 };
 use crate::stream::{
     active_file_index, compute_stream_layout, file_scroll_offset, StreamLayoutParams,
@@ -435,13 +435,14 @@ fn update_comment(model: &mut Model, msg: Message) {
                 editor.clear_line();
             }
         }
-        Message::SaveComment => {
+        Message::SaveComment | Message::SaveDraft => {
             if let Some(editor) = model.inline_editor.take() {
                 let body = editor.body();
                 if !body.is_empty() {
                     model.pending_comment_submission = Some(PendingCommentSubmission {
                         request: editor.request,
                         body,
+                        draft: matches!(msg, Message::SaveDraft),
                     });
                 }
             }
@@ -601,6 +602,7 @@ fn update_navigation(model: &mut Model, msg: &Message) {
             model.diff_scroll = 0;
             model.diff_cursor = 0;
             model.expanded_thread = None;
+            model.diff_since_revision = None;
             model.current_review = None; // Clear to trigger reload
             model.current_diff = None;
             model.current_file_content = None;
@@ -630,6 +632,26 @@ fn update_navigation(model: &mut Model, msg: &Message) {
                 // Already at top level, could quit or no-op
             }
         },
+        Message::StackParent | Message::StackChild => {
+            let parent = matches!(msg, Message::StackParent);
+            let target = model.current_review.as_ref().and_then(|review| {
+                if parent {
+                    review.depends_on.clone()
+                } else {
+                    review.dependents.first().cloned()
+                }
+            });
+            if let Some(review_id) = target {
+                update_navigation(model, &Message::SelectReview(review_id));
+            } else {
+                model.flash_message = Some(if parent {
+                    "Not stacked on another review".to_string()
+                } else {
+                    "No reviews are stacked on this one".to_string()
+                });
+                model.needs_redraw = true;
+            }
+        }
         _ => {}
     }
 }
@@ -656,6 +678,48 @@ fn update_view_filter(model: &mut Model, msg: &Message) {
             update_active_file_from_scroll(model);
         }
 
+        Message::CycleDiffBase => {
+            let latest = model
+                .current_review
+                .as_ref()
+                .map_or(1, crate::db::ReviewDetail::latest_revision);
+            if latest < 2 {
+                model.flash_message = Some("Review has only one revision".to_string());
+            } else {
+                // Newest interdiff first, then older ones, then back to the full diff
+                model.diff_since_revision = match model.diff_since_revision {
+                    None => Some(latest - 1),
+                    Some(rev) if rev > 1 => Some(rev - 1),
+                    Some(_) => None,
+                };
+                model.flash_message = Some(model.diff_since_revision.map_or_else(
+                    || "Showing full diff".to_string(),
+                    |rev| format!("Showing changes since revision {rev}"),
+                ));
+                model.diff_scroll = 0;
+                model.diff_cursor = 0;
+                model.pending_reload = true;
+            }
+            model.needs_redraw = true;
+        }
+
+        Message::CycleThreadKindFilter => {
+            let next = model.thread_kind_filter.map_or(0, |kind| {
+                THREAD_KINDS
+                    .iter()
+                    .position(|k| *k == kind)
+                    .map_or(0, |i| i + 1)
+            });
+            model.thread_kind_filter = THREAD_KINDS.get(next).copied();
+            model.flash_message = Some(model.thread_kind_filter.map_or_else(
+                || "Showing all threads".to_string(),
+                |kind| format!("Showing {kind} threads"),
+            ));
+            model.sidebar_index = 0;
+            model.sidebar_scroll = 0;
+            model.needs_redraw = true;
+        }
+
         Message::ToggleSidebar => {
             model.sidebar_visible = !model.sidebar_visible;
             if !model.sidebar_visible && matches!(model.focus, Focus::FileSidebar) {
@@ -826,11 +890,12 @@ pub fn update(model: &mut Model, msg: Message) {
         | Message::CommentDeleteWord
         | Message::CommentClearLine
         | Message::SaveComment
+        | Message::SaveDraft
         | Message::CancelComment => {
             update_comment(model, msg);
         }
 
-        Message::SelectReview(_) | Message::Back => {
+        Message::SelectReview(_) | Message::Back | Message::StackParent | Message::StackChild => {
             update_navigation(model, &msg);
         }
 
@@ -859,6 +924,8 @@ pub fn update(model: &mut Model, msg: Message) {
 
         Message::CycleStatusFilter
         | Message::ToggleDiffView
+        | Message::CycleDiffBase
+        | Message::CycleThreadKindFilter
         | Message::ToggleSidebar
         | Message::ToggleDiffWrap
         | Message::OpenFileInEditor => {
@@ -873,29 +940,42 @@ pub fn update(model: &mut Model, msg: Message) {
             model.search_input.push_str(text);
             model.list_index = 0;
             model.list_scroll = 0;
+            model.pending_search = model.search_full_text;
             model.needs_redraw = true;
         }
         Message::SearchBackspace => {
             model.search_input.pop();
             model.list_index = 0;
             model.list_scroll = 0;
+            model.pending_search = model.search_full_text;
             model.needs_redraw = true;
         }
         Message::SearchDeleteWord => {
             delete_last_word(&mut model.search_input);
             model.list_index = 0;
             model.list_scroll = 0;
+            model.pending_search = model.search_full_text;
             model.needs_redraw = true;
         }
         Message::SearchClearLine => {
             model.search_input.clear();
             model.list_index = 0;
             model.list_scroll = 0;
+            model.pending_search = model.search_full_text;
             model.needs_redraw = true;
         }
         Message::SearchClear => {
             model.search_input.clear();
             model.search_active = false;
+            model.search_hits.clear();
+            model.list_index = 0;
+            model.list_scroll = 0;
+            model.needs_redraw = true;
+        }
+        Message::SearchToggleMode => {
+            model.search_full_text = !model.search_full_text;
+            model.search_hits.clear();
+            model.pending_search = model.search_full_text;
             model.list_index = 0;
             model.list_scroll = 0;
             model.needs_redraw = true;
@@ -92,6 +92,7 @@ pub fn view(model: &Model, buffer: &mut OptimizedBuffer) {
     let help_area = Rect::new(panel.x, hotkey_row, panel.width, 1);
     let hints = [
         HotkeyHint::new("Submit", "ctrl+s"),
+        HotkeyHint::new("Draft", "ctrl+d"),
         HotkeyHint::new("Cancel", "esc"),
     ];
     draw_help_bar_ext(
@@ -176,15 +177,17 @@ fn render_existing_comments(
             buffer,
             content_x,
             y,
-            &format!("@{}", comment.author),
+            &comment.author_label(),
             content_width,
             theme.style_primary().with_bg(theme.panel_bg),
         );
         y += 1;
 
-        for line in
-            render_markdown_with_highlighter(&comment.body, body_width as usize, Some(highlighter))
-        {
+        for line in render_markdown_with_highlighter(
+            comment.display_body(),
+            body_width as usize,
+            Some(highlighter),
+        ) {
             if y >= panel.y + panel.height - 3 {
                 break 'comments;
             }
@@ -217,7 +220,7 @@ fn existing_comments_height(comments: &[Comment], content_width: u32) -> u32 {
     let mut rows = 0u32;
     for comment in comments.iter().skip(skip) {
         rows += 1;
-        rows += render_markdown(&comment.body, body_width).len() as u32;
+        rows += render_markdown(comment.display_body(), body_width).len() as u32;
     }
 
     rows + 1
@@ -229,7 +232,7 @@ fn existing_comments_start_index(comments: &[Comment], content_width: u32) -> us
     let mut count = 0usize;
 
     for comment in comments.iter().rev() {
-        let comment_rows = 1 + render_markdown(&comment.body, body_width).len() as u32;
+        let comment_rows = 1 + render_markdown(comment.display_body(), body_width).len() as u32;
         if count > 0 && rows + comment_rows > MAX_CONTEXT_ROWS {
             break;
         }
@@ -337,6 +340,9 @@ mod tests {
             author: author.to_string(),
             body: body.to_string(),
             created_at: "2026-03-10T00:00:00Z".to_string(),
+            edited_at: None,
+            retracted_at: None,
+            suggestion: None,
         }
     }
 
@@ -46,18 +46,17 @@ pub fn map_threads_to_diff(diff: &ParsedDiff, threads: &[&ThreadSummary]) -> Vec
     // anchoring when a thread's line number coincidentally matches a removed line
     // in a different commit.
     for thread in threads {
-        let start_line = thread.selection_start as u32;
-        let display_line = new_line_to_display.get(&start_line);
+        // Use the drifted span so edits since the thread was created don't
+        // misplace the highlight
+        let (start, end) = thread.current_range();
+        let display_line = new_line_to_display.get(&(start as u32));
 
         if let Some(&display_line) = display_line {
-            let line_count = thread
-                .selection_end
-                .map_or(1, |end| (end - thread.selection_start + 1) as usize);
+            let line_count = (end - start + 1) as usize;
 
             // Comment block goes after the last line of the range
-            let end_line = thread.selection_end.unwrap_or(thread.selection_start) as u32;
             let comment_after_line = new_line_to_display
-                .get(&end_line)
+                .get(&(end as u32))
                 .copied()
                 .unwrap_or(display_line);
 
@@ -81,13 +80,7 @@ pub fn map_threads_to_diff(diff: &ParsedDiff, threads: &[&ThreadSummary]) -> Vec
 pub(super) fn build_thread_ranges(threads: &[&ThreadSummary]) -> Vec<(i64, i64)> {
     threads
         .iter()
-        .map(|thread| {
-            let end = thread.selection_end.unwrap_or(thread.selection_start);
-            (
-                thread.selection_start.min(end),
-                thread.selection_start.max(end),
-            )
-        })
+        .map(|thread| thread.current_range())
         .collect()
 }
 
@@ -99,3 +92,68 @@ pub(super) fn line_in_thread_ranges(line: Option<i64>, ranges: &[(i64, i64)]) ->
         .iter()
         .any(|(start, end)| line >= *start && line <= *end)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread(start: i64, end: Option<i64>, current: Option<(i64, i64)>) -> ThreadSummary {
+        ThreadSummary {
+            thread_id: "th-1".to_string(),
+            file_path: "src/lib.rs".to_string(),
+            selection_start: start,
+            selection_end: end,
+            status: "open".to_string(),
+            comment_count: 1,
+            current_start: current.map(|(s, _)| s),
+            current_end: current.map(|(_, e)| e),
+            moved_from: None,
+            kind: None,
+        }
+    }
+
+    const DIFF: &str = "\
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,6 @@
+ a
++x
++y
+ b
+ c
+ d
+";
+
+    #[test]
+    fn map_threads_to_diff_uses_drifted_span() {
+        let diff = ParsedDiff::parse(DIFF);
+        // Originally lines 2-3; two lines were inserted above, so now 4-5
+        let drifted = thread(2, Some(3), Some((4, 5)));
+        let anchors = map_threads_to_diff(&diff, &[&drifted]);
+
+        assert_eq!(anchors.len(), 1);
+        // Display index 0 is the hunk header, new line N is at index N
+        assert_eq!(anchors[0].display_line, 4);
+        assert_eq!(anchors[0].comment_after_line, 5);
+        assert_eq!(anchors[0].line_count, 2);
+    }
+
+    #[test]
+    fn build_thread_ranges_uses_drifted_span() {
+        let drifted = thread(2, Some(3), Some((4, 5)));
+        let ranges = build_thread_ranges(&[&drifted]);
+
+        assert_eq!(ranges, vec![(4, 5)]);
+        assert!(line_in_thread_ranges(Some(5), &ranges));
+        assert!(!line_in_thread_ranges(Some(2), &ranges));
+    }
+
+    #[test]
+    fn map_threads_to_diff_falls_back_to_original_selection() {
+        let diff = ParsedDiff::parse(DIFF);
+        let anchors = map_threads_to_diff(&diff, &[&thread(2, Some(3), None)]);
+
+        assert_eq!(anchors[0].display_line, 2);
+        assert_eq!(anchors[0].line_count, 2);
+    }
+}
@@ -21,6 +21,8 @@ pub(super) enum CommentLineKind {
     Header,
     Author,
     Markdown(MarkdownStyle),
+    /// A line of a suggested change; `true` for added lines
+    Suggestion(bool),
 }
 
 #[derive(Clone)]
@@ -42,15 +44,19 @@ fn build_comment_lines(
         || format!("{}", thread.selection_start),
         |end| format!("{}-{}", thread.selection_start, end),
     );
+    let header = thread.kind.as_ref().map_or_else(
+        || thread.thread_id.clone(),
+        |kind| format!("{} [{kind}]", thread.thread_id),
+    );
     let mut right_text = format!("{}:{}", thread.file_path, line_range);
-    let right_max = content_width.saturating_sub(thread.thread_id.len().saturating_add(1));
+    let right_max = content_width.saturating_sub(header.len().saturating_add(1));
     if right_max > 0 && right_text.len() > right_max {
         right_text = crate::view::components::truncate_path(&right_text, right_max);
     } else if right_max == 0 {
         right_text.clear();
     }
     content_lines.push(CommentLine {
-        content: MarkdownContent::Text(thread.thread_id.clone()),
+        content: MarkdownContent::Text(header),
         right: if right_text.is_empty() {
             None
         } else {
@@ -58,6 +64,13 @@ fn build_comment_lines(
         },
         kind: CommentLineKind::Header,
     });
+    if let Some(old_path) = &thread.moved_from {
+        content_lines.push(CommentLine {
+            content: MarkdownContent::Text(format!("moved from {old_path}")),
+            right: None,
+            kind: CommentLineKind::Markdown(MarkdownStyle::Quote),
+        });
+    }
     content_lines.push(CommentLine {
         content: MarkdownContent::Text(String::new()),
         right: None,
@@ -65,7 +78,7 @@ fn build_comment_lines(
     });
 
     for (index, comment) in comments.iter().enumerate() {
-        let left = format!("@{}", comment.author);
+        let left = comment.author_label();
         let right_max = content_width.saturating_sub(left.len().saturating_add(1));
         let right = if right_max > 0 {
             let mut id = comment.comment_id.clone();
@@ -82,10 +95,12 @@ fn build_comment_lines(
             kind: CommentLineKind::Author,
         });
         let rendered = match highlighter {
-            Some(highlighter) => {
-                render_markdown_with_highlighter(&comment.body, content_width, Some(highlighter))
-            }
-            None => render_markdown(&comment.body, content_width),
+            Some(highlighter) => render_markdown_with_highlighter(
+                comment.display_body(),
+                content_width,
+                Some(highlighter),
+            ),
+            None => render_markdown(comment.display_body(), content_width),
         };
         for line in rendered {
             content_lines.push(CommentLine {
@@ -94,6 +109,26 @@ fn build_comment_lines(
                 kind: CommentLineKind::Markdown(line.style),
             });
         }
+        if let Some(suggestion) = comment
+            .suggestion
+            .as_ref()
+            .filter(|_| comment.retracted_at.is_none())
+        {
+            content_lines.push(CommentLine {
+                content: MarkdownContent::Text("Suggested change:".to_string()),
+                right: None,
+                kind: CommentLineKind::Markdown(MarkdownStyle::Quote),
+            });
+            let removed = suggestion.removed.iter().map(|l| (format!("- {l}"), false));
+            let added = suggestion.added.iter().map(|l| (format!("+ {l}"), true));
+            for (text, is_added) in removed.chain(added) {
+                content_lines.push(CommentLine {
+                    content: MarkdownContent::Text(text),
+                    right: None,
+                    kind: CommentLineKind::Suggestion(is_added),
+                });
+            }
+        }
         if index + 1 < comments.len() {
             content_lines.push(CommentLine {
                 content: MarkdownContent::Text(String::new()),
@@ -198,8 +233,13 @@ pub(super) fn emit_comment_block(
                     CommentLineKind::Header => MarkdownStyle::Body,
                     CommentLineKind::Author => MarkdownStyle::Heading,
                     CommentLineKind::Markdown(style) => style,
+                    CommentLineKind::Suggestion(_) => MarkdownStyle::Code,
+                };
+                let line_bg = match line.kind {
+                    CommentLineKind::Suggestion(true) => theme.diff.added_bg,
+                    CommentLineKind::Suggestion(false) => theme.diff.removed_bg,
+                    _ => markdown_line_bg(theme, block_bg, markdown_style),
                 };
-                let line_bg = markdown_line_bg(theme, block_bg, markdown_style);
                 let (left_style, right_style) = match line.kind {
                     CommentLineKind::Header => {
                         (theme.style_muted_on(line_bg), theme.style_muted_on(line_bg))
@@ -211,6 +251,10 @@ pub(super) fn emit_comment_block(
                     CommentLineKind::Markdown(style) => {
                         (style.style(theme, line_bg), theme.style_muted_on(line_bg))
                     }
+                    CommentLineKind::Suggestion(_) => (
+                        theme.style_foreground_on(line_bg),
+                        theme.style_muted_on(line_bg),
+                    ),
                 };
                 buffer_fill_rect(buf, area.x, y, area.width, 1, theme.background);
                 buffer_fill_rect(buf, block.x, y, block.width, 1, block_bg);
@@ -302,6 +346,9 @@ mod tests {
             author: author.to_string(),
             body: body.to_string(),
             created_at: "2026-03-10T00:00:00Z".to_string(),
+            edited_at: None,
+            retracted_at: None,
+            suggestion: None,
         }
     }
 
@@ -313,6 +360,10 @@ mod tests {
             selection_end: None,
             status: "open".to_string(),
             comment_count: 2,
+            current_start: None,
+            current_end: None,
+            moved_from: None,
+            kind: None,
         }
     }
 
@@ -334,4 +385,86 @@ mod tests {
             .count();
         assert!(blanks >= 2);
     }
+
+    #[test]
+    fn build_comment_lines_marks_edited_and_retracted() {
+        let mut edited = comment("th-1234.1", "alice", "fixed typo");
+        edited.edited_at = Some("2026-03-10T01:00:00Z".to_string());
+        let mut retracted = comment("th-1234.2", "bob", "secret body");
+        retracted.retracted_at = Some("2026-03-10T02:00:00Z".to_string());
+
+        let lines = build_comment_lines(&thread(), &[edited, retracted], 40, None);
+        let texts: Vec<String> = lines
+            .iter()
+            .map(|line| match &line.content {
+                MarkdownContent::Text(text) => text.clone(),
+                other => format!("{other:?}"),
+            })
+            .collect();
+
+        assert!(texts.iter().any(|t| t == "@alice (edited)"));
+        assert!(texts.iter().any(|t| t == "@bob"));
+        assert!(!texts.iter().any(|t| t.contains("secret body")));
+    }
+
+    #[test]
+    fn build_comment_lines_marks_thread_kind() {
+        let mut blocking = thread();
+        blocking.kind = Some("blocking".to_string());
+
+        let lines =
+            build_comment_lines(&blocking, &[comment("th-1234.1", "alice", "hi")], 40, None);
+        assert!(matches!(
+            &lines[0].content,
+            MarkdownContent::Text(text) if text == "th-1234 [blocking]"
+        ));
+    }
+
+    #[test]
+    fn build_comment_lines_shows_moved_from() {
+        let mut moved = thread();
+        moved.moved_from = Some("src/old.rs".to_string());
+
+        let lines = build_comment_lines(&moved, &[comment("th-1234.1", "alice", "hi")], 40, None);
+        assert!(lines.iter().any(
+            |line| matches!(&line.content, MarkdownContent::Text(text) if text == "moved from src/old.rs")
+        ));
+        let plain = build_comment_lines(&thread(), &[], 40, None);
+        assert!(!plain.iter().any(
+            |line| matches!(&line.content, MarkdownContent::Text(text) if text.starts_with("moved from"))
+        ));
+    }
+
+    #[test]
+    fn build_comment_lines_renders_suggestion_diff() {
+        let mut suggesting = comment("th-1234.1", "alice", "use a constant");
+        suggesting.suggestion = Some(crate::db::SuggestionDiff {
+            removed: vec!["let x = 1;".to_string()],
+            added: vec!["let x = ONE;".to_string()],
+        });
+
+        let lines = build_comment_lines(&thread(), &[suggesting.clone()], 40, None);
+        let diff: Vec<(String, bool)> = lines
+            .iter()
+            .filter_map(|line| match (&line.kind, &line.content) {
+                (CommentLineKind::Suggestion(added), MarkdownContent::Text(text)) => {
+                    Some((text.clone(), *added))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            diff,
+            vec![
+                ("- let x = 1;".to_string(), false),
+                ("+ let x = ONE;".to_string(), true),
+            ]
+        );
+
+        suggesting.retracted_at = Some("2026-03-10T02:00:00Z".to_string());
+        let lines = build_comment_lines(&thread(), &[suggesting], 40, None);
+        assert!(!lines
+            .iter()
+            .any(|line| matches!(line.kind, CommentLineKind::Suggestion(_))));
+    }
 }
@@ -186,6 +186,7 @@ fn draw_sidebar_thread_item(
     if let SidebarItem::Thread {
         thread_id,
         status,
+        kind,
         comment_count,
         ..
     } = item
@@ -232,6 +233,26 @@ fn draw_sidebar_thread_item(
         };
         draw_text_truncated(buffer, thread_x, y, thread_id, id_width, text_style);
 
+        // Kind marker after the thread ID, if it fits
+        if let Some(kind) = kind {
+            let marker_x = thread_x + thread_id.chars().count() as u32 + 1;
+            let marker_len = kind.chars().count() as u32;
+            if marker_x + marker_len < indicator_x {
+                let marker_color = if kind == "blocking" {
+                    theme.error
+                } else {
+                    theme.muted
+                };
+                buffer_draw_text(
+                    buffer,
+                    marker_x,
+                    y,
+                    kind,
+                    Style::fg(marker_color).with_bg(row_bg),
+                );
+            }
+        }
+
         buffer_draw_text(
             buffer,
             indicator_x,
@@ -333,7 +354,73 @@ fn draw_file_sidebar(model: &Model, buffer: &mut OptimizedBuffer, area: Rect) {
             text_width,
             theme.style_muted(),
         );
-        y += 2;
+        y += 1;
+
+        // Revision and diff base, once the review has been updated
+        let latest = review.latest_revision();
+        if latest > 1 {
+            let (label, style) = model.diff_since_revision.map_or_else(
+                || {
+                    (
+                        format!("rev {latest} \u{b7} full diff"),
+                        theme.style_muted(),
+                    )
+                },
+                |rev| {
+                    (
+                        format!("rev {latest} \u{b7} since rev {rev}"),
+                        Style::fg(theme.warning),
+                    )
+                },
+            );
+            draw_text_truncated(buffer, text_x, y, &label, text_width, style);
+            y += 1;
+        }
+
+        // Neighbours in a review stack ({ and } open them)
+        let stack = review
+            .depends_on
+            .iter()
+            .map(|parent| format!("\u{2191} {parent}"))
+            .chain(
+                review
+                    .dependents
+                    .iter()
+                    .map(|child| format!("\u{2193} {child}")),
+            );
+        for label in stack {
+            if y >= bottom {
+                break;
+            }
+            draw_text_truncated(buffer, text_x, y, &label, text_width, theme.style_muted());
+            y += 1;
+        }
+
+        // Votes, with stale ones (cast before the latest changes) dimmed
+        for vote in &review.votes {
+            if y >= bottom {
+                break;
+            }
+            let (label, style) = if vote.stale {
+                (
+                    format!("~ {} {} (stale)", vote.reviewer, vote.vote),
+                    theme.style_muted(),
+                )
+            } else if vote.vote == "lgtm" {
+                (
+                    format!("\u{2713} {}", vote.reviewer),
+                    Style::fg(theme.success),
+                )
+            } else {
+                (
+                    format!("\u{2717} {}", vote.reviewer),
+                    Style::fg(theme.error),
+                )
+            };
+            draw_text_truncated(buffer, text_x, y, &label, text_width, style);
+            y += 1;
+        }
+        y += 1;
     }
 
     if items.is_empty() {
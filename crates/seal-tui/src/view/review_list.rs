@@ -1,5 +1,7 @@
 //! Review list screen rendering
 
+use std::fmt::Write as _;
+
 use crate::render_backend::{buffer_draw_text, buffer_fill_rect, OptimizedBuffer, Style};
 
 use super::components::{
@@ -95,9 +97,15 @@ fn draw_search_bar(model: &Model, buffer: &mut OptimizedBuffer, x: u32, y: u32,
 
     let text_x = x + 5;
     if model.search_active {
-        let max_chars = width.saturating_sub(8) as usize; // 5 margin + "/ " + cursor
+        let prefix = if model.search_full_text {
+            "full text / "
+        } else {
+            "/ "
+        };
+        // 5 margin + prefix + cursor
+        let max_chars = width.saturating_sub(6 + prefix.len() as u32) as usize;
         let visible = tail_chars(&model.search_input, max_chars);
-        let prompt = format!("/ {visible}\u{2588}");
+        let prompt = format!("{prefix}{visible}\u{2588}");
         buffer_draw_text(buffer, text_x, y, &prompt, theme.style_foreground());
     } else {
         buffer_draw_text(buffer, text_x, y, "Press / to search", theme.style_muted());
@@ -200,13 +208,27 @@ fn draw_review_item(
     buffer_draw_text(buffer, x2, y2, &badge, Style::fg(badge_color).with_bg(bg));
     x2 += badge.len() as u32 + 2;
 
-    // Author -> Reviewers
-    let people = if review.reviewers.is_empty() {
-        format!("@{}", review.author)
-    } else {
-        let reviewers: Vec<String> = review.reviewers.iter().map(|r| format!("@{r}")).collect();
-        format!("@{} -> {}", review.author, reviewers.join(", "))
-    };
+    // Author -> Reviewers and labels, or the best match when searching full text
+    let snippet = model
+        .search_full_text
+        .then(|| model.search_hits.get(&review.review_id))
+        .flatten();
+    let people = snippet.map_or_else(
+        || {
+            let mut people = if review.reviewers.is_empty() {
+                format!("@{}", review.author)
+            } else {
+                let reviewers: Vec<String> =
+                    review.reviewers.iter().map(|r| format!("@{r}")).collect();
+                format!("@{} -> {}", review.author, reviewers.join(", "))
+            };
+            for label in &review.labels {
+                let _ = write!(people, "  #{label}");
+            }
+            people
+        },
+        |snippet| snippet.replace('\n', " "),
+    );
     let people_color = if selected {
         theme.selection_fg
     } else {
@@ -246,10 +268,19 @@ fn render_help_bar(model: &Model, buffer: &mut OptimizedBuffer, area: Rect) {
     );
 
     if model.search_active {
+        let mode_hint = HotkeyHint::new(
+            if model.search_full_text {
+                "Search titles"
+            } else {
+                "Search full text"
+            },
+            "Tab",
+        );
         let hints = &[
             HotkeyHint::new("Commands", "ctrl+p"),
             HotkeyHint::new("Select", "Enter"),
             filter_hint,
+            mode_hint,
             HotkeyHint::new("Clear", "Esc"),
             HotkeyHint::new("Quit", "ctrl+c"),
         ];